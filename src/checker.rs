@@ -0,0 +1,1912 @@
+use std::collections::HashSet;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::diagnostics::Diagnostic;
+use crate::initialization;
+use crate::options::{Lifetime, Options};
+use crate::sema::{
+    self, is_compound_assignment, nominal_name, signature, standard_value_type, Binding, Delegation, Member, NominalType, Scope, Substitution, Throwing,
+    TypeTable, Witness,
+};
+use crate::stdlib;
+use crate::synthesis;
+use crate::syntax::{Declaration, Expression, Statement, StatementSequence, SwiftType};
+use crate::syntax::declaration::{
+    AccessControl,
+    ClassDeclaration,
+    FunDeclaration,
+    FunctionParameter,
+    GenericRequirement,
+    GenericsDeclaration,
+    InitializerDeclaration,
+    PropertyKind,
+    ReferenceOwnership,
+    VariablePropertyDeclaration,
+};
+use crate::syntax::expression;
+use crate::syntax::statement;
+
+/// Checks a program and returns the diagnostics it produces.
+pub fn check(program: &StatementSequence, options: &Options) -> Vec<Diagnostic> {
+    let table = TypeTable::new(program);
+    let mut checker = Checker::new(&table);
+    checker.lifetime = options.lifetime;
+    checker.check_program(program);
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.extend(initialization::analyze(program, &table));
+    diagnostics
+}
+
+/// The kind of declaration whose members are being checked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Container {
+    Struct,
+    Enum,
+    Class,
+    Extension,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Context<'a> {
+    self_type: Option<&'a str>,
+    in_initializer: bool,
+    is_static: bool,
+    is_mutating: bool,
+    throws: Throws,
+}
+
+impl Context<'_> {
+    // Errors thrown by top-level code end the program.
+    const TOP_LEVEL: Context<'static> =
+        Context { self_type: None, in_initializer: false, is_static: false, is_mutating: false, throws: Throws::Any };
+}
+
+/// The errors the function being checked may throw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Throws {
+    Any,
+    /// Only those its function parameters throw, for a function declared `rethrows`.
+    Rethrows,
+    Never,
+}
+
+impl Throws {
+    fn of(function: &FunDeclaration) -> Throws {
+        match (function.is_throwing, function.is_rethrowing) {
+            (true, _) => Throws::Any,
+            (false, true) => Throws::Rethrows,
+            (false, false) => Throws::Never,
+        }
+    }
+}
+
+/// What catches the errors thrown inside a `do` block, a `try?` or `try!`, or a closure.
+#[derive(Debug, Clone, Copy)]
+enum Handler {
+    /// A `do` statement's catch clauses, which pass on what they don't match unless one is exhaustive.
+    Do { is_exhaustive: bool },
+    Try,
+    /// A closure, which throws the errors thrown in its body. Closures are identified by address.
+    Closure(usize),
+}
+
+/// Why errors thrown at some point of the program aren't handled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unhandled {
+    NotThrowing,
+    Rethrowing,
+    InexhaustiveCatch,
+}
+
+/// Progress through the body of the initializer being checked.
+#[derive(Debug, Clone)]
+struct InitializerState<'a> {
+    decl: &'a InitializerDeclaration,
+    /// The delegation that must happen before `self` can be used, until it does.
+    awaiting: Option<Delegation>,
+}
+
+/// A closure being checked, and the captures already reported for it.
+#[derive(Debug, Clone)]
+struct ClosureCaptures {
+    /// Bindings in scope frames below this depth are declared outside the closure.
+    depth: usize,
+    reported: Vec<String>,
+}
+
+/// Walks a program reporting semantic errors.
+pub struct Checker<'a> {
+    table: &'a TypeTable<'a>,
+    scope: Scope,
+    context: Context<'a>,
+    initializer: Option<InitializerState<'a>>,
+    lifetime: Lifetime,
+    closures: Vec<ClosureCaptures>,
+    /// The types the function being checked returns, collected when it declares an opaque result type.
+    returns: Option<Vec<Option<SwiftType>>>,
+    /// The handlers enclosing the point being checked, innermost last, up to the function boundary.
+    handlers: Vec<Handler>,
+    /// The `try` expressions covering the point being checked.
+    tries: usize,
+    /// The scope frame declaring the parameters of the function being checked.
+    parameter_frame: usize,
+    /// The closures found to throw.
+    throwing_closures: HashSet<usize>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Checker<'a> {
+    pub fn new(table: &'a TypeTable<'a>) -> Self {
+        Checker {
+            table,
+            scope: Scope::default(),
+            context: Context::TOP_LEVEL,
+            initializer: None,
+            lifetime: Lifetime::default(),
+            closures: Vec::new(),
+            returns: None,
+            handlers: Vec::new(),
+            tries: 0,
+            parameter_frame: 0,
+            throwing_closures: HashSet::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub fn check_program(&mut self, program: &'a StatementSequence) {
+        self.scope.push();
+        for statement in program.statements() {
+            self.check_statement(statement);
+        }
+        self.scope.pop();
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn error(&mut self, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::error(message));
+    }
+
+    fn warning(&mut self, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::warning(message));
+    }
+
+    fn with_context<R>(&mut self, context: Context<'a>, f: impl FnOnce(&mut Self) -> R) -> R {
+        let saved = std::mem::replace(&mut self.context, context);
+        let saved_returns = self.returns.take();
+        let saved_handlers = std::mem::take(&mut self.handlers);
+        let saved_tries = std::mem::take(&mut self.tries);
+        let saved_parameter_frame = std::mem::replace(&mut self.parameter_frame, self.scope.depth());
+        self.scope.push();
+        let result = f(self);
+        self.scope.pop();
+        self.parameter_frame = saved_parameter_frame;
+        self.tries = saved_tries;
+        self.handlers = saved_handlers;
+        self.returns = saved_returns;
+        self.context = saved;
+        result
+    }
+
+    fn infer(&self, expression: &Expression) -> Option<SwiftType> {
+        self.table.infer(&self.scope, self.context.self_type, expression)
+    }
+
+    // ---------------------------------------------------------------------
+    // Declarations
+    // ---------------------------------------------------------------------
+
+    fn check_declaration(&mut self, declaration: &'a Declaration) {
+        match declaration {
+            Declaration::Function(function) => {
+                self.check_function_modifiers(function, None);
+                self.check_generics(&[], function.generics.as_ref());
+                self.check_function(function, Context { in_initializer: false, ..self.context });
+            }
+            Declaration::Var(var) => {
+                if let Some(ty) = &var.ty {
+                    self.check_type(ty);
+                }
+                if let Some(value) = &var.initial_value {
+                    self.check_expression(value);
+                    self.check_integer_literal(value, var.ty.as_ref());
+                    self.check_character_literal(value, var.ty.as_ref());
+                }
+                let ty = var.ty.clone().or_else(|| var.initial_value.as_ref().and_then(|value| self.infer(value)));
+                self.check_ownership(var.ownership, ty.as_ref(), false);
+                self.scope.declare(&var.name, Binding { ty, is_constant: false, is_inout: false, ownership: var.ownership });
+            }
+            Declaration::Let(constant) => {
+                if let Some(ty) = &constant.ty {
+                    self.check_type(ty);
+                }
+                if let Some(value) = &constant.initial_value {
+                    self.check_expression(value);
+                    self.check_integer_literal(value, constant.ty.as_ref());
+                    self.check_character_literal(value, constant.ty.as_ref());
+                }
+                let ty = constant
+                    .ty
+                    .clone()
+                    .or_else(|| constant.initial_value.as_ref().and_then(|value| self.infer(value)));
+                self.check_ownership(constant.ownership, ty.as_ref(), true);
+                self.scope.declare(&constant.name, Binding { ty, is_constant: true, is_inout: false, ownership: constant.ownership });
+            }
+            Declaration::Struct(decl) => {
+                self.check_generics(&[], decl.generics.as_ref());
+                self.check_members(&decl.name, Container::Struct, &decl.properties, &decl.methods, &decl.initializers);
+                self.check_conformances(&decl.name, &decl.conformances);
+            }
+            Declaration::Enum(decl) => {
+                self.check_generics(&[], decl.generics.as_ref());
+                self.check_members(&decl.name, Container::Enum, &decl.properties, &decl.methods, &decl.initializers);
+                self.check_conformances(&decl.name, &decl.conformances);
+            }
+            Declaration::Class(decl) => {
+                self.check_generics(&[], decl.generics.as_ref());
+                self.check_class_initializers(decl);
+                self.check_members(&decl.name, Container::Class, &decl.properties, &decl.methods, &decl.initializers);
+                self.check_deinitializer(decl);
+                self.check_conformances(&decl.name, &decl.conformances);
+            }
+            Declaration::Extension(decl) => {
+                let Some(nominal) = self.table.nominal(&decl.type_name) else { return };
+                self.check_extension_where_clause(nominal, &decl.where_clause);
+                self.check_members(nominal.name(), Container::Extension, &decl.properties, &decl.methods, &decl.initializers);
+                self.check_conformances(nominal.name(), &decl.conformances);
+            }
+            Declaration::Protocol(decl) => {
+                let parameters = self.protocol_parameters(&decl.name);
+                for associated in &decl.associated_types {
+                    for constraint in &associated.constraints {
+                        self.check_constraint(&SwiftType::Custom(associated.name.clone()), constraint);
+                    }
+                }
+                self.check_generic_types(&parameters, &[]);
+            }
+            Declaration::TypeAlias(_) | Declaration::Import(_) => {}
+            Declaration::Initializer(_) | Declaration::Deinitializer(_) => {}
+        }
+    }
+
+    fn check_members(
+        &mut self,
+        type_name: &'a str,
+        container: Container,
+        properties: &'a [VariablePropertyDeclaration],
+        methods: &'a [FunDeclaration],
+        initializers: &'a [InitializerDeclaration],
+    ) {
+        let is_class = matches!(self.table.nominal(type_name), Some(NominalType::Class(_)));
+        let type_generics = self.table.nominal(type_name).and_then(|nominal| nominal.generics());
+        let outer = type_generics.into_iter().collect::<Vec<_>>();
+        for property in properties {
+            self.check_property(type_name, container, property);
+            if is_class {
+                self.check_property_override(type_name, container, property);
+            } else {
+                self.check_class_only_modifiers(property.is_override, property.is_final);
+            }
+        }
+        for method in methods {
+            self.check_function_modifiers(method, Some(container));
+            self.check_generics(&outer, method.generics.as_ref());
+            if is_class {
+                self.check_method_override(type_name, container, method);
+            } else {
+                self.check_class_only_modifiers(method.is_override, method.is_final);
+            }
+            let context = Context {
+                self_type: Some(type_name),
+                in_initializer: false,
+                is_static: method.is_static || method.is_class,
+                is_mutating: method.is_mutating,
+                throws: Throws::of(method),
+            };
+            self.check_function(method, context);
+        }
+        for initializer in initializers {
+            self.check_generics(&outer, initializer.generics.as_ref());
+            if is_class {
+                self.check_initializer_override(type_name, initializer);
+            } else {
+                self.check_class_only_modifiers(initializer.is_override, false);
+            }
+            let awaiting = self.check_initializer_delegation(type_name, container, initializer);
+            let state = InitializerState { decl: initializer, awaiting };
+            let saved = self.initializer.replace(state);
+            let throws = if initializer.is_throwing { Throws::Any } else { Throws::Never };
+            let context = Context { self_type: Some(type_name), in_initializer: true, is_static: false, is_mutating: true, throws };
+            self.with_context(context, |this| {
+                this.declare_parameters(&initializer.parameters);
+                this.check_block(&initializer.body);
+            });
+            self.initializer = saved;
+        }
+    }
+
+    /// Checks an initializer's kind against the delegation it performs, and returns the delegation
+    /// that must complete before the body may use `self`.
+    fn check_initializer_delegation(
+        &mut self,
+        type_name: &str,
+        container: Container,
+        initializer: &'a InitializerDeclaration,
+    ) -> Option<Delegation> {
+        let nominal = self.table.nominal(type_name)?;
+        let delegations = sema::delegations(&initializer.body);
+        let delegates_to_self = delegations.iter().any(|(delegation, _)| *delegation == Delegation::ToSelf);
+        let delegates_to_super = delegations.iter().any(|(delegation, _)| *delegation == Delegation::ToSuper);
+        let superclass = self.table.superclass(type_name);
+        match nominal {
+            NominalType::Class(_) if initializer.is_convenience => {
+                if delegates_to_super {
+                    self.error(format!(
+                        "convenience initializer for '{type_name}' must delegate (with 'self.init') rather than chaining to a superclass initializer (with 'super.init')"
+                    ));
+                } else if !delegates_to_self {
+                    self.error("'self.init' isn't called on all paths before returning from initializer");
+                }
+            }
+            NominalType::Class(_) => {
+                if container == Container::Extension {
+                    self.error(format!(
+                        "designated initializer cannot be declared in an extension of '{type_name}'; did you mean this to be a convenience initializer?"
+                    ));
+                }
+                if delegates_to_self {
+                    self.error(format!(
+                        "designated initializer for '{type_name}' cannot delegate (with 'self.init'); did you mean this to be a convenience initializer?"
+                    ));
+                }
+                match superclass {
+                    None if delegates_to_super => self.error("'super' members cannot be referenced in a root class"),
+                    // Without an explicit call, `super.init()` is called implicitly if it exists.
+                    Some(superclass)
+                        if !delegates_to_super
+                            && self.table.nominal(superclass).is_some()
+                            && self.table.initializer(superclass, &[]).is_none() =>
+                    {
+                        self.error("'super.init' isn't called on all paths before returning from initializer");
+                    }
+                    _ => {}
+                }
+            }
+            NominalType::Struct(_) | NominalType::Enum(_) => {
+                if initializer.is_convenience {
+                    let kind = if matches!(nominal, NominalType::Struct(_)) { "structs" } else { "enums" };
+                    self.error(format!("delegating initializers in {kind} are not marked with 'convenience'"));
+                }
+                if delegates_to_super {
+                    self.error("'super' cannot be used outside of class members");
+                }
+            }
+            NominalType::Protocol(_) => {}
+        }
+        if initializer.is_required {
+            match nominal {
+                NominalType::Class(_) if container == Container::Extension => self.error(format!(
+                    "'required' initializer must be declared directly in class '{type_name}' (not in an extension)"
+                )),
+                NominalType::Class(_) => {}
+                _ => self.error(format!("'required' initializer in non-class type '{type_name}'")),
+            }
+        }
+        match nominal {
+            NominalType::Class(_) if superclass.is_some() && delegates_to_super => Some(Delegation::ToSuper),
+            _ if delegates_to_self => Some(Delegation::ToSelf),
+            _ => None,
+        }
+    }
+
+    fn check_class_only_modifiers(&mut self, is_override: bool, is_final: bool) {
+        if is_override {
+            self.error("'override' can only be specified on class members");
+        }
+        if is_final {
+            self.error("only classes and class members can be declared 'final'");
+        }
+    }
+
+    /// `override` must be present exactly when a method replaces one of a superclass.
+    fn check_method_override(&mut self, type_name: &str, container: Container, method: &FunDeclaration) {
+        let name = signature(&method.name, method.parameters.iter().map(|parameter| parameter.label.as_deref()));
+        let kind = if method.is_static || method.is_class { "class method" } else { "instance method" };
+        match self.table.overridden_method(type_name, method) {
+            None if method.is_override => self.error(format!("method '{name}' does not override any method from its superclass")),
+            None => {}
+            Some(_) if !method.is_override => {
+                self.error(format!("overriding declaration requires an 'override' keyword ('{name}')"))
+            }
+            Some(overridden) if overridden.is_static => self.error(format!("cannot override static method '{name}'")),
+            Some(overridden) if overridden.is_final => self.error(format!("{kind} '{name}' overrides a 'final' {kind}")),
+            Some(_) if container == Container::Extension => {
+                self.error(format!("declarations in extensions cannot override yet ('{name}')"))
+            }
+            Some(_) => {}
+        }
+    }
+
+    /// An overriding property provides a getter (and setter) or observers for a superclass property.
+    fn check_property_override(&mut self, type_name: &str, container: Container, property: &VariablePropertyDeclaration) {
+        let name = &property.name;
+        let Some(overridden) = self.table.overridden_property(type_name, name) else {
+            if property.is_override {
+                self.error(format!("property '{name}' does not override any property from its superclass"));
+            }
+            return;
+        };
+        if !property.is_override {
+            self.error(format!("overriding declaration requires an 'override' keyword ('{name}')"));
+            return;
+        }
+        if overridden.is_static {
+            self.error(format!("cannot override static property '{name}'"));
+        } else if overridden.is_final {
+            self.error(format!("property '{name}' overrides a 'final' property"));
+        } else if container == Container::Extension {
+            self.error(format!("declarations in extensions cannot override yet ('{name}')"));
+        }
+        match &property.kind {
+            PropertyKind::Stored(_) if !property.is_observed() => {
+                self.error(format!("cannot override with a stored property '{name}'"))
+            }
+            PropertyKind::Stored(_) if !overridden.is_settable() => {
+                self.error(format!("cannot observe read-only property '{name}'; it can't change"))
+            }
+            PropertyKind::Computed(_) if overridden.stored().is_some() && overridden.is_constant => {
+                self.error(format!("cannot override immutable 'let' property '{name}' with the getter of a 'var'"))
+            }
+            PropertyKind::Computed(_) if overridden.is_settable() && !property.is_settable() => {
+                self.error(format!("cannot override mutable property with read-only property '{name}'"))
+            }
+            _ => {}
+        }
+    }
+
+    /// Initializers matching a designated superclass initializer override it; `required` ones need no `override`.
+    fn check_initializer_override(&mut self, type_name: &str, initializer: &InitializerDeclaration) {
+        let overridden = self.table.overridden_initializer(type_name, &initializer.parameters);
+        let name = signature("init", initializer.parameters.iter().map(|parameter| parameter.label.as_deref()));
+        match overridden {
+            None if initializer.is_override => {
+                self.error(format!("initializer '{name}' does not override a designated initializer from its superclass"))
+            }
+            Some(overridden) if !initializer.is_override && !overridden.is_required => {
+                self.error(format!("overriding declaration requires an 'override' keyword ('{name}')"))
+            }
+            _ => {}
+        }
+    }
+
+    /// Checks the body of a `deinit`. Without reference counting, when it runs can't be guaranteed.
+    fn check_deinitializer(&mut self, decl: &'a ClassDeclaration) {
+        let Some(deinitializer) = &decl.deinitializer else { return };
+        if self.lifetime == Lifetime::Finalization {
+            self.warning(format!(
+                "'deinit' of '{}' runs only once the garbage collector reclaims an instance, which may be late or never; \
+                 compile with reference counting to run it when the last reference goes away",
+                decl.name
+            ));
+            self.check_finalized_references(decl);
+        }
+        let context = Context { self_type: Some(&decl.name), in_initializer: false, is_static: false, is_mutating: false, throws: Throws::Never };
+        self.with_context(context, |this| this.check_block(&deinitializer.body));
+    }
+
+    /// The properties a `deinit` reads outlive the instance until it is reclaimed, so an instance they
+    /// refer to that refers back keeps it from ever being reclaimed.
+    fn check_finalized_references(&mut self, decl: &'a ClassDeclaration) {
+        let reads = self.table.deinitializer_reads(decl);
+        let mut names = Vec::new();
+        let mut class = Some(decl.name.as_str());
+        while let Some(name) = class {
+            let Some(nominal) = self.table.nominal(name) else { break };
+            for property in nominal.properties().iter().filter(|property| !property.is_static && property.has_storage()) {
+                let is_read = reads.as_ref().is_none_or(|reads| reads.contains(property.name.as_str()));
+                let ty = self.table.member_type(name, &property.name);
+                let holds_references = ty.is_some_and(|ty| self.table.holds_references(&ty));
+                if is_read && holds_references && property.ownership == ReferenceOwnership::Strong {
+                    names.push(format!("'{}'", property.name));
+                }
+            }
+            class = self.table.superclass(name);
+        }
+        if !names.is_empty() {
+            self.warning(format!(
+                "'deinit' of '{}' reads {}, which keeps the instances it refers to alive until the instance is reclaimed; \
+                 one that refers back to the instance keeps it from ever being reclaimed",
+                decl.name,
+                names.join(", ")
+            ));
+        }
+    }
+
+    /// Reference counting doesn't follow values captured by closures, so the instances they hold
+    /// may be deinitialized while a closure can still use them.
+    fn check_capture(&mut self, name: &str, frame: usize, ty: Option<SwiftType>) {
+        if self.lifetime != Lifetime::ReferenceCounting || !self.table.has_deinitializers() {
+            return;
+        }
+        let Some(closure) = self.closures.last_mut() else { return };
+        let holds_references = ty.is_some_and(|ty| self.table.holds_references(&ty));
+        if frame >= closure.depth || !holds_references || closure.reported.iter().any(|reported| reported == name) {
+            return;
+        }
+        closure.reported.push(name.to_string());
+        self.warning(format!(
+            "closure captures '{name}' without keeping it alive; the instances it refers to may be deinitialized \
+             while the closure can still use them"
+        ));
+    }
+
+    /// Checks the values a closure captures when it is created, returning the types they have inside it.
+    fn check_capture_list(&mut self, capture_list: &'a [expression::CaptureListItem]) -> Vec<Option<SwiftType>> {
+        let mut types = Vec::new();
+        for item in capture_list {
+            let ty = match &item.value {
+                Some(value) => {
+                    self.check_expression(value);
+                    self.infer(value)
+                }
+                None if item.name == "self" => {
+                    self.check_self_capture();
+                    self.context.self_type.map(|name| SwiftType::Custom(name.to_string()))
+                }
+                None => match self.scope.lookup_frame(&item.name) {
+                    Some((frame, binding)) => {
+                        let ty = binding.ty.clone();
+                        self.check_capture(&item.name, frame, ty.clone());
+                        ty
+                    }
+                    None => None,
+                },
+            };
+            // Weak captures are implicitly optional.
+            let ty = match ty {
+                Some(ty) if item.ownership == ReferenceOwnership::Weak && !matches!(ty, SwiftType::Optional(_)) => {
+                    Some(SwiftType::Optional(Box::new(ty)))
+                }
+                ty => ty,
+            };
+            self.check_ownership(item.ownership, ty.as_ref(), false);
+            types.push(ty);
+        }
+        types
+    }
+
+    /// `weak` and `unowned` only apply to class instances, and a `weak` variable must be able to become `nil`.
+    fn check_ownership(&mut self, ownership: ReferenceOwnership, ty: Option<&SwiftType>, is_constant: bool) {
+        if ownership == ReferenceOwnership::Strong {
+            return;
+        }
+        let keyword = ownership.keyword();
+        if ownership == ReferenceOwnership::Weak && is_constant {
+            self.error(format!("'{keyword}' must be a mutable variable, because it may change at runtime"));
+        }
+        let Some(ty) = ty else { return };
+        let referent = match ty {
+            SwiftType::Optional(wrapped) => wrapped.as_ref(),
+            ty => ty,
+        };
+        let is_unknown = matches!(referent, SwiftType::Custom(name) if self.table.nominal(name).is_none() && name != "AnyObject");
+        if !is_unknown && !self.table.is_class_bound(referent) {
+            self.error(format!("'{keyword}' may only be applied to class and class-bound protocol types, not '{referent}'"));
+        } else if ownership == ReferenceOwnership::Weak && !matches!(ty, SwiftType::Optional(_)) {
+            self.error(format!("'{keyword}' variable should have optional type '{ty}?'"));
+        }
+    }
+
+    fn check_self_capture(&mut self) {
+        let Some(self_type) = self.context.self_type.filter(|_| !self.context.is_static) else { return };
+        self.check_capture("self", 0, Some(SwiftType::Custom(self_type.to_string())));
+    }
+
+    /// Checks that the type provides a witness for every requirement of the protocols a declaration
+    /// lists, and of the protocols those inherit from.
+    fn check_conformances(&mut self, type_name: &'a str, conformances: &[String]) {
+        let is_open_class = matches!(self.table.nominal(type_name), Some(NominalType::Class(decl)) if !decl.is_final);
+        for name in conformances {
+            match self.table.nominal(name) {
+                Some(NominalType::Protocol(_)) => {}
+                Some(_) => {
+                    self.error(format!("inheritance from non-protocol type '{name}'"));
+                    continue;
+                }
+                // Of the standard library protocols, those the runtime calls need their witnesses,
+                // declared or synthesized.
+                None => {
+                    for protocol in stdlib::protocols(name) {
+                        let is_missing = |requirement: &&str| {
+                            self.table.standard_witness(type_name, requirement).is_none() && !synthesis::derives_coding(self.table, type_name, requirement)
+                        };
+                        if protocol.requirements.iter().any(is_missing) {
+                            self.error(format!("type '{type_name}' does not conform to protocol '{}'", protocol.name));
+                        }
+                    }
+                    continue;
+                }
+            }
+            for protocol in self.table.protocols(name) {
+                let conformance = self.table.conformance(type_name, protocol);
+                for (associated, ty) in &conformance.associated_types {
+                    let Some(ty) = ty else {
+                        self.error(format!(
+                            "type '{type_name}' does not conform to protocol '{}'; protocol requires nested type '{}'",
+                            protocol.name, associated.name
+                        ));
+                        continue;
+                    };
+                    for constraint in &associated.constraints {
+                        if self.table.satisfies(ty, constraint) == Some(false) {
+                            self.error(format!(
+                                "type '{type_name}' does not conform to protocol '{}'; type '{ty}' used for '{}' does not conform to '{constraint}'",
+                                protocol.name, associated.name
+                            ));
+                        }
+                    }
+                }
+                for requirement in conformance.missing() {
+                    self.error(format!(
+                        "type '{type_name}' does not conform to protocol '{}'; protocol requires {}",
+                        protocol.name,
+                        requirement.description()
+                    ));
+                }
+                for (requirement, witness) in &conformance.witnesses {
+                    let Some(Witness::Initializer(initializer)) = witness else { continue };
+                    if is_open_class && !initializer.is_required {
+                        self.error(format!(
+                            "initializer requirement '{}' can only be satisfied by a 'required' initializer in non-final class '{type_name}'",
+                            requirement.signature()
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks the generic parameters of a declaration and its `where` clause. The clause may also
+    /// constrain the parameters of the enclosing type, `outer`.
+    fn check_generics(&mut self, outer: &[&'a GenericsDeclaration], generics: Option<&'a GenericsDeclaration>) {
+        let Some(generics) = generics else { return };
+        let parameters = outer.iter().copied().chain([generics]).flat_map(generic_parameters).collect::<Vec<_>>();
+        for parameter in &generics.type_parameters {
+            for constraint in &parameter.constraints {
+                self.check_constraint(&SwiftType::Custom(parameter.name.clone()), constraint);
+            }
+        }
+        self.check_generic_types(&parameters, &generics.where_clause);
+    }
+
+    /// Checks the `where` clause of an extension, which may constrain the generic parameters of the
+    /// extended type, or `Self` and the associated types of an extended protocol.
+    fn check_extension_where_clause(&mut self, nominal: NominalType<'a>, where_clause: &'a [GenericRequirement]) {
+        if where_clause.is_empty() {
+            return;
+        }
+        let parameters = match (nominal, nominal.generics()) {
+            (NominalType::Protocol(decl), _) => self.protocol_parameters(&decl.name),
+            (_, Some(generics)) => generic_parameters(generics),
+            (_, None) => {
+                self.error(format!("trailing 'where' clause for extension of non-generic type '{}'", nominal.name()));
+                return;
+            }
+        };
+        self.check_generic_types(&parameters, where_clause);
+    }
+
+    /// The names a protocol's extensions and associated types may constrain: `Self`, which conforms
+    /// to the protocol, and its associated types with their constraints.
+    fn protocol_parameters(&self, protocol: &str) -> Vec<(String, Option<SwiftType>)> {
+        let mut parameters = vec![("Self".to_string(), Some(SwiftType::Custom(protocol.to_string())))];
+        for decl in self.table.protocols(protocol) {
+            for associated in &decl.associated_types {
+                parameters.push((associated.name.clone(), None));
+                parameters.extend(associated.constraints.iter().map(|constraint| (associated.name.clone(), Some(constraint.clone()))));
+            }
+        }
+        parameters
+    }
+
+    /// Checks the types a `where` clause names against the generic parameters in scope, given with
+    /// the conformance constraints on each (`None` for the parameter itself).
+    fn check_generic_types(&mut self, parameters: &[(String, Option<SwiftType>)], where_clause: &'a [GenericRequirement]) {
+        let mut parameters = parameters.to_vec();
+        for requirement in where_clause {
+            let GenericRequirement::Conformance { subject: SwiftType::Custom(name), constraint } = requirement else { continue };
+            if parameters.iter().any(|(parameter, _)| parameter == name) {
+                parameters.push((name.clone(), Some(constraint.clone())));
+            }
+        }
+        for requirement in where_clause {
+            match requirement {
+                GenericRequirement::Conformance { subject, constraint } => {
+                    self.check_generic_type(&parameters, subject, true);
+                    self.check_constraint(subject, constraint);
+                }
+                GenericRequirement::SameType { left, right } => {
+                    self.check_generic_type(&parameters, left, true);
+                    self.check_generic_type(&parameters, right, false);
+                }
+            }
+        }
+    }
+
+    /// Reports a type in a `where` clause that names neither a generic parameter in scope nor a
+    /// declared type, or an associated type none of its base's constraints provides.
+    fn check_generic_type(&mut self, parameters: &[(String, Option<SwiftType>)], ty: &SwiftType, is_subject: bool) {
+        match ty {
+            SwiftType::Custom(name) => {
+                let is_parameter = parameters.iter().any(|(parameter, _)| parameter == name);
+                if is_subject && !is_parameter && self.table.nominal(name).is_none() {
+                    self.error(format!("cannot find type '{name}' in scope"));
+                }
+            }
+            SwiftType::Nested(base, name) => {
+                self.check_generic_type(parameters, base, true);
+                let SwiftType::Custom(base) = base.as_ref() else { return };
+                let constraints = parameters
+                    .iter()
+                    .filter(|(parameter, _)| parameter == base)
+                    .filter_map(|(_, constraint)| match constraint {
+                        Some(SwiftType::Custom(constraint)) => Some(constraint.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let is_parameter = parameters.iter().any(|(parameter, _)| parameter == base);
+                // Protocols of the standard library provide associated types of their own.
+                let is_known = constraints.iter().all(|constraint| matches!(self.table.nominal(constraint), Some(NominalType::Protocol(_))));
+                let is_member =
+                    constraints.iter().any(|constraint| self.table.associated_type_declaration(constraint, name).is_some());
+                if is_parameter && is_known && !is_member {
+                    self.error(format!("'{name}' is not a member type of type '{base}'"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// A generic parameter or associated type may only be constrained to protocols and classes.
+    fn check_constraint(&mut self, subject: &SwiftType, constraint: &SwiftType) {
+        let SwiftType::Custom(name) = constraint else { return };
+        if matches!(self.table.nominal(name), Some(NominalType::Struct(_) | NominalType::Enum(_))) {
+            self.error(format!("type '{subject}' constrained to non-protocol, non-class type '{name}'"));
+        }
+    }
+
+    /// Checks that a class can be initialized at all, and provides the `required` initializers of its superclass.
+    fn check_class_initializers(&mut self, decl: &'a ClassDeclaration) {
+        let name = &decl.name;
+        let declares_designated = decl.initializers.iter().any(|initializer| !initializer.is_convenience);
+        if !declares_designated && decl.properties.iter().any(|property| property.requires_initialization()) {
+            self.error(format!("class '{name}' has no initializers"));
+        }
+        let Some(superclass) = decl.superclass.as_deref() else { return };
+        if self.table.is_cyclic(name) {
+            self.error(format!("'{name}' inherits from itself"));
+            return;
+        }
+        if let Some(NominalType::Class(superclass_decl)) = self.table.nominal(superclass) {
+            if superclass_decl.is_final {
+                self.error(format!("inheritance from a final class '{superclass}'"));
+            }
+            if !decl.superclass_arguments.is_empty() {
+                self.check_type(&SwiftType::Generic(superclass.to_string(), decl.superclass_arguments.clone()));
+            } else if let Some(generics) = &superclass_decl.generics {
+                let parameters = generics.type_parameters.iter().map(|parameter| parameter.name.as_str()).collect::<Vec<_>>();
+                self.error(format!("reference to generic type '{superclass}' requires arguments in <...>; expected <{}>", parameters.join(", ")));
+            }
+        }
+        let available = self.table.initializers(name);
+        for required in self.table.initializers(superclass).into_iter().filter(|initializer| initializer.is_required) {
+            if available.iter().any(|initializer| std::ptr::eq(*initializer, required)) {
+                continue;
+            }
+            let labels = required.parameters.iter().map(|parameter| parameter.label.as_deref());
+            let own = decl
+                .initializers
+                .iter()
+                .find(|initializer| sema::same_labels(&initializer.parameters, &required.parameters));
+            match own {
+                None => self.error(format!(
+                    "'required' initializer '{}' must be provided by subclass of '{superclass}'",
+                    signature("init", labels)
+                )),
+                Some(initializer) if !initializer.is_required => {
+                    self.error("'required' modifier must be present on all overrides of a required initializer")
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn check_property(&mut self, type_name: &'a str, container: Container, property: &'a VariablePropertyDeclaration) {
+        let name = &property.name;
+        let is_type_property = property.is_static || property.is_class;
+        if property.is_class && container != Container::Class && container != Container::Extension {
+            self.error(format!(
+                "class properties are only allowed within classes; use 'static' to declare a static property '{name}'"
+            ));
+        }
+        let context = Context {
+            self_type: Some(type_name),
+            in_initializer: false,
+            is_static: is_type_property,
+            is_mutating: false,
+            throws: Throws::Never,
+        };
+        if let Some(ty) = &property.ty {
+            self.check_type(ty);
+        }
+        match &property.kind {
+            PropertyKind::Stored(stored) => {
+                if !is_type_property {
+                    match container {
+                        Container::Enum => self.error(format!("enums must not contain stored properties ('{name}')")),
+                        Container::Extension => {
+                            self.error(format!("extensions must not contain stored properties ('{name}')"))
+                        }
+                        Container::Struct | Container::Class => {}
+                    }
+                }
+                if property.is_class {
+                    self.error(format!("class stored properties not supported in classes; did you mean 'static'? ('{name}')"));
+                }
+                if stored.is_lazy && property.is_constant {
+                    self.error(format!("'lazy' cannot be used on a let ('{name}')"));
+                }
+                if stored.is_lazy && stored.initial_value.is_none() {
+                    self.error(format!("lazy properties must have an initializer ('{name}')"));
+                }
+                if stored.is_lazy && is_type_property {
+                    self.error(format!("'lazy' must not be used on an already-lazy static property ('{name}')"));
+                }
+                if is_type_property && stored.initial_value.is_none() {
+                    self.error(format!("'static var' declaration requires an initializer expression ('{name}')"));
+                }
+                if property.is_observed() && property.is_constant {
+                    self.error(format!("'let' declarations cannot be observing properties ('{name}')"));
+                }
+                if property.ty.is_none() && stored.initial_value.is_none() && !property.is_override {
+                    self.error(format!("type annotation missing in pattern ('{name}')"));
+                }
+                if let Some(value) = &stored.initial_value {
+                    // Only `lazy` initial values may refer to `self`.
+                    let value_context = if stored.is_lazy { context } else { Context { self_type: None, ..context } };
+                    self.with_context(value_context, |this| this.check_expression(value));
+                }
+                let ty = self.table.member_type(type_name, name);
+                if property.ty.is_none() && self.table.is_circular(type_name, name) {
+                    self.error(format!("circular reference ('{name}')"));
+                }
+                self.check_ownership(property.ownership, ty.as_ref(), property.is_constant);
+                for (observer, default_name) in [(&stored.will_set, "newValue"), (&stored.did_set, "oldValue")] {
+                    let Some(observer) = observer else { continue };
+                    let observer_context = Context { is_mutating: true, ..context };
+                    self.with_context(observer_context, |this| {
+                        let parameter = observer.parameter_name.as_deref().unwrap_or(default_name);
+                        this.scope.declare(parameter, Binding { ty: ty.clone(), is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                        this.check_block(&observer.body);
+                    });
+                }
+            }
+            PropertyKind::Computed(computed) => {
+                if let Some(body) = &computed.getter.body {
+                    let owner = format!("property '{name}'");
+                    self.with_context(context, |this| this.check_returning_body(body, property.ty.as_ref(), &owner));
+                }
+                if let Some(setter) = &computed.setter {
+                    let setter_context = Context { is_mutating: true, ..context };
+                    self.with_context(setter_context, |this| {
+                        if setter.parameters.is_empty() {
+                            let ty = this.table.member_type(type_name, name);
+                            this.scope.declare("newValue", Binding { ty, is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                        }
+                        this.declare_parameters(&setter.parameters);
+                        if let Some(body) = &setter.body {
+                            this.check_block(body);
+                        }
+                    });
+                }
+            }
+        }
+        if property.setter_access_control.is_some() && !property.is_settable() {
+            self.error(format!("'private(set)' modifier cannot be applied to read-only property '{name}'"));
+        }
+    }
+
+    fn check_function_modifiers(&mut self, function: &FunDeclaration, container: Option<Container>) {
+        let name = &function.name;
+        if function.is_mutating {
+            match container {
+                None => self.error(format!("'mutating' is only valid on methods ('{name}')")),
+                Some(Container::Class) => {
+                    self.error(format!("'mutating' is not valid on instance methods in classes ('{name}')"))
+                }
+                Some(_) if function.is_static || function.is_class => {
+                    self.error(format!("static functions must not be declared mutating ('{name}')"))
+                }
+                Some(_) => {}
+            }
+        }
+        if function.is_class && !matches!(container, Some(Container::Class | Container::Extension)) {
+            self.error(format!(
+                "class methods are only allowed within classes; use 'static' to declare a static method '{name}'"
+            ));
+        }
+        if function.is_static && container.is_none() {
+            self.error(format!("static methods may only be declared on a type ('{name}')"));
+        }
+    }
+
+    fn check_function(&mut self, function: &'a FunDeclaration, context: Context<'a>) {
+        if let Some(ty) = &function.return_type {
+            self.check_type(ty);
+        }
+        let Some(body) = &function.body else { return };
+        let kind = if context.self_type.is_some() { method_kind(function) } else { "global function" };
+        let owner = format!("{kind} '{}'", signature(&function.name, function.parameters.iter().map(|parameter| parameter.label.as_deref())));
+        self.with_context(Context { throws: Throws::of(function), ..context }, |this| {
+            this.declare_parameters(&function.parameters);
+            this.check_returning_body(body, function.return_type.as_ref(), &owner);
+        });
+    }
+
+    /// Checks the body of a function or getter. An opaque result type is inferred from the body:
+    /// every `return` must give it the same underlying type, which must meet its constraints.
+    fn check_returning_body(&mut self, body: &'a StatementSequence, result: Option<&SwiftType>, owner: &str) {
+        let Some(SwiftType::Opaque(constraint)) = result else {
+            self.check_block(body);
+            return;
+        };
+        self.returns = Some(Vec::new());
+        self.check_block(body);
+        let mut returns = self.returns.take().unwrap_or_default();
+        // A single-expression body returns its value implicitly.
+        if let ([Statement::Expression(expression)], true) = (body.statements(), returns.is_empty()) {
+            returns.push(self.infer(expression));
+        }
+        if returns.is_empty() {
+            self.error("function declares an opaque return type, but has no return statements in its body from which to infer an underlying type");
+            return;
+        }
+        // Returns whose type can't be inferred locally are given the benefit of the doubt.
+        let mut types = returns.into_iter().flatten();
+        let Some(underlying) = types.next() else { return };
+        if types.any(|ty| ty != underlying) {
+            self.error("function declares an opaque return type, but the return statements in its body do not have matching underlying types");
+            return;
+        }
+        let constraints = match constraint.as_ref() {
+            SwiftType::Composition(types) => types.clone(),
+            constraint => vec![constraint.clone()],
+        };
+        let unmet = constraints
+            .into_iter()
+            .filter(|constraint| self.table.satisfies(&underlying, constraint) == Some(false))
+            .map(|constraint| GenericRequirement::Conformance { subject: underlying.clone(), constraint })
+            .collect();
+        self.report_unmet_requirements(&format!("return type of {owner}"), unmet);
+    }
+
+    /// Reports an integer literal stored as a fixed-width type it doesn't fit in.
+    fn check_integer_literal(&mut self, value: &Expression, ty: Option<&SwiftType>) {
+        let Some(SwiftType::SizedInteger(integer)) = ty else { return };
+        let literal = match value {
+            Expression::Literal(expression::Literal::Integer(value)) => i128::from(*value),
+            Expression::UnaryExpression(unary) if unary.operator.symbol == "-" => match unary.operand.as_ref() {
+                Expression::Literal(expression::Literal::Integer(value)) => -i128::from(*value),
+                _ => return,
+            },
+            _ => return,
+        };
+        let name = integer.name();
+        if literal < 0 && !integer.is_signed() {
+            self.error(format!("negative integer '{literal}' overflows when stored into unsigned type '{name}'"));
+            return;
+        }
+        let bits = if integer.is_signed() { integer.bits() - 1 } else { integer.bits() };
+        if literal >= 1i128 << bits || literal < -(1i128 << bits) {
+            self.error(format!("integer literal '{literal}' overflows when stored into '{name}'"));
+        }
+    }
+
+    /// Reports a string literal stored as a `Character` that isn't exactly one extended grapheme cluster.
+    fn check_character_literal(&mut self, value: &Expression, ty: Option<&SwiftType>) {
+        let Expression::Literal(expression::Literal::String(literal)) = value else { return };
+        if ty == Some(&SwiftType::Character) && literal.graphemes(true).count() != 1 {
+            self.error("cannot convert value of type 'String' to specified type 'Character'");
+        }
+    }
+
+    /// Reports a type declared in the program that keys a `Set` or `Dictionary` without being
+    /// `Hashable`.
+    fn check_hashable(&mut self, key: &SwiftType) {
+        let SwiftType::Custom(name) = key else { return };
+        let is_declared = matches!(self.table.nominal(name), Some(NominalType::Struct(_) | NominalType::Enum(_) | NominalType::Class(_)));
+        if is_declared && !synthesis::is_hashable(self.table, name) {
+            self.error(format!("type '{name}' does not conform to protocol 'Hashable'"));
+        }
+    }
+
+    /// Checks the constraints written in existential, opaque and composition types.
+    fn check_type(&mut self, ty: &SwiftType) {
+        match ty {
+            SwiftType::Optional(inner)
+            | SwiftType::ImplicitlyUnwrappedOptional(inner)
+            | SwiftType::Array(inner)
+            | SwiftType::Metatype(inner)
+            | SwiftType::ProtocolMetatype(inner) => self.check_type(inner),
+            SwiftType::Generic(name, arguments) => {
+                let expected = match self.table.nominal(name) {
+                    Some(nominal) => Some(nominal.generics().map_or(0, |generics| generics.type_parameters.len())),
+                    None => match name.as_str() {
+                        "Array" | "Set" | "Optional" => Some(1),
+                        "Dictionary" => Some(2),
+                        name => standard_value_type(name).map(|_| 0),
+                    },
+                };
+                match expected {
+                    Some(0) => self.error(format!("cannot specialize non-generic type '{name}'")),
+                    Some(expected) if arguments.len() != expected => {
+                        let amount = if arguments.len() > expected { "many" } else { "few" };
+                        self.error(format!(
+                            "generic type '{name}' specialized with too {amount} type parameters (got {}, but expected {expected})",
+                            arguments.len()
+                        ));
+                    }
+                    _ => {}
+                }
+                arguments.iter().for_each(|ty| self.check_type(ty));
+            }
+            SwiftType::Set(element) => {
+                self.check_hashable(element);
+                self.check_type(element);
+            }
+            SwiftType::Dictionary(key, value) => {
+                self.check_hashable(key);
+                self.check_type(key);
+                self.check_type(value);
+            }
+            SwiftType::Tuple(types) | SwiftType::LabeledTuple(_, types) => types.iter().for_each(|ty| self.check_type(ty)),
+            SwiftType::Function(parameters, result, _) => {
+                parameters.iter().for_each(|ty| self.check_type(ty));
+                self.check_type(result);
+            }
+            SwiftType::Existential(constraint) => {
+                if self.is_concrete_type(constraint, true) {
+                    self.warning(format!("'any' has no effect on concrete type '{constraint}'"));
+                }
+                self.check_type(constraint);
+            }
+            SwiftType::Opaque(constraint) => {
+                if self.is_concrete_type(constraint, false) {
+                    self.error("an 'opaque' type must specify only 'Any', 'AnyObject', protocols, and/or a base class");
+                }
+                self.check_type(constraint);
+            }
+            SwiftType::Composition(types) => {
+                let mut class = None;
+                for ty in types {
+                    if self.is_concrete_type(ty, false) {
+                        self.error(format!("non-protocol, non-class type '{ty}' cannot be used within a protocol-constrained type"));
+                    } else if let SwiftType::Custom(name) = ty {
+                        if matches!(self.table.nominal(name), Some(NominalType::Class(_))) {
+                            if let Some(first) = class.replace(name) {
+                                self.error(format!(
+                                    "protocol-constrained type cannot contain class '{name}' because it already contains class '{first}'"
+                                ));
+                            }
+                        }
+                    }
+                    self.check_type(ty);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// True for a type that is neither a protocol nor a composition of them; classes count only
+    /// when `classes` is set, since opaque types and compositions may name a base class.
+    fn is_concrete_type(&self, ty: &SwiftType, classes: bool) -> bool {
+        match ty {
+            SwiftType::Custom(name) | SwiftType::Generic(name, _) => match self.table.nominal(name) {
+                Some(NominalType::Struct(_) | NominalType::Enum(_)) => true,
+                Some(NominalType::Class(_)) => classes,
+                _ => false,
+            },
+            SwiftType::Existential(_)
+            | SwiftType::Opaque(_)
+            | SwiftType::Composition(_)
+            | SwiftType::Nested(..)
+            | SwiftType::Any
+            | SwiftType::AnyObject => false,
+            _ => true,
+        }
+    }
+
+    fn declare_parameters(&mut self, parameters: &'a [FunctionParameter]) {
+        for parameter in parameters {
+            self.check_type(&parameter.ty);
+            if let Some(default_value) = &parameter.default_value {
+                self.check_expression(default_value);
+            }
+            let ty = if parameter.is_variadic {
+                SwiftType::Array(Box::new(parameter.ty.clone()))
+            } else {
+                parameter.ty.clone()
+            };
+            self.scope.declare(&parameter.internal_name, Binding {
+                ty: Some(ty),
+                is_constant: !parameter.is_inout,
+                is_inout: parameter.is_inout,
+                ownership: ReferenceOwnership::Strong,
+            });
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Statements
+    // ---------------------------------------------------------------------
+
+    fn check_block(&mut self, body: &'a StatementSequence) {
+        self.scope.push();
+        for statement in body.statements() {
+            self.check_statement(statement);
+        }
+        self.scope.pop();
+    }
+
+    fn check_statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::Break(_) | Statement::Continue(_) => {}
+            Statement::Expression(expression) => self.check_expression(expression),
+            Statement::Declaration(declaration) => self.check_declaration(declaration),
+            Statement::Return(statement) => {
+                if let Some(expression) = &statement.expression {
+                    self.check_expression(expression);
+                    let ty = self.infer(expression);
+                    if let Some(returns) = &mut self.returns {
+                        returns.push(ty);
+                    }
+                    let returns_nil = matches!(expression.as_ref(), Expression::Literal(expression::Literal::Nil));
+                    let is_failable = self.initializer.as_ref().is_some_and(|state| state.decl.is_failable);
+                    if returns_nil && self.context.in_initializer && !is_failable {
+                        self.error("only a failable initializer can return 'nil'");
+                    }
+                }
+            }
+            Statement::If(statement) => {
+                self.check_expression(&statement.condition);
+                self.check_block(&statement.body);
+                if let Some(else_body) = &statement.else_body {
+                    self.check_block(else_body);
+                }
+            }
+            Statement::ForLoop(statement) => {
+                self.check_expression(&statement.range.0);
+                self.check_expression(&statement.range.1);
+                self.scope.push();
+                self.scope.declare(&statement.variable, Binding {
+                    ty: Some(SwiftType::Integer),
+                    is_constant: true,
+                    is_inout: false,
+                    ownership: ReferenceOwnership::Strong,
+                });
+                self.check_block(&statement.body);
+                self.scope.pop();
+            }
+            Statement::ForIn(statement) => {
+                self.check_expression(&statement.sequence);
+                let sequence = self.infer(&statement.sequence);
+                let element = sequence.as_ref().and_then(stdlib::element_type);
+                if let Some(ty) = sequence.filter(|ty| element.is_none() && standard_value_type(&ty.to_string()).is_some()) {
+                    self.error(format!("for-in loop requires '{ty}' to conform to 'Sequence'"));
+                }
+                self.scope.push();
+                self.scope.declare(&statement.variable, Binding {
+                    ty: element,
+                    is_constant: true,
+                    is_inout: false,
+                    ownership: ReferenceOwnership::Strong,
+                });
+                self.check_block(&statement.body);
+                self.scope.pop();
+            }
+            Statement::WhileLoop(statement) => {
+                self.check_expression(&statement.condition);
+                self.check_block(&statement.body);
+            }
+            Statement::RepeatWhileLoop(statement) => {
+                self.check_block(&statement.body);
+                self.check_expression(&statement.condition);
+            }
+            Statement::Switch(statement) => self.check_switch(statement),
+            Statement::Guard(statement) => {
+                self.check_expression(&statement.condition);
+                self.check_block(&statement.body);
+            }
+            Statement::Throw(statement) => {
+                self.check_expression(&statement.expression);
+                match self.unhandled(false) {
+                    None => {}
+                    Some(Unhandled::NotThrowing) => self.error("error is not handled because the enclosing function is not declared 'throws'"),
+                    Some(Unhandled::Rethrowing) => self.error("a function declared 'rethrows' may only throw if its parameter does"),
+                    Some(Unhandled::InexhaustiveCatch) => self.error("error is not handled because the enclosing catch is not exhaustive"),
+                }
+            }
+            Statement::DoCatch(statement) => self.check_do_catch(statement),
+            Statement::Assignment(statement) => {
+                self.check_expression(&statement.value);
+                self.check_expression(&statement.target);
+                self.check_assignment_target(&statement.target);
+            }
+        }
+    }
+
+    /// Checks a `do` statement. A catch clause without a pattern binds the error as `error`.
+    fn check_do_catch(&mut self, statement: &'a statement::DoCatchStatement) {
+        if statement.catch_clauses.is_empty() {
+            self.check_block(&statement.body);
+            return;
+        }
+        let is_exhaustive = statement.catch_clauses.iter().any(statement::CatchClause::is_exhaustive);
+        self.handlers.push(Handler::Do { is_exhaustive });
+        self.check_block(&statement.body);
+        self.handlers.pop();
+        for clause in &statement.catch_clauses {
+            self.scope.push();
+            let bindings = match &clause.pattern {
+                Some(pattern) => self.table.pattern_bindings(pattern, None),
+                None => vec![("error", None)],
+            };
+            for (name, ty) in bindings {
+                self.scope.declare(name, Binding { ty, is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+            }
+            if let Some(guard) = &clause.guard_expression {
+                self.check_expression(guard);
+            }
+            self.check_block(&clause.body);
+            self.scope.pop();
+        }
+    }
+
+    /// Why errors thrown at the point being checked would escape the enclosing function when it
+    /// can't throw them, or `None` if something handles them. The callee of a call `may_rethrow`
+    /// when it is one of the function's parameters. Closures throwing errors are recorded.
+    fn unhandled(&mut self, may_rethrow: bool) -> Option<Unhandled> {
+        let mut is_inexhaustive = false;
+        for handler in self.handlers.iter().rev() {
+            match handler {
+                Handler::Do { is_exhaustive: true } | Handler::Try => return None,
+                Handler::Do { is_exhaustive: false } => is_inexhaustive = true,
+                Handler::Closure(closure) => {
+                    self.throwing_closures.insert(*closure);
+                    return None;
+                }
+            }
+        }
+        match self.context.throws {
+            Throws::Any => None,
+            Throws::Rethrows if may_rethrow => None,
+            _ if is_inexhaustive => Some(Unhandled::InexhaustiveCatch),
+            Throws::Rethrows => Some(Unhandled::Rethrowing),
+            Throws::Never => Some(Unhandled::NotThrowing),
+        }
+    }
+
+    /// A call that can throw must be marked with `try`, and its errors handled.
+    fn check_call_effects(&mut self, call: &'a expression::CallExpression) {
+        let Some(throwing) = self.table.call_throwing(&self.scope, self.context.self_type, call) else { return };
+        let rethrown = call.arguments.iter().map(|argument| &argument.value).chain(call.trailing_closures.iter().map(|closure| &closure.closure));
+        let may_rethrow = match throwing {
+            Throwing::Always => self.is_function_parameter(&call.callee),
+            Throwing::Rethrows if rethrown.clone().any(|argument| self.is_throwing_function(argument)) => true,
+            Throwing::Rethrows => return,
+        };
+        match (self.tries > 0, self.unhandled(may_rethrow)) {
+            (false, None) => self.error("call can throw but is not marked with 'try'"),
+            (false, Some(_)) => self.error("call can throw, but it is not marked with 'try' and the error is not handled"),
+            (true, None) => {}
+            (true, Some(Unhandled::NotThrowing)) => self.error("errors thrown from here are not handled"),
+            (true, Some(Unhandled::Rethrowing)) => {
+                self.error("call can throw, but the error is not handled; a function declared 'rethrows' may only throw if its parameter does")
+            }
+            (true, Some(Unhandled::InexhaustiveCatch)) => {
+                self.error("errors thrown from here are not handled because the enclosing catch is not exhaustive")
+            }
+        }
+    }
+
+    /// True for a function-typed parameter of the function being checked, the only callee whose
+    /// errors a function declared `rethrows` may throw. Locals holding functions don't count.
+    fn is_function_parameter(&self, callee: &Expression) -> bool {
+        let Expression::Identifier(identifier) = callee else { return false };
+        matches!(
+            self.scope.lookup_frame(&identifier.name),
+            Some((frame, Binding { ty: Some(SwiftType::Function(..)), .. })) if frame == self.parameter_frame
+        )
+    }
+
+    /// True for a closure that throws, or a value of a throwing function type.
+    fn is_throwing_function(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Closure(closure) => self.throwing_closures.contains(&(closure.as_ref() as *const expression::Closure as usize)),
+            expression => matches!(self.infer(expression), Some(SwiftType::Function(_, _, attributes)) if attributes.is_throwing),
+        }
+    }
+
+    fn check_switch(&mut self, statement: &'a statement::SwitchStatement) {
+        self.check_expression(&statement.expression);
+        let subject = self.infer(&statement.expression);
+        for case in &statement.cases {
+            self.scope.push();
+            if let Some(pattern) = case.patterns.first() {
+                for (name, ty) in self.table.pattern_bindings(pattern, subject.as_ref()) {
+                    self.scope.declare(name, Binding { ty, is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                }
+            }
+            if let Some(guard) = &case.guard_expression {
+                self.check_expression(guard);
+            }
+            self.check_block(&case.body);
+            self.scope.pop();
+        }
+        if let Some(default_case) = &statement.default_case {
+            self.check_block(default_case);
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Expressions
+    // ---------------------------------------------------------------------
+
+    fn check_expression(&mut self, expression: &'a Expression) {
+        match expression {
+            Expression::Literal(expression::Literal::Character(literal)) => {
+                if literal.graphemes(true).count() != 1 {
+                    self.error(format!("character literal '{literal}' must be a single extended grapheme cluster"));
+                }
+            }
+            Expression::SuperExpression | Expression::Literal(_) | Expression::KeyPath(_) | Expression::TypeExpression(_) => {}
+            Expression::SelfExpression => self.check_self_capture(),
+            Expression::Identifier(identifier) => match self.scope.lookup_frame(&identifier.name) {
+                Some((frame, binding)) => {
+                    let ty = binding.ty.clone();
+                    self.check_capture(&identifier.name, frame, ty);
+                }
+                None => {
+                    self.check_self_use(&identifier.name);
+                    let is_member = self
+                        .context
+                        .self_type
+                        .is_some_and(|name| self.table.member(name, &identifier.name).is_some());
+                    if is_member {
+                        self.check_self_capture();
+                    }
+                }
+            },
+            Expression::BinaryExpression(binary) => {
+                self.check_expression(&binary.left);
+                self.check_expression(&binary.right);
+                if is_compound_assignment(&binary.operator.symbol) {
+                    self.check_assignment_target(&binary.left);
+                }
+            }
+            Expression::UnaryExpression(unary) => self.check_expression(&unary.operand),
+            Expression::StringInterpolation(interpolation) => interpolation.values().for_each(|value| self.check_expression(value)),
+            Expression::CallExpression(call) => self.check_call(call),
+            Expression::Try(expression) => {
+                let is_handler = expression.kind != expression::TryKind::Propagating;
+                if is_handler {
+                    self.handlers.push(Handler::Try);
+                }
+                self.tries += 1;
+                self.check_expression(&expression.expression);
+                self.tries -= 1;
+                if is_handler {
+                    self.handlers.pop();
+                }
+            }
+            Expression::Closure(closure) => {
+                let captures = self.check_capture_list(&closure.capture_list);
+                // Uses of `self` captured weakly or unowned are not reported.
+                let reported = closure
+                    .capture_list
+                    .iter()
+                    .filter(|item| item.name == "self" && item.value.is_none() && item.ownership != ReferenceOwnership::Strong)
+                    .map(|item| item.name.clone())
+                    .collect();
+                self.closures.push(ClosureCaptures { depth: self.scope.depth(), reported });
+                for (item, ty) in closure.capture_list.iter().zip(&captures) {
+                    if item.ownership == ReferenceOwnership::Strong {
+                        self.check_capture(&item.name, 0, ty.clone());
+                    }
+                }
+                self.scope.push();
+                for (item, ty) in closure.capture_list.iter().zip(captures) {
+                    if item.name != "self" || item.value.is_some() {
+                        self.scope.declare(&item.name, Binding { ty, is_constant: true, is_inout: false, ownership: item.ownership });
+                    }
+                }
+                for parameter in &closure.parameters {
+                    self.scope.declare(&parameter.name, Binding {
+                        ty: parameter.type_annotation.clone(),
+                        is_constant: true,
+                        is_inout: false,
+                        ownership: ReferenceOwnership::Strong,
+                    });
+                }
+                // A closure's `return`s give its own result, not the enclosing function's, and the
+                // errors its body throws are its own.
+                let saved_returns = self.returns.take();
+                let saved_tries = std::mem::take(&mut self.tries);
+                self.handlers.push(Handler::Closure(closure.as_ref() as *const expression::Closure as usize));
+                for statement in &closure.body {
+                    self.check_statement(statement);
+                }
+                self.handlers.pop();
+                self.tries = saved_tries;
+                self.returns = saved_returns;
+                self.scope.pop();
+                self.closures.pop();
+            }
+            Expression::Subscript(subscript) => {
+                self.check_expression(&subscript.target);
+                self.check_expression(&subscript.index);
+                if let Some(default_value) = &subscript.default_value {
+                    self.check_expression(default_value);
+                }
+            }
+            Expression::Conditional(conditional) => {
+                self.check_expression(&conditional.condition);
+                self.check_expression(&conditional.true_expression);
+                self.check_expression(&conditional.false_expression);
+            }
+            Expression::Tuple(tuple) => tuple.elements.iter().for_each(|element| self.check_expression(element)),
+            Expression::Array(array) => array.elements.iter().for_each(|element| self.check_expression(element)),
+            Expression::Dictionary(dictionary) => {
+                for (key, value) in &dictionary.elements {
+                    self.check_expression(key);
+                    self.check_expression(value);
+                }
+            }
+            Expression::MemberAccess(access) => {
+                self.check_expression(&access.target);
+                if matches!(access.target.as_ref(), Expression::SelfExpression) {
+                    self.check_self_use(&access.member);
+                }
+                self.check_constrained_member(access);
+                self.check_standard_member(access);
+                self.check_tuple_member(access);
+            }
+            Expression::TypeCasting(cast) => self.check_expression(&cast.expression),
+            Expression::PatternMatch(pattern_match) => {
+                self.check_expression(&pattern_match.pattern);
+                self.check_expression(&pattern_match.expression);
+            }
+            Expression::Assignment(assignment) => {
+                self.check_expression(&assignment.value);
+                self.check_expression(&assignment.target);
+                self.check_assignment_target(&assignment.target);
+            }
+        }
+    }
+
+    fn check_call(&mut self, call: &'a expression::CallExpression) {
+        self.check_expression(&call.callee);
+        for argument in &call.arguments {
+            self.check_expression(&argument.value);
+            if argument.is_inout {
+                self.check_assignment_target(&argument.value);
+            }
+        }
+        for closure in &call.trailing_closures {
+            self.check_expression(&closure.closure);
+        }
+        self.check_call_effects(call);
+        if let Some((delegation, _)) = sema::delegation_call(call) {
+            self.check_delegation(delegation, call);
+            return;
+        }
+        self.check_generic_call(call);
+        self.check_specialized_construction(call);
+        self.check_mutating_call(call);
+        self.check_standard_call(call);
+        if let Some(type_name) = self.table.type_reference(&self.scope, &call.callee) {
+            let labels = self.table.construction_labels(type_name, call);
+            self.check_construction(type_name, &labels);
+        }
+    }
+
+    /// A mutating method of a value type needs a mutable receiver, as an assignment to it would.
+    fn check_mutating_call(&mut self, call: &'a expression::CallExpression) {
+        let Expression::MemberAccess(access) = call.callee.as_ref() else { return };
+        let Some(receiver) = self.infer(&access.target) else { return };
+        let Some(type_name) = nominal_name(&receiver) else { return };
+        let is_mutating = matches!(self.table.member(type_name, &access.member), Some(Member::Method(method)) if method.is_mutating);
+        if !is_mutating || !self.table.is_value_type(&receiver) {
+            return;
+        }
+        match access.target.as_ref() {
+            Expression::Identifier(identifier) if self.scope.lookup(&identifier.name).is_some_and(|binding| binding.is_constant && !binding.is_inout) => {
+                self.error(format!("cannot use mutating member on immutable value: '{}' is a 'let' constant", identifier.name));
+            }
+            target => self.check_value_base(target),
+        }
+    }
+
+    /// The standard library's members of a value must exist, unless an extension declares them.
+    fn check_standard_member(&mut self, access: &'a expression::MemberAccessExpression) {
+        let Some(receiver) = self.standard_receiver(access) else { return };
+        // Members of the wrapped value are reached through an optional, as if chained.
+        if !matches!(receiver, SwiftType::Optional(_)) && !stdlib::has_member(&receiver, &access.member) {
+            self.error(format!("value of type '{receiver}' has no member '{}'", access.member));
+        }
+    }
+
+    /// The members of a tuple are its elements, by position or label.
+    fn check_tuple_member(&mut self, access: &'a expression::MemberAccessExpression) {
+        let Some(ty @ (SwiftType::Tuple(_) | SwiftType::LabeledTuple(..))) = self.infer(&access.target) else { return };
+        if access.member != "self" && sema::tuple_index(&ty, &access.member).is_none() {
+            self.error(format!("value of tuple type '{ty}' has no member '{}'", access.member));
+        }
+    }
+
+    /// A call of a standard library method must match its labels, and a mutating one needs a
+    /// mutable receiver.
+    fn check_standard_call(&mut self, call: &'a expression::CallExpression) {
+        if let Expression::Identifier(identifier) = call.callee.as_ref() {
+            self.check_standard_function_call(&identifier.name, call);
+            return;
+        }
+        let Expression::MemberAccess(access) = call.callee.as_ref() else { return };
+        let Some(receiver) = self.standard_receiver(access) else { return };
+        match self.table.standard_method(&receiver, &access.member, call) {
+            Some(member) if member.is_mutating => match access.target.as_ref() {
+                Expression::Identifier(identifier) if self.scope.lookup(&identifier.name).is_some_and(|binding| binding.is_constant && !binding.is_inout) => {
+                    self.error(format!("cannot use mutating member on immutable value: '{}' is a 'let' constant", identifier.name));
+                }
+                target => self.check_assignment_target(target),
+            },
+            Some(_) => {}
+            // A member of another name was already reported.
+            None if stdlib::has_member(&receiver, &access.member) => {
+                self.error(format!("no exact matches in call to instance method '{}'", access.member));
+            }
+            None => {}
+        }
+    }
+
+    /// A call of a standard library function must match its labels, unless the program declares
+    /// something of that name where the call is.
+    fn check_standard_function_call(&mut self, name: &str, call: &'a expression::CallExpression) {
+        let is_declared = self.scope.lookup(name).is_some()
+            || self.table.function(name).is_some()
+            || self.table.nominal(name).is_some()
+            || self.context.self_type.is_some_and(|type_name| self.table.member(type_name, name).is_some());
+        if is_declared || !stdlib::has_global_function(name) {
+            return;
+        }
+        let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+        if stdlib::global_function(name, &labels).is_none() {
+            match name.starts_with(char::is_uppercase) {
+                true => self.error("no exact matches in call to initializer"),
+                false => self.error(format!("no exact matches in call to global function '{name}'")),
+            }
+        }
+    }
+
+    /// The type of a value whose members the standard library declares, unless an extension of the
+    /// type declares this one.
+    fn standard_receiver(&self, access: &expression::MemberAccessExpression) -> Option<SwiftType> {
+        if self.table.type_reference(&self.scope, &access.target).is_some() {
+            return None;
+        }
+        let receiver = self.infer(&access.target).filter(stdlib::has_members)?;
+        let is_extended = nominal_name(&receiver).is_some_and(|type_name| self.table.member(type_name, &access.member).is_some());
+        (!is_extended).then_some(receiver)
+    }
+
+    /// Checks the explicit arguments of a construction like `Stack<Int>()` against the requirements
+    /// of the type's generic signature.
+    fn check_specialized_construction(&mut self, call: &'a expression::CallExpression) {
+        let (Expression::Identifier(identifier), Some(arguments)) = (call.callee.as_ref(), &call.generic_type_arguments) else { return };
+        let Some(nominal) = self.table.nominal(&identifier.name) else { return };
+        let Some(generics) = nominal.generics() else { return };
+        let kind = match nominal {
+            NominalType::Struct(_) => "generic struct",
+            NominalType::Enum(_) => "generic enum",
+            NominalType::Class(_) => "generic class",
+            NominalType::Protocol(_) => return,
+        };
+        let substitution = Substitution::specializing(generics, arguments);
+        let unmet = self.table.unmet_requirements(&TypeTable::generic_requirements(generics), &substitution);
+        self.report_unmet_requirements(&format!("{kind} '{}'", identifier.name), unmet);
+    }
+
+    /// Checks the arguments of a call to a generic function or method against the requirements of its
+    /// generic signature, as far as their types are known.
+    fn check_generic_call(&mut self, call: &'a expression::CallExpression) {
+        let (kind, function) = match call.callee.as_ref() {
+            Expression::Identifier(identifier) if self.scope.lookup(&identifier.name).is_none() => {
+                let member = self.context.self_type.and_then(|name| self.table.member(name, &identifier.name));
+                match (member, self.table.function(&identifier.name)) {
+                    (Some(Member::Method(method)), _) => (method_kind(method), method),
+                    (None, Some(function)) => ("global function", function),
+                    _ => return,
+                }
+            }
+            Expression::MemberAccess(access) => {
+                let type_name = match self.table.type_reference(&self.scope, &access.target) {
+                    Some(name) => Some(name.to_string()),
+                    None => self.infer(&access.target).as_ref().and_then(nominal_name).map(str::to_string),
+                };
+                match type_name.and_then(|name| self.table.member(&name, &access.member)) {
+                    Some(Member::Method(method)) => (method_kind(method), method),
+                    _ => return,
+                }
+            }
+            _ => return,
+        };
+        let Some(generics) = &function.generics else { return };
+        let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+        let arguments = call.arguments.iter().map(|argument| self.infer(&argument.value)).collect::<Vec<_>>();
+        let substitution = self.table.generic_arguments(generics, &function.parameters, &labels, &arguments);
+        let name = signature(&function.name, function.parameters.iter().map(|parameter| parameter.label.as_deref()));
+        let unmet = self.table.unmet_requirements(&TypeTable::generic_requirements(generics), &substitution);
+        self.report_unmet_requirements(&format!("{kind} '{name}'"), unmet);
+    }
+
+    /// Reports a member reached through a value whose type doesn't meet the `where` clause of the
+    /// extension providing it: of a protocol it conforms to, or of its own generic type.
+    fn check_constrained_member(&mut self, access: &'a expression::MemberAccessExpression) {
+        let type_name = match self.table.type_reference(&self.scope, &access.target) {
+            Some(name) => Some(name.to_string()),
+            None => self.infer(&access.target).as_ref().and_then(nominal_name).map(str::to_string),
+        };
+        let Some(type_name) = type_name else { return };
+        let (owner, member, unmet) = match self.table.unavailable_default(&type_name, &access.member) {
+            Some((protocol, member, unmet)) => (protocol.to_string(), member, unmet),
+            None => {
+                // A member of a generic type from a constrained extension needs its arguments to
+                // meet the `where` clause.
+                let Some(ty) = self.infer(&access.target) else { return };
+                let Some((member, unmet)) = self.table.unavailable_extension_member(&ty, &access.member) else { return };
+                (type_name, member, unmet)
+            }
+        };
+        let name = match member {
+            Member::Method(method) => {
+                let labels = method.parameters.iter().map(|parameter| parameter.label.as_deref());
+                format!("{} '{}'", method_kind(method), signature(&method.name, labels))
+            }
+            Member::Property(property) => format!("property '{}'", property.name),
+            Member::Case(case) => format!("enum case '{}'", case.name),
+        };
+        self.report_unmet_requirements(&format!("referencing {name} on '{owner}'"), unmet);
+    }
+
+    /// Reports the requirements of a generic signature or `where` clause that a use fails, as
+    /// `global function 'f(_:)' requires that 'X' conform to 'P'`.
+    fn report_unmet_requirements(&mut self, subject: &str, unmet: Vec<GenericRequirement>) {
+        for requirement in unmet {
+            match requirement {
+                GenericRequirement::Conformance { subject: ty, constraint } => {
+                    let relation = match &constraint {
+                        SwiftType::Custom(name) if matches!(self.table.nominal(name), Some(NominalType::Class(_))) => "inherit from",
+                        _ => "conform to",
+                    };
+                    self.error(format!("{subject} requires that '{ty}' {relation} '{constraint}'"));
+                }
+                GenericRequirement::SameType { left, right } => {
+                    self.error(format!("{subject} requires the types '{left}' and '{right}' be equivalent"));
+                }
+            }
+        }
+    }
+
+    /// Checks a `self.init` or `super.init` call, which completes the first phase of initialization.
+    fn check_delegation(&mut self, delegation: Delegation, call: &'a expression::CallExpression) {
+        let Some(self_type) = self.context.self_type.filter(|_| self.context.in_initializer) else {
+            self.error("initializer delegation can only occur within an initializer");
+            return;
+        };
+        let type_name = match delegation {
+            Delegation::ToSelf => Some(self_type),
+            Delegation::ToSuper => self.table.superclass(self_type),
+        };
+        let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+        if let Some(type_name) = type_name {
+            match self.table.initializer(type_name, &labels) {
+                Some(target) => {
+                    let name = signature("init", target.parameters.iter().map(|parameter| parameter.label.as_deref()));
+                    if delegation == Delegation::ToSuper && target.is_convenience {
+                        self.error(format!("must call a designated initializer of the superclass '{type_name}'"));
+                    }
+                    let is_failable = self.initializer.as_ref().is_some_and(|state| state.decl.is_failable);
+                    if target.is_failable && !is_failable {
+                        self.error(format!(
+                            "a non-failable initializer cannot delegate to failable initializer '{name}' written with 'init?'"
+                        ));
+                    }
+                }
+                None => self.check_construction(type_name, &labels),
+            }
+        }
+        if let Some(state) = self.initializer.as_mut().filter(|state| state.awaiting == Some(delegation)) {
+            state.awaiting = None;
+        }
+    }
+
+    /// Reports uses of `self` in an initializer before the delegation that must precede them.
+    /// Before `super.init`, a subclass may only assign its own stored properties.
+    fn check_self_use(&mut self, name: &str) {
+        let Some(awaiting) = self.initializer.as_ref().and_then(|state| state.awaiting) else { return };
+        let Some(self_type) = self.context.self_type else { return };
+        let Some(member) = self.table.member(self_type, name) else { return };
+        if member.is_static() {
+            return;
+        }
+        match (awaiting, member) {
+            (Delegation::ToSuper, Member::Method(_)) => {
+                self.error(format!("'self' used in method call '{name}' before 'super.init' call"));
+            }
+            (Delegation::ToSuper, Member::Property(property)) => {
+                let is_own_storage = property.has_storage() && self.table.member_owner(self_type, name) == Some(self_type);
+                if !is_own_storage {
+                    self.error(format!("'self' used in property access '{name}' before 'super.init' call"));
+                }
+            }
+            (Delegation::ToSelf, Member::Method(_) | Member::Property(_)) => {
+                self.error("'self' used before 'self.init' call or assignment to 'self'");
+            }
+            (_, Member::Case(_)) => {}
+        }
+    }
+
+    /// Reports constructions `T(...)` that no accessible initializer of `T` accepts.
+    fn check_construction(&mut self, type_name: &str, labels: &[Option<&str>]) {
+        let Some(nominal) = self.table.nominal(type_name) else { return };
+        if let Some(initializer) = self.table.initializer(type_name, labels) {
+            if initializer.access_control == AccessControl::Private && self.context.self_type != Some(type_name) {
+                let name = signature("init", initializer.parameters.iter().map(|parameter| parameter.label.as_deref()));
+                self.error(format!("'{name}' is inaccessible due to 'private' protection level"));
+            }
+            return;
+        }
+        let candidates = nominal
+            .initializers()
+            .iter()
+            .chain(self.table.extensions(type_name).iter().flat_map(|extension| extension.initializers.iter()))
+            .map(|initializer| signature("init", initializer.parameters.iter().map(|parameter| parameter.label.as_deref())))
+            .collect::<Vec<_>>();
+        let accepted = match nominal {
+            NominalType::Enum(decl) => match labels {
+                [Some("rawValue")] => decl.raw_type.is_some(),
+                [Some("from")] => synthesis::derives_coding(self.table, type_name, "init(from:)"),
+                _ => false,
+            },
+            NominalType::Protocol(_) => {
+                self.error(format!("'{type_name}' cannot be constructed because it has no accessible initializers"));
+                return;
+            }
+            NominalType::Struct(_) | NominalType::Class(_) => candidates.is_empty() && labels.is_empty(),
+        };
+        if accepted {
+            return;
+        }
+        let call = signature(type_name, labels.iter().copied());
+        if candidates.is_empty() {
+            self.error(format!("no initializer of '{type_name}' accepts the call '{call}'"));
+        } else {
+            let candidates = candidates.join(", ");
+            self.error(format!("no initializer of '{type_name}' accepts the call '{call}'; candidates are: {candidates}"));
+        }
+    }
+
+    /// Reports assignments to constants, get-only properties, and properties whose setter is inaccessible.
+    fn check_assignment_target(&mut self, target: &'a Expression) {
+        match target {
+            Expression::Identifier(identifier) if self.scope.lookup(&identifier.name).is_none() => {
+                let Some(self_type) = self.context.self_type else { return };
+                self.check_property_assignment(self_type, &identifier.name, true);
+                if let Some(Member::Property(property)) = self.table.member(self_type, &identifier.name) {
+                    if !(property.is_static || property.is_class) {
+                        self.check_self_is_mutable();
+                    }
+                }
+            }
+            Expression::MemberAccess(access) => {
+                let is_self = matches!(access.target.as_ref(), Expression::SelfExpression);
+                let type_name = match self.table.type_reference(&self.scope, &access.target) {
+                    Some(type_name) => Some(type_name.to_string()),
+                    None => self.infer(&access.target).as_ref().and_then(nominal_name).map(str::to_string),
+                };
+                if let Some(type_name) = type_name {
+                    if let Some(type_name) = self.table.nominal(&type_name).map(|nominal| nominal.name()) {
+                        self.check_property_assignment(type_name, &access.member, is_self);
+                    }
+                }
+                // Mutating a property of a value type mutates the value that holds it.
+                let is_value = self.infer(&access.target).map(|ty| self.table.is_value_type(&ty)).unwrap_or(false);
+                if is_value {
+                    self.check_value_base(&access.target);
+                }
+            }
+            Expression::Subscript(subscript) => self.check_value_base(&subscript.target),
+            _ => {}
+        }
+    }
+
+    /// The base of a mutated value-type path must itself be mutable.
+    fn check_value_base(&mut self, base: &'a Expression) {
+        match base {
+            Expression::Identifier(identifier) => match self.scope.lookup(&identifier.name) {
+                Some(binding) if binding.is_constant && !binding.is_inout => {
+                    let name = identifier.name.clone();
+                    self.error(format!("cannot assign to property: '{name}' is a 'let' constant"));
+                }
+                Some(_) => {}
+                None => self.check_assignment_target(base),
+            },
+            Expression::SelfExpression => self.check_self_is_mutable(),
+            Expression::MemberAccess(_) | Expression::Subscript(_) => self.check_assignment_target(base),
+            _ => {}
+        }
+    }
+
+    fn check_self_is_mutable(&mut self) {
+        let Some(self_type) = self.context.self_type else { return };
+        let is_value_type = self.table.nominal(self_type).map(|nominal| nominal.is_value_type()).unwrap_or(false);
+        if is_value_type && !self.context.is_mutating && !self.context.is_static {
+            self.error(format!("cannot assign to property: 'self' is immutable (in '{self_type}'; mark the method 'mutating')"));
+        }
+    }
+
+    fn check_property_assignment(&mut self, type_name: &str, name: &str, through_self: bool) {
+        let Some(Member::Property(property)) = self.table.member(type_name, name) else { return };
+        let owner = self.table.member_owner(type_name, name).unwrap_or(type_name);
+        let in_own_initializer = through_self && self.context.in_initializer && self.context.self_type == Some(owner);
+        match &property.kind {
+            PropertyKind::Computed(computed) if computed.setter.is_none() => {
+                self.error(format!("cannot assign to property: '{name}' is a get-only property"));
+                return;
+            }
+            PropertyKind::Stored(_) if property.is_constant && !in_own_initializer => {
+                self.error(format!("cannot assign to property: '{name}' is a 'let' constant"));
+                return;
+            }
+            _ => {}
+        }
+        if property.setter_access_control == Some(AccessControl::Private) && self.context.self_type != Some(owner) {
+            self.error(format!("cannot assign to property: '{name}' setter is inaccessible"));
+        }
+    }
+}
+
+/// How Swift refers to a method in diagnostics about its generic signature.
+fn method_kind(method: &FunDeclaration) -> &'static str {
+    if method.is_static || method.is_class {
+        "static method"
+    } else {
+        "instance method"
+    }
+}
+
+/// The generic parameters of a declaration with the constraints written after each (`None` for the
+/// parameter itself), as `check_generic_types` takes them.
+fn generic_parameters(generics: &GenericsDeclaration) -> Vec<(String, Option<SwiftType>)> {
+    generics
+        .type_parameters
+        .iter()
+        .flat_map(|parameter| {
+            let constraints = parameter.constraints.iter().map(|constraint| Some(constraint.clone()));
+            [None].into_iter().chain(constraints).map(|constraint| (parameter.name.clone(), constraint))
+        })
+        .collect()
+}
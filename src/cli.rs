@@ -0,0 +1,39 @@
+use crate::options::Options;
+use std::path::PathBuf;
+
+/// How to invoke the compiler, printed with errors in its arguments.
+pub const USAGE: &str = "usage: swift-oxide [--int number|bigint] <output.js>";
+
+/// What the command line asks for: the options to compile with, and where to write the module.
+#[derive(Debug, Clone)]
+pub struct Arguments {
+    pub options: Options,
+    pub output: PathBuf,
+}
+
+/// Parses the arguments after the program name. `--int` takes its value as the next argument or
+/// after `=`, and the one remaining argument is the output file.
+pub fn parse_arguments(arguments: impl IntoIterator<Item = String>) -> Result<Arguments, String> {
+    let mut options = Options::default();
+    let mut outputs = Vec::new();
+    let mut arguments = arguments.into_iter();
+    while let Some(argument) = arguments.next() {
+        let (name, inline_value) = match argument.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (argument.clone(), None),
+        };
+        match name.as_str() {
+            "--int" => {
+                let value = inline_value.or_else(|| arguments.next()).ok_or("missing value for '--int'")?;
+                options.int_representation = value.parse()?;
+            }
+            name if name.starts_with('-') => return Err(format!("unknown option '{name}'")),
+            _ => outputs.push(PathBuf::from(argument)),
+        }
+    }
+    match <[PathBuf; 1]>::try_from(outputs) {
+        Ok([output]) => Ok(Arguments { options, output }),
+        Err(outputs) if outputs.is_empty() => Err("missing output file".to_string()),
+        Err(_) => Err("expected one output file".to_string()),
+    }
+}
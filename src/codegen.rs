@@ -0,0 +1,2915 @@
+use crate::options::{IntRepresentation, Lifetime, Options};
+use crate::runtime;
+use crate::stdlib::{self, StandardMember};
+use crate::sema::{
+    self, function_type, is_compound_assignment, nominal_name, standard_value_type, Binding, Delegation, Member, NominalType, Scope,
+    Substitution, TypeTable, Witness,
+};
+use crate::synthesis;
+use crate::syntax::{Declaration, Expression, Statement, StatementSequence, SwiftType};
+use crate::syntax::declaration::{
+    ClassDeclaration,
+    DeinitializerDeclaration,
+    EnumDeclaration,
+    ExtensionDeclaration,
+    FunDeclaration,
+    FunctionParameter,
+    GenericRequirement,
+    GenericsDeclaration,
+    InitializerDeclaration,
+    PropertyKind,
+    ReferenceOwnership,
+    StructDeclaration,
+    VariablePropertyDeclaration,
+};
+use crate::syntax::expression::{self, InterpolationSegment, Literal, TryKind};
+use crate::syntax::statement::{self, Pattern};
+
+/// Compiles a whole program to an ES module.
+pub fn generate(program: &StatementSequence, options: &Options) -> String {
+    let table = TypeTable::new(program);
+    JsCodegen::new(&table, options).emit_program(program)
+}
+
+/// How `self` is spelled in the JavaScript emitted for the current function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Receiver {
+    /// Top-level code and free functions have no `self`.
+    None,
+    /// Instance methods, computed properties and class initializers.
+    This,
+    /// Type methods: `self` is the constructor, and only static members are implicit.
+    Type,
+    /// `mutating` methods receive `self` as an inout reference named `$self`.
+    Inout,
+    /// Struct and enum initializers build `self` in a local before returning it.
+    Local,
+}
+
+impl Receiver {
+    fn js(self) -> &'static str {
+        match self {
+            Receiver::None => "undefined",
+            Receiver::This | Receiver::Type => "this",
+            Receiver::Inout => "$self.value",
+            Receiver::Local => "self",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Context<'a> {
+    self_type: Option<&'a str>,
+    receiver: Receiver,
+    in_initializer: bool,
+    /// Inside a `willSet`/`didSet` observer, the property whose storage it accesses directly.
+    observing: Option<&'a str>,
+    /// The declared result type of the function or getter being emitted, which returned values are converted to.
+    result: Option<&'a SwiftType>,
+}
+
+impl Context<'_> {
+    const TOP_LEVEL: Context<'static> =
+        Context { self_type: None, receiver: Receiver::None, in_initializer: false, observing: None, result: None };
+}
+
+/// Where the members of a type are being emitted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MemberStyle {
+    /// Inside a `class { ... }` body.
+    ClassBody,
+    /// Assigned onto an existing class, as extensions do.
+    Assignment,
+}
+
+/// Emits JavaScript for Swift declarations, statements, and expressions.
+///
+/// Nominal types become JS classes: methods live on the prototype, type members are
+/// `static`, initializers are `$init$<labels>` methods, and enum cases are instances
+/// carrying a `$case` name and `$values` payload.
+///
+/// With reference counting, values holding class instances are either owned or borrowed.
+/// Calls, constructions and computed properties produce owned values, which storage takes over
+/// and other uses release; reading storage borrows, and storing a borrowed value retains it.
+/// Locals are released when their block exits.
+pub struct JsCodegen<'a> {
+    table: &'a TypeTable<'a>,
+    lifetime: Lifetime,
+    int_representation: IntRepresentation,
+    scope: Scope,
+    context: Context<'a>,
+    /// Innermost last; `Some` holds the label a Swift `break` must use to leave a `switch`.
+    break_targets: Vec<Option<String>>,
+    /// Innermost last; the reference-counted locals each enclosing block releases on exit.
+    block_locals: Vec<Vec<String>>,
+    /// Owned temporaries lent out by the full expression being emitted.
+    autoreleases: usize,
+    /// Inside a closure capturing `self` in its capture list, how it was captured.
+    captured_self: Option<ReferenceOwnership>,
+    /// Innermost last; generic parameters whose type metadata a hidden `$<name>` parameter holds.
+    metadata: Vec<String>,
+    /// The parameter types of the function type the closure being emitted is passed as.
+    closure_context: Vec<SwiftType>,
+    temporaries: usize,
+    out: String,
+    indent: usize,
+}
+
+impl<'a> JsCodegen<'a> {
+    pub fn new(table: &'a TypeTable<'a>, options: &Options) -> Self {
+        JsCodegen {
+            table,
+            lifetime: options.lifetime,
+            int_representation: options.int_representation,
+            scope: Scope::default(),
+            context: Context::TOP_LEVEL,
+            break_targets: Vec::new(),
+            block_locals: Vec::new(),
+            autoreleases: 0,
+            captured_self: None,
+            metadata: Vec::new(),
+            closure_context: Vec::new(),
+            temporaries: 0,
+            out: String::new(),
+            indent: 0,
+        }
+    }
+
+    pub fn emit_program(mut self, program: &'a StatementSequence) -> String {
+        self.line(&format!("import * as $rt from \"{}\";", runtime::MODULE_NAME));
+        if self.int_representation == IntRepresentation::BigInt {
+            self.line("$rt.useBigIntForInt();");
+        }
+        self.scope.push();
+        // Swift resolves type names regardless of declaration order, but JS classes are not hoisted.
+        // Protocols and their extensions come first, so that conforming types can install defaults.
+        let mut statements = program.statements().iter().collect::<Vec<_>>();
+        statements.sort_by_key(|statement| match statement {
+            Statement::Declaration(declaration) if self.is_protocol_declaration(declaration) => 0,
+            Statement::Declaration(declaration) if is_type_declaration(declaration) => 1,
+            _ => 2,
+        });
+        for statement in dependency_order(statements) {
+            self.emit_statement(statement);
+        }
+        self.out
+    }
+
+    /// True for protocols and extensions of protocols.
+    fn is_protocol_declaration(&self, declaration: &Declaration) -> bool {
+        match declaration {
+            Declaration::Protocol(_) => true,
+            Declaration::Extension(decl) => matches!(self.table.nominal(&decl.type_name), Some(NominalType::Protocol(_))),
+            _ => false,
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Output helpers
+    // ---------------------------------------------------------------------
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.indent {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn temporary(&mut self, prefix: &str) -> String {
+        self.temporaries += 1;
+        format!("${prefix}{}", self.temporaries)
+    }
+
+    fn with_context<R>(&mut self, context: Context<'a>, f: impl FnOnce(&mut Self) -> R) -> R {
+        let saved = std::mem::replace(&mut self.context, context);
+        let saved_breaks = std::mem::take(&mut self.break_targets);
+        let saved_metadata = self.metadata.len();
+        self.scope.push();
+        let result = f(self);
+        self.scope.pop();
+        self.metadata.truncate(saved_metadata);
+        self.break_targets = saved_breaks;
+        self.context = saved;
+        result
+    }
+
+    /// Brings hidden metadata parameters into scope for the rest of the context, and returns their
+    /// JS spelling.
+    fn declare_metadata(&mut self, names: Vec<String>) -> Vec<String> {
+        let parameters = names.iter().map(|name| format!("${name}")).collect();
+        self.metadata.extend(names);
+        parameters
+    }
+
+    /// The JS expression holding the type metadata of a generic parameter, if it is available here:
+    /// a hidden parameter, or the instance of a generic type that stores it.
+    fn metadata_value(&self, name: &str) -> Option<String> {
+        if self.metadata.iter().any(|parameter| parameter == name) {
+            return Some(format!("${name}"));
+        }
+        let self_type = self.context.self_type.filter(|_| !matches!(self.context.receiver, Receiver::Type | Receiver::None))?;
+        let stored = self.table.type_metadata(self_type).iter().any(|parameter| parameter == name);
+        stored.then(|| format!("{}.${name}", self.emit_self()))
+    }
+
+    /// The metadata a call passes for the generic parameters `names` of the callee: the type each is
+    /// bound to by explicit generic arguments or the types of the arguments, or `undefined`.
+    fn metadata_arguments(
+        &self,
+        generics: &GenericsDeclaration,
+        names: &[String],
+        parameters: &[FunctionParameter],
+        call: &expression::CallExpression,
+    ) -> Vec<String> {
+        let substitution = match &call.generic_type_arguments {
+            Some(types) => {
+                let mut substitution = Substitution::default();
+                for (parameter, ty) in generics.type_parameters.iter().zip(types) {
+                    substitution.bind(&parameter.name, ty.clone());
+                }
+                substitution
+            }
+            None => {
+                let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+                let arguments = call.arguments.iter().map(|argument| self.infer(&argument.value)).collect::<Vec<_>>();
+                self.table.generic_arguments(generics, parameters, &labels, &arguments)
+            }
+        };
+        names
+            .iter()
+            .map(|name| substitution.get(name).map_or_else(|| "undefined".to_string(), |ty| self.type_value(ty)))
+            .collect()
+    }
+
+    /// The metadata a call to a generic function or method passes ahead of its arguments.
+    fn function_metadata_arguments(&self, function: &'a FunDeclaration, call: &expression::CallExpression) -> Vec<String> {
+        let Some(generics) = &function.generics else { return Vec::new() };
+        let names = self.table.function_metadata(function);
+        self.metadata_arguments(generics, &names, &function.parameters, call)
+    }
+
+    /// The metadata a construction of a generic type passes ahead of the initializer's arguments.
+    fn construction_metadata(&self, type_name: &str, call: &expression::CallExpression) -> Vec<String> {
+        let labels = self.table.construction_labels(type_name, call);
+        let initializer = self.table.initializer(type_name, &labels);
+        // An inherited initializer takes the metadata of the superclass declaring it.
+        let owner = initializer.and_then(|initializer| self.table.initializer_owner(type_name, initializer));
+        if let Some(owner) = owner.filter(|owner| *owner != type_name) {
+            return self.superclass_metadata(type_name, owner);
+        }
+        let names = self.table.type_metadata(type_name);
+        let Some(generics) = self.table.nominal(type_name).and_then(|nominal| nominal.generics()).filter(|_| !names.is_empty()) else {
+            return Vec::new();
+        };
+        let parameters = initializer.map(|initializer| initializer.parameters.as_slice()).unwrap_or_default();
+        self.metadata_arguments(generics, &names, parameters, call)
+    }
+
+    /// The metadata an initializer of a generic superclass takes, from the type arguments of the
+    /// superclass declarations.
+    fn superclass_metadata(&self, type_name: &str, superclass: &str) -> Vec<String> {
+        let substitution = self.table.superclass_substitution(type_name, superclass);
+        self.table
+            .type_metadata(superclass)
+            .iter()
+            .map(|name| substitution.get(name).map_or_else(|| "undefined".to_string(), |ty| self.type_value(ty)))
+            .collect()
+    }
+
+    fn infer(&self, expression: &Expression) -> Option<SwiftType> {
+        self.table.infer(&self.scope, self.context.self_type, expression)
+    }
+
+    // ---------------------------------------------------------------------
+    // Statements
+    // ---------------------------------------------------------------------
+
+    fn emit_block(&mut self, body: &'a StatementSequence) {
+        self.indent += 1;
+        self.scope.push();
+        self.emit_statements(body.statements());
+        self.scope.pop();
+        self.indent -= 1;
+    }
+
+    /// Emits the statements of a block. With reference counting, the locals the block owns are
+    /// declared up front and released by a `finally`, however the block is left.
+    fn emit_statements(&mut self, statements: &'a [Statement]) {
+        if self.lifetime != Lifetime::ReferenceCounting {
+            for statement in statements {
+                self.emit_statement(statement);
+            }
+            return;
+        }
+        self.block_locals.push(Vec::new());
+        let saved = std::mem::take(&mut self.out);
+        for statement in statements {
+            self.emit_statement(statement);
+        }
+        let body = std::mem::replace(&mut self.out, saved);
+        let locals = self.block_locals.pop().unwrap_or_default();
+        if locals.is_empty() {
+            self.out.push_str(&body);
+            return;
+        }
+        self.line(&format!("let {};", locals.join(", ")));
+        self.line("try {");
+        for line in body.lines() {
+            self.out.push_str("  ");
+            self.out.push_str(line);
+            self.out.push('\n');
+        }
+        self.line("} finally {");
+        for local in locals.iter().rev() {
+            self.line(&format!("  $rt.release({local});"));
+        }
+        self.line("}");
+    }
+
+    /// Declares a local whose initial value has been emitted. Locals holding class instances are
+    /// owned by the enclosing block under reference counting, which declares them itself.
+    fn emit_local(&mut self, keyword: &str, name: &str, value: Option<String>, ty: Option<&SwiftType>) {
+        let is_counted = self.lifetime == Lifetime::ReferenceCounting && ty.is_some_and(|ty| self.table.holds_references(ty));
+        match self.block_locals.last_mut() {
+            Some(locals) if is_counted => {
+                locals.push(name.to_string());
+                if let Some(value) = value {
+                    self.line(&format!("{name} = {value};"));
+                }
+            }
+            _ => match value {
+                Some(value) => self.line(&format!("{keyword} {name} = {value};")),
+                None => self.line(&format!("let {name};")),
+            },
+        }
+    }
+
+    fn emit_statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::Break(statement::BreakStatement { label }) => {
+                let target = label.clone().or_else(|| self.break_targets.last().cloned().flatten());
+                match target {
+                    Some(label) => self.line(&format!("break {label};")),
+                    None => self.line("break;"),
+                }
+            }
+            Statement::Continue(statement::ContinueStatement { label }) => match label {
+                Some(label) => self.line(&format!("continue {label};")),
+                None => self.line("continue;"),
+            },
+            Statement::Expression(expression) => {
+                let js = self.emit_full_expression(|this| this.emit_expression(expression));
+                if self.delegates_to_failable(expression) {
+                    // A failing `init?` fails every initializer that delegated to it.
+                    let js = if self.context.receiver == Receiver::Local { format!("({js})") } else { js };
+                    self.line(&format!("if ({js} === null) return null;"));
+                } else if self.is_counted(expression) && self.is_owned(expression) {
+                    self.line(&format!("$rt.release({js});"));
+                } else {
+                    self.line(&format!("{js};"));
+                }
+            }
+            Statement::Declaration(declaration) => self.emit_declaration(declaration),
+            Statement::Return(statement) => self.emit_return(statement),
+            Statement::If(statement) => self.emit_if(statement, false),
+            Statement::ForLoop(statement) => {
+                let lower = self.emit_expression(&statement.range.0);
+                let upper = self.emit_expression(&statement.range.1);
+                let name = &statement.variable;
+                self.line(&format!("for (let {name} = {lower}; {name} <= {upper}; {name}++) {{"));
+                self.scope.push();
+                self.scope.declare(name, Binding { ty: Some(SwiftType::Integer), is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                self.emit_loop_body(&statement.body);
+                self.scope.pop();
+                self.line("}");
+            }
+            Statement::ForIn(statement) => {
+                let ty = self.infer(&statement.sequence);
+                let mut sequence = self.emit_expression(&statement.sequence);
+                // A string iterates over its characters, not its code points.
+                if ty == Some(SwiftType::String) {
+                    sequence = format!("$rt.characters({sequence})");
+                }
+                let name = &statement.variable;
+                self.line(&format!("for (const {name} of {sequence}) {{"));
+                self.scope.push();
+                self.scope.declare(name, Binding { ty: ty.as_ref().and_then(stdlib::element_type), is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                self.emit_loop_body(&statement.body);
+                self.scope.pop();
+                self.line("}");
+            }
+            Statement::WhileLoop(statement) => {
+                let condition = self.emit_condition(&statement.condition);
+                self.line(&format!("while ({condition}) {{"));
+                self.emit_loop_body(&statement.body);
+                self.line("}");
+            }
+            Statement::RepeatWhileLoop(statement) => {
+                self.line("do {");
+                self.emit_loop_body(&statement.body);
+                let condition = self.emit_condition(&statement.condition);
+                self.line(&format!("}} while ({condition});"));
+            }
+            Statement::Switch(statement) => self.emit_switch(statement),
+            Statement::Guard(statement) => {
+                let condition = self.emit_condition(&statement.condition);
+                self.line(&format!("if (!({condition})) {{"));
+                self.emit_block(&statement.body);
+                self.line("}");
+            }
+            Statement::Throw(statement) => {
+                let value = self.emit_full_expression(|this| this.emit_expression(&statement.expression));
+                self.line(&format!("throw {value};"));
+            }
+            Statement::DoCatch(statement) => self.emit_do_catch(statement),
+            Statement::Assignment(statement) => {
+                let js = self.emit_full_expression(|this| this.emit_assignment(&statement.target, &statement.value));
+                self.line(&format!("{js};"));
+            }
+        }
+    }
+
+    fn emit_loop_body(&mut self, body: &'a StatementSequence) {
+        self.break_targets.push(None);
+        self.emit_block(body);
+        self.break_targets.pop();
+    }
+
+    fn delegates_to_failable(&self, expression: &Expression) -> bool {
+        let Some((delegation, call)) = sema::delegation(expression) else { return false };
+        let type_name = match delegation {
+            Delegation::ToSelf => self.context.self_type,
+            Delegation::ToSuper => self.context.self_type.and_then(|name| self.table.superclass(name)),
+        };
+        let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+        type_name
+            .and_then(|name| self.table.initializer(name, &labels))
+            .is_some_and(|initializer| initializer.is_failable)
+    }
+
+    fn emit_return(&mut self, statement: &'a statement::ReturnStatement) {
+        let value = match &statement.expression {
+            Some(expression) if matches!(expression.as_ref(), Expression::Literal(Literal::Nil)) => Some("null".to_string()),
+            Some(expression) => Some(self.emit_stored_value(expression, self.context.result)),
+            None if self.context.in_initializer => Some(self.context.receiver.js().to_string()),
+            None => None,
+        };
+        match value {
+            Some(value) => self.line(&format!("return {value};")),
+            None => self.line("return;"),
+        }
+    }
+
+    fn emit_if(&mut self, statement: &'a statement::IfStatement, is_else_if: bool) {
+        let condition = self.emit_condition(&statement.condition);
+        if is_else_if {
+            self.line(&format!("}} else if ({condition}) {{"));
+        } else {
+            self.line(&format!("if ({condition}) {{"));
+        }
+        self.emit_block(&statement.body);
+        if let Some(else_body) = &statement.else_body {
+            if let [Statement::If(nested)] = else_body.statements() {
+                return self.emit_if(nested, true);
+            }
+            self.line("} else {");
+            self.emit_block(else_body);
+        }
+        self.line("}");
+    }
+
+    /// Lowers a `switch` to a labeled block of `if`s, so that `break` leaves the switch
+    /// and bindings introduced by patterns are scoped to their case.
+    fn emit_switch(&mut self, statement: &'a statement::SwitchStatement) {
+        let label = self.temporary("switch");
+        let subject = self.temporary("subject");
+        let value = self.emit_full_expression(|this| {
+            if this.is_counted(&statement.expression) {
+                this.emit_owned(&statement.expression)
+            } else {
+                this.emit_expression(&statement.expression)
+            }
+        });
+        let ty = self.infer(&statement.expression);
+        self.line(&format!("{label}: {{"));
+        self.indent += 1;
+        self.emit_local("const", &subject, Some(value), ty.as_ref());
+        self.break_targets.push(Some(label.clone()));
+        for case in &statement.cases {
+            self.line("{");
+            self.indent += 1;
+            self.scope.push();
+            let bindings = match case.patterns.first() {
+                Some(pattern) => self.table.pattern_bindings(pattern, ty.as_ref()),
+                None => Vec::new(),
+            };
+            for (name, ty) in &bindings {
+                self.scope.declare(name, Binding { ty: ty.clone(), is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+            }
+            if !bindings.is_empty() {
+                let names = bindings.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+                self.line(&format!("let {};", names.join(", ")));
+            }
+            let alternatives = case
+                .patterns
+                .iter()
+                .map(|pattern| self.pattern_condition(pattern, &subject))
+                .collect::<Vec<_>>();
+            let mut condition = match alternatives.as_slice() {
+                [single] => single.clone(),
+                _ => format!("({})", alternatives.join(" || ")),
+            };
+            if let Some(guard) = &case.guard_expression {
+                let guard = self.emit_expression(guard);
+                condition = format!("{condition} && {guard}");
+            }
+            self.line(&format!("if ({condition}) {{"));
+            self.emit_block(&case.body);
+            if !ends_with_jump(&case.body) {
+                self.indent += 1;
+                self.line(&format!("break {label};"));
+                self.indent -= 1;
+            }
+            self.line("}");
+            self.scope.pop();
+            self.indent -= 1;
+            self.line("}");
+        }
+        if let Some(default_case) = &statement.default_case {
+            self.indent -= 1;
+            self.emit_block(default_case);
+            self.indent += 1;
+        }
+        self.break_targets.pop();
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    /// Emits a `do` statement as a JS `try`, whose `catch` tries each clause in turn like the cases of
+    /// a `switch`, and throws the error on unless one matches.
+    fn emit_do_catch(&mut self, statement: &'a statement::DoCatchStatement) {
+        if statement.catch_clauses.is_empty() {
+            self.line("{");
+            self.emit_block(&statement.body);
+            self.line("}");
+            return;
+        }
+        let label = self.temporary("catch");
+        let error = self.temporary("error");
+        self.line("try {");
+        self.emit_block(&statement.body);
+        self.line(&format!("}} catch ({error}) {{"));
+        self.indent += 1;
+        self.line(&format!("$rt.rethrowTraps({error});"));
+        self.line(&format!("{label}: {{"));
+        self.indent += 1;
+        let mut is_exhaustive = false;
+        for clause in &statement.catch_clauses {
+            self.line("{");
+            self.indent += 1;
+            self.scope.push();
+            let condition = match &clause.pattern {
+                Some(pattern) => {
+                    let bindings = self.table.pattern_bindings(pattern, None);
+                    for (name, ty) in &bindings {
+                        self.scope.declare(name, Binding { ty: ty.clone(), is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                    }
+                    if !bindings.is_empty() {
+                        let names = bindings.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+                        self.line(&format!("let {};", names.join(", ")));
+                    }
+                    // Errors of any type reach the clause, so a case pattern checks the type too.
+                    let condition = self.pattern_condition(pattern, &error);
+                    match pattern {
+                        Pattern::EnumCase(statement::EnumCasePattern { enum_name: Some(enum_name), .. }) => {
+                            let ty = self.type_value(&SwiftType::Custom(enum_name.clone()));
+                            format!("$rt.cast({error}, {ty}) !== null && {condition}")
+                        }
+                        _ => condition,
+                    }
+                }
+                None => {
+                    self.scope.declare("error", Binding { ty: None, is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                    self.line(&format!("const error = {error};"));
+                    "true".to_string()
+                }
+            };
+            let condition = match &clause.guard_expression {
+                Some(guard) => {
+                    let guard = self.emit_expression(guard);
+                    if condition == "true" { guard } else { format!("{condition} && {guard}") }
+                }
+                None => condition,
+            };
+            if condition == "true" {
+                self.indent -= 1;
+                self.emit_block(&clause.body);
+                self.indent += 1;
+            } else {
+                self.line(&format!("if ({condition}) {{"));
+                self.emit_block(&clause.body);
+                if !ends_with_jump(&clause.body) {
+                    self.indent += 1;
+                    self.line(&format!("break {label};"));
+                    self.indent -= 1;
+                }
+                self.line("}");
+            }
+            self.scope.pop();
+            self.indent -= 1;
+            self.line("}");
+            // Later clauses can't be reached.
+            if clause.is_exhaustive() {
+                is_exhaustive = true;
+                break;
+            }
+        }
+        if !is_exhaustive {
+            self.line(&format!("throw {error};"));
+        }
+        self.indent -= 1;
+        self.line("}");
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    /// A JS condition testing `subject` against the pattern, assigning pattern bindings as it goes.
+    fn pattern_condition(&mut self, pattern: &Pattern, subject: &str) -> String {
+        match pattern {
+            Pattern::Wildcard => "true".to_string(),
+            Pattern::Identifier(identifier) => format!("(({} = {subject}), true)", identifier.name),
+            // Loose equality matches integers whether they are numbers or BigInts.
+            Pattern::Literal(literal) if matches!(literal.value, Literal::Integer(_)) => {
+                format!("{subject} == {}", emit_literal(&literal.value))
+            }
+            // Strings match by canonical equivalence.
+            Pattern::Literal(literal) if matches!(literal.value, Literal::String(_) | Literal::Character(_)) => {
+                format!("$rt.compareStrings({subject}, {}) === 0", emit_literal(&literal.value))
+            }
+            Pattern::Literal(literal) => format!("{subject} === {}", emit_literal(&literal.value)),
+            Pattern::Tuple(tuple) => join_conditions(
+                tuple
+                    .elements
+                    .iter()
+                    .enumerate()
+                    .map(|(index, element)| self.pattern_condition(element, &format!("{subject}[{index}]")))
+                    .collect(),
+            ),
+            Pattern::EnumCase(case) => {
+                let mut conditions = vec![format!("{subject}.$case === \"{}\"", case.case_name)];
+                for (index, value) in case.associated_values.iter().enumerate() {
+                    conditions.push(self.pattern_condition(value, &format!("{subject}.$values[{index}]")));
+                }
+                join_conditions(conditions)
+            }
+            Pattern::TypePattern(pattern) => {
+                format!("$rt.cast({subject}, {}) !== null", self.type_value(&pattern.ty))
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Declarations
+    // ---------------------------------------------------------------------
+
+    fn emit_declaration(&mut self, declaration: &'a Declaration) {
+        match declaration {
+            Declaration::Function(function) => self.emit_function(function),
+            Declaration::Var(var) => {
+                let ty = var.ty.clone().or_else(|| var.initial_value.as_ref().and_then(|value| self.infer(value)));
+                let value = var.initial_value.as_ref().map(|value| self.emit_initial_value(value, var.ownership, var.ty.as_ref()));
+                let owned_ty = ty.as_ref().filter(|_| var.ownership == ReferenceOwnership::Strong);
+                self.emit_local("let", &var.name, value, owned_ty);
+                self.scope.declare(&var.name, Binding { ty, is_constant: false, is_inout: false, ownership: var.ownership });
+            }
+            Declaration::Let(constant) => {
+                let ty = constant
+                    .ty
+                    .clone()
+                    .or_else(|| constant.initial_value.as_ref().and_then(|value| self.infer(value)));
+                // Without an initial value, a constant is initialized later on every path, which JS `const` can't express.
+                let value = constant.initial_value.as_ref().map(|value| self.emit_initial_value(value, constant.ownership, constant.ty.as_ref()));
+                let owned_ty = ty.as_ref().filter(|_| constant.ownership == ReferenceOwnership::Strong);
+                self.emit_local("const", &constant.name, value, owned_ty);
+                self.scope.declare(&constant.name, Binding { ty, is_constant: true, is_inout: false, ownership: constant.ownership });
+            }
+            Declaration::Struct(decl) => self.emit_struct(decl),
+            Declaration::Enum(decl) => self.emit_enum(decl),
+            Declaration::Class(decl) => self.emit_class(decl),
+            Declaration::Extension(decl) => self.emit_extension(decl),
+            // Protocols exist at runtime for casts; conforming types record their witnesses.
+            Declaration::Protocol(decl) => self.line(&format!("const {} = $rt.protocol({});", decl.name, js_string(&decl.name))),
+            // Aliases and imports only matter to the type checker.
+            Declaration::TypeAlias(_) | Declaration::Import(_) => {}
+            // Only meaningful inside a type declaration, where they are emitted with their type.
+            Declaration::Initializer(_) | Declaration::Deinitializer(_) => {}
+        }
+    }
+
+    fn emit_function(&mut self, function: &'a FunDeclaration) {
+        let Some(body) = &function.body else { return };
+        self.scope.declare(&function.name, Binding {
+            ty: Some(function_type(&function.parameters, function.return_type.as_ref(), function.is_throwing || function.is_rethrowing)),
+            is_constant: true,
+            is_inout: false,
+            ownership: ReferenceOwnership::Strong,
+        });
+        let context = Context { in_initializer: false, result: function.return_type.as_ref(), ..self.context };
+        self.with_context(context, |this| {
+            let mut parameters = this.declare_metadata(this.table.function_metadata(function));
+            parameters.extend(this.emit_parameters(&function.parameters));
+            let parameters = parameters.join(", ");
+            // Nested functions inside members are arrows so they keep the member's `this`.
+            let close = if context.receiver == Receiver::None {
+                this.line(&format!("function {}({parameters}) {{", function.name));
+                "}"
+            } else {
+                this.line(&format!("const {} = ({parameters}) => {{", function.name));
+                "};"
+            };
+            this.emit_function_body(body, function.return_type.is_some());
+            this.line(close);
+        });
+    }
+
+    /// Declares the parameters in the current scope and returns their JS spelling.
+    fn emit_parameters(&mut self, parameters: &'a [FunctionParameter]) -> Vec<String> {
+        let mut emitted = Vec::new();
+        for parameter in parameters {
+            let name = &parameter.internal_name;
+            let js = match (&parameter.default_value, parameter.is_variadic) {
+                (_, true) => format!("...{name}"),
+                (Some(default_value), false) => format!("{name} = {}", self.emit_expression(default_value)),
+                (None, false) => name.clone(),
+            };
+            let ty = if parameter.is_variadic {
+                SwiftType::Array(Box::new(parameter.ty.clone()))
+            } else {
+                parameter.ty.clone()
+            };
+            self.scope.declare(name, Binding { ty: Some(ty), is_constant: !parameter.is_inout, is_inout: parameter.is_inout, ownership: ReferenceOwnership::Strong });
+            emitted.push(js);
+        }
+        emitted
+    }
+
+    /// Emits a function body, honoring Swift's implicit return of single-expression bodies.
+    fn emit_function_body(&mut self, body: &'a StatementSequence, returns_value: bool) {
+        if let (true, [Statement::Expression(expression)]) = (returns_value, body.statements()) {
+            self.indent += 1;
+            let value = self.emit_stored_value(expression, self.context.result);
+            self.line(&format!("return {value};"));
+            self.indent -= 1;
+            return;
+        }
+        self.emit_block(body);
+    }
+
+    fn emit_struct(&mut self, decl: &'a StructDeclaration) {
+        self.line(&format!("class {} {{", decl.name));
+        self.indent += 1;
+        self.emit_stored_fields(&decl.name, &decl.properties);
+        self.emit_initializers(&decl.name, &decl.initializers, MemberStyle::ClassBody);
+        self.emit_properties(&decl.name, &decl.properties, MemberStyle::ClassBody);
+        self.emit_methods(&decl.name, &decl.methods, MemberStyle::ClassBody);
+        self.line("$copy() {");
+        self.line("  return $rt.copyStruct(this);");
+        self.line("}");
+        self.indent -= 1;
+        self.line("}");
+        let members = self.reflected_properties(&decl.name, &decl.properties);
+        self.emit_reflection(&decl.name, "struct", &members);
+        self.emit_static_properties(&decl.name, &decl.properties);
+        self.emit_conformances(&decl.name, &decl.conformances, None);
+    }
+
+    fn emit_enum(&mut self, decl: &'a EnumDeclaration) {
+        let name = &decl.name;
+        self.line(&format!("class {name} {{"));
+        self.indent += 1;
+        self.line("constructor($case, $values) {");
+        self.line("  this.$case = $case;");
+        self.line("  this.$values = $values;");
+        self.line("  Object.freeze(this);");
+        self.line("}");
+        for case in &decl.cases {
+            if case.associated_values.is_empty() {
+                self.line(&format!("static {} = new {name}(\"{}\", []);", case.name, case.name));
+            } else {
+                let values = (0..case.associated_values.len()).map(|index| format!("${index}")).collect::<Vec<_>>().join(", ");
+                self.line(&format!("static {}({values}) {{", case.name));
+                self.line(&format!("  return new {name}(\"{}\", [{values}]);", case.name));
+                self.line("}");
+            }
+        }
+        if decl.raw_type.is_some() {
+            self.emit_raw_values(decl);
+        }
+        self.emit_coding(decl);
+        self.emit_initializers(name, &decl.initializers, MemberStyle::ClassBody);
+        self.emit_properties(name, &decl.properties, MemberStyle::ClassBody);
+        self.emit_methods(name, &decl.methods, MemberStyle::ClassBody);
+        self.indent -= 1;
+        self.line("}");
+        let cases = decl
+            .cases
+            .iter()
+            .map(|case| {
+                let values = case.associated_values.iter().map(|value| {
+                    let label = value.label.as_deref().map(js_string).unwrap_or_else(|| "null".to_string());
+                    format!("[{label}, {}]", self.type_value(&value.ty))
+                });
+                format!("{}: [{}]", case.name, values.collect::<Vec<_>>().join(", "))
+            })
+            .collect::<Vec<_>>();
+        self.emit_reflection(name, "enum", &format!("({{ {} }})", cases.join(", ")));
+        self.emit_static_properties(name, &decl.properties);
+        self.emit_conformances(name, &decl.conformances, None);
+    }
+
+    /// Emits `rawValue` and the synthesized `init?(rawValue:)` for enums with a raw type.
+    fn emit_raw_values(&mut self, decl: &'a EnumDeclaration) {
+        let mut raw_values = Vec::new();
+        let mut next_integer = 0;
+        let suffix = if decl.raw_type.as_ref().is_some_and(|ty| self.is_big_integer(ty)) { "n" } else { "" };
+        for case in &decl.cases {
+            let raw_value = match (&case.raw_value, &decl.raw_type) {
+                (Some(Expression::Literal(Literal::Integer(value))), _) => {
+                    next_integer = value + 1;
+                    format!("{value}{suffix}")
+                }
+                (Some(expression), _) => self.emit_expression(expression),
+                (None, Some(SwiftType::String)) => js_string(&case.name),
+                (None, _) => {
+                    next_integer += 1;
+                    format!("{}{suffix}", next_integer - 1)
+                }
+            };
+            raw_values.push((&case.name, raw_value));
+        }
+        self.line("get rawValue() {");
+        self.indent += 1;
+        self.line("switch (this.$case) {");
+        for (case, raw_value) in &raw_values {
+            self.line(&format!("  case \"{case}\": return {raw_value};"));
+        }
+        self.line("}");
+        self.indent -= 1;
+        self.line("}");
+        let has_explicit = decl.initializers.iter().any(|initializer| {
+            matches!(initializer.parameters.as_slice(), [parameter] if parameter.label.as_deref() == Some("rawValue"))
+        });
+        if !has_explicit {
+            self.line("static $init$rawValue(rawValue) {");
+            self.indent += 1;
+            for (case, raw_value) in &raw_values {
+                self.line(&format!("if (rawValue === {raw_value}) return {}.{case};", decl.name));
+            }
+            self.line("return null;");
+            self.indent -= 1;
+            self.line("}");
+        }
+    }
+
+    /// Emits the `encode(to:)` and `init(from:)` an enum derives. An enum with a raw type codes its
+    /// raw value; others code a keyed container with the case name as its only key, holding the
+    /// associated values keyed by their labels, or `_0`, `_1`, ...: `{"upc":{"_0":8,"_1":85909}}`.
+    fn emit_coding(&mut self, decl: &'a EnumDeclaration) {
+        let name = &decl.name;
+        let cases = decl
+            .cases
+            .iter()
+            .map(|case| {
+                let values = case.associated_values.iter().enumerate().map(|(index, value)| {
+                    let key = value.label.clone().unwrap_or_else(|| format!("_{index}"));
+                    format!("[{}, {}]", js_string(&key), self.type_value(&value.ty))
+                });
+                format!("{}: [{}]", case.name, values.collect::<Vec<_>>().join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        if synthesis::derives_coding(self.table, name, "encode(to:)") {
+            self.line("encode(encoder) {");
+            match &decl.raw_type {
+                Some(_) => self.line("  $rt.encodeRawValue(encoder, this.rawValue);"),
+                None => self.line(&format!("  $rt.encodeCase(encoder, this, {{ {cases} }});")),
+            }
+            self.line("}");
+        }
+        if synthesis::derives_coding(self.table, name, "init(from:)") {
+            self.line(&format!("static {}(decoder) {{", coding_member("init(from:)")));
+            match &decl.raw_type {
+                Some(raw_type) => self.line(&format!("  return $rt.decodeRawValue(decoder, {name}, {});", self.type_value(raw_type))),
+                None => self.line(&format!("  return $rt.decodeCase(decoder, {name}, {{ {cases} }});")),
+            }
+            self.line("}");
+        }
+    }
+
+    fn emit_class(&mut self, decl: &'a ClassDeclaration) {
+        match &decl.superclass {
+            Some(superclass) => self.line(&format!("class {} extends {superclass} {{", decl.name)),
+            None => self.line(&format!("class {} {{", decl.name)),
+        }
+        self.indent += 1;
+        if decl.superclass.is_none() && self.lifetime == Lifetime::ReferenceCounting {
+            // Held by the expression creating the instance until it is stored or released.
+            self.line("$retainCount = 1;");
+        }
+        self.emit_stored_fields(&decl.name, &decl.properties);
+        self.emit_initializers(&decl.name, &decl.initializers, MemberStyle::ClassBody);
+        self.emit_properties(&decl.name, &decl.properties, MemberStyle::ClassBody);
+        self.emit_methods(&decl.name, &decl.methods, MemberStyle::ClassBody);
+        if let Some(deinitializer) = &decl.deinitializer {
+            self.emit_deinitializer(&decl.name, deinitializer);
+        }
+        self.indent -= 1;
+        self.line("}");
+        let members = self.reflected_properties(&decl.name, &decl.properties);
+        self.emit_reflection(&decl.name, "class", &members);
+        self.emit_static_properties(&decl.name, &decl.properties);
+        self.emit_conformances(&decl.name, &decl.conformances, None);
+    }
+
+    /// Records what the runtime shows of the type's values when printing them: the stored properties
+    /// of structs and classes, or the associated values of each enum case, as labels and types. They
+    /// are returned by a function since their types may be declared further on.
+    fn emit_reflection(&mut self, type_name: &str, kind: &str, members: &str) {
+        self.line(&format!("$rt.reflect({type_name}, {}, () => {members});", js_string(kind)));
+    }
+
+    /// The instance properties with storage a struct or class declares itself: `[["x", "Int"]]`.
+    fn reflected_properties(&self, type_name: &str, properties: &[VariablePropertyDeclaration]) -> String {
+        let properties = properties
+            .iter()
+            .filter(|property| !property.is_static && property.has_storage())
+            .map(|property| {
+                let ty = self.table.member_type(type_name, &property.name);
+                let ty = ty.map(|ty| self.type_value(&ty)).unwrap_or_else(|| "undefined".to_string());
+                format!("[{}, {ty}]", js_string(&property.name))
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]", properties.join(", "))
+    }
+
+    /// `deinit` becomes `$deinit`, which the runtime calls before releasing the stored properties.
+    /// The superclass deinitializer runs after the subclass one, as in Swift.
+    fn emit_deinitializer(&mut self, type_name: &'a str, deinitializer: &'a DeinitializerDeclaration) {
+        let context = Context { self_type: Some(type_name), receiver: Receiver::This, ..Context::TOP_LEVEL };
+        self.with_context(context, |this| {
+            this.line("$deinit() {");
+            this.emit_block(&deinitializer.body);
+            if this.table.superclass(type_name).is_some_and(|superclass| this.table.has_deinitializer(superclass)) {
+                this.line("  super.$deinit();");
+            }
+            this.line("}");
+        });
+    }
+
+    /// Extensions of types declared in this program add their members onto the existing JS class.
+    fn emit_extension(&mut self, decl: &'a ExtensionDeclaration) {
+        let Some(nominal) = self.table.nominal(&decl.type_name) else { return };
+        self.emit_initializers(nominal.name(), &decl.initializers, MemberStyle::Assignment);
+        self.emit_properties(nominal.name(), &decl.properties, MemberStyle::Assignment);
+        self.emit_methods(nominal.name(), &decl.methods, MemberStyle::Assignment);
+        self.emit_static_properties(nominal.name(), &decl.properties);
+        let condition = self.conformance_condition(nominal, &decl.where_clause);
+        self.emit_conformances(nominal.name(), &decl.conformances, condition.as_deref());
+    }
+
+    /// A JS predicate checking the `where` clause of a conditional conformance on an instance. With
+    /// generic types erased, the values the instance stores for a generic parameter stand in for its
+    /// type argument. `None` when no stored property tells, so the conformance always holds.
+    fn conformance_condition(&self, nominal: NominalType<'a>, where_clause: &[GenericRequirement]) -> Option<String> {
+        let generics = nominal.generics()?;
+        let is_parameter = |ty: &SwiftType| match ty {
+            SwiftType::Custom(name) => generics.type_parameters.iter().any(|parameter| parameter.name == *name),
+            _ => false,
+        };
+        let checks = where_clause
+            .iter()
+            .filter_map(|requirement| match requirement {
+                GenericRequirement::Conformance { subject, constraint } if is_parameter(subject) => Some((subject, constraint)),
+                GenericRequirement::SameType { left, right } if is_parameter(left) => Some((left, right)),
+                GenericRequirement::SameType { left, right } if is_parameter(right) => Some((right, left)),
+                _ => None,
+            })
+            .flat_map(|(parameter, constraint)| {
+                nominal
+                    .properties()
+                    .iter()
+                    .filter(|property| property.has_storage() && !property.is_static)
+                    .filter(move |property| match &property.ty {
+                        Some(SwiftType::Optional(ty) | SwiftType::Array(ty)) => **ty == *parameter,
+                        Some(ty) => ty == parameter,
+                        None => false,
+                    })
+                    .map(move |property| {
+                        let value = load_reference(property.ownership, &format!("self.{}", property.name));
+                        format!("$rt.satisfies({value}, {})", self.type_value(constraint))
+                    })
+            })
+            .collect::<Vec<_>>();
+        (!checks.is_empty()).then(|| format!("(self) => {}", checks.join(" && ")))
+    }
+
+    /// Records the witness table of each protocol the declaration conforms to, directly or through
+    /// protocol inheritance, mapping requirement signatures to the JS members witnessing them. The
+    /// runtime also installs the protocol's extension members the type lacks. A conditional
+    /// conformance passes the predicate that decides, per instance, whether it holds.
+    fn emit_conformances(&mut self, type_name: &'a str, conformances: &[String], condition: Option<&str>) {
+        for name in conformances {
+            for protocol in self.table.protocols(name) {
+                let conformance = self.table.conformance(type_name, protocol);
+                let witnesses = conformance
+                    .witnesses
+                    .iter()
+                    .filter_map(|(requirement, witness)| {
+                        Some(format!("{}: {}", js_string(&requirement.signature()), js_string(&witness_member((*witness)?))))
+                    })
+                    .collect::<Vec<_>>();
+                let condition = condition.map(|condition| format!(", {condition}")).unwrap_or_default();
+                self.line(&format!("$rt.conform({type_name}, {}, {{ {} }}{condition});", protocol.name, witnesses.join(", ")));
+            }
+            // The runtime's own protocols, whose requirements it calls: `==` and `hash(into:)` to
+            // key dictionaries and sets, `encode(to:)` and `init(from:)` to code values. Enums
+            // derive the latter as emitted members.
+            for protocol in stdlib::protocols(name) {
+                let witnesses = protocol
+                    .requirements
+                    .iter()
+                    .filter_map(|requirement| {
+                        let member = match self.table.standard_witness(type_name, requirement) {
+                            Some(witness) => witness_member(witness),
+                            None if synthesis::derives_coding(self.table, type_name, requirement) => coding_member(requirement),
+                            None => return None,
+                        };
+                        Some(format!("{}: {}", js_string(requirement), js_string(&member)))
+                    })
+                    .collect::<Vec<_>>();
+                let condition = condition.map(|condition| format!(", {condition}")).unwrap_or_default();
+                // `Codable` and `CodingKey` have no requirements of their own.
+                let witnesses = if witnesses.is_empty() { "{}".to_string() } else { format!("{{ {} }}", witnesses.join(", ")) };
+                self.line(&format!("$rt.conform({type_name}, $rt.{}, {witnesses}{condition});", protocol.name));
+            }
+        }
+    }
+
+    /// Opens a member function and returns the text that closes it.
+    /// Operators like `==` are members with computed names: `static ["=="](lhs, rhs)`.
+    fn open_member(&mut self, style: MemberStyle, type_name: &str, is_static: bool, name: &str, parameters: &[String]) -> &'static str {
+        let parameters = parameters.join(", ");
+        let is_operator = !name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$');
+        let (key, access) = match is_operator {
+            true => (format!("[{}]", js_string(name)), format!("[{}]", js_string(name))),
+            false => (name.to_string(), format!(".{name}")),
+        };
+        match (style, is_static) {
+            (MemberStyle::ClassBody, true) => self.line(&format!("static {key}({parameters}) {{")),
+            (MemberStyle::ClassBody, false) => self.line(&format!("{key}({parameters}) {{")),
+            (MemberStyle::Assignment, true) => self.line(&format!("{type_name}{access} = function ({parameters}) {{")),
+            (MemberStyle::Assignment, false) => {
+                self.line(&format!("{type_name}.prototype{access} = function ({parameters}) {{"))
+            }
+        }
+        match style {
+            MemberStyle::ClassBody => "}",
+            MemberStyle::Assignment => "};",
+        }
+    }
+
+    fn emit_initializers(&mut self, type_name: &'a str, initializers: &'a [InitializerDeclaration], style: MemberStyle) {
+        let is_class = matches!(self.table.nominal(type_name), Some(NominalType::Class(_)));
+        for initializer in initializers {
+            let receiver = if is_class { Receiver::This } else { Receiver::Local };
+            let context = Context { self_type: Some(type_name), receiver, in_initializer: true, observing: None, result: None };
+            self.with_context(context, |this| {
+                let metadata = this.table.type_metadata(type_name);
+                let mut parameters = this.declare_metadata(metadata.clone());
+                parameters.extend(this.emit_parameters(&initializer.parameters));
+                let name = initializer_name(&initializer.parameters);
+                // Class initializers run on an allocated instance so `super.init` can chain;
+                // value type initializers are factories since an enum `init` assigns `self` outright.
+                let close = this.open_member(style, type_name, !is_class, &name, &parameters);
+                this.indent += 1;
+                match this.table.nominal(type_name) {
+                    Some(NominalType::Struct(_)) => this.line(&format!("let self = new {type_name}();")),
+                    Some(NominalType::Enum(_)) => this.line("let self;"),
+                    _ => {}
+                }
+                for name in &metadata {
+                    this.line(&format!("{}.${name} = ${name};", receiver.js()));
+                }
+                this.indent -= 1;
+                this.emit_block(&initializer.body);
+                this.indent += 1;
+                let ends_with_return = matches!(initializer.body.statements().last(), Some(Statement::Return(_)));
+                if !ends_with_return {
+                    if let Some(call) = this.implicit_super_init(type_name, initializer) {
+                        this.line(&format!("{call};"));
+                    }
+                    this.line(&format!("return {};", receiver.js()));
+                }
+                this.indent -= 1;
+                this.line(close);
+            });
+        }
+    }
+
+    /// A designated initializer of a subclass that never calls `super.init` chains to the
+    /// superclass `init()` once its own properties are set, as Swift does implicitly.
+    fn implicit_super_init(&self, type_name: &str, initializer: &InitializerDeclaration) -> Option<String> {
+        let superclass = self.table.superclass(type_name)?;
+        if initializer.is_convenience || !sema::delegations(&initializer.body).is_empty() {
+            return None;
+        }
+        let inherited = self.table.initializer(superclass, &[])?;
+        Some(format!("super.{}()", initializer_name(&inherited.parameters)))
+    }
+
+    /// True for stored properties kept in a `$stored$<name>` field behind an accessor pair: observed,
+    /// lazy, `weak` and `unowned` properties, and those a subclass overrides, whose accessors reach
+    /// them through `super`.
+    fn has_storage_field(&self, type_name: &str, property: &VariablePropertyDeclaration) -> bool {
+        property.has_storage()
+            && (property.is_observed()
+                || property.is_lazy()
+                || property.ownership != ReferenceOwnership::Strong
+                || self.table.is_overridden(type_name, &property.name)
+                || self.is_finalized(type_name, &property.name))
+    }
+
+    /// True for properties that a `deinit` run after garbage collection reads.
+    fn is_finalized(&self, type_name: &str, name: &str) -> bool {
+        self.lifetime == Lifetime::Finalization && self.table.deinit_reads(type_name).contains(&name)
+    }
+
+    /// Where a property with a storage field keeps its value, relative to the instance: its
+    /// `$stored$<name>` field, or the `$fields` store that outlives the instance for a `deinit` to read.
+    fn storage(&self, type_name: &str, name: &str) -> String {
+        if self.is_finalized(type_name, name) {
+            format!("$fields.{name}")
+        } else {
+            storage_name(name)
+        }
+    }
+
+    /// Instance stored properties become class fields holding their initial values.
+    /// Observed, overridden, lazy, `weak` and `unowned` properties keep their value in a `$stored$<name>` field behind an accessor pair.
+    /// Those a `deinit` reads without reference counting start out in the shared `$fields` store.
+    fn emit_stored_fields(&mut self, type_name: &'a str, properties: &'a [VariablePropertyDeclaration]) {
+        let context = Context { self_type: Some(type_name), receiver: Receiver::This, ..Context::TOP_LEVEL };
+        let mut finalized = Vec::new();
+        for property in properties.iter().filter(|property| !property.is_static && property.has_storage()) {
+            let Some(stored) = property.stored() else { continue };
+            if stored.is_lazy {
+                continue;
+            }
+            let value = match &stored.initial_value {
+                Some(value) => {
+                    Some(self.with_context(context, |this| this.emit_initial_value(value, property.ownership, property.ty.as_ref())))
+                }
+                None if property.is_default_initialized() => Some("null".to_string()),
+                None => None,
+            };
+            if self.is_finalized(type_name, &property.name) {
+                finalized.push(format!("{}: {}", property.name, value.as_deref().unwrap_or("undefined")));
+                continue;
+            }
+            let field = if self.has_storage_field(type_name, property) {
+                storage_name(&property.name)
+            } else {
+                property.name.clone()
+            };
+            match value {
+                Some(value) => self.line(&format!("{field} = {value};")),
+                None => self.line(&format!("{field};")),
+            }
+        }
+        if !finalized.is_empty() {
+            self.line(&format!("$fields = $rt.fieldStore(this, {{ {} }});", finalized.join(", ")));
+        }
+    }
+
+    fn emit_properties(&mut self, type_name: &'a str, properties: &'a [VariablePropertyDeclaration], style: MemberStyle) {
+        for property in properties {
+            let is_static = property.is_static || property.is_class;
+            let receiver = if is_static { Receiver::Type } else { Receiver::This };
+            let context = Context { self_type: Some(type_name), receiver, ..Context::TOP_LEVEL };
+            let ty = self.table.member_type(type_name, &property.name);
+            // Setters of reference-counted properties retain the borrowed new value.
+            let retains = self.lifetime == Lifetime::ReferenceCounting
+                && property.ownership == ReferenceOwnership::Strong
+                && ty.as_ref().is_some_and(|ty| self.table.holds_references(ty));
+            match &property.kind {
+                PropertyKind::Computed(computed) => {
+                    let close = self.open_accessors(style, type_name, is_static, &property.name);
+                    if let Some(body) = &computed.getter.body {
+                        let context = Context { result: property.ty.as_ref(), ..context };
+                        self.with_context(context, |this| {
+                            this.line(&accessor_head(style, is_static, "get", &property.name, ""));
+                            this.emit_function_body(body, true);
+                            this.line(accessor_close(style));
+                        });
+                    }
+                    if let Some(body) = computed.setter.as_ref().and_then(|setter| setter.body.as_ref()) {
+                        let parameter = computed
+                            .setter
+                            .as_ref()
+                            .and_then(|setter| setter.parameters.first())
+                            .map(|parameter| parameter.internal_name.as_str())
+                            .unwrap_or("newValue");
+                        self.with_context(context, |this| {
+                            this.scope.declare(parameter, Binding { ty: ty.clone(), is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                            this.line(&accessor_head(style, is_static, "set", &property.name, parameter));
+                            this.emit_block(body);
+                            this.line(accessor_close(style));
+                        });
+                    }
+                    if let Some(close) = close {
+                        self.indent -= 1;
+                        self.line(close);
+                    }
+                }
+                PropertyKind::Stored(_) if is_static => {}
+                PropertyKind::Stored(stored) if stored.is_lazy => {
+                    let storage = self.storage(type_name, &property.name);
+                    let is_initialized = match storage.split_once('.') {
+                        Some((store, name)) => format!("Object.hasOwn(this.{store}, \"{name}\")"),
+                        None => format!("Object.hasOwn(this, \"{storage}\")"),
+                    };
+                    self.line(&format!("get {}() {{", property.name));
+                    self.indent += 1;
+                    self.line(&format!("if (!{is_initialized}) {{"));
+                    if let Some(value) = &stored.initial_value {
+                        let value = self.with_context(context, |this| this.emit_stored_value(value, property.ty.as_ref()));
+                        self.line(&format!("  this.{storage} = {value};"));
+                    }
+                    self.line("}");
+                    self.line(&format!("return this.{storage};"));
+                    self.indent -= 1;
+                    self.line("}");
+                    self.emit_storage_setter(&property.name, &storage, property.ownership, retains);
+                }
+                PropertyKind::Stored(stored) if property.is_observed() => {
+                    // Observers added by an override wrap the superclass property.
+                    let storage = if property.is_override {
+                        format!("super.{}", property.name)
+                    } else {
+                        format!("this.{}", self.storage(type_name, &property.name))
+                    };
+                    let ownership = if property.is_override { ReferenceOwnership::Strong } else { property.ownership };
+                    let value = load_reference(ownership, &storage);
+                    self.line(&format!("get {}() {{", property.name));
+                    self.line(&format!("  return {value};"));
+                    self.line("}");
+                    self.line(&format!("set {}($newValue) {{", property.name));
+                    self.indent += 1;
+                    self.line(&format!("const $oldValue = {value};"));
+                    let observing = Context { observing: Some(&property.name), ..context };
+                    if let Some(observer) = &stored.will_set {
+                        let parameter = observer.parameter_name.as_deref().unwrap_or("newValue");
+                        self.with_context(observing, |this| this.emit_observer(parameter, "$newValue", ty.clone(), &observer.body));
+                    }
+                    // The old value stays alive until `didSet` has seen it; an override's storage is the superclass setter.
+                    let retains = retains && !property.is_override;
+                    if retains {
+                        self.line(&format!("{storage} = $rt.retain($newValue);"));
+                    } else {
+                        self.line(&format!("{storage} = {};", store_reference(ownership, "$newValue")));
+                    }
+                    if let Some(observer) = &stored.did_set {
+                        let parameter = observer.parameter_name.as_deref().unwrap_or("oldValue");
+                        self.with_context(observing, |this| this.emit_observer(parameter, "$oldValue", ty.clone(), &observer.body));
+                    }
+                    if retains {
+                        self.line("$rt.release($oldValue);");
+                    }
+                    self.indent -= 1;
+                    self.line("}");
+                }
+                PropertyKind::Stored(_) if self.has_storage_field(type_name, property) => {
+                    let storage = self.storage(type_name, &property.name);
+                    self.line(&format!("get {}() {{", property.name));
+                    self.line(&format!("  return {};", load_reference(property.ownership, &format!("this.{storage}"))));
+                    self.line("}");
+                    self.emit_storage_setter(&property.name, &storage, property.ownership, retains);
+                }
+                PropertyKind::Stored(_) => {}
+            }
+        }
+    }
+
+    fn emit_storage_setter(&mut self, name: &str, storage: &str, ownership: ReferenceOwnership, retains: bool) {
+        self.line(&format!("set {name}(newValue) {{"));
+        if retains {
+            self.line(&format!("  this.{storage} = $rt.replacing(this.{storage}, $rt.retain(newValue));"));
+        } else {
+            self.line(&format!("  this.{storage} = {};", store_reference(ownership, "newValue")));
+        }
+        self.line("}");
+    }
+
+    fn emit_observer(&mut self, parameter: &str, value: &str, ty: Option<SwiftType>, body: &'a StatementSequence) {
+        self.line("{");
+        self.indent += 1;
+        self.line(&format!("const {parameter} = {value};"));
+        self.scope.declare(parameter, Binding { ty, is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+        self.indent -= 1;
+        self.emit_block(body);
+        self.line("}");
+    }
+
+    /// Extensions define accessors with `Object.defineProperty`; returns the text closing the call.
+    fn open_accessors(&mut self, style: MemberStyle, type_name: &str, is_static: bool, name: &str) -> Option<&'static str> {
+        if style == MemberStyle::ClassBody {
+            return None;
+        }
+        let target = if is_static { type_name.to_string() } else { format!("{type_name}.prototype") };
+        self.line(&format!("Object.defineProperty({target}, \"{name}\", {{"));
+        self.indent += 1;
+        self.line("configurable: true,");
+        Some("});")
+    }
+
+    fn emit_methods(&mut self, type_name: &'a str, methods: &'a [FunDeclaration], style: MemberStyle) {
+        for method in methods {
+            let Some(body) = &method.body else { continue };
+            let is_static = method.is_static || method.is_class;
+            let receiver = match (is_static, method.is_mutating) {
+                (true, _) => Receiver::Type,
+                (false, true) => Receiver::Inout,
+                (false, false) => Receiver::This,
+            };
+            let context = Context { self_type: Some(type_name), receiver, result: method.return_type.as_ref(), ..Context::TOP_LEVEL };
+            self.with_context(context, |this| {
+                let mut parameters = this.declare_metadata(this.table.function_metadata(method));
+                parameters.extend(this.emit_parameters(&method.parameters));
+                if receiver == Receiver::Inout {
+                    parameters.insert(0, "$self".to_string());
+                }
+                let close = this.open_member(style, type_name, is_static, &method.name, &parameters);
+                this.emit_function_body(body, method.return_type.is_some());
+                this.line(close);
+            });
+        }
+    }
+
+    /// Stored type properties are initialized lazily on first access, as in Swift.
+    fn emit_static_properties(&mut self, type_name: &'a str, properties: &'a [VariablePropertyDeclaration]) {
+        for property in properties.iter().filter(|property| property.is_static) {
+            let Some(value) = property.stored().and_then(|stored| stored.initial_value.as_ref()) else { continue };
+            let context = Context { self_type: Some(type_name), receiver: Receiver::Type, ..Context::TOP_LEVEL };
+            let value = self.with_context(context, |this| this.emit_stored_value(value, property.ty.as_ref()));
+            // A `function` so that `this` is the declaring type, as in a type method.
+            self.line(&format!(
+                "$rt.lazyStatic({type_name}, \"{}\", function () {{ return {value}; }}, {});",
+                property.name, property.is_constant
+            ));
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Expressions
+    // ---------------------------------------------------------------------
+
+    /// Emits an expression whose value is about to be stored, copying value types
+    /// read from existing storage.
+    fn emit_value(&mut self, expression: &'a Expression) -> String {
+        let js = self.emit_expression(expression);
+        let is_place = matches!(
+            expression,
+            Expression::Identifier(_) | Expression::SelfExpression | Expression::MemberAccess(_) | Expression::Subscript(_)
+        );
+        match self.infer(expression) {
+            Some(ty) if is_place && self.is_copied(&ty) => format!("$rt.copy({js})"),
+            _ => js,
+        }
+    }
+
+    /// Emits a full expression whose value is about to be stored as `ty`, when that is known.
+    fn emit_stored_value(&mut self, expression: &'a Expression, ty: Option<&SwiftType>) -> String {
+        self.emit_full_expression(|this| this.emit_converted(expression, ty))
+    }
+
+    /// Emits a value that storage of type `ty` takes over. Values stored as an existential type, or
+    /// as `Any`, are boxed; so are the elements of an array literal stored as an array of one.
+    fn emit_converted(&mut self, expression: &'a Expression, ty: Option<&SwiftType>) -> String {
+        let unwrapped = match ty {
+            Some(SwiftType::Optional(wrapped)) => Some(wrapped.as_ref()),
+            ty => ty,
+        };
+        match (unwrapped, expression) {
+            (Some(SwiftType::Array(element)), Expression::Array(array)) if self.is_erased(element) => {
+                let elements = array.elements.iter().map(|value| self.emit_converted(value, Some(element))).collect::<Vec<_>>();
+                return format!("[{}]", elements.join(", "));
+            }
+            // Sets are written as array literals, and represented by JS sets keyed by Swift equality.
+            (Some(SwiftType::Set(element)), Expression::Array(array)) => {
+                let elements = array.elements.iter().map(|value| self.emit_converted(value, Some(element))).collect::<Vec<_>>();
+                return format!("new $rt.HashSet([{}])", elements.join(", "));
+            }
+            _ => {}
+        }
+        let js = self.emit_owned(expression);
+        let js = self.convert_integer(ty, expression, js);
+        let js = self.convert_float(ty, expression, js);
+        self.box_existential(ty, expression, js)
+    }
+
+    /// Integer literals, and arithmetic on them, are `Int`s unless used as another numeric type,
+    /// and are converted when that type is represented differently: as a BigInt or a number.
+    fn convert_integer(&self, ty: Option<&SwiftType>, expression: &Expression, js: String) -> String {
+        let ty = match ty {
+            Some(SwiftType::Optional(wrapped)) => wrapped,
+            Some(ty) => ty,
+            None => return js,
+        };
+        let is_numeric = is_integer(ty) || matches!(ty, SwiftType::Double | SwiftType::Float);
+        let is_big = self.is_big_integer(ty);
+        if !is_numeric || is_big == self.is_big_integer(&SwiftType::Integer) || self.infer(expression) != Some(SwiftType::Integer) {
+            return js;
+        }
+        let (sign, literal) = match expression {
+            Expression::UnaryExpression(unary) if unary.operator.symbol == "-" => ("-", unary.operand.as_ref()),
+            expression => ("", expression),
+        };
+        match (literal, is_big) {
+            (Expression::Literal(Literal::Integer(value)), true) => format!("{sign}{value}n"),
+            (Expression::Literal(Literal::Integer(value)), false) => format!("{sign}{value}"),
+            (_, true) => format!("BigInt({js})"),
+            (_, false) => format!("Number({js})"),
+        }
+    }
+
+    /// Literals used as a `Float`, and arithmetic on them, are rounded to single precision, unless
+    /// they are exact in it.
+    fn convert_float(&self, ty: Option<&SwiftType>, expression: &Expression, js: String) -> String {
+        let ty = match ty {
+            Some(SwiftType::Optional(wrapped)) => wrapped,
+            Some(ty) => ty,
+            None => return js,
+        };
+        let is_exact = match expression {
+            Expression::Literal(Literal::Float(value)) => f64::from(*value as f32) == *value,
+            Expression::Literal(Literal::Integer(value)) => value.unsigned_abs() <= 1 << f32::MANTISSA_DIGITS,
+            _ => false,
+        };
+        match self.infer(expression) {
+            Some(SwiftType::Double | SwiftType::Integer) if *ty == SwiftType::Float && !is_exact => format!("Math.fround({js})"),
+            _ => js,
+        }
+    }
+
+    /// The integer types represented by BigInts: the 64-bit ones, except `Int` unless chosen.
+    fn is_big_integer(&self, ty: &SwiftType) -> bool {
+        match ty {
+            SwiftType::Integer => self.int_representation == IntRepresentation::BigInt,
+            SwiftType::SizedInteger(integer) => integer.bits() == 64,
+            _ => false,
+        }
+    }
+
+    /// Boxes a value converted to an existential type (or an optional one) with the witness tables
+    /// of its protocols. Boxing an existential rewraps the value inside for the new protocols.
+    /// A `Double` or `Float` converted to one, or to `Any`, is boxed with its type, which the JS
+    /// number doesn't tell.
+    fn box_existential(&self, ty: Option<&SwiftType>, expression: &Expression, js: String) -> String {
+        let ty = match ty {
+            Some(SwiftType::Optional(wrapped)) => wrapped,
+            Some(ty) => ty,
+            None => return js,
+        };
+        let value_type = self.infer(expression);
+        let is_erased = self.is_erased(ty);
+        let js = match value_type.as_ref().map(|value_type| match value_type {
+            SwiftType::Optional(wrapped) => wrapped.as_ref(),
+            value_type => value_type,
+        }) {
+            Some(floating_point @ (SwiftType::Double | SwiftType::Float)) if is_erased => {
+                format!("$rt.floatingPoint({js}, {})", js_string(&floating_point.to_string()))
+            }
+            _ => js,
+        };
+        // A value of the same existential type is already boxed for these protocols.
+        let is_boxed = value_type.is_some_and(|value_type| value_type == *ty);
+        match self.table.existential_protocols(ty) {
+            Some(protocols) if !is_boxed && !matches!(expression, Expression::Literal(Literal::Nil)) => {
+                let protocols = protocols.into_iter().map(str::to_string).collect();
+                format!("$rt.existential({})", prepend(js, protocols))
+            }
+            _ => js,
+        }
+    }
+
+    /// True for `Any` and the existential types, whose values may be of any type that converts to them.
+    fn is_erased(&self, ty: &SwiftType) -> bool {
+        *ty == SwiftType::Any || self.table.existential_protocols(ty).is_some()
+    }
+
+    /// Emits the target of a member access, opening an existential box to reach the value inside.
+    fn emit_member_target(&mut self, target: &'a Expression) -> String {
+        let js = self.emit_operand(target);
+        match self.infer(target) {
+            Some(ty) if self.table.existential_protocols(&ty).is_some() => format!("$rt.open({js})"),
+            _ => js,
+        }
+    }
+
+    /// Emits the initial value of a variable or stored property with the given ownership.
+    fn emit_initial_value(&mut self, expression: &'a Expression, ownership: ReferenceOwnership, ty: Option<&SwiftType>) -> String {
+        match ownership {
+            ReferenceOwnership::Strong => self.emit_stored_value(expression, ty),
+            ownership => self.emit_full_expression(|this| this.emit_reference_value(expression, ownership)),
+        }
+    }
+
+    /// Emits a value stored into a `weak` or `unowned` variable, which neither retains it nor takes it over.
+    fn emit_reference_value(&mut self, expression: &'a Expression, ownership: ReferenceOwnership) -> String {
+        let js = self.emit_value(expression);
+        let js = self.lend(expression, js);
+        store_reference(ownership, &js)
+    }
+
+    /// Releases the owned temporaries lent out while emitting a full expression once it has been evaluated.
+    fn emit_full_expression(&mut self, emit: impl FnOnce(&mut Self) -> String) -> String {
+        let saved = std::mem::replace(&mut self.autoreleases, 0);
+        let js = emit(self);
+        let lent = std::mem::replace(&mut self.autoreleases, saved);
+        if lent > 0 {
+            format!("$rt.drain($rt.pool(), {js})")
+        } else {
+            js
+        }
+    }
+
+    /// Emits a value that storage takes over, retaining borrowed values under reference counting.
+    fn emit_owned(&mut self, expression: &'a Expression) -> String {
+        let js = self.emit_value(expression);
+        if self.is_counted(expression) && !self.is_owned(expression) {
+            format!("$rt.retain({js})")
+        } else {
+            js
+        }
+    }
+
+    /// Emits a value that is only used, not stored.
+    fn emit_operand(&mut self, expression: &'a Expression) -> String {
+        let js = self.emit_expression(expression);
+        self.lend(expression, js)
+    }
+
+    /// Keeps an owned temporary used where a borrowed value is expected alive until the end of
+    /// its full expression, which then releases it.
+    fn lend(&mut self, expression: &Expression, js: String) -> String {
+        if self.is_counted(expression) && self.is_owned(expression) {
+            self.autoreleases += 1;
+            format!("$rt.autorelease({js})")
+        } else {
+            js
+        }
+    }
+
+    /// True when the expression's value is reference counted: it holds class instances, and the
+    /// program is compiled with reference counting.
+    fn is_counted(&self, expression: &Expression) -> bool {
+        self.lifetime == Lifetime::ReferenceCounting
+            && self.infer(expression).is_some_and(|ty| self.table.holds_references(&ty))
+    }
+
+    /// True for expressions handing their caller a value it must release: calls, constructions,
+    /// literals and computed properties. Other expressions borrow existing storage.
+    fn is_owned(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::CallExpression(_) => sema::delegation(expression).is_none(),
+            Expression::Array(_) | Expression::Dictionary(_) | Expression::Tuple(_) => true,
+            Expression::Try(expression) => self.is_owned(&expression.expression),
+            Expression::MemberAccess(access) => self
+                .infer(&access.target)
+                .as_ref()
+                .and_then(nominal_name)
+                .is_some_and(|name| self.is_computed(name, &access.member)),
+            Expression::Identifier(identifier) => {
+                self.scope.lookup(&identifier.name).is_none()
+                    && self.context.self_type.is_some_and(|name| self.is_computed(name, &identifier.name))
+            }
+            _ => false,
+        }
+    }
+
+    fn is_computed(&self, type_name: &str, name: &str) -> bool {
+        matches!(self.table.member(type_name, name), Some(Member::Property(property)) if property.stored().is_none())
+    }
+
+    /// True when assigning to the l-value stores into a variable, field or element directly, rather
+    /// than through a setter or `inout` reference that retains the new value itself.
+    fn stores_directly(&self, target: &Expression) -> bool {
+        match target {
+            Expression::Identifier(identifier) => match self.scope.lookup(&identifier.name) {
+                Some(binding) => !binding.is_inout,
+                None => {
+                    self.direct_storage(&identifier.name).is_some()
+                        || self.context.self_type.is_some_and(|name| self.is_plain_field(name, &identifier.name))
+                }
+            },
+            Expression::MemberAccess(access) => {
+                (matches!(access.target.as_ref(), Expression::SelfExpression) && self.direct_storage(&access.member).is_some())
+                    || self
+                        .infer(&access.target)
+                        .as_ref()
+                        .and_then(nominal_name)
+                        .is_some_and(|name| self.is_plain_field(name, &access.member))
+            }
+            Expression::Subscript(_) => true,
+            Expression::SelfExpression => self.context.receiver == Receiver::Local,
+            _ => false,
+        }
+    }
+
+    /// True for stored properties kept in a field of the same name, without accessors.
+    fn is_plain_field(&self, type_name: &str, name: &str) -> bool {
+        let Some(owner) = self.table.member_owner(type_name, name) else { return false };
+        match self.table.member(type_name, name) {
+            Some(Member::Property(property)) => property.has_storage() && !self.has_storage_field(owner, property),
+            _ => false,
+        }
+    }
+
+    /// Enum values are immutable in JS, so only structs and collections need copying.
+    fn is_copied(&self, ty: &SwiftType) -> bool {
+        match ty {
+            SwiftType::Array(_) | SwiftType::Dictionary(_, _) | SwiftType::Set(_) | SwiftType::Tuple(_) | SwiftType::LabeledTuple(..) => true,
+            SwiftType::Optional(wrapped) | SwiftType::ImplicitlyUnwrappedOptional(wrapped) => self.is_copied(wrapped),
+            SwiftType::Generic(name, _) => matches!(self.table.nominal(name), Some(NominalType::Struct(_))),
+            // Existential boxes are copied with the value inside, which may be a struct.
+            SwiftType::Custom(name) => matches!(self.table.nominal(name), Some(NominalType::Struct(_) | NominalType::Protocol(_))),
+            SwiftType::Existential(_) | SwiftType::Opaque(_) | SwiftType::Composition(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Emits an expression in a position that is already parenthesized, like an `if` condition.
+    fn emit_condition(&mut self, expression: &'a Expression) -> String {
+        let js = self.emit_full_expression(|this| this.emit_expression(expression));
+        strip_parentheses(&js).to_string()
+    }
+
+    fn emit_expression(&mut self, expression: &'a Expression) -> String {
+        match expression {
+            Expression::SelfExpression => self.emit_self(),
+            Expression::SuperExpression => "super".to_string(),
+            Expression::Identifier(identifier) => self.emit_identifier(&identifier.name),
+            Expression::Literal(Literal::Integer(value)) if self.is_big_integer(&SwiftType::Integer) => format!("{value}n"),
+            Expression::Literal(literal) => emit_literal(literal),
+            // `"\(count) items"` joins the text with the description of each value.
+            Expression::StringInterpolation(interpolation) => {
+                let parts = interpolation
+                    .segments
+                    .iter()
+                    .map(|segment| match segment {
+                        InterpolationSegment::Text(text) => js_string(text),
+                        InterpolationSegment::Expression(value) => self.emit_description(value),
+                    })
+                    .collect::<Vec<_>>();
+                match parts.as_slice() {
+                    [] => js_string(""),
+                    [part] => part.clone(),
+                    parts => format!("({})", parts.join(" + ")),
+                }
+            }
+            // Errors propagate as JS exceptions, which `try?` and `try!` catch.
+            Expression::Try(expression) => {
+                let value = self.emit_expression(&expression.expression);
+                match expression.kind {
+                    TryKind::Propagating => value,
+                    TryKind::Optional => format!("$rt.tryOptional(() => {value})"),
+                    TryKind::Forced => format!("$rt.tryForced(() => {value})"),
+                }
+            }
+            Expression::BinaryExpression(binary) => self.emit_binary(binary),
+            Expression::UnaryExpression(unary) => {
+                let operand = self.emit_operand(&unary.operand);
+                // Negating the minimum of a signed type overflows, and any other value of an unsigned one.
+                let operation = match unary.operator.symbol.as_str() {
+                    "-" if !matches!(unary.operand.as_ref(), Expression::Literal(_)) => Some("negate"),
+                    "~" => Some("bitwiseNot"),
+                    _ => None,
+                };
+                match (self.infer(&unary.operand).filter(is_integer), operation) {
+                    (Some(ty), Some(operation)) => format!("$rt.{operation}({operand}, {})", self.type_value(&ty)),
+                    _ => format!("{}{operand}", unary.operator.symbol),
+                }
+            }
+            Expression::CallExpression(call) => self.emit_call(call),
+            Expression::Closure(closure) => self.emit_closure(closure),
+            Expression::Subscript(subscript) => {
+                let target = self.emit_operand(&subscript.target);
+                let index = self.emit_operand(&subscript.index);
+                match (self.infer(&subscript.target), &subscript.default_value) {
+                    (Some(SwiftType::Dictionary(_, _)), Some(default_value)) => {
+                        let default_value = self.emit_value(default_value);
+                        format!("$rt.valueOrDefault({target}, {index}, {default_value})")
+                    }
+                    (Some(SwiftType::Dictionary(_, _)), None) => format!("({target}.get({index}) ?? null)"),
+                    (Some(SwiftType::String), _) => format!("$rt.characterAt({target}, {index})"),
+                    _ => format!("{target}[{index}]"),
+                }
+            }
+            Expression::Conditional(conditional) => {
+                let condition = self.emit_operand(&conditional.condition);
+                let when_true = self.emit_operand(&conditional.true_expression);
+                let when_false = self.emit_operand(&conditional.false_expression);
+                format!("({condition} ? {when_true} : {when_false})")
+            }
+            Expression::Tuple(tuple) => {
+                let elements = tuple.elements.iter().map(|element| self.emit_owned(element)).collect::<Vec<_>>();
+                format!("[{}]", elements.join(", "))
+            }
+            Expression::Array(array) => {
+                let elements = array.elements.iter().map(|element| self.emit_owned(element)).collect::<Vec<_>>();
+                format!("[{}]", elements.join(", "))
+            }
+            Expression::Dictionary(dictionary) => {
+                let entries = dictionary
+                    .elements
+                    .iter()
+                    .map(|(key, value)| format!("[{}, {}]", self.emit_operand(key), self.emit_owned(value)))
+                    .collect::<Vec<_>>();
+                format!("new $rt.HashMap([{}])", entries.join(", "))
+            }
+            // `Point.self` and `Int.self` are the types themselves, represented as `$rt.cast` takes them.
+            Expression::MemberAccess(access) if access.member == "self" => match (self.infer(expression), access.target.as_ref()) {
+                (Some(SwiftType::Metatype(instance) | SwiftType::ProtocolMetatype(instance)), Expression::Identifier(identifier))
+                    if self.scope.lookup(&identifier.name).is_none() =>
+                {
+                    self.type_value(&instance)
+                }
+                _ => self.emit_operand(&access.target),
+            },
+            Expression::MemberAccess(access) => {
+                // `Int.max` depends on how `Int` is represented, which the runtime knows.
+                if let Some(integer) = sema::integer_bound(&self.scope, access) {
+                    return format!("$rt.integerBound({}, {})", self.type_value(&integer), js_string(&access.member));
+                }
+                if let Some(standard) = self.standard_property(&access.target, &access.member) {
+                    let target = self.emit_operand(&access.target);
+                    return format!("$rt.{}({target})", standard.function);
+                }
+                let target_type = self.infer(&access.target);
+                let protocol = target_type
+                    .as_ref()
+                    .and_then(nominal_name)
+                    .and_then(|name| self.table.statically_dispatched(name, &access.member));
+                // Tuples are arrays, whose elements labels name too.
+                let index = match &target_type {
+                    Some(ty) => sema::tuple_index(ty, &access.member),
+                    None => access.member.parse::<usize>().ok(),
+                };
+                let target = self.emit_member_target(&access.target);
+                match (protocol, index) {
+                    (Some(protocol), _) => format!("$rt.protocolGet({protocol}, {}, {target})", js_string(&access.member)),
+                    (None, Some(index)) => format!("{target}[{index}]"),
+                    (None, None) => format!("{target}.{}", access.member),
+                }
+            }
+            Expression::TypeCasting(cast) => {
+                let value = self.emit_operand(&cast.expression);
+                let js = format!("$rt.cast({value}, {})", self.type_value(&cast.target_type));
+                self.box_existential(Some(&cast.target_type), expression, js)
+            }
+            Expression::PatternMatch(_) => "$rt.unsupported(\"pattern match expression\")".to_string(),
+            Expression::KeyPath(key_path) => {
+                let path = key_path.path.iter().map(|component| format!(".{component}")).collect::<String>();
+                format!("(($root) => $root{path})")
+            }
+            Expression::Assignment(assignment) => self.emit_assignment(&assignment.target, &assignment.value),
+            Expression::TypeExpression(ty) => self.type_value(ty),
+        }
+    }
+
+    /// `self`, or the copy a closure captured in its capture list.
+    fn emit_self(&self) -> String {
+        match self.captured_self {
+            Some(ownership) => load_reference(ownership, "$capturedSelf"),
+            None => self.context.receiver.js().to_string(),
+        }
+    }
+
+    fn emit_identifier(&mut self, name: &str) -> String {
+        if let Some(binding) = self.scope.lookup(name) {
+            return if binding.is_inout { format!("{name}.value") } else { load_reference(binding.ownership, name) };
+        }
+        if let Some(metadata) = self.metadata_value(name) {
+            return metadata;
+        }
+        if let Some(self_type) = self.context.self_type {
+            if let Some(member) = self.table.member(self_type, name) {
+                let is_type_context = self.context.receiver == Receiver::Type;
+                if member.is_static() == is_type_context {
+                    if let Some(protocol) = self.table.statically_dispatched(self_type, name) {
+                        return format!("$rt.protocolGet({protocol}, {}, {})", js_string(name), self.emit_self());
+                    }
+                    return format!("{}.{name}", self.emit_self());
+                }
+            }
+            // Inside a protocol extension, requirements are members of `self` too.
+            if self.context.receiver != Receiver::Type && self.table.requirement_type(self_type, name).is_some() {
+                return format!("{}.{name}", self.emit_self());
+            }
+        }
+        match name {
+            "print" => "$rt.print".to_string(),
+            name if stdlib::is_runtime_type(name) => format!("$rt.{name}"),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Arithmetic on integers is checked for overflow by the runtime, which needs the operands'
+    /// type; a literal operand has the type of the other one.
+    fn emit_binary(&mut self, binary: &'a expression::BinaryExpression) -> String {
+        let operator = binary.operator.symbol.as_str();
+        let operand_type = match binary.left.as_ref() {
+            Expression::Literal(_) => self.infer(&binary.right),
+            left => self.infer(left),
+        };
+        let integer_type = operand_type.as_ref().filter(|ty| is_integer(ty));
+        if is_compound_assignment(operator) {
+            if let Expression::Subscript(subscript) = binary.left.as_ref() {
+                if let Some(default_value) = &subscript.default_value {
+                    return self.emit_default_compound_assignment(subscript, default_value, binary, integer_type);
+                }
+            }
+            let target = self.emit_place(&binary.left);
+            let value = self.emit_expression(&binary.right);
+            let value = self.convert_integer(operand_type.as_ref(), &binary.right, value);
+            let value = self.convert_float(operand_type.as_ref(), &binary.right, value);
+            let operator = &operator[..operator.len() - 1];
+            return match (integer_type, integer_operation(operator)) {
+                (Some(ty), Some(operation)) => format!("{target} = $rt.{operation}({target}, {value}, {})", self.type_value(ty)),
+                _ if is_float_arithmetic(operand_type.as_ref(), operator) => format!("{target} = Math.fround({target} {operator} {value})"),
+                _ => format!("{target} {operator}= {value}"),
+            };
+        }
+        let left = self.emit_operand(&binary.left);
+        let left = self.convert_integer(operand_type.as_ref(), &binary.left, left);
+        let left = self.convert_float(operand_type.as_ref(), &binary.left, left);
+        let right = self.emit_operand(&binary.right);
+        let right = self.convert_integer(operand_type.as_ref(), &binary.right, right);
+        let right = self.convert_float(operand_type.as_ref(), &binary.right, right);
+        // The wrapping operators only exist for integers, `Int` unless the operands say otherwise.
+        let integer_type = match integer_type {
+            None if operator.starts_with('&') => Some(&SwiftType::Integer),
+            ty => ty,
+        };
+        if let (Some(ty), Some(operation)) = (integer_type, integer_operation(operator)) {
+            return format!("$rt.{operation}({left}, {right}, {})", self.type_value(ty));
+        }
+        if is_float_arithmetic(operand_type.as_ref(), operator) {
+            return format!("Math.fround({left} {operator} {right})");
+        }
+        // Values other than numbers, booleans and strings compare by their `Equatable` witness, or
+        // element-wise,
+        let is_nil = |operand: &Expression| matches!(operand, Expression::Literal(Literal::Nil));
+        if matches!(operator, "==" | "!=") && operand_type.as_ref().is_some_and(compares_by_witness) && !is_nil(&binary.left) && !is_nil(&binary.right) {
+            let negation = if operator == "!=" { "!" } else { "" };
+            return format!("{negation}$rt.equals({left}, {right})");
+        }
+        // and order by their `Comparable` one, which the other operators are written with.
+        if operand_type.as_ref().is_some_and(compares_by_witness) {
+            match operator {
+                "<" => return format!("$rt.less({left}, {right})"),
+                ">" => return format!("$rt.less({right}, {left})"),
+                "<=" => return format!("!$rt.less({right}, {left})"),
+                ">=" => return format!("!$rt.less({left}, {right})"),
+                _ => {}
+            }
+        }
+        let operator = match operator {
+            "==" => "===",
+            "!=" => "!==",
+            operator => operator,
+        };
+        // Strings and characters compare by canonical equivalence, not by their code units.
+        if matches!(operand_type, Some(SwiftType::String | SwiftType::Character)) && matches!(operator, "===" | "!==" | "<" | ">" | "<=" | ">=") {
+            return format!("($rt.compareStrings({left}, {right}) {operator} 0)");
+        }
+        format!("({left} {operator} {right})")
+    }
+
+    /// `counts[word, default: 0] += 1` updates the value of a dictionary's key, starting from the
+    /// default when the key is missing.
+    fn emit_default_compound_assignment(
+        &mut self,
+        subscript: &'a expression::SubscriptExpression,
+        default_value: &'a Expression,
+        binary: &'a expression::BinaryExpression,
+        integer_type: Option<&SwiftType>,
+    ) -> String {
+        let operator = &binary.operator.symbol[..binary.operator.symbol.len() - 1];
+        let dictionary = self.emit_operand(&subscript.target);
+        let key = self.emit_operand(&subscript.index);
+        let default_value = self.emit_value(default_value);
+        let value_type = self.infer(&subscript.target).and_then(|ty| match ty {
+            SwiftType::Dictionary(_, value) => Some(*value),
+            _ => None,
+        });
+        let value = self.emit_expression(&binary.right);
+        let value = self.convert_integer(integer_type, &binary.right, value);
+        let value = self.convert_float(value_type.as_ref(), &binary.right, value);
+        let update = match (integer_type, integer_operation(operator)) {
+            (Some(ty), Some(operation)) => format!("$rt.{operation}($value, {value}, {})", self.type_value(ty)),
+            _ if is_float_arithmetic(value_type.as_ref(), operator) => format!("Math.fround($value {operator} {value})"),
+            _ => format!("$value {operator} {value}"),
+        };
+        format!("$rt.modifyValue({dictionary}, {key}, {default_value}, ($value) => {update})")
+    }
+
+    /// Under reference counting, storing an owned value releases the one it replaces.
+    fn emit_assignment(&mut self, target: &'a Expression, value: &'a Expression) -> String {
+        let is_counted = self.lifetime == Lifetime::ReferenceCounting;
+        if let Expression::Subscript(subscript) = target {
+            if let Some(SwiftType::Dictionary(_, element)) = self.infer(&subscript.target) {
+                let is_counted = is_counted && self.table.holds_references(&element);
+                let dictionary = self.emit_operand(&subscript.target);
+                let key = self.emit_operand(&subscript.index);
+                return match value {
+                    Expression::Literal(Literal::Nil) if is_counted => format!("$rt.deleteValue({dictionary}, {key})"),
+                    Expression::Literal(Literal::Nil) => format!("{dictionary}.delete({key})"),
+                    _ if is_counted => {
+                        let value = self.emit_owned(value);
+                        format!("{dictionary}.set({key}, $rt.replacing({dictionary}.get({key}), {value}))")
+                    }
+                    _ => {
+                        let value = self.emit_value(value);
+                        format!("{dictionary}.set({key}, {value})")
+                    }
+                };
+            }
+        }
+        let ownership = self.target_ownership(target);
+        if ownership != ReferenceOwnership::Strong {
+            let place = self.emit_place(target);
+            let value = self.emit_reference_value(value, ownership);
+            return format!("{place} = {value}");
+        }
+        let is_counted = is_counted && (self.is_counted(target) || self.is_counted(value));
+        let place = self.emit_place(target);
+        let ty = self.infer(target);
+        if is_counted && self.stores_directly(target) {
+            let value = self.emit_converted(value, ty.as_ref());
+            return format!("{place} = $rt.replacing({place}, {value})");
+        }
+        let js = self.emit_value(value);
+        let js = self.lend(value, js);
+        let js = self.convert_integer(ty.as_ref(), value, js);
+        let value = self.box_existential(ty.as_ref(), value, js);
+        format!("{place} = {value}")
+    }
+
+    /// How an l-value refers to its instance when it is a `weak` or `unowned` variable or
+    /// stored directly; properties assigned through their setter are wrapped by the setter.
+    fn target_ownership(&self, target: &Expression) -> ReferenceOwnership {
+        let name = match target {
+            Expression::Identifier(identifier) => match self.scope.lookup(&identifier.name) {
+                Some(binding) if binding.is_inout => return ReferenceOwnership::Strong,
+                Some(binding) => return binding.ownership,
+                None => &identifier.name,
+            },
+            Expression::MemberAccess(access) if matches!(access.target.as_ref(), Expression::SelfExpression) => &access.member,
+            _ => return ReferenceOwnership::Strong,
+        };
+        match (self.direct_storage(name), self.context.self_type.and_then(|self_type| self.table.member(self_type, name))) {
+            (Some(_), Some(Member::Property(property))) if !property.is_override => property.ownership,
+            _ => ReferenceOwnership::Strong,
+        }
+    }
+
+    /// Emits an assignable JS expression for a Swift l-value.
+    fn emit_place(&mut self, expression: &'a Expression) -> String {
+        let storage = match expression {
+            Expression::Identifier(identifier) if self.scope.lookup(&identifier.name).is_none() => {
+                self.direct_storage(&identifier.name)
+            }
+            Expression::MemberAccess(access) if matches!(access.target.as_ref(), Expression::SelfExpression) => {
+                self.direct_storage(&access.member)
+            }
+            _ => None,
+        };
+        if let Some(storage) = storage {
+            return storage;
+        }
+        match expression {
+            Expression::SelfExpression if self.context.receiver == Receiver::This => {
+                "$rt.unsupported(\"assignment to self in a class\")".to_string()
+            }
+            // The standard library properties that can be set, like `outputFormatting`, are fields.
+            Expression::MemberAccess(access) if self.standard_property(&access.target, &access.member).is_some() => {
+                format!("{}.{}", self.emit_operand(&access.target), access.member)
+            }
+            _ => self.emit_expression(expression),
+        }
+    }
+
+    /// Assignments inside a property's own observers, or in the initializers of the type
+    /// declaring it, store directly without triggering `willSet`/`didSet` or subclass overrides.
+    fn direct_storage(&self, name: &str) -> Option<String> {
+        let self_type = self.context.self_type?;
+        let is_observing = self.context.observing == Some(name);
+        if !is_observing && !self.context.in_initializer {
+            return None;
+        }
+        if self.table.member_owner(self_type, name)? != self_type {
+            return None;
+        }
+        match self.table.member(self_type, name)? {
+            Member::Property(property) if property.is_override && property.is_observed() => {
+                is_observing.then(|| format!("super.{name}"))
+            }
+            Member::Property(property) if self.has_storage_field(self_type, property) => {
+                Some(format!("{}.{}", self.context.receiver.js(), self.storage(self_type, name)))
+            }
+            _ => None,
+        }
+    }
+
+    /// A reference to the storage named by an l-value, for `inout` arguments and `mutating` receivers.
+    fn emit_reference(&mut self, expression: &'a Expression) -> String {
+        match expression {
+            Expression::SelfExpression if self.context.receiver == Receiver::Inout => "$self".to_string(),
+            Expression::Identifier(identifier)
+                if self.scope.lookup(&identifier.name).map(|binding| binding.is_inout).unwrap_or(false) =>
+            {
+                identifier.name.clone()
+            }
+            _ if self.target_ownership(expression) != ReferenceOwnership::Strong => {
+                let ownership = self.target_ownership(expression);
+                let place = self.emit_place(expression);
+                let value = load_reference(ownership, &place);
+                let stored = store_reference(ownership, "$value");
+                format!("$rt.ref(() => {value}, ($value) => {place} = {stored})")
+            }
+            _ if self.is_counted(expression) && self.stores_directly(expression) => {
+                let place = self.emit_place(expression);
+                format!("$rt.ref(() => {place}, ($value) => {place} = $rt.replacing({place}, $rt.retain($value)))")
+            }
+            _ => {
+                let place = self.emit_place(expression);
+                format!("$rt.ref(() => {place}, ($value) => {place} = $value)")
+            }
+        }
+    }
+
+    /// Emits the arguments of a call to a callee with the given parameters, when known, converting
+    /// each argument to its parameter's type.
+    fn emit_arguments(
+        &mut self,
+        arguments: &'a [expression::Argument],
+        trailing: &'a [expression::TrailingClosure],
+        parameters: &[FunctionParameter],
+    ) -> Vec<String> {
+        let labels = sema::call_labels(parameters, arguments, trailing);
+        let parameters = argument_parameters(parameters, &labels);
+        let mut emitted = Vec::new();
+        for (argument, parameter) in arguments.iter().zip(&parameters) {
+            if argument.is_inout {
+                emitted.push(self.emit_reference(&argument.value));
+            } else {
+                emitted.push(self.emit_argument(&argument.value, parameter.map(|parameter| &parameter.ty)));
+            }
+        }
+        // Trailing closures take their parameter types from the parameters they fill.
+        for (closure, parameter) in trailing.iter().zip(&parameters[arguments.len()..]) {
+            emitted.push(self.emit_argument(&closure.closure, parameter.map(|parameter| &parameter.ty)));
+        }
+        emitted
+    }
+
+    /// An argument passed as a parameter of type `ty`. A closure takes its parameter types from it.
+    fn emit_argument(&mut self, value: &'a Expression, ty: Option<&SwiftType>) -> String {
+        if let (Expression::Closure(_), Some(SwiftType::Function(parameters, ..))) = (value, ty) {
+            self.closure_context = parameters.clone();
+        }
+        let js = self.emit_value(value);
+        let js = self.lend(value, js);
+        let js = self.convert_integer(ty, value, js);
+        self.box_existential(ty, value, js)
+    }
+
+    /// The arguments of a call to a standard library function or method, including its trailing
+    /// closures, passed as the parameters of its function type. A mutating method stores them.
+    fn emit_standard_arguments(&mut self, call: &'a expression::CallExpression, ty: &SwiftType, stores: bool) -> Vec<String> {
+        let parameters = match ty {
+            SwiftType::Function(parameters, ..) => parameters.as_slice(),
+            _ => &[],
+        };
+        let arguments = call
+            .arguments
+            .iter()
+            .map(|argument| &argument.value)
+            .chain(call.trailing_closures.iter().map(|closure| &closure.closure));
+        arguments
+            .enumerate()
+            .map(|(index, argument)| match argument {
+                Expression::Closure(_) => self.emit_argument(argument, parameters.get(index)),
+                _ if stores => self.emit_converted(argument, parameters.get(index)),
+                _ => self.emit_argument(argument, parameters.get(index)),
+            })
+            .collect()
+    }
+
+    fn emit_call(&mut self, call: &'a expression::CallExpression) -> String {
+        let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+        match call.callee.as_ref() {
+            Expression::Identifier(identifier) if self.table.type_reference(&self.scope, &call.callee).is_some() => {
+                let metadata = self.construction_metadata(&identifier.name, call);
+                let labels = self.table.construction_labels(&identifier.name, call);
+                let initializer = self.table.initializer(&identifier.name, &labels);
+                let parameters = initializer.map(|initializer| initializer.parameters.as_slice()).unwrap_or_default();
+                let arguments = self.emit_arguments(&call.arguments, &call.trailing_closures, parameters);
+                self.emit_construction(&identifier.name, &labels, metadata, arguments)
+            }
+            // `T(...)` constructs a value of a generic parameter's type, known from its metadata.
+            Expression::Identifier(identifier) if self.scope.lookup(&identifier.name).is_none() && self.metadata_value(&identifier.name).is_some() => {
+                let arguments = self.emit_arguments(&call.arguments, &call.trailing_closures, &[]);
+                self.emit_dynamic_construction(self.metadata_value(&identifier.name).unwrap_or_default(), &labels, arguments)
+            }
+            // `String(describing: value)` and `String(value)` describe any value, as `print` does.
+            Expression::Identifier(identifier)
+                if identifier.name == "String" && self.scope.lookup("String").is_none() && matches!(labels.as_slice(), [None | Some("describing")]) =>
+            {
+                self.emit_description(&call.arguments[0].value)
+            }
+            // `Double(count)` converts to a standard library type.
+            Expression::Identifier(identifier) if self.scope.lookup(&identifier.name).is_none() && standard_value_type(&identifier.name).is_some() => {
+                let ty = standard_value_type(&identifier.name).unwrap_or_else(SwiftType::void);
+                let arguments = self.emit_arguments(&call.arguments, &call.trailing_closures, &[]);
+                self.emit_dynamic_construction(self.type_value(&ty), &labels, arguments)
+            }
+            Expression::MemberAccess(access) => self.emit_method_call(call, access, &labels),
+            Expression::Identifier(identifier) if self.standard_function(&identifier.name, &labels).is_some() => {
+                let function = self.standard_function(&identifier.name, &labels);
+                function.map(|function| self.emit_standard_function_call(call, function)).unwrap_or_default()
+            }
+            Expression::Identifier(identifier) if self.scope.lookup(&identifier.name).is_none() => {
+                // An unqualified call inside a type may name a method of `self`.
+                let is_mutating_method = self
+                    .context
+                    .self_type
+                    .and_then(|name| self.table.member(name, &identifier.name))
+                    .map(|member| matches!(member, Member::Method(method) if method.is_mutating))
+                    .unwrap_or(false);
+                let method = self.context.self_type.and_then(|name| self.table.member(name, &identifier.name));
+                let function = match method {
+                    Some(Member::Method(method)) => Some(method),
+                    _ => self.table.function(&identifier.name),
+                };
+                let mut arguments = function.map(|function| self.function_metadata_arguments(function, call)).unwrap_or_default();
+                let parameters = function.map(|function| function.parameters.as_slice()).unwrap_or_default();
+                arguments.extend(self.emit_arguments(&call.arguments, &call.trailing_closures, parameters));
+                let protocol = self.context.self_type.and_then(|name| self.table.statically_dispatched(name, &identifier.name));
+                if is_mutating_method {
+                    let receiver = self.emit_reference(&Expression::SelfExpression);
+                    return match protocol {
+                        Some(protocol) => format!("{protocol}.prototype.{}({})", identifier.name, prepend(receiver, arguments)),
+                        None => mutating_call(&receiver, &identifier.name, arguments),
+                    };
+                }
+                if let Some(protocol) = protocol {
+                    let receiver = self.emit_self();
+                    return format!("{protocol}.prototype.{}.call({})", identifier.name, prepend(receiver, arguments));
+                }
+                let callee = self.emit_identifier(&identifier.name);
+                format!("{callee}({})", arguments.join(", "))
+            }
+            callee => {
+                // A generic function called by name still takes the metadata it needs.
+                let function = match callee {
+                    Expression::Identifier(identifier) => self.table.function(&identifier.name),
+                    _ => None,
+                };
+                let mut arguments = function.map(|function| self.function_metadata_arguments(function, call)).unwrap_or_default();
+                let parameters = function.map(|function| function.parameters.as_slice()).unwrap_or_default();
+                let callee = self.emit_expression(callee);
+                arguments.extend(self.emit_arguments(&call.arguments, &call.trailing_closures, parameters));
+                format!("{callee}({})", arguments.join(", "))
+            }
+        }
+    }
+
+    fn emit_method_call(
+        &mut self,
+        call: &'a expression::CallExpression,
+        access: &'a expression::MemberAccessExpression,
+        labels: &[Option<&str>],
+    ) -> String {
+        let type_reference = self.table.type_reference(&self.scope, &access.target);
+        let is_case = type_reference
+            .and_then(|name| self.table.member(name, &access.member))
+            .is_some_and(|member| matches!(member, Member::Case(_)));
+        let receiver_type = self.infer(&access.target);
+        let method_type = type_reference.or_else(|| receiver_type.as_ref().and_then(nominal_name));
+        let method = method_type.and_then(|name| self.table.member(name, &access.member));
+        let standard = receiver_type
+            .as_ref()
+            .filter(|_| type_reference.is_none())
+            .and_then(|receiver| Some((receiver, self.table.standard_method(receiver, &access.member, call)?)));
+        if let Some((receiver, member)) = standard {
+            return self.emit_standard_method_call(call, access, receiver, member);
+        }
+        let arguments = if is_case {
+            // An enum case stores its associated values.
+            call.arguments.iter().map(|argument| self.emit_owned(&argument.value)).collect()
+        } else {
+            let (mut arguments, parameters) = match method {
+                Some(Member::Method(method)) => (self.function_metadata_arguments(method, call), method.parameters.as_slice()),
+                _ if access.member == "init" => {
+                    let initializer = match access.target.as_ref() {
+                        Expression::SuperExpression => self.context.self_type.and_then(|name| self.table.superclass(name)),
+                        Expression::SelfExpression => self.context.self_type,
+                        _ => type_reference,
+                    }
+                    .and_then(|name| self.table.initializer(name, labels));
+                    (Vec::new(), initializer.map(|initializer| initializer.parameters.as_slice()).unwrap_or_default())
+                }
+                _ => (Vec::new(), &[][..]),
+            };
+            arguments.extend(self.emit_arguments(&call.arguments, &call.trailing_closures, parameters));
+            arguments
+        };
+        if access.member == "init" {
+            return match access.target.as_ref() {
+                // `super.init(...)` and `self.init(...)` delegate to another initializer of an existing instance.
+                Expression::SuperExpression => {
+                    let superclass = self.context.self_type.and_then(|name| self.table.superclass(name));
+                    let name = self.initializer_name(superclass, labels);
+                    let metadata = match (self.context.self_type, superclass) {
+                        (Some(self_type), Some(superclass)) => self.superclass_metadata(self_type, superclass),
+                        _ => Vec::new(),
+                    };
+                    let mut arguments = arguments;
+                    arguments.splice(0..0, metadata);
+                    format!("super.{name}({})", arguments.join(", "))
+                }
+                Expression::SelfExpression => {
+                    let name = self.initializer_name(self.context.self_type, labels);
+                    // Delegating initializers pass on the metadata they received.
+                    let metadata = self.context.self_type.map(|name| self.table.type_metadata(name)).unwrap_or_default();
+                    let mut arguments = arguments;
+                    arguments.splice(0..0, metadata.iter().map(|name| format!("${name}")));
+                    match self.context.receiver {
+                        Receiver::Local => {
+                            let type_name = self.context.self_type.unwrap_or_default();
+                            format!("self = {type_name}.{name}({})", arguments.join(", "))
+                        }
+                        receiver => format!("{}.{name}({})", receiver.js(), arguments.join(", ")),
+                    }
+                }
+                target => match (self.table.type_reference(&self.scope, target), target) {
+                    (Some(type_name), _) => {
+                        let metadata = self.construction_metadata(type_name, call);
+                        self.emit_construction(type_name, labels, metadata, arguments)
+                    }
+                    (None, Expression::Identifier(identifier)) if self.metadata_value(&identifier.name).is_some() => {
+                        self.emit_dynamic_construction(self.metadata_value(&identifier.name).unwrap_or_default(), labels, arguments)
+                    }
+                    _ => "$rt.unsupported(\"initializer call on a value\")".to_string(),
+                },
+            };
+        }
+        if let Some(type_name) = type_reference {
+            return format!("{type_name}.{}({})", access.member, arguments.join(", "));
+        }
+        let receiver_name = receiver_type.as_ref().and_then(nominal_name);
+        let protocol = receiver_name.and_then(|name| self.table.statically_dispatched(name, &access.member));
+        if let Some(Member::Method(method)) = method {
+            if method.is_mutating {
+                let receiver = self.emit_reference(&access.target);
+                return match protocol {
+                    Some(protocol) => format!("{protocol}.prototype.{}({})", access.member, prepend(receiver, arguments)),
+                    None => mutating_call(&receiver, &access.member, arguments),
+                };
+            }
+        }
+        let target = self.emit_member_target(&access.target);
+        match protocol {
+            Some(protocol) => format!("{protocol}.prototype.{}.call({})", access.member, prepend(target, arguments)),
+            None => format!("{target}.{}({})", access.member, arguments.join(", ")),
+        }
+    }
+
+    /// The standard library function a call by name reaches, unless the program declares one of
+    /// that name where the call is.
+    fn standard_function(&self, name: &str, labels: &[Option<&str>]) -> Option<&'static StandardMember> {
+        let is_declared = self.scope.lookup(name).is_some()
+            || self.table.function(name).is_some()
+            || self.context.self_type.is_some_and(|type_name| self.table.member(type_name, name).is_some());
+        if is_declared {
+            return None;
+        }
+        stdlib::global_function(name, labels)
+    }
+
+    /// `print` and `debugPrint` take any number of items before their labeled arguments, which the
+    /// runtime takes as an array. `dump` and `Mirror(reflecting:)` take the static type of the value
+    /// they reflect after it.
+    fn emit_standard_function_call(&mut self, call: &'a expression::CallExpression, function: &StandardMember) -> String {
+        match function.name() {
+            "print" | "debugPrint" => return self.emit_print(call, function),
+            "dump" | "Mirror" => {
+                let Some((value, options)) = call.arguments.split_first() else { return format!("$rt.{}()", function.function) };
+                let mut arguments = vec![self.emit_argument(&value.value, None)];
+                arguments.push(self.description_type(&value.value).unwrap_or_else(|| "undefined".to_string()));
+                for label in ["name", "indent", "maxDepth", "maxItems"] {
+                    let option = options.iter().find(|argument| argument.label.as_deref() == Some(label));
+                    arguments.push(option.map(|argument| self.emit_operand(&argument.value)).unwrap_or_else(|| "undefined".to_string()));
+                }
+                while arguments.len() > 1 && arguments.last().is_some_and(|argument| argument == "undefined") {
+                    arguments.pop();
+                }
+                return format!("$rt.{}({})", function.function, arguments.join(", "));
+            }
+            _ => {}
+        }
+        let ty = self.table.standard_call_type(&self.scope, self.context.self_type, function, None, call);
+        let arguments = self.emit_standard_arguments(call, &ty, false);
+        format!("$rt.{}({})", function.function, arguments.join(", "))
+    }
+
+    /// `debugPrint` describes its items as `String(reflecting:)` does.
+    fn emit_print(&mut self, call: &'a expression::CallExpression, function: &StandardMember) -> String {
+        let is_debug = function.name() == "debugPrint";
+        let (items, options) = call.arguments.iter().partition::<Vec<_>, _>(|argument| argument.label.is_none());
+        let items = items
+            .into_iter()
+            .map(|argument| {
+                let item = self.emit_argument(&argument.value, None);
+                match (self.description_type(&argument.value), is_debug) {
+                    (Some(ty), false) => format!("$rt.describe({item}, {ty})"),
+                    (Some(ty), true) => format!("$rt.debugDescribe({item}, {ty})"),
+                    (None, false) => item,
+                    (None, true) => format!("$rt.debugDescribe({item})"),
+                }
+            })
+            .collect::<Vec<_>>();
+        if options.is_empty() && !is_debug {
+            return format!("$rt.print({})", items.join(", "));
+        }
+        let option = |label: &str| options.iter().find(|argument| argument.label.as_deref() == Some(label));
+        let separator = option("separator").map(|argument| self.emit_operand(&argument.value)).unwrap_or_else(|| js_string(" "));
+        let terminator = option("terminator").map(|argument| self.emit_operand(&argument.value)).unwrap_or_else(|| js_string("\n"));
+        format!("$rt.{}([{}], {separator}, {terminator})", function.function, items.join(", "))
+    }
+
+    /// `String(describing: value)`. Strings describe themselves; the runtime describes other values.
+    fn emit_description(&mut self, value: &'a Expression) -> String {
+        let js = self.emit_operand(value);
+        if matches!(self.infer(value), Some(SwiftType::String)) {
+            return js;
+        }
+        match self.description_type(value) {
+            Some(ty) => format!("$rt.describe({js}, {ty})"),
+            None => format!("$rt.describe({js})"),
+        }
+    }
+
+    /// The static type the runtime needs to describe a value whose JS value doesn't tell how Swift
+    /// prints it: `1.0` for a `Double`, `Optional(5)` for an `Int?`, or a tuple in parentheses.
+    fn description_type(&self, value: &Expression) -> Option<String> {
+        let ty = self.infer(value)?;
+        self.is_described_by_type(&ty).then(|| self.type_value(&ty))
+    }
+
+    /// Floating-point types, optionals and tuples, collections of them, and type parameters, which
+    /// may be any of these.
+    fn is_described_by_type(&self, ty: &SwiftType) -> bool {
+        match ty {
+            SwiftType::Double
+            | SwiftType::Float
+            | SwiftType::Optional(_)
+            | SwiftType::ImplicitlyUnwrappedOptional(_)
+            | SwiftType::Tuple(_)
+            | SwiftType::LabeledTuple(..) => true,
+            SwiftType::Array(element) | SwiftType::Set(element) => self.is_described_by_type(element),
+            SwiftType::Dictionary(key, value) => self.is_described_by_type(key) || self.is_described_by_type(value),
+            SwiftType::Custom(name) => self.metadata_value(name).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Calls the runtime function implementing a standard library method, passing the receiver first.
+    /// Mutating methods change arrays, dictionaries and sets in place, and store their arguments;
+    /// strings are immutable in JS, so the result replaces the receiver.
+    fn emit_standard_method_call(
+        &mut self,
+        call: &'a expression::CallExpression,
+        access: &'a expression::MemberAccessExpression,
+        receiver: &SwiftType,
+        member: &StandardMember,
+    ) -> String {
+        let ty = self.table.standard_call_type(&self.scope, self.context.self_type, member, Some(receiver), call);
+        let arguments = self.emit_standard_arguments(call, &ty, member.is_mutating);
+        if member.is_mutating && *receiver == SwiftType::String {
+            let place = self.emit_place(&access.target);
+            return format!("{place} = $rt.{}({})", member.function, prepend(place.clone(), arguments));
+        }
+        let target = self.emit_operand(&access.target);
+        format!("$rt.{}({})", member.function, prepend(target, arguments))
+    }
+
+    /// The standard library property an access on a value reaches, unless the value's type declares
+    /// one of that name itself, in an extension.
+    fn standard_property(&self, target: &Expression, name: &str) -> Option<&'static StandardMember> {
+        if self.table.type_reference(&self.scope, target).is_some() {
+            return None;
+        }
+        let ty = self.infer(target)?;
+        if nominal_name(&ty).is_some_and(|type_name| self.table.member(type_name, name).is_some()) {
+            return None;
+        }
+        stdlib::member(&ty, name)
+    }
+
+    fn initializer_name(&self, type_name: Option<&str>, labels: &[Option<&str>]) -> String {
+        type_name
+            .and_then(|name| self.table.initializer(name, labels))
+            .map(|initializer| initializer_name(&initializer.parameters))
+            .unwrap_or_else(|| initializer_name_for_labels(labels))
+    }
+
+    /// `Point(x: 1, y: 2)` for value types calls the static initializer; classes allocate first.
+    /// Generic types that need metadata receive it ahead of the initializer's arguments.
+    fn emit_construction(&mut self, type_name: &str, labels: &[Option<&str>], metadata: Vec<String>, arguments: Vec<String>) -> String {
+        let nominal = self.table.nominal(type_name);
+        let initializer = self.table.initializer(type_name, labels);
+        let arguments = match initializer {
+            Some(initializer) => align_arguments(&initializer.parameters, labels, arguments),
+            None => arguments,
+        };
+        let arguments = metadata.into_iter().chain(arguments).collect::<Vec<_>>().join(", ");
+        match (nominal, initializer) {
+            (Some(NominalType::Class(_)), Some(initializer)) => {
+                let instance = format!("new {type_name}().{}({arguments})", initializer_name(&initializer.parameters));
+                if self.lifetime == Lifetime::Finalization && self.table.has_deinitializer(type_name) {
+                    format!("$rt.track({instance})")
+                } else {
+                    instance
+                }
+            }
+            (Some(NominalType::Enum(decl)), None) if decl.raw_type.is_some() && labels == [Some("rawValue")] => {
+                format!("{type_name}.$init$rawValue({arguments})")
+            }
+            (Some(NominalType::Enum(_)), None) if labels == [Some("from")] => format!("{type_name}.{}({arguments})", coding_member("init(from:)")),
+            (_, Some(initializer)) => format!("{type_name}.{}({arguments})", initializer_name(&initializer.parameters)),
+            (_, None) => format!("new {type_name}()"),
+        }
+    }
+
+    /// `T(...)` for a generic parameter `T`, whose initializers are only known at run time.
+    /// Conversions like `Double(count)` construct standard library types the same way.
+    fn emit_dynamic_construction(&mut self, metadata: String, labels: &[Option<&str>], arguments: Vec<String>) -> String {
+        let initializer = js_string(&initializer_name_for_labels(labels));
+        format!("$rt.construct({})", [metadata, initializer].into_iter().chain(arguments).collect::<Vec<_>>().join(", "))
+    }
+
+    /// A closure with a capture list is wrapped in a function called right away, whose
+    /// parameters hold the captured values.
+    fn emit_closure(&mut self, closure: &'a expression::Closure) -> String {
+        let context = std::mem::take(&mut self.closure_context);
+        if closure.capture_list.is_empty() {
+            return self.emit_closure_function(closure, &context);
+        }
+        let mut parameters = Vec::new();
+        let mut values = Vec::new();
+        let mut bindings = Vec::new();
+        for item in &closure.capture_list {
+            let (value, ty) = match &item.value {
+                Some(value) => {
+                    let js = self.emit_value(value);
+                    (self.lend(value, js), self.infer(value))
+                }
+                None => {
+                    let (js, ty) = if item.name == "self" {
+                        (self.emit_self(), self.context.self_type.map(|name| SwiftType::Custom(name.to_string())))
+                    } else {
+                        let ty = self.scope.lookup(&item.name).and_then(|binding| binding.ty.clone());
+                        (self.emit_identifier(&item.name), ty)
+                    };
+                    (if ty.as_ref().is_some_and(|ty| self.is_copied(ty)) { format!("$rt.copy({js})") } else { js }, ty)
+                }
+            };
+            values.push(store_reference(item.ownership, &value));
+            if item.name == "self" && item.value.is_none() {
+                parameters.push("$capturedSelf".to_string());
+                continue;
+            }
+            let ty = match ty {
+                Some(ty) if item.ownership == ReferenceOwnership::Weak && !matches!(ty, SwiftType::Optional(_)) => {
+                    Some(SwiftType::Optional(Box::new(ty)))
+                }
+                ty => ty,
+            };
+            parameters.push(item.name.clone());
+            bindings.push((&item.name, Binding { ty, is_constant: true, is_inout: false, ownership: item.ownership }));
+        }
+        let captured_self = closure
+            .capture_list
+            .iter()
+            .find(|item| item.name == "self" && item.value.is_none())
+            .map(|item| item.ownership)
+            .or(self.captured_self);
+        let saved = std::mem::replace(&mut self.captured_self, captured_self);
+        self.scope.push();
+        for (name, binding) in bindings {
+            self.scope.declare(name, binding);
+        }
+        let function = self.emit_closure_function(closure, &context);
+        self.scope.pop();
+        self.captured_self = saved;
+        format!("(({}) => {function})({})", parameters.join(", "), values.join(", "))
+    }
+
+    fn emit_closure_function(&mut self, closure: &'a expression::Closure, context: &[SwiftType]) -> String {
+        self.scope.push();
+        let (parameters, destructures) = sema::closure_parameters(closure, context);
+        let parameters = parameters
+            .into_iter()
+            .map(|(name, ty)| {
+                self.scope.declare(&name, Binding { ty, is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+                name
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        // Tuples are arrays, whose elements the closure takes apart.
+        let parameters = if destructures { format!("[{parameters}]") } else { parameters };
+        let result = if let [Statement::Expression(expression)] = closure.body.as_slice() {
+            let body = self.emit_full_expression(|this| {
+                if this.is_counted(expression) {
+                    this.emit_owned(expression)
+                } else {
+                    this.emit_expression(expression)
+                }
+            });
+            format!("(({parameters}) => {body})")
+        } else {
+            let saved = std::mem::take(&mut self.out);
+            let saved_indent = std::mem::replace(&mut self.indent, 1);
+            let saved_result = std::mem::replace(&mut self.context.result, closure.return_type.as_deref());
+            self.break_targets.push(None);
+            self.emit_statements(&closure.body);
+            self.break_targets.pop();
+            self.context.result = saved_result;
+            self.indent = saved_indent;
+            let body = std::mem::replace(&mut self.out, saved);
+            let padding = "  ".repeat(self.indent);
+            let body = body.lines().map(|line| format!("{padding}{line}\n")).collect::<String>();
+            format!("(({parameters}) => {{\n{body}{padding}}})")
+        };
+        self.scope.pop();
+        result
+    }
+
+    /// The runtime representation of a type, as accepted by `$rt.cast`. Collections, optionals and
+    /// tuples are described by their element types, which decoding and `print` need: `{ array: "Int" }`.
+    fn type_value(&self, ty: &SwiftType) -> String {
+        match ty {
+            SwiftType::Existential(constraint) | SwiftType::Opaque(constraint) => self.type_value(constraint),
+            SwiftType::Composition(types) => format!("[{}]", types.iter().map(|ty| self.type_value(ty)).collect::<Vec<_>>().join(", ")),
+            SwiftType::Custom(name) | SwiftType::Generic(name, _) if self.table.nominal(name).is_some() => name.clone(),
+            SwiftType::Custom(name) => match SwiftType::named(name) {
+                SwiftType::Custom(_) => self
+                    .metadata_value(name)
+                    .or_else(|| stdlib::has_members(ty).then(|| js_string(name)))
+                    .or_else(|| stdlib::is_runtime_type(name).then(|| format!("$rt.{name}")))
+                    .unwrap_or_else(|| "undefined".to_string()),
+                ty => self.type_value(&ty),
+            },
+            SwiftType::Array(element) => format!("{{ array: {} }}", self.type_value(element)),
+            SwiftType::Set(element) => format!("{{ set: {} }}", self.type_value(element)),
+            SwiftType::Dictionary(key, value) => format!("{{ dictionary: [{}, {}] }}", self.type_value(key), self.type_value(value)),
+            SwiftType::Optional(wrapped) | SwiftType::ImplicitlyUnwrappedOptional(wrapped) => {
+                format!("{{ optional: {} }}", self.type_value(wrapped))
+            }
+            SwiftType::Tuple(elements) if !elements.is_empty() => {
+                format!("{{ tuple: [{}] }}", elements.iter().map(|element| self.type_value(element)).collect::<Vec<_>>().join(", "))
+            }
+            SwiftType::LabeledTuple(labels, elements) => format!(
+                "{{ tuple: [{}], labels: [{}] }}",
+                elements.iter().map(|element| self.type_value(element)).collect::<Vec<_>>().join(", "),
+                labels.iter().map(|label| js_string(label)).collect::<Vec<_>>().join(", ")
+            ),
+            SwiftType::Any | SwiftType::AnyObject => js_string(&ty.to_string()),
+            // Nested types of the standard library are static members of the runtime's types.
+            SwiftType::Nested(base, name) if matches!(base.as_ref(), SwiftType::Custom(base) if stdlib::is_runtime_type(base)) => {
+                format!("$rt.{base}.{name}")
+            }
+            ty => match nominal_name(ty) {
+                Some(name) => js_string(name),
+                None => "undefined".to_string(),
+            },
+        }
+    }
+}
+
+fn is_integer(ty: &SwiftType) -> bool {
+    matches!(ty, SwiftType::Integer | SwiftType::SizedInteger(_))
+}
+
+/// Arithmetic on `Float`s, which JS numbers do in double precision, and is rounded to single.
+fn is_float_arithmetic(ty: Option<&SwiftType>, operator: &str) -> bool {
+    ty == Some(&SwiftType::Float) && matches!(operator, "+" | "-" | "*" | "/")
+}
+
+/// True for the types whose values `==` and `<` can't compare as JS values: structs, enums, class
+/// instances, collections and tuples, and type parameters that may be any of these.
+fn compares_by_witness(ty: &SwiftType) -> bool {
+    match ty {
+        SwiftType::Optional(wrapped) | SwiftType::ImplicitlyUnwrappedOptional(wrapped) => compares_by_witness(wrapped),
+        SwiftType::Custom(_)
+        | SwiftType::Generic(..)
+        | SwiftType::Nested(..)
+        | SwiftType::Array(_)
+        | SwiftType::Dictionary(..)
+        | SwiftType::Set(_)
+        | SwiftType::Tuple(_)
+        | SwiftType::LabeledTuple(..)
+        | SwiftType::Existential(_)
+        | SwiftType::Opaque(_)
+        | SwiftType::Composition(_)
+        | SwiftType::Any => true,
+        _ => false,
+    }
+}
+
+/// The runtime function performing an arithmetic, bitwise or shift operator on integers.
+fn integer_operation(operator: &str) -> Option<&'static str> {
+    Some(match operator {
+        "+" => "add",
+        "-" => "subtract",
+        "*" => "multiply",
+        "/" => "divide",
+        "%" => "remainder",
+        "&+" => "wrappingAdd",
+        "&-" => "wrappingSubtract",
+        "&*" => "wrappingMultiply",
+        "&" => "bitwiseAnd",
+        "|" => "bitwiseOr",
+        "^" => "bitwiseXor",
+        "<<" => "shiftLeft",
+        ">>" => "shiftRight",
+        "&<<" => "maskingShiftLeft",
+        "&>>" => "maskingShiftRight",
+        _ => return None,
+    })
+}
+
+fn is_type_declaration(declaration: &Declaration) -> bool {
+    matches!(
+        declaration,
+        Declaration::Struct(_) | Declaration::Enum(_) | Declaration::Class(_) | Declaration::Protocol(_) | Declaration::Extension(_)
+    )
+}
+
+/// Reorders statements so that a superclass comes before its subclasses and a type before its
+/// extensions, keeping the order of independent statements.
+fn dependency_order(statements: Vec<&Statement>) -> Vec<&Statement> {
+    fn place<'s>(index: usize, statements: &[&'s Statement], placed: &mut [bool], order: &mut Vec<&'s Statement>) {
+        if placed[index] {
+            return;
+        }
+        placed[index] = true;
+        if let Some(dependency) = declaration_dependency(statements[index]) {
+            for (other, statement) in statements.iter().enumerate() {
+                if declared_type_name(statement) == Some(dependency) {
+                    place(other, statements, placed, order);
+                }
+            }
+        }
+        order.push(statements[index]);
+    }
+    let mut placed = vec![false; statements.len()];
+    let mut order = Vec::with_capacity(statements.len());
+    for index in 0..statements.len() {
+        place(index, &statements, &mut placed, &mut order);
+    }
+    order
+}
+
+/// The name of the type a statement declares.
+fn declared_type_name(statement: &Statement) -> Option<&str> {
+    let Statement::Declaration(declaration) = statement else { return None };
+    match declaration.as_ref() {
+        Declaration::Struct(decl) => Some(&decl.name),
+        Declaration::Enum(decl) => Some(&decl.name),
+        Declaration::Class(decl) => Some(&decl.name),
+        Declaration::Protocol(decl) => Some(&decl.name),
+        _ => None,
+    }
+}
+
+/// The type that must be declared before a statement runs: the superclass of a class, or the
+/// type an extension extends.
+fn declaration_dependency(statement: &Statement) -> Option<&str> {
+    let Statement::Declaration(declaration) = statement else { return None };
+    match declaration.as_ref() {
+        Declaration::Class(decl) => decl.superclass.as_deref(),
+        Declaration::Extension(decl) => Some(&decl.type_name),
+        _ => None,
+    }
+}
+
+fn mutating_call(receiver: &str, method: &str, arguments: Vec<String>) -> String {
+    let mut parts = vec![receiver.to_string(), js_string(method)];
+    parts.extend(arguments);
+    format!("$rt.mutating({})", parts.join(", "))
+}
+
+/// Wraps a value stored into a `weak` or `unowned` variable so that it doesn't keep the instance alive.
+fn store_reference(ownership: ReferenceOwnership, js: &str) -> String {
+    match ownership {
+        ReferenceOwnership::Strong => js.to_string(),
+        ReferenceOwnership::Weak => format!("$rt.weak({js})"),
+        ReferenceOwnership::Unowned => format!("$rt.unowned({js})"),
+    }
+}
+
+/// Reads the instance a `weak` or `unowned` variable refers to.
+fn load_reference(ownership: ReferenceOwnership, js: &str) -> String {
+    match ownership {
+        ReferenceOwnership::Strong => js.to_string(),
+        ReferenceOwnership::Weak => format!("$rt.loadWeak({js})"),
+        ReferenceOwnership::Unowned => format!("$rt.loadUnowned({js})"),
+    }
+}
+
+/// The field holding the value of an observed or lazy stored property.
+fn storage_name(property: &str) -> String {
+    format!("$stored${property}")
+}
+
+fn accessor_head(style: MemberStyle, is_static: bool, kind: &str, name: &str, parameter: &str) -> String {
+    match (style, is_static) {
+        (MemberStyle::ClassBody, true) => format!("static {kind} {name}({parameter}) {{"),
+        (MemberStyle::ClassBody, false) => format!("{kind} {name}({parameter}) {{"),
+        (MemberStyle::Assignment, _) => format!("{kind}({parameter}) {{"),
+    }
+}
+
+fn accessor_close(style: MemberStyle) -> &'static str {
+    match style {
+        MemberStyle::ClassBody => "}",
+        MemberStyle::Assignment => "},",
+    }
+}
+
+/// Arguments with the receiver of a call first, as `Function.prototype.call` and `mutating` methods take them.
+fn prepend(receiver: String, arguments: Vec<String>) -> String {
+    std::iter::once(receiver).chain(arguments).collect::<Vec<_>>().join(", ")
+}
+
+/// The JS method name of an initializer, built from its argument labels: `init(x:y:)` is `$init$x$y`.
+pub fn initializer_name(parameters: &[FunctionParameter]) -> String {
+    initializer_name_for_labels(&parameters.iter().map(|parameter| parameter.label.as_deref()).collect::<Vec<_>>())
+}
+
+/// The JS member of a type that witnesses a protocol requirement.
+fn witness_member(witness: Witness) -> String {
+    match witness {
+        Witness::Property(property) => property.name.clone(),
+        Witness::Method(method) => method.name.clone(),
+        Witness::Initializer(initializer) => initializer_name(&initializer.parameters),
+    }
+}
+
+/// The JS member an enum's derived `encode(to:)` or `init(from:)` is emitted as.
+fn coding_member(requirement: &str) -> String {
+    match requirement {
+        "init(from:)" => initializer_name_for_labels(&[Some("from")]),
+        _ => "encode".to_string(),
+    }
+}
+
+fn initializer_name_for_labels(labels: &[Option<&str>]) -> String {
+    let mut name = "$init".to_string();
+    for label in labels {
+        name.push('$');
+        name.push_str(label.unwrap_or("_"));
+    }
+    name
+}
+
+/// The parameter each argument of a call binds to, matched by label as `align_arguments` does.
+fn argument_parameters<'p>(parameters: &'p [FunctionParameter], labels: &[Option<&str>]) -> Vec<Option<&'p FunctionParameter>> {
+    let mut matched = Vec::new();
+    let mut labels = labels.iter().peekable();
+    for parameter in parameters {
+        if labels.next_if(|label| **label == parameter.label.as_deref()).is_none() {
+            continue;
+        }
+        matched.push(Some(parameter));
+        if parameter.is_variadic {
+            while labels.next_if(|label| label.is_none()).is_some() {
+                matched.push(Some(parameter));
+            }
+        }
+    }
+    matched.extend(labels.map(|_| None));
+    matched
+}
+
+/// Lines call-site arguments up with the parameters they bind to, passing `undefined` for omitted
+/// defaulted parameters so that the JS default applies and later arguments keep their position.
+fn align_arguments(parameters: &[FunctionParameter], labels: &[Option<&str>], mut arguments: Vec<String>) -> Vec<String> {
+    let mut aligned = Vec::new();
+    let trailing = arguments.split_off(labels.len().min(arguments.len()));
+    let mut arguments = labels.iter().zip(arguments).peekable();
+    for parameter in parameters {
+        match arguments.peek() {
+            Some((label, _)) if **label == parameter.label.as_deref() => {
+                aligned.extend(arguments.next().map(|(_, argument)| argument));
+                if parameter.is_variadic {
+                    while let Some((_, argument)) = arguments.next_if(|(label, _)| label.is_none()) {
+                        aligned.push(argument);
+                    }
+                }
+            }
+            _ if parameter.is_variadic => {}
+            _ => aligned.push("undefined".to_string()),
+        }
+    }
+    aligned.extend(arguments.map(|(_, argument)| argument));
+    if trailing.is_empty() {
+        while aligned.last().map(String::as_str) == Some("undefined") {
+            aligned.pop();
+        }
+    }
+    aligned.extend(trailing);
+    aligned
+}
+
+fn join_conditions(conditions: Vec<String>) -> String {
+    let conditions = conditions.into_iter().filter(|condition| condition != "true").collect::<Vec<_>>();
+    match conditions.as_slice() {
+        [] => "true".to_string(),
+        [single] => single.clone(),
+        _ => format!("({})", conditions.join(" && ")),
+    }
+}
+
+/// Removes one pair of parentheses enclosing the whole expression, if any.
+fn strip_parentheses(js: &str) -> &str {
+    let Some(inner) = js.strip_prefix('(').and_then(|rest| rest.strip_suffix(')')) else { return js };
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for character in inner.chars() {
+        match character {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' => depth += 1,
+            ')' if depth == 0 => return js,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    inner
+}
+
+/// True if control never falls off the end of the block.
+fn ends_with_jump(body: &StatementSequence) -> bool {
+    matches!(
+        body.statements().last(),
+        Some(Statement::Return(_) | Statement::Throw(_) | Statement::Break(_) | Statement::Continue(_))
+    )
+}
+
+fn emit_literal(literal: &Literal) -> String {
+    match literal {
+        Literal::Integer(value) => value.to_string(),
+        Literal::Float(value) => format!("{value:?}"),
+        Literal::Bool(value) => value.to_string(),
+        Literal::String(value) => js_string(value),
+        Literal::Character(value) => js_string(value),
+        Literal::Nil => "null".to_string(),
+    }
+}
+
+pub fn js_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if character.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", character as u32)),
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
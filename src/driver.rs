@@ -0,0 +1,44 @@
+use crate::checker;
+use crate::codegen;
+use crate::diagnostics::Diagnostic;
+use crate::options::Options;
+use crate::runtime;
+use crate::synthesis;
+use crate::syntax::StatementSequence;
+use std::path::Path;
+
+/// The result of compiling a program that passed checking.
+#[derive(Debug, Clone)]
+pub struct Compilation {
+    /// The generated ES module.
+    pub javascript: String,
+    /// The runtime modules the generated one imports, with the file names they are written under
+    /// next to it.
+    pub runtime: &'static [(&'static str, &'static str)],
+    /// Warnings reported while checking.
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl Compilation {
+    /// Writes the generated module to `output`, and the runtime modules next to it.
+    pub fn write(&self, output: &Path) -> std::io::Result<()> {
+        std::fs::write(output, &self.javascript)?;
+        let directory = output.parent().unwrap_or(Path::new(""));
+        for (name, source) in self.runtime {
+            std::fs::write(directory.join(name), source)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs every pass over a program: synthesis of implicit members, checking, and code generation.
+/// Returns all diagnostics instead of code when any of them is an error.
+pub fn compile(program: &StatementSequence, options: &Options) -> Result<Compilation, Vec<Diagnostic>> {
+    let mut program = program.clone();
+    synthesis::synthesize(&mut program);
+    let diagnostics = checker::check(&program, options);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(diagnostics);
+    }
+    Ok(Compilation { javascript: codegen::generate(&program, options), runtime: runtime::MODULES, warnings: diagnostics })
+}
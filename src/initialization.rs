@@ -0,0 +1,683 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::diagnostics::Diagnostic;
+use crate::sema::{self, is_compound_assignment, Delegation, Member, NominalType, TypeTable};
+use crate::syntax::{Declaration, Expression, Statement, StatementSequence};
+use crate::syntax::declaration::{
+    FunDeclaration,
+    FunctionParameter,
+    InitializerDeclaration,
+    PropertyKind,
+    VariablePropertyDeclaration,
+};
+use crate::syntax::expression::{self, Literal};
+use crate::syntax::statement::{self, Pattern};
+
+/// Runs definite initialization analysis: every variable, constant and stored property of `self`
+/// must be assigned on all paths before it is read, and constants at most once.
+pub fn analyze(program: &StatementSequence, table: &TypeTable) -> Vec<Diagnostic> {
+    let mut analyzer = Analyzer::new(table);
+    analyzer.scopes.push(HashMap::new());
+    analyzer.analyze_block(program.statements());
+    analyzer.diagnostics
+}
+
+/// A variable whose initialization is tracked.
+#[derive(Debug, Clone)]
+struct Variable {
+    /// As spelled in diagnostics: `x` for locals, `self.x` for stored properties.
+    name: String,
+    is_constant: bool,
+    /// True when the declaration itself gives the value.
+    has_initial_value: bool,
+    /// The number of loops enclosing the declaration.
+    loop_depth: usize,
+}
+
+/// What is known about the tracked variables at a point in the program.
+#[derive(Debug, Clone)]
+struct State {
+    /// Variables initialized on every path reaching this point.
+    definite: HashSet<usize>,
+    /// Variables initialized on some path reaching this point.
+    possible: HashSet<usize>,
+    /// False after `return`, `throw`, `break` and `continue`.
+    reachable: bool,
+}
+
+impl State {
+    fn entry() -> Self {
+        State { definite: HashSet::new(), possible: HashSet::new(), reachable: true }
+    }
+    fn unreachable() -> Self {
+        State { reachable: false, ..State::entry() }
+    }
+    /// The state where two paths meet.
+    fn join(self, other: State) -> State {
+        match (self.reachable, other.reachable) {
+            (false, _) => other,
+            (_, false) => self,
+            (true, true) => State {
+                definite: self.definite.intersection(&other.definite).copied().collect(),
+                possible: self.possible.union(&other.possible).copied().collect(),
+                reachable: true,
+            },
+        }
+    }
+}
+
+/// The states at the jumps out of an enclosing loop or `switch`.
+#[derive(Debug)]
+struct JumpTarget {
+    is_loop: bool,
+    breaks: Vec<State>,
+    continues: Vec<State>,
+}
+
+/// The function whose body is being analyzed.
+#[derive(Debug, Default)]
+struct Function<'a> {
+    self_type: Option<&'a str>,
+    /// In an initializer, the stored properties of `self` it must assign.
+    properties: HashMap<&'a str, usize>,
+    is_initializer: bool,
+    /// Subclass initializers report uses of `self` against `super.init` instead.
+    reports_self_use: bool,
+    reported_self_use: bool,
+    reported_incomplete: bool,
+}
+
+struct Analyzer<'a> {
+    table: &'a TypeTable<'a>,
+    variables: Vec<Variable>,
+    /// Local names in scope, innermost last; `None` for bindings that are always initialized.
+    scopes: Vec<HashMap<&'a str, Option<usize>>>,
+    state: State,
+    jump_targets: Vec<JumpTarget>,
+    loop_depth: usize,
+    function: Function<'a>,
+    /// Set while analyzing a closure body, whose reads are captures.
+    in_closure: bool,
+    reported: HashSet<usize>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Analyzer<'a> {
+    fn new(table: &'a TypeTable<'a>) -> Self {
+        Analyzer {
+            table,
+            variables: Vec::new(),
+            scopes: Vec::new(),
+            state: State::entry(),
+            jump_targets: Vec::new(),
+            loop_depth: 0,
+            function: Function::default(),
+            in_closure: false,
+            reported: HashSet::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn error(&mut self, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::error(message));
+    }
+
+    fn track(&mut self, name: String, is_constant: bool, has_initial_value: bool) -> usize {
+        let id = self.variables.len();
+        self.variables.push(Variable { name, is_constant, has_initial_value, loop_depth: self.loop_depth });
+        if has_initial_value {
+            self.state.definite.insert(id);
+            self.state.possible.insert(id);
+        }
+        id
+    }
+
+    fn declare(&mut self, name: &'a str, id: Option<usize>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, id);
+        }
+    }
+
+    /// Resolves a name to a tracked local or stored property; `Err` if it names something else.
+    fn resolve(&self, name: &str) -> Result<usize, ()> {
+        match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+            Some(Some(id)) => Ok(*id),
+            Some(None) => Err(()),
+            None => self.function.properties.get(name).copied().ok_or(()),
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Functions and declarations
+    // ---------------------------------------------------------------------
+
+    /// Analyzes a nested body with its own scope and jump targets. Variables of the enclosing code
+    /// are treated as initialized, since the body may run at any later point.
+    fn analyze_function(&mut self, function: Function<'a>, parameters: &'a [FunctionParameter], body: &'a [Statement]) {
+        let saved_function = std::mem::replace(&mut self.function, function);
+        let saved_targets = std::mem::take(&mut self.jump_targets);
+        let saved_depth = std::mem::replace(&mut self.loop_depth, 0);
+        let outer = (0..self.variables.len()).collect::<HashSet<_>>();
+        let entry = State { definite: outer.clone(), possible: outer, reachable: true };
+        let saved_state = std::mem::replace(&mut self.state, entry);
+        self.scopes.push(HashMap::new());
+        for parameter in parameters {
+            self.declare(&parameter.internal_name, None);
+        }
+        let properties = self.function.properties.values().copied().collect::<Vec<_>>();
+        for id in properties {
+            self.state.definite.remove(&id);
+            self.state.possible.remove(&id);
+        }
+        self.analyze_block(body);
+        if self.function.is_initializer && self.state.reachable {
+            self.check_initialized_on_return();
+        }
+        self.scopes.pop();
+        self.state = saved_state;
+        self.loop_depth = saved_depth;
+        self.jump_targets = saved_targets;
+        self.function = saved_function;
+    }
+
+    fn analyze_method(&mut self, self_type: Option<&'a str>, function: &'a FunDeclaration) {
+        let Some(body) = &function.body else { return };
+        let context = Function { self_type, ..Function::default() };
+        self.analyze_function(context, &function.parameters, body.statements());
+    }
+
+    fn analyze_initializer(&mut self, type_name: &'a str, initializer: &'a InitializerDeclaration) {
+        let mut function =
+            Function { self_type: Some(type_name), is_initializer: true, reports_self_use: true, ..Function::default() };
+        let delegates_to_self =
+            sema::delegations(&initializer.body).iter().any(|(delegation, _)| *delegation == Delegation::ToSelf);
+        match self.table.nominal(type_name) {
+            // A delegating initializer leaves every property to the initializer it calls.
+            Some(nominal @ (NominalType::Struct(_) | NominalType::Class(_))) if !delegates_to_self => {
+                for property in nominal.properties().iter().filter(|property| property.requires_initialization()) {
+                    let id = self.track(format!("self.{}", property.name), property.is_constant, false);
+                    function.properties.insert(&property.name, id);
+                }
+                function.reports_self_use = self.table.superclass(type_name).is_none();
+            }
+            _ => function.is_initializer = false,
+        }
+        self.analyze_function(function, &initializer.parameters, initializer.body.statements());
+    }
+
+    fn analyze_members(
+        &mut self,
+        type_name: &'a str,
+        properties: &'a [VariablePropertyDeclaration],
+        methods: &'a [FunDeclaration],
+        initializers: &'a [InitializerDeclaration],
+    ) {
+        for property in properties {
+            match &property.kind {
+                PropertyKind::Computed(computed) => {
+                    self.analyze_method(Some(type_name), &computed.getter);
+                    if let Some(setter) = &computed.setter {
+                        self.analyze_method(Some(type_name), setter);
+                    }
+                }
+                PropertyKind::Stored(stored) => {
+                    for observer in stored.will_set.iter().chain(&stored.did_set) {
+                        let context = Function { self_type: Some(type_name), ..Function::default() };
+                        self.analyze_function(context, &[], observer.body.statements());
+                    }
+                }
+            }
+        }
+        for method in methods {
+            self.analyze_method(Some(type_name), method);
+        }
+        for initializer in initializers {
+            self.analyze_initializer(type_name, initializer);
+        }
+    }
+
+    fn analyze_declaration(&mut self, declaration: &'a Declaration) {
+        match declaration {
+            Declaration::Var(var) => {
+                if let Some(value) = &var.initial_value {
+                    self.analyze_expression(value);
+                }
+                let id = self.track(var.name.clone(), false, var.initial_value.is_some());
+                self.declare(&var.name, Some(id));
+            }
+            Declaration::Let(constant) => {
+                if let Some(value) = &constant.initial_value {
+                    self.analyze_expression(value);
+                }
+                let id = self.track(constant.name.clone(), true, constant.initial_value.is_some());
+                self.declare(&constant.name, Some(id));
+            }
+            Declaration::Function(function) => {
+                self.declare(&function.name, None);
+                self.analyze_method(self.function.self_type, function);
+            }
+            Declaration::Struct(decl) => {
+                self.analyze_members(&decl.name, &decl.properties, &decl.methods, &decl.initializers)
+            }
+            Declaration::Enum(decl) => self.analyze_members(&decl.name, &decl.properties, &decl.methods, &decl.initializers),
+            Declaration::Class(decl) => {
+                self.analyze_members(&decl.name, &decl.properties, &decl.methods, &decl.initializers);
+                if let Some(deinitializer) = &decl.deinitializer {
+                    let context = Function { self_type: Some(&decl.name), ..Function::default() };
+                    self.analyze_function(context, &[], deinitializer.body.statements());
+                }
+            }
+            Declaration::Extension(decl) => {
+                let Some(nominal) = self.table.nominal(&decl.type_name) else { return };
+                self.analyze_members(nominal.name(), &decl.properties, &decl.methods, &decl.initializers);
+            }
+            Declaration::Protocol(_) | Declaration::TypeAlias(_) | Declaration::Import(_) => {}
+            Declaration::Initializer(_) | Declaration::Deinitializer(_) => {}
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Statements
+    // ---------------------------------------------------------------------
+
+    fn analyze_block(&mut self, statements: &'a [Statement]) {
+        self.scopes.push(HashMap::new());
+        for statement in statements {
+            self.analyze_statement(statement);
+        }
+        self.scopes.pop();
+    }
+
+    /// Analyzes a branch starting from `entry` and returns the state at its end.
+    fn analyze_branch(&mut self, entry: State, statements: &'a [Statement]) -> State {
+        self.state = entry;
+        self.analyze_block(statements);
+        std::mem::replace(&mut self.state, State::unreachable())
+    }
+
+    fn analyze_statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::Expression(expression) => self.analyze_expression(expression),
+            Statement::Declaration(declaration) => self.analyze_declaration(declaration),
+            Statement::Assignment(assignment) => {
+                self.analyze_expression(&assignment.value);
+                self.assign(&assignment.target);
+            }
+            Statement::Return(statement) => {
+                if let Some(expression) = &statement.expression {
+                    self.analyze_expression(expression);
+                }
+                let returns_nil = matches!(
+                    statement.expression.as_deref(),
+                    Some(Expression::Literal(Literal::Nil))
+                );
+                if self.function.is_initializer && !returns_nil && self.state.reachable {
+                    self.check_initialized_on_return();
+                }
+                self.state = State::unreachable();
+            }
+            Statement::Throw(statement) => {
+                self.analyze_expression(&statement.expression);
+                self.state = State::unreachable();
+            }
+            Statement::Break(_) => {
+                let state = std::mem::replace(&mut self.state, State::unreachable());
+                if let Some(target) = self.jump_targets.last_mut() {
+                    target.breaks.push(state);
+                }
+            }
+            Statement::Continue(_) => {
+                let state = std::mem::replace(&mut self.state, State::unreachable());
+                if let Some(target) = self.jump_targets.iter_mut().rev().find(|target| target.is_loop) {
+                    target.continues.push(state);
+                }
+            }
+            Statement::If(statement) => {
+                self.analyze_expression(&statement.condition);
+                let entry = self.state.clone();
+                let then_state = self.analyze_branch(entry.clone(), statement.body.statements());
+                let else_state = match &statement.else_body {
+                    Some(else_body) => self.analyze_branch(entry, else_body.statements()),
+                    None => entry,
+                };
+                self.state = then_state.join(else_state);
+            }
+            Statement::Guard(statement) => {
+                self.analyze_expression(&statement.condition);
+                let entry = self.state.clone();
+                // The `else` body must exit the scope, so only the passing path continues.
+                self.analyze_branch(entry.clone(), statement.body.statements());
+                self.state = entry;
+            }
+            Statement::Switch(statement) => self.analyze_switch(statement),
+            Statement::WhileLoop(statement) => {
+                self.analyze_expression(&statement.condition);
+                self.analyze_loop(&statement.body, true);
+            }
+            Statement::ForLoop(statement) => {
+                self.analyze_expression(&statement.range.0);
+                self.analyze_expression(&statement.range.1);
+                self.scopes.push(HashMap::from([(statement.variable.as_str(), None)]));
+                self.analyze_loop(&statement.body, true);
+                self.scopes.pop();
+            }
+            Statement::ForIn(statement) => {
+                self.analyze_expression(&statement.sequence);
+                self.scopes.push(HashMap::from([(statement.variable.as_str(), None)]));
+                self.analyze_loop(&statement.body, true);
+                self.scopes.pop();
+            }
+            Statement::RepeatWhileLoop(statement) => {
+                self.analyze_loop(&statement.body, false);
+                self.analyze_expression(&statement.condition);
+            }
+            Statement::DoCatch(statement) => {
+                let entry = self.state.clone();
+                let body_state = self.analyze_branch(entry.clone(), statement.body.statements());
+                // The body may throw at any point: only what held on entry is definite in `catch`,
+                // while anything it assigned may have been.
+                let catch_entry = State {
+                    possible: entry.possible.union(&body_state.possible).copied().collect(),
+                    ..entry
+                };
+                // Errors no clause matches leave the initializer.
+                let mut exit = body_state;
+                for clause in &statement.catch_clauses {
+                    let mut bindings = HashMap::new();
+                    match &clause.pattern {
+                        Some(pattern) => pattern_bindings(pattern, &mut bindings),
+                        None => {
+                            bindings.insert("error", None);
+                        }
+                    }
+                    self.scopes.push(bindings);
+                    self.state = catch_entry.clone();
+                    if let Some(guard) = &clause.guard_expression {
+                        self.analyze_expression(guard);
+                    }
+                    let clause_state = self.analyze_branch(self.state.clone(), clause.body.statements());
+                    self.scopes.pop();
+                    exit = exit.join(clause_state);
+                }
+                self.state = exit;
+            }
+        }
+    }
+
+    /// Loops run their body any number of times; `repeat` bodies at least once.
+    fn analyze_loop(&mut self, body: &'a StatementSequence, may_skip: bool) {
+        let entry = self.state.clone();
+        self.jump_targets.push(JumpTarget { is_loop: true, breaks: Vec::new(), continues: Vec::new() });
+        self.loop_depth += 1;
+        let body_state = self.analyze_branch(entry.clone(), body.statements());
+        self.loop_depth -= 1;
+        let target = self.jump_targets.pop().expect("loop target");
+        let mut exit = target.continues.into_iter().fold(body_state, State::join);
+        if may_skip {
+            exit = exit.join(entry);
+        }
+        self.state = target.breaks.into_iter().fold(exit, State::join);
+    }
+
+    /// Switches are exhaustive, so some case body always runs.
+    fn analyze_switch(&mut self, statement: &'a statement::SwitchStatement) {
+        self.analyze_expression(&statement.expression);
+        let entry = self.state.clone();
+        self.jump_targets.push(JumpTarget { is_loop: false, breaks: Vec::new(), continues: Vec::new() });
+        let mut exit = State::unreachable();
+        for case in &statement.cases {
+            self.state = entry.clone();
+            let mut bindings = HashMap::new();
+            for pattern in &case.patterns {
+                pattern_bindings(pattern, &mut bindings);
+            }
+            self.scopes.push(bindings);
+            if let Some(guard) = &case.guard_expression {
+                self.analyze_expression(guard);
+            }
+            let case_state = self.analyze_branch(self.state.clone(), case.body.statements());
+            self.scopes.pop();
+            exit = exit.join(case_state);
+        }
+        if let Some(default_case) = &statement.default_case {
+            exit = exit.join(self.analyze_branch(entry, default_case.statements()));
+        }
+        let target = self.jump_targets.pop().expect("switch target");
+        self.state = target.breaks.into_iter().fold(exit, State::join);
+    }
+
+    // ---------------------------------------------------------------------
+    // Expressions
+    // ---------------------------------------------------------------------
+
+    fn analyze_expression(&mut self, expression: &'a Expression) {
+        match expression {
+            Expression::Literal(_) | Expression::KeyPath(_) | Expression::TypeExpression(_) | Expression::SuperExpression => {}
+            Expression::SelfExpression => self.use_self(),
+            Expression::Identifier(identifier) => self.read_name(&identifier.name),
+            Expression::MemberAccess(access) => match access.target.as_ref() {
+                Expression::SelfExpression => self.read_member(&access.member),
+                target => self.analyze_expression(target),
+            },
+            Expression::BinaryExpression(binary) => {
+                self.analyze_expression(&binary.left);
+                self.analyze_expression(&binary.right);
+                if is_compound_assignment(&binary.operator.symbol) {
+                    self.assign(&binary.left);
+                }
+            }
+            Expression::UnaryExpression(unary) => self.analyze_expression(&unary.operand),
+            Expression::StringInterpolation(interpolation) => interpolation.values().for_each(|value| self.analyze_expression(value)),
+            Expression::CallExpression(call) => self.analyze_call(call),
+            Expression::Try(expression) => self.analyze_expression(&expression.expression),
+            Expression::Closure(closure) => self.analyze_closure(closure),
+            Expression::Subscript(subscript) => {
+                self.analyze_expression(&subscript.target);
+                self.analyze_expression(&subscript.index);
+            }
+            Expression::Conditional(conditional) => {
+                self.analyze_expression(&conditional.condition);
+                self.analyze_expression(&conditional.true_expression);
+                self.analyze_expression(&conditional.false_expression);
+            }
+            Expression::Tuple(tuple) => tuple.elements.iter().for_each(|element| self.analyze_expression(element)),
+            Expression::Array(array) => array.elements.iter().for_each(|element| self.analyze_expression(element)),
+            Expression::Dictionary(dictionary) => {
+                for (key, value) in &dictionary.elements {
+                    self.analyze_expression(key);
+                    self.analyze_expression(value);
+                }
+            }
+            Expression::TypeCasting(cast) => self.analyze_expression(&cast.expression),
+            Expression::PatternMatch(pattern_match) => self.analyze_expression(&pattern_match.expression),
+            Expression::Assignment(assignment) => {
+                self.analyze_expression(&assignment.value);
+                self.assign(&assignment.target);
+            }
+        }
+    }
+
+    fn analyze_call(&mut self, call: &'a expression::CallExpression) {
+        let delegation = sema::delegation_call(call).map(|(delegation, _)| delegation);
+        if delegation.is_none() {
+            self.analyze_expression(&call.callee);
+        }
+        for argument in &call.arguments {
+            self.analyze_expression(&argument.value);
+        }
+        for closure in &call.trailing_closures {
+            self.analyze_expression(&closure.closure);
+        }
+        match delegation {
+            Some(Delegation::ToSuper) if self.state.reachable => {
+                let mut missing = self.uninitialized_properties();
+                missing.sort();
+                for name in missing {
+                    self.error(format!("property '{name}' not initialized at super.init call"));
+                }
+                self.initialize_self();
+            }
+            Some(_) => self.initialize_self(),
+            None => {}
+        }
+    }
+
+    fn analyze_closure(&mut self, closure: &'a expression::Closure) {
+        // The capture list is evaluated when the closure is created.
+        for item in &closure.capture_list {
+            match &item.value {
+                Some(value) => self.analyze_expression(value),
+                None if item.name == "self" => self.use_self(),
+                None => self.read_name(&item.name),
+            }
+        }
+        // Assignments in a closure don't initialize anything for the code that creates it.
+        let saved_state = self.state.clone();
+        let saved_targets = std::mem::take(&mut self.jump_targets);
+        let was_in_closure = std::mem::replace(&mut self.in_closure, true);
+        let captures = closure.capture_list.iter().filter(|item| item.name != "self" || item.value.is_some());
+        let parameters = closure.parameters.iter().map(|parameter| parameter.name.as_str());
+        self.scopes.push(captures.map(|item| item.name.as_str()).chain(parameters).map(|name| (name, None)).collect());
+        self.analyze_block(&closure.body);
+        self.scopes.pop();
+        self.in_closure = was_in_closure;
+        self.jump_targets = saved_targets;
+        self.state = saved_state;
+    }
+
+    fn read_name(&mut self, name: &str) {
+        match self.resolve(name) {
+            Ok(id) => self.read(id),
+            Err(()) if self.scopes.iter().any(|scope| scope.contains_key(name)) => {}
+            Err(()) => self.read_member(name),
+        }
+    }
+
+    /// A read of `self.name`, or of `name` resolving to a member of `self`.
+    fn read_member(&mut self, name: &str) {
+        if let Some(id) = self.function.properties.get(name).copied() {
+            self.read(id);
+            return;
+        }
+        let Some(self_type) = self.function.self_type else { return };
+        match self.table.member(self_type, name) {
+            Some(Member::Method(method)) if !method.is_static && !method.is_class => self.use_self(),
+            Some(Member::Property(property)) if !(property.is_static || property.is_class) => {
+                if matches!(property.kind, PropertyKind::Computed(_)) {
+                    self.use_self();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn read(&mut self, id: usize) {
+        if !self.state.reachable || self.state.definite.contains(&id) || !self.reported.insert(id) {
+            return;
+        }
+        let variable = &self.variables[id];
+        let kind = if variable.is_constant { "constant" } else { "variable" };
+        let name = &variable.name;
+        let message = if self.in_closure {
+            format!("{kind} '{name}' captured by a closure before being initialized")
+        } else {
+            format!("{kind} '{name}' used before being initialized")
+        };
+        self.error(message);
+    }
+
+    /// Using `self` as a whole (calling a method, passing it along) requires all its stored properties.
+    fn use_self(&mut self) {
+        if !self.function.reports_self_use || self.function.reported_self_use || !self.state.reachable {
+            return;
+        }
+        if !self.uninitialized_properties().is_empty() {
+            self.function.reported_self_use = true;
+            self.error("'self' used before all stored properties are initialized");
+        }
+    }
+
+    fn assign(&mut self, target: &'a Expression) {
+        let id = match target {
+            Expression::Identifier(identifier) => self.resolve(&identifier.name).ok(),
+            Expression::MemberAccess(access) if matches!(access.target.as_ref(), Expression::SelfExpression) => {
+                self.function.properties.get(access.member.as_str()).copied()
+            }
+            Expression::SelfExpression => {
+                self.initialize_self();
+                return;
+            }
+            _ => None,
+        };
+        let Some(id) = id else {
+            // Stored properties with a default value, like optionals, are initialized already.
+            if let Expression::MemberAccess(access) = target {
+                if matches!(access.target.as_ref(), Expression::SelfExpression) && self.is_stored_property(&access.member) {
+                    return;
+                }
+            }
+            // Assigning part of a value, as in `point.x = 1`, reads the rest of it.
+            if let Expression::MemberAccess(access) = target {
+                self.analyze_expression(&access.target);
+            } else if let Expression::Subscript(subscript) = target {
+                self.analyze_expression(&subscript.target);
+                self.analyze_expression(&subscript.index);
+            }
+            return;
+        };
+        if self.in_closure {
+            return;
+        }
+        let variable = &self.variables[id];
+        if variable.is_constant {
+            let name = variable.name.clone();
+            if variable.has_initial_value {
+                self.error(format!("cannot assign to value: '{name}' is a 'let' constant"));
+            } else if self.state.possible.contains(&id) || self.loop_depth > variable.loop_depth {
+                self.error(format!("immutable value '{name}' may only be initialized once"));
+            }
+        }
+        self.state.definite.insert(id);
+        self.state.possible.insert(id);
+    }
+
+    fn uninitialized_properties(&self) -> Vec<String> {
+        self.function
+            .properties
+            .values()
+            .filter(|id| !self.state.definite.contains(id))
+            .map(|id| self.variables[*id].name.clone())
+            .collect()
+    }
+
+    fn is_stored_property(&self, name: &str) -> bool {
+        let Some(self_type) = self.function.self_type else { return false };
+        matches!(self.table.member(self_type, name), Some(Member::Property(property)) if !property.is_static && property.has_storage())
+    }
+
+    /// After `self.init`, `super.init` or `self = ...`, every stored property holds a value.
+    fn initialize_self(&mut self) {
+        let properties = self.function.properties.values().copied().collect::<Vec<_>>();
+        self.state.definite.extend(&properties);
+        self.state.possible.extend(properties);
+    }
+
+    fn check_initialized_on_return(&mut self) {
+        if self.function.reported_incomplete || self.uninitialized_properties().is_empty() {
+            return;
+        }
+        self.function.reported_incomplete = true;
+        self.error("return from initializer without initializing all stored properties");
+    }
+}
+
+fn pattern_bindings<'a>(pattern: &'a Pattern, bindings: &mut HashMap<&'a str, Option<usize>>) {
+    match pattern {
+        Pattern::Identifier(identifier) => {
+            bindings.insert(&identifier.name, None);
+        }
+        Pattern::Tuple(tuple) => tuple.elements.iter().for_each(|element| pattern_bindings(element, bindings)),
+        Pattern::EnumCase(case) => case.associated_values.iter().for_each(|value| pattern_bindings(value, bindings)),
+        Pattern::Literal(_) | Pattern::Wildcard | Pattern::TypePattern(_) => {}
+    }
+}
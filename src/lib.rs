@@ -0,0 +1,12 @@
+pub mod checker;
+pub mod cli;
+pub mod codegen;
+pub mod diagnostics;
+pub mod driver;
+pub mod initialization;
+pub mod options;
+pub mod runtime;
+pub mod sema;
+pub mod stdlib;
+pub mod synthesis;
+pub mod syntax;
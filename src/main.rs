@@ -1,6 +1,28 @@
-pub mod syntax;
+use std::process::ExitCode;
+use swift_oxide::cli::{self, USAGE};
+use swift_oxide::driver;
+use swift_oxide::syntax::StatementSequence;
 
-fn main() {
-    println!("Hello, world!");
+fn main() -> ExitCode {
+    let arguments = match cli::parse_arguments(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            eprintln!("error: {message}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    // Programs are built as syntax trees by embedders, as there is no parser for Swift source yet,
+    // so the module written sets up the runtime for the options, with the runtime next to it.
+    let compilation = match driver::compile(&StatementSequence::new(Vec::new()), &arguments.options) {
+        Ok(compilation) => compilation,
+        Err(diagnostics) => {
+            diagnostics.iter().for_each(|diagnostic| eprintln!("{diagnostic}"));
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(error) = compilation.write(&arguments.output) {
+        eprintln!("error: could not write '{}': {error}", arguments.output.display());
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
 }
-
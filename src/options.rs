@@ -0,0 +1,44 @@
+/// Settings that change how a program is checked and compiled.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub lifetime: Lifetime,
+    pub int_representation: IntRepresentation,
+}
+
+/// How the lifetime of class instances, and so the timing of `deinit`, is emulated in JavaScript.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lifetime {
+    /// `deinit` runs whenever the garbage collector reclaims an instance, through a
+    /// `FinalizationRegistry`. This may be much later than in Swift, or never.
+    #[default]
+    Finalization,
+    /// Generated code counts strong references to class instances, running `deinit` as soon as the
+    /// last one goes away: when a variable goes out of scope or is reassigned, or when an element is
+    /// removed from a collection.
+    ReferenceCounting,
+}
+
+/// How Swift's `Int` is represented in JavaScript. The other integer types don't depend on it:
+/// `Int64`, `UInt64` and `UInt` are always `BigInt`s, and the narrower types numbers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntRepresentation {
+    /// A `Number`, which is fast and what JavaScript APIs take, but only holds integers up to 2^53
+    /// exactly. Arithmetic traps beyond that, where Swift would not overflow yet.
+    #[default]
+    Number,
+    /// A `BigInt`, with exactly the range of a 64-bit `Int`.
+    BigInt,
+}
+
+/// Parses the value of the `--int` command-line option: `number` or `bigint`.
+impl std::str::FromStr for IntRepresentation {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "number" => Ok(IntRepresentation::Number),
+            "bigint" => Ok(IntRepresentation::BigInt),
+            _ => Err(format!("invalid value '{value}' for '--int': expected 'number' or 'bigint'")),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+/// The runtime module imported by generated JavaScript, written next to the output as `swift-runtime.js`.
+pub const CORE: &str = include_str!("runtime/core.js");
+
+/// The standard library members and functions, re-exported by the core module and written next to
+/// it as `swift-stdlib.js`.
+pub const STDLIB: &str = include_str!("runtime/stdlib.js");
+
+/// Encoders, decoders and JSON coding, re-exported by the core module and written next to it as
+/// `swift-codable.js`.
+pub const CODABLE: &str = include_str!("runtime/codable.js");
+
+/// `Mirror` and `dump`, re-exported by the core module and written next to it as
+/// `swift-reflection.js`.
+pub const REFLECTION: &str = include_str!("runtime/reflection.js");
+
+/// Every runtime module with the file name it's written under.
+pub const MODULES: &[(&str, &str)] = &[
+    ("swift-runtime.js", CORE),
+    ("swift-stdlib.js", STDLIB),
+    ("swift-codable.js", CODABLE),
+    ("swift-reflection.js", REFLECTION),
+];
+
+/// The module specifier generated code uses to import the runtime.
+pub const MODULE_NAME: &str = "./swift-runtime.js";
@@ -0,0 +1,918 @@
+// `Codable` support: encoders and decoders, the containers `encode(to:)` and `init(from:)` use,
+// and JSON coding as Foundation's `JSONEncoder` and `JSONDecoder` do it. Encoding builds a tree of
+// `Map`s for keyed containers, arrays for unkeyed ones, and the JSON values of single values;
+// decoding reads the tree `JSON.parse` returns. Types to decode are described as `$rt.cast` takes
+// them, with collections and optionals described by their element types: `{ array: "Int" }`.
+import { HashMap, HashSet, combine, compareStrings, construct, describe, open, standardCase, toInt, typeName, unsupported, witnesses } from "./swift-runtime.js";
+
+const integerTypes = ["Int", "Int8", "Int16", "Int32", "Int64", "UInt", "UInt8", "UInt16", "UInt32", "UInt64"];
+
+// Seconds between 1970 and 2001, when `Date`'s reference date falls.
+const referenceDate = 978307200;
+
+// `Data` is an array of bytes, and `Date` an instant in seconds since 1970.
+class Data extends Array {
+  toString() {
+    return `${this.length} bytes`;
+  }
+}
+
+class Instant {
+  constructor(seconds) {
+    this.$seconds = seconds;
+    Object.freeze(this);
+  }
+
+  static $conformances = new Map([
+    ["Equatable", { "==(_:_:)": "==" }],
+    ["Hashable", { "hash(into:)": "hash" }],
+    ["Comparable", { "<(_:_:)": "<" }],
+  ]);
+
+  static ["=="](lhs, rhs) {
+    return lhs.$seconds === rhs.$seconds;
+  }
+
+  static ["<"](lhs, rhs) {
+    return lhs.$seconds < rhs.$seconds;
+  }
+
+  hash(hasher) {
+    combine(hasher.value, this.$seconds);
+  }
+
+  $copy() {
+    return this;
+  }
+
+  // `2001-01-01 00:00:00 +0000`
+  toString() {
+    return new Date(this.$seconds * 1000).toISOString().replace("T", " ").replace(/\.\d+Z$/, " +0000");
+  }
+}
+
+export function makeData(bytes) {
+  return Data.from(bytes);
+}
+
+export function now() {
+  return new Instant(Date.now() / 1000);
+}
+
+export function dateSince1970(seconds) {
+  return new Instant(seconds);
+}
+
+export function timeIntervalSince1970(date) {
+  return date.$seconds;
+}
+
+export function timeIntervalSince(date, other) {
+  return date.$seconds - other.$seconds;
+}
+
+// `String(decoding: data, as: UTF8.self)` names the encoding by this type.
+export class UTF8 {}
+
+// Coding keys are enum cases, named by their `String` raw value or else their case name, or values
+// of other types declaring `stringValue`. The runtime's own keys index unkeyed containers and name
+// the keys of dictionaries.
+class JSONKey {
+  constructor(stringValue, intValue = null) {
+    this.stringValue = stringValue;
+    this.intValue = intValue;
+  }
+
+  static index(index) {
+    return new JSONKey(`Index ${index}`, toInt(index));
+  }
+
+  static superKey = new JSONKey("super");
+}
+
+export function stringValue(key) {
+  if (typeof key === "string") return key;
+  if (key.$case === undefined) return key.stringValue;
+  return typeof key.rawValue === "string" ? key.rawValue : key.$case;
+}
+
+export function intValue(key) {
+  if (key.$case === undefined) return key.intValue ?? null;
+  return typeof key.rawValue === "number" || typeof key.rawValue === "bigint" ? key.rawValue : null;
+}
+
+// `CodingKeys(stringValue: "name", intValue: nil)`
+function describeKey(key) {
+  const name = key instanceof JSONKey ? "_JSONKey" : key.constructor.name;
+  const index = intValue(key);
+  return `${name}(stringValue: ${JSON.stringify(stringValue(key))}, intValue: ${index === null ? "nil" : index})`;
+}
+
+// The cases of a coding key enum, which are static fields of its class.
+function keyCases(keys) {
+  return Object.values(keys).filter((value) => value instanceof keys);
+}
+
+// The errors coding throws, printed as Swift prints them.
+class Context {
+  constructor(codingPath, debugDescription, underlyingError = null) {
+    this.codingPath = codingPath;
+    this.debugDescription = debugDescription;
+    this.underlyingError = underlyingError;
+  }
+
+  $describe(owner) {
+    const path = this.codingPath.map(describeKey).join(", ");
+    const underlying = this.underlyingError === null ? "nil" : `Optional(${describe(this.underlyingError)})`;
+    return `Swift.${owner}.Context(codingPath: [${path}], debugDescription: ${JSON.stringify(this.debugDescription)}, underlyingError: ${underlying})`;
+  }
+}
+
+export function contextDescription(context) {
+  return context.debugDescription;
+}
+
+export class DecodingError {
+  static typeMismatch(type, context) {
+    return standardCase(DecodingError, "typeMismatch", [type, context]);
+  }
+
+  static valueNotFound(type, context) {
+    return standardCase(DecodingError, "valueNotFound", [type, context]);
+  }
+
+  static keyNotFound(key, context) {
+    return standardCase(DecodingError, "keyNotFound", [key, context]);
+  }
+
+  static dataCorrupted(context) {
+    return standardCase(DecodingError, "dataCorrupted", [context]);
+  }
+
+  static Context(codingPath, debugDescription, underlyingError = null) {
+    return new Context(codingPath, debugDescription, underlyingError);
+  }
+
+  toString() {
+    const context = this.$values[this.$values.length - 1].$describe("DecodingError");
+    switch (this.$case) {
+      case "keyNotFound": return `keyNotFound(${describeKey(this.$values[0])}, ${context})`;
+      case "dataCorrupted": return `dataCorrupted(${context})`;
+      default: return `${this.$case}(${qualifiedName(this.$values[0])}, ${context})`;
+    }
+  }
+}
+
+export class EncodingError {
+  static invalidValue(value, context) {
+    return standardCase(EncodingError, "invalidValue", [value, context]);
+  }
+
+  static Context(codingPath, debugDescription, underlyingError = null) {
+    return new Context(codingPath, debugDescription, underlyingError);
+  }
+
+  toString() {
+    const [value, context] = this.$values;
+    return `invalidValue(${describe(value)}, ${context.$describe("EncodingError")})`;
+  }
+}
+
+// Standard library types are qualified by their module when an error is printed: `Swift.Int`.
+function qualifiedName(type) {
+  if (typeof type === "function") return type.name;
+  if (type !== null && typeof type === "object") {
+    const [kind, element] = Object.entries(type)[0];
+    const elements = Array.isArray(element) ? element : [element];
+    const name = kind === "dictionary" ? "Dictionary" : kind[0].toUpperCase() + kind.slice(1);
+    return `Swift.${name}<${elements.map(qualifiedName).join(", ")}>`;
+  }
+  if (type === "Any") return type;
+  return type === "Date" || type === "Data" ? `Foundation.${type}` : `Swift.${type}`;
+}
+
+// The options of a `JSONEncoder` or `JSONDecoder`, which take their strategies from Swift's enums.
+function strategy(type, name, values = []) {
+  return standardCase(type, name, values);
+}
+
+export class JSONEncoder {
+  outputFormatting = [];
+  keyEncodingStrategy = JSONEncoder.KeyEncodingStrategy.useDefaultKeys;
+  dateEncodingStrategy = JSONEncoder.DateEncodingStrategy.deferredToDate;
+
+  // An option set, assigned an option or an array of them.
+  static OutputFormatting = class OutputFormatting {
+    static prettyPrinted = "prettyPrinted";
+    static sortedKeys = "sortedKeys";
+    static withoutEscapingSlashes = "withoutEscapingSlashes";
+  };
+
+  static KeyEncodingStrategy = class KeyEncodingStrategy {
+    static useDefaultKeys = strategy(KeyEncodingStrategy, "useDefaultKeys");
+    static convertToSnakeCase = strategy(KeyEncodingStrategy, "convertToSnakeCase");
+
+    static custom(transform) {
+      return strategy(KeyEncodingStrategy, "custom", [transform]);
+    }
+  };
+
+  static DateEncodingStrategy = class DateEncodingStrategy {
+    static deferredToDate = strategy(DateEncodingStrategy, "deferredToDate");
+    static secondsSince1970 = strategy(DateEncodingStrategy, "secondsSince1970");
+    static millisecondsSince1970 = strategy(DateEncodingStrategy, "millisecondsSince1970");
+    static iso8601 = strategy(DateEncodingStrategy, "iso8601");
+
+    static custom(encode) {
+      return strategy(DateEncodingStrategy, "custom", [encode]);
+    }
+  };
+}
+
+export class JSONDecoder {
+  keyDecodingStrategy = JSONDecoder.KeyDecodingStrategy.useDefaultKeys;
+  dateDecodingStrategy = JSONDecoder.DateDecodingStrategy.deferredToDate;
+
+  static KeyDecodingStrategy = class KeyDecodingStrategy {
+    static useDefaultKeys = strategy(KeyDecodingStrategy, "useDefaultKeys");
+    static convertFromSnakeCase = strategy(KeyDecodingStrategy, "convertFromSnakeCase");
+
+    static custom(transform) {
+      return strategy(KeyDecodingStrategy, "custom", [transform]);
+    }
+  };
+
+  static DateDecodingStrategy = class DateDecodingStrategy {
+    static deferredToDate = strategy(DateDecodingStrategy, "deferredToDate");
+    static secondsSince1970 = strategy(DateDecodingStrategy, "secondsSince1970");
+    static millisecondsSince1970 = strategy(DateDecodingStrategy, "millisecondsSince1970");
+    static iso8601 = strategy(DateDecodingStrategy, "iso8601");
+
+    static custom(decode) {
+      return strategy(DateDecodingStrategy, "custom", [decode]);
+    }
+  };
+}
+
+export function makeJSONEncoder() {
+  return new JSONEncoder();
+}
+
+export function makeJSONDecoder() {
+  return new JSONDecoder();
+}
+
+export function outputFormatting(encoder) {
+  return encoder.outputFormatting;
+}
+
+export function keyEncodingStrategy(encoder) {
+  return encoder.keyEncodingStrategy;
+}
+
+export function dateEncodingStrategy(encoder) {
+  return encoder.dateEncodingStrategy;
+}
+
+export function keyDecodingStrategy(decoder) {
+  return decoder.keyDecodingStrategy;
+}
+
+export function dateDecodingStrategy(decoder) {
+  return decoder.dateDecodingStrategy;
+}
+
+// `myURLValue` is `my_url_value`; leading and trailing underscores are kept.
+function snakeCase(key) {
+  const [, leading, words, trailing] = /^(_*)(.*?)(_*)$/s.exec(key);
+  const separated = words.replace(/([A-Z]+)([A-Z][a-z])/g, "$1_$2").replace(/([a-z0-9])([A-Z])/g, "$1_$2");
+  return leading + separated.toLowerCase() + trailing;
+}
+
+// `my_url_value` is `myUrlValue`.
+function camelCase(key) {
+  const [, leading, words, trailing] = /^(_*)(.*?)(_*)$/s.exec(key);
+  if (!words.includes("_")) return key;
+  const [first, ...rest] = words.split("_").filter((word) => word !== "");
+  const capitalized = rest.map((word) => word[0].toUpperCase() + word.slice(1).toLowerCase());
+  return leading + [first, ...capitalized].join("") + trailing;
+}
+
+// Custom key strategies receive the coding path ending in the key, and return the key to use.
+function encodedKey(key, codingPath, options) {
+  const strategy = options.keyEncodingStrategy;
+  switch (strategy.$case) {
+    case "convertToSnakeCase": return snakeCase(stringValue(key));
+    case "custom": return stringValue(strategy.$values[0]([...codingPath, key]));
+    default: return stringValue(key);
+  }
+}
+
+function decodedKey(name, codingPath, options) {
+  const strategy = options.keyDecodingStrategy;
+  switch (strategy.$case) {
+    case "convertFromSnakeCase": return camelCase(name);
+    case "custom": return stringValue(strategy.$values[0]([...codingPath, new JSONKey(name)]));
+    default: return name;
+  }
+}
+
+// Encoding.
+class Encoder {
+  $value = undefined;
+
+  constructor(options, codingPath) {
+    this.$options = options;
+    this.codingPath = codingPath;
+  }
+}
+
+class KeyedEncodingContainer {
+  constructor(encoder, storage, codingPath) {
+    this.$encoder = encoder;
+    this.$storage = storage;
+    this.codingPath = codingPath;
+  }
+
+  $set(key, value) {
+    this.$storage.set(encodedKey(key, this.codingPath, this.$encoder.$options), value);
+  }
+
+  $encode(value, key) {
+    this.$set(key, encodeTree(value, this.$encoder.$options, [...this.codingPath, key]));
+  }
+
+  $nestedContainer(key, storage) {
+    this.$set(key, storage);
+    return [this.$encoder, storage, [...this.codingPath, key]];
+  }
+}
+
+class UnkeyedEncodingContainer {
+  constructor(encoder, storage, codingPath) {
+    this.$encoder = encoder;
+    this.$storage = storage;
+    this.codingPath = codingPath;
+  }
+
+  get $count() {
+    return toInt(this.$storage.length);
+  }
+
+  $encode(value) {
+    const key = JSONKey.index(this.$storage.length);
+    this.$storage.push(encodeTree(value, this.$encoder.$options, [...this.codingPath, key]));
+  }
+
+  $nestedContainer(storage) {
+    const key = JSONKey.index(this.$storage.length);
+    this.$storage.push(storage);
+    return [this.$encoder, storage, [...this.codingPath, key]];
+  }
+}
+
+class SingleValueEncodingContainer {
+  constructor(encoder) {
+    this.$encoder = encoder;
+    this.codingPath = encoder.codingPath;
+  }
+
+  $encode(value) {
+    this.$encoder.$value = encodeTree(value, this.$encoder.$options, this.codingPath);
+  }
+}
+
+// An encoder whose value is stored in a container once it has been encoded, for `superEncoder()`.
+class SuperEncoder extends Encoder {
+  constructor(options, codingPath, store) {
+    super(options, codingPath);
+    this.$store = store;
+  }
+}
+
+function encoderStorage(encoder, isKeyed) {
+  const matches = isKeyed ? encoder.$value instanceof Map : Array.isArray(encoder.$value);
+  if (!matches) encoder.$value = isKeyed ? new Map() : [];
+  encoder.$store?.(encoder.$value);
+  return encoder.$value;
+}
+
+export function codingPath(coder) {
+  return coder.codingPath;
+}
+
+export function keyedEncodingContainer(encoder, keys) {
+  return new KeyedEncodingContainer(encoder, encoderStorage(encoder, true), encoder.codingPath);
+}
+
+export function unkeyedEncodingContainer(encoder) {
+  return new UnkeyedEncodingContainer(encoder, encoderStorage(encoder, false), encoder.codingPath);
+}
+
+export function singleValueEncodingContainer(encoder) {
+  return new SingleValueEncodingContainer(encoder);
+}
+
+export function encodeForKey(container, value, key) {
+  container.$encode(value, key);
+}
+
+export function encodeIfPresentForKey(container, value, key) {
+  if (value !== null) container.$encode(value, key);
+}
+
+export function encodeNilForKey(container, key) {
+  container.$set(key, null);
+}
+
+export function encodeValue(container, value) {
+  container.$encode(value);
+}
+
+export function encodeNil(container) {
+  if (container instanceof UnkeyedEncodingContainer) container.$storage.push(null);
+  else container.$encoder.$value = null;
+}
+
+export function containerCount(container) {
+  return container.$count;
+}
+
+export function nestedContainer(container, keys) {
+  if (container instanceof UnkeyedDecodingContainer) return keyedDecodingContainer(container.$nestedDecoder(), keys);
+  return new KeyedEncodingContainer(...container.$nestedContainer(new Map()));
+}
+
+export function nestedUnkeyedContainer(container) {
+  if (container instanceof UnkeyedDecodingContainer) return unkeyedDecodingContainer(container.$nestedDecoder());
+  return new UnkeyedEncodingContainer(...container.$nestedContainer([]));
+}
+
+// `superEncoder()` and `superDecoder()` code the superclass's properties under the key `super`.
+export function superCoder(container) {
+  if (container instanceof KeyedDecodingContainer) {
+    const value = container.$entries.get("super") ?? null;
+    return new Decoder(value, container.$decoder.$options, [...container.codingPath, JSONKey.superKey]);
+  }
+  const path = [...container.codingPath, JSONKey.superKey];
+  return new SuperEncoder(container.$encoder.$options, path, (value) => container.$storage.set("super", value));
+}
+
+// A value as JSON: `null`, a boolean, number, BigInt or string, an array, or a `Map` of its keys.
+function encodeTree(value, options, codingPath) {
+  value = open(value);
+  if (value === null || value === undefined) return null;
+  switch (typeof value) {
+    case "boolean": case "string": case "bigint": return value;
+    case "number": {
+      if (Number.isFinite(value)) return value;
+      const name = Number.isNaN(value) ? "nan" : value > 0 ? "inf" : "-inf";
+      throw EncodingError.invalidValue(value, new Context(codingPath, `Unable to encode Double.${name} directly in JSON.`));
+    }
+  }
+  if (value instanceof Data) return btoa(String.fromCharCode(...value));
+  if (value instanceof Instant) return encodeDate(value, options, codingPath);
+  if (Array.isArray(value) || value instanceof Set) {
+    return Array.from(value, (element, index) => encodeTree(element, options, [...codingPath, JSONKey.index(index)]));
+  }
+  if (value instanceof Map) return encodeDictionary(value, options, codingPath);
+  const witness = witnesses(value, "Encodable")?.["encode(to:)"];
+  if (witness === undefined) return unsupported(`encoding ${describe(value)}, which isn't Encodable`);
+  const encoder = new Encoder(options, codingPath);
+  value[witness](encoder);
+  // A value that encodes nothing is an empty object.
+  return encoder.$value === undefined ? new Map() : encoder.$value;
+}
+
+// Dictionaries keyed by strings or integers are objects; others are arrays of their keys and
+// values in turn.
+function encodeDictionary(map, options, codingPath) {
+  const entries = [...map];
+  if (entries.every(([key]) => typeof key === "string" || typeof key === "number" || typeof key === "bigint")) {
+    return new Map(entries.map(([key, value]) => [String(key), encodeTree(value, options, [...codingPath, new JSONKey(String(key))])]));
+  }
+  return entries.flatMap(([key, value], index) => {
+    const path = (offset) => [...codingPath, JSONKey.index(index * 2 + offset)];
+    return [encodeTree(key, options, path(0)), encodeTree(value, options, path(1))];
+  });
+}
+
+function encodeDate(date, options, codingPath) {
+  const strategy = options.dateEncodingStrategy;
+  switch (strategy.$case) {
+    case "secondsSince1970": return date.$seconds;
+    case "millisecondsSince1970": return date.$seconds * 1000;
+    case "iso8601": return new Date(Math.floor(date.$seconds) * 1000).toISOString().replace(/\.\d+Z$/, "Z");
+    case "custom": {
+      const encoder = new Encoder(options, codingPath);
+      strategy.$values[0](date, encoder);
+      return encoder.$value === undefined ? new Map() : encoder.$value;
+    }
+    default: return date.$seconds - referenceDate;
+  }
+}
+
+export function encodeJSON(encoder, value) {
+  const formatting = [].concat(encoder.outputFormatting);
+  const text = serialize(encodeTree(value, encoder, []), new Set(formatting), "");
+  return Data.from(new TextEncoder().encode(text));
+}
+
+// Pretty printing indents by two spaces and puts a space on each side of a colon.
+function serialize(value, formatting, indent) {
+  if (value === null) return "null";
+  switch (typeof value) {
+    case "boolean": case "number": case "bigint": return String(value);
+    case "string": {
+      const string = JSON.stringify(value);
+      return formatting.has("withoutEscapingSlashes") ? string : string.replaceAll("/", "\\/");
+    }
+  }
+  const pretty = formatting.has("prettyPrinted");
+  const inner = pretty ? indent + "  " : "";
+  let elements;
+  if (value instanceof Map) {
+    const keys = [...value.keys()];
+    if (formatting.has("sortedKeys")) keys.sort(compareStrings);
+    const colon = pretty ? " : " : ":";
+    elements = keys.map((key) => serialize(key, formatting, inner) + colon + serialize(value.get(key), formatting, inner));
+  } else {
+    elements = value.map((element) => serialize(element, formatting, inner));
+  }
+  const [open, close] = value instanceof Map ? ["{", "}"] : ["[", "]"];
+  if (elements.length === 0) return open + close;
+  if (!pretty) return open + elements.join(",") + close;
+  return `${open}\n${elements.map((element) => inner + element).join(",\n")}\n${indent}${close}`;
+}
+
+// Decoding.
+class Decoder {
+  constructor(value, options, codingPath) {
+    this.$value = value;
+    this.$options = options;
+    this.codingPath = codingPath;
+  }
+}
+
+class KeyedDecodingContainer {
+  constructor(decoder, keys) {
+    this.$decoder = decoder;
+    this.$keys = keys;
+    this.codingPath = decoder.codingPath;
+    const options = decoder.$options;
+    this.$entries = new Map(Object.entries(decoder.$value).map(([name, value]) => [decodedKey(name, this.codingPath, options), value]));
+  }
+
+  // The value for a key, which must be present.
+  $value(key) {
+    const name = stringValue(key);
+    if (!this.$entries.has(name)) {
+      const description = `No value associated with key ${describeKey(key)} (${JSON.stringify(name)}).`;
+      throw DecodingError.keyNotFound(key, new Context(this.codingPath, description));
+    }
+    return this.$entries.get(name);
+  }
+
+  $nestedDecoder(key) {
+    return new Decoder(this.$value(key), this.$decoder.$options, [...this.codingPath, key]);
+  }
+}
+
+class UnkeyedDecodingContainer {
+  currentIndex = 0;
+
+  constructor(decoder) {
+    this.$decoder = decoder;
+    this.$elements = decoder.$value;
+    this.codingPath = decoder.codingPath;
+  }
+
+  get $count() {
+    return toInt(this.$elements.length);
+  }
+
+  get $isAtEnd() {
+    return this.currentIndex >= this.$elements.length;
+  }
+
+  // The decoder of the current element, which moves on to the next one.
+  $nestedDecoder(type) {
+    const path = [...this.codingPath, JSONKey.index(this.currentIndex)];
+    if (this.$isAtEnd) {
+      const description = "Unkeyed container is at end.";
+      throw DecodingError.valueNotFound(type ?? UnkeyedDecodingContainer, new Context(path, description));
+    }
+    return new Decoder(this.$elements[this.currentIndex++], this.$decoder.$options, path);
+  }
+}
+
+// How a JSON value is named when it isn't the type expected.
+function found(value) {
+  if (value === null) return "null";
+  if (Array.isArray(value)) return "an array";
+  switch (typeof value) {
+    case "string": return "a string";
+    case "number": case "bigint": return "number";
+    case "boolean": return "bool";
+    default: return "a dictionary";
+  }
+}
+
+function mismatch(type, value, codingPath) {
+  const description = `Expected to decode ${typeName(type)} but found ${found(value)} instead.`;
+  return DecodingError.typeMismatch(type, new Context(codingPath, description));
+}
+
+function isObject(value) {
+  return value !== null && typeof value === "object" && !Array.isArray(value);
+}
+
+const anyDictionary = { dictionary: ["String", "Any"] };
+
+export function keyedDecodingContainer(decoder, keys) {
+  if (decoder.$value === null) {
+    const description = "Cannot get keyed decoding container -- found null value instead";
+    throw DecodingError.valueNotFound(KeyedDecodingContainer, new Context(decoder.codingPath, description));
+  }
+  if (!isObject(decoder.$value)) throw mismatch(anyDictionary, decoder.$value, decoder.codingPath);
+  return new KeyedDecodingContainer(decoder, keys);
+}
+
+export function unkeyedDecodingContainer(decoder) {
+  if (decoder.$value === null) {
+    const description = "Cannot get unkeyed decoding container -- found null value instead";
+    throw DecodingError.valueNotFound(UnkeyedDecodingContainer, new Context(decoder.codingPath, description));
+  }
+  if (!Array.isArray(decoder.$value)) throw mismatch({ array: "Any" }, decoder.$value, decoder.codingPath);
+  return new UnkeyedDecodingContainer(decoder);
+}
+
+export function singleValueDecodingContainer(decoder) {
+  return decoder;
+}
+
+export function allKeys(container) {
+  return keyCases(container.$keys).filter((key) => container.$entries.has(stringValue(key)));
+}
+
+export function containsKey(container, key) {
+  return container.$entries.has(stringValue(key));
+}
+
+export function decodeForKey(container, type, key) {
+  const decoder = container.$nestedDecoder(key);
+  return decodeTree(decoder.$value, type, decoder.$options, decoder.codingPath);
+}
+
+export function decodeIfPresentForKey(container, type, key) {
+  if (container.$entries.get(stringValue(key)) == null) return null;
+  return decodeForKey(container, type, key);
+}
+
+export function decodeNilForKey(container, key) {
+  return container.$value(key) === null;
+}
+
+export function nestedContainerForKey(container, keys, key) {
+  if (container instanceof KeyedEncodingContainer) return new KeyedEncodingContainer(...container.$nestedContainer(key, new Map()));
+  return keyedDecodingContainer(container.$nestedDecoder(key), keys);
+}
+
+export function nestedUnkeyedContainerForKey(container, key) {
+  if (container instanceof KeyedEncodingContainer) return new UnkeyedEncodingContainer(...container.$nestedContainer(key, []));
+  return unkeyedDecodingContainer(container.$nestedDecoder(key));
+}
+
+export function isAtEnd(container) {
+  return container.$isAtEnd;
+}
+
+export function currentIndex(container) {
+  return toInt(container.currentIndex);
+}
+
+// The single value container of a decoder is the decoder itself.
+export function decodeValue(container, type) {
+  const decoder = container instanceof UnkeyedDecodingContainer ? container.$nestedDecoder(type) : container;
+  return decodeTree(decoder.$value, type, decoder.$options, decoder.codingPath);
+}
+
+export function decodeIfPresent(container, type) {
+  if (!container.$isAtEnd && container.$elements[container.currentIndex] === null) {
+    container.currentIndex += 1;
+    return null;
+  }
+  return container.$isAtEnd ? null : decodeValue(container, type);
+}
+
+export function decodeNil(container) {
+  if (!(container instanceof UnkeyedDecodingContainer)) return container.$value === null;
+  if (container.$isAtEnd || container.$elements[container.currentIndex] !== null) return false;
+  container.currentIndex += 1;
+  return true;
+}
+
+function decodeTree(value, type, options, codingPath) {
+  if (type !== null && typeof type === "object" && "optional" in type) {
+    return value === null ? null : decodeTree(value, type.optional, options, codingPath);
+  }
+  if (value === null) {
+    const description = `Expected ${typeName(type)} value but found null instead.`;
+    throw DecodingError.valueNotFound(type, new Context(codingPath, description));
+  }
+  const element = (value, type, index) => decodeTree(value, type, options, [...codingPath, JSONKey.index(index)]);
+  if (type !== null && typeof type === "object") {
+    if ("dictionary" in type) return decodeDictionary(value, type, options, codingPath);
+    if (!Array.isArray(value)) throw mismatch(type, value, codingPath);
+    if ("set" in type) return new HashSet(value.map((item, index) => element(item, type.set, index)));
+    return value.map((item, index) => element(item, type.array, index));
+  }
+  if (integerTypes.includes(type)) {
+    if (typeof value !== "number" && typeof value !== "bigint") throw mismatch(type, value, codingPath);
+    const integer = construct(type, "$init$exactly", value);
+    if (integer === null) {
+      throw DecodingError.dataCorrupted(new Context(codingPath, `Parsed JSON number <${value}> does not fit in ${type}.`));
+    }
+    return integer;
+  }
+  switch (type) {
+    case "Double": case "Float": {
+      if (typeof value !== "number" && typeof value !== "bigint") throw mismatch(type, value, codingPath);
+      return type === "Float" ? Math.fround(Number(value)) : Number(value);
+    }
+    case "Bool": case "String": {
+      if (typeof value !== (type === "Bool" ? "boolean" : "string")) throw mismatch(type, value, codingPath);
+      return value;
+    }
+    case "Date": return decodeDate(value, options, codingPath);
+    case "Data": {
+      if (typeof value !== "string") throw mismatch(type, value, codingPath);
+      try {
+        return Data.from(atob(value), (character) => character.charCodeAt(0));
+      } catch {
+        throw DecodingError.dataCorrupted(new Context(codingPath, "Encountered Data is not valid Base64."));
+      }
+    }
+  }
+  const witness = typeof type === "function" ? type.$conformances?.get("Decodable")?.["init(from:)"] : undefined;
+  if (witness === undefined) return unsupported(`decoding a value of type ${typeName(type)}, which isn't Decodable`);
+  return construct(type, witness, new Decoder(value, options, codingPath));
+}
+
+// The counterpart of `encodeDictionary`.
+function decodeDictionary(value, type, options, codingPath) {
+  const [keyType, valueType] = type.dictionary;
+  const decode = (value, type, key) => decodeTree(value, type, options, [...codingPath, key]);
+  if (keyType === "String" || integerTypes.includes(keyType)) {
+    if (!isObject(value)) throw mismatch(type, value, codingPath);
+    return new HashMap(Object.entries(value).map(([name, element]) => {
+      const key = new JSONKey(name);
+      if (keyType === "String") return [name, decode(element, valueType, key)];
+      const integer = /^-?\d+$/.test(name) ? construct(keyType, "$init$exactly", BigInt(name)) : null;
+      if (integer === null) {
+        throw DecodingError.typeMismatch(keyType, new Context([...codingPath, key], `Expected ${keyType} key but found String key instead.`));
+      }
+      return [integer, decode(element, valueType, key)];
+    }));
+  }
+  if (!Array.isArray(value)) throw mismatch(type, value, codingPath);
+  if (value.length % 2 !== 0) {
+    throw DecodingError.dataCorrupted(new Context(codingPath, "Expected collection of key-value pairs; encountered odd-length array instead."));
+  }
+  const entries = [];
+  for (let i = 0; i < value.length; i += 2) {
+    entries.push([decode(value[i], keyType, JSONKey.index(i)), decode(value[i + 1], valueType, JSONKey.index(i + 1))]);
+  }
+  return new HashMap(entries);
+}
+
+function decodeDate(value, options, codingPath) {
+  const strategy = options.dateDecodingStrategy;
+  const seconds = (type) => decodeTree(value, type, options, codingPath);
+  switch (strategy.$case) {
+    case "secondsSince1970": return new Instant(seconds("Double"));
+    case "millisecondsSince1970": return new Instant(seconds("Double") / 1000);
+    case "iso8601": {
+      const string = decodeTree(value, "String", options, codingPath);
+      const time = /^\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d(Z|[+-]\d\d:?\d\d)$/.test(string) ? Date.parse(string) : NaN;
+      if (Number.isNaN(time)) throw DecodingError.dataCorrupted(new Context(codingPath, "Expected date string to be ISO8601-formatted."));
+      return new Instant(time / 1000);
+    }
+    case "custom": return strategy.$values[0](new Decoder(value, options, codingPath));
+    default: return new Instant(seconds("Double") + referenceDate);
+  }
+}
+
+const jsonToken = /"(?:[^"\\]|\\.)*"|-?\d+(?:\.\d+)?(?:[eE][+-]?\d+)?/g;
+const jsonString = /"(?:[^"\\]|\\.)*"/y;
+const jsonNumber = /-?\d+(?:\.\d+)?(?:[eE][+-]?\d+)?/y;
+const jsonSpace = /[ \t\n\r]*/y;
+
+function isUnsafeInteger(token) {
+  return /^-?\d+$/.test(token) && !Number.isSafeInteger(Number(token));
+}
+
+// `JSON.parse` rounds integers beyond the precision of a number, so valid JSON text that has any
+// is parsed again, keeping their digits as BigInts.
+function parseKeepingIntegers(text) {
+  let position = 0;
+  const match = (pattern) => {
+    pattern.lastIndex = position;
+    const [token] = pattern.exec(text);
+    position = pattern.lastIndex;
+    return token;
+  };
+  const value = () => {
+    match(jsonSpace);
+    const character = text[position];
+    let result;
+    if (character === "{" || character === "[") {
+      const isObject = character === "{";
+      result = isObject ? {} : [];
+      position += 1;
+      match(jsonSpace);
+      while (text[position] !== (isObject ? "}" : "]")) {
+        if (isObject) {
+          const key = JSON.parse(match(jsonString));
+          match(jsonSpace);
+          position += 1;
+          // A key like `__proto__` is a property, as `JSON.parse` makes it.
+          Object.defineProperty(result, key, { value: value(), enumerable: true, writable: true, configurable: true });
+        } else {
+          result.push(value());
+        }
+        if (text[position] === ",") position += 1;
+        match(jsonSpace);
+      }
+      position += 1;
+    } else if (character === "\"") {
+      result = JSON.parse(match(jsonString));
+    } else if (character === "t" || character === "f" || character === "n") {
+      result = { t: true, f: false, n: null }[character];
+      position += { t: 4, f: 5, n: 4 }[character];
+    } else {
+      const token = match(jsonNumber);
+      result = isUnsafeInteger(token) ? BigInt(token) : Number(token);
+    }
+    match(jsonSpace);
+    return result;
+  };
+  return value();
+}
+
+export function decodeJSON(decoder, type, data) {
+  let text;
+  let value;
+  try {
+    text = new TextDecoder("utf-8", { fatal: true }).decode(Uint8Array.from(data));
+    value = JSON.parse(text);
+  } catch (error) {
+    throw DecodingError.dataCorrupted(new Context([], "The given data was not valid JSON.", error));
+  }
+  // Outside strings, the digits of valid JSON are numbers.
+  if ([...text.matchAll(jsonToken)].some(([token]) => isUnsafeInteger(token))) value = parseKeepingIntegers(text);
+  return decodeTree(value, type, decoder, []);
+}
+
+// The derived coding of enums. Raw values are coded as single values.
+export function encodeRawValue(encoder, rawValue) {
+  encoder.$value = encodeTree(rawValue, encoder.$options, encoder.codingPath);
+}
+
+export function decodeRawValue(decoder, type, rawType) {
+  const rawValue = decodeTree(decoder.$value, rawType, decoder.$options, decoder.codingPath);
+  const value = type.$init$rawValue(rawValue);
+  if (value === null) {
+    const description = `Cannot initialize ${type.name} from invalid ${rawType} value ${describe(rawValue)}`;
+    throw DecodingError.dataCorrupted(new Context(decoder.codingPath, description));
+  }
+  return value;
+}
+
+// Other enums are keyed by their case, holding its associated values keyed as `cases` lists them
+// with their types: `{ upc: [["_0", "Int"], ["_1", "Int"]], qrCode: [["_0", "String"]] }`.
+export function encodeCase(encoder, value, cases) {
+  const container = keyedEncodingContainer(encoder);
+  const key = new JSONKey(value.$case);
+  const [, storage, path] = container.$nestedContainer(key, new Map());
+  const values = new KeyedEncodingContainer(encoder, storage, path);
+  cases[value.$case].forEach(([name], index) => {
+    if (value.$values[index] !== null) values.$encode(value.$values[index], new JSONKey(name));
+  });
+}
+
+export function decodeCase(decoder, type, cases) {
+  const container = keyedDecodingContainer(decoder);
+  const names = [...container.$entries.keys()].filter((name) => Object.hasOwn(cases, name));
+  if (names.length !== 1) {
+    const description = "Invalid number of keys found, expected one.";
+    throw DecodingError.typeMismatch(type, new Context(decoder.codingPath, description));
+  }
+  const [name] = names;
+  const values = keyedDecodingContainer(container.$nestedDecoder(new JSONKey(name)));
+  const associated = cases[name].map(([key, valueType]) => {
+    const decode = typeof valueType === "object" && "optional" in valueType ? decodeIfPresentForKey : decodeForKey;
+    return decode(values, valueType, new JSONKey(key));
+  });
+  return associated.length === 0 ? type[name] : type[name](...associated);
+}
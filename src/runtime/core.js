@@ -0,0 +1,1041 @@
+// Runtime support imported by every module emitted by swift-oxide.
+
+export * from "./swift-stdlib.js";
+export * from "./swift-codable.js";
+export * from "./swift-reflection.js";
+
+// `console.log` always ends a line, so text printed with another terminator waits for the rest of
+// its line, or for the program to exit.
+let pendingLine = "";
+
+export function print(...items) {
+  printItems(items, " ", "\n");
+}
+
+export function printItems(items, separator, terminator) {
+  const lines = (pendingLine + items.map(describe).join(separator) + terminator).split("\n");
+  pendingLine = lines.pop();
+  for (const line of lines) console.log(line);
+}
+
+globalThis.process?.on?.("exit", () => {
+  if (pendingLine !== "") process.stdout.write(pendingLine);
+});
+
+// Swift's `String(describing:)`, which `print` and string interpolation use. The static type of
+// the value, described as `$rt.cast` takes it, tells what the JS value can't: that `1` is a
+// `Double`, printed `1.0`, or that a value is optional. Values of types conforming to
+// `CustomStringConvertible` or `CustomDebugStringConvertible` describe themselves.
+export function describe(value, type) {
+  if (isOptionalType(type)) return value === null ? "nil" : `Optional(${debugDescribe(value, type.optional)})`;
+  if (value instanceof Existential) type = value.$type ?? type;
+  value = open(value);
+  if (value === null || value === undefined) return "nil";
+  if (typeof value === "string") return value;
+  return customDescription(value, false) ?? format(value, type, false);
+}
+
+// Swift's `String(reflecting:)`, which `debugPrint` uses, and which describes the values inside
+// collections, optionals, structs and enum cases: strings are quoted, and the names of types
+// qualified by their module.
+export function debugDescribe(value, type) {
+  if (isOptionalType(type)) return value === null ? "nil" : `Optional(${debugDescribe(value, type.optional)})`;
+  if (value instanceof Existential) type = value.$type ?? type;
+  value = open(value);
+  if (value === null || value === undefined) return "nil";
+  if (typeof value === "string") return quote(value);
+  return customDescription(value, true) ?? format(value, type, true);
+}
+
+// The `description` or `debugDescription` a type gives its values, preferring the latter for
+// `String(reflecting:)`.
+export function customDescription(value, isDebug) {
+  const description = witnesses(value, "CustomStringConvertible")?.description;
+  const debugDescription = witnesses(value, "CustomDebugStringConvertible")?.debugDescription;
+  const witness = isDebug ? debugDescription ?? description : description ?? debugDescription;
+  return witness === undefined ? undefined : value[witness];
+}
+
+// Programs are compiled as a single module, which Swift names `main` when it runs a script.
+const moduleName = "main";
+
+// Types declared by the program record what reflecting on their values shows: whether they are
+// structs, classes or enums, and the labels and types of their stored properties, or of the
+// associated values of each case.
+export function reflect(type, kind, members) {
+  Object.defineProperty(type, "$reflection", { value: { kind, members } });
+}
+
+// What a value is made of, as `Mirror` shows it and descriptions print it: its display style and
+// its children, as labels, values and types. The children of a class instance are the stored
+// properties `subject`, its class or a superclass, declares; `superclass` is the next class up.
+// Types a tuple's elements or an enum case's associated values are labeled with are `labels`.
+export function reflectValue(value, type, subject) {
+  if (isOptionalType(type) || value === null || value === undefined) {
+    const children = value === null || value === undefined ? [] : [["some", value, type?.optional]];
+    return { style: "optional", children, superclass: null };
+  }
+  value = open(value);
+  const reflected = (style, children, superclass = null) => ({ style, children, superclass });
+  if (value === null || typeof value !== "object") return reflected(null, []);
+  if (Array.isArray(type?.tuple)) {
+    return reflected("tuple", value.map((element, i) => [type.labels?.[i] ?? `.${i}`, element, type.tuple[i]]));
+  }
+  if (value instanceof Map) {
+    const [keyType, valueType] = type?.dictionary ?? [];
+    const entry = { tuple: [keyType, valueType], labels: ["key", "value"] };
+    return reflected("dictionary", [...value].map((pair) => [null, pair, entry]));
+  }
+  if (value instanceof Set) return reflected("set", [...value].map((element) => [null, element, type?.set]));
+  if (Array.isArray(value) && !isDescribedByRuntime(value)) {
+    return reflected("collection", value.map((element) => [null, element, type?.array]));
+  }
+  subject ??= value.constructor;
+  const reflection = Object.hasOwn(subject, "$reflection") ? subject.$reflection : undefined;
+  if (reflection === undefined) return reflected(null, []);
+  if (reflection.kind === "enum") {
+    const values = reflection.members()[value.$case] ?? [];
+    if (values.length === 0) return reflected("enum", []);
+    // A single associated value is the child itself; several are a tuple.
+    if (values.length === 1 && values[0][0] === null) return reflected("enum", [[value.$case, value.$values[0], values[0][1]]]);
+    const payload = { tuple: values.map(([, type]) => type), labels: values.map(([label]) => label) };
+    return reflected("enum", [[value.$case, value.$values, payload]]);
+  }
+  const children = reflection.members().map(([label, type]) => [label, value[label], type]);
+  const superclass = Object.getPrototypeOf(subject);
+  return reflected(reflection.kind, children, Object.hasOwn(superclass, "$reflection") ? superclass : null);
+}
+
+// Optional types are described as `{ optional: "Int" }`. Types declared with a static `optional`
+// member, like `Mirror.DisplayStyle`, aren't.
+export function isOptionalType(type) {
+  return type !== null && typeof type === "object" && "optional" in type;
+}
+
+// The runtime's own types, like `Data`, describe themselves.
+function isDescribedByRuntime(value) {
+  return value.toString !== Object.prototype.toString && value.toString !== Array.prototype.toString;
+}
+
+function format(value, type, isNested) {
+  switch (typeof value) {
+    case "number":
+      if (type === "Float") return formatFloatingPoint(value, shortestFloat(value), 2 ** 24);
+      // Without a type, only a fraction tells a `Double` apart.
+      if (type === "Double" || !Number.isInteger(value)) return formatFloatingPoint(value, value.toExponential(), 2 ** 53);
+      return String(value);
+    case "function": return value.$reflection === undefined ? "(Function)" : typeName(value, isNested);
+    case "object": break;
+    default: return String(value);
+  }
+  if (isDescribedByRuntime(value)) return value.toString();
+  // Metatypes, described as `$rt.cast` takes them.
+  if (Object.getPrototypeOf(value) === Object.prototype) return typeName(value, isNested);
+  const { style, children } = reflectValue(value, type);
+  const elements = (describeElement) => children.map(([label, child, type]) => describeElement(label, debugDescribe(child, type))).join(", ");
+  switch (style) {
+    case "tuple": return `(${elements((label, element) => (label.startsWith(".") ? element : `${label}: ${element}`))})`;
+    case "dictionary": {
+      if (children.length === 0) return "[:]";
+      const [keyType, valueType] = type?.dictionary ?? [];
+      return `[${[...value].map(([key, element]) => `${debugDescribe(key, keyType)}: ${debugDescribe(element, valueType)}`).join(", ")}]`;
+    }
+    case "set": case "collection": return `[${elements((_, element) => element)}]`;
+    // Classes print their qualified name, whatever they store.
+    case "class": return typeName(value.constructor, true);
+    case "struct": return `${typeName(value.constructor, isNested)}(${elements((label, element) => `${label}: ${element}`)})`;
+    case "enum": {
+      const caseName = isNested ? `${typeName(value.constructor, true)}.${value.$case}` : value.$case;
+      if (children.length === 0) return caseName;
+      const [[, payload, payloadType]] = children;
+      const description = debugDescribe(payload, payloadType);
+      return payloadType?.tuple === undefined ? `${caseName}(${description})` : `${caseName}${description}`;
+    }
+    default: return String(value);
+  }
+}
+
+// The name of a type as Swift prints it: `Point`, `Array<Int>` or `(Int, String)`, or qualified by
+// its module, `main.Point`, for `String(reflecting:)`.
+export function typeName(type, isQualified = false) {
+  if (typeof type === "function") return isQualified ? type.$reflection?.qualifiedName ?? `${moduleName}.${type.name}` : type.name;
+  if (type === undefined) return "Any";
+  if (type !== null && typeof type === "object") {
+    const name = (element) => typeName(element, isQualified);
+    if ("optional" in type) return `Optional<${name(type.optional)}>`;
+    if ("array" in type) return `Array<${name(type.array)}>`;
+    if ("set" in type) return `Set<${name(type.set)}>`;
+    if ("dictionary" in type) return `Dictionary<${name(type.dictionary[0])}, ${name(type.dictionary[1])}>`;
+    if ("tuple" in type) return `(${type.tuple.map((element, i) => (type.labels?.[i] ? `${type.labels[i]}: ` : "") + name(element)).join(", ")})`;
+  }
+  return String(type);
+}
+
+// The case of a standard library enum, represented as those of the program are.
+export function standardCase(type, name, values) {
+  const value = Object.create(type.prototype);
+  value.$case = name;
+  value.$values = values;
+  return Object.freeze(value);
+}
+
+// Swift's `debugDescription` of a string, quoted with its special characters escaped.
+function quote(string) {
+  const escapes = { "\0": "\\0", "\t": "\\t", "\n": "\\n", "\r": "\\r", "\"": "\\\"", "'": "\\'", "\\": "\\\\" };
+  const escaped = string.replace(/[\0-\x1f\x7f"'\\]/g, (character) => escapes[character] ?? `\\u{${character.charCodeAt(0).toString(16)}}`);
+  return `"${escaped}"`;
+}
+
+// The fewest significant digits that read back as the same `Float`, in the form of
+// `toExponential`, which gives them for a `Double`.
+function shortestFloat(value) {
+  for (let digits = 1; digits < 9; digits++) {
+    const exponential = value.toExponential(digits - 1);
+    if (Math.fround(Number(exponential)) === Math.fround(value)) return exponential;
+  }
+  return value.toExponential(8);
+}
+
+// Swift prints floating-point values with the fewest digits that read back as the same value: in
+// exponential notation when they are small, or too large for every integer to be exact, and with
+// at least one fractional digit otherwise.
+function formatFloatingPoint(value, exponential, limit) {
+  if (Number.isNaN(value)) return "nan";
+  if (!Number.isFinite(value)) return value < 0 ? "-inf" : "inf";
+  if (value === 0) return Object.is(value, -0) ? "-0.0" : "0.0";
+  const [mantissa, exponentText] = exponential.split("e");
+  const exponent = Number(exponentText);
+  const sign = value < 0 ? "-" : "";
+  const digits = mantissa.replace(/[-.]/g, "");
+  if (exponent < -4 || Math.abs(value) > limit) {
+    const fraction = digits.length > 1 ? `.${digits.slice(1)}` : "";
+    return `${sign}${digits[0]}${fraction}e${exponent < 0 ? "-" : "+"}${String(Math.abs(exponent)).padStart(2, "0")}`;
+  }
+  if (exponent < 0) return `${sign}0.${"0".repeat(-exponent - 1)}${digits}`;
+  const whole = digits.slice(0, exponent + 1).padEnd(exponent + 1, "0");
+  return `${sign}${whole}.${digits.slice(exponent + 1) || "0"}`;
+}
+
+// Copies a value-typed value (struct, array, dictionary, tuple) before it is stored elsewhere.
+// Class instances, enum cases and primitives are returned as is.
+export function copy(value) {
+  if (Array.isArray(value)) return value.map(copy);
+  if (value instanceof Map) return new HashMap([...value].map(([key, element]) => [key, copy(element)]));
+  if (value instanceof Set) return new HashSet([...value].map(copy));
+  if (value !== null && typeof value === "object" && typeof value.$copy === "function") return value.$copy();
+  return value;
+}
+
+export function copyStruct(value) {
+  const result = Object.create(Object.getPrototypeOf(value));
+  for (const key of Object.keys(value)) result[key] = copy(value[key]);
+  return result;
+}
+
+// A reference to a storage location, used for `inout` arguments and `mutating` receivers.
+export function ref(get, set) {
+  return {
+    get value() { return get(); },
+    set value(newValue) { set(newValue); },
+  };
+}
+
+// Calls a `mutating` method; the method writes its changes back through `self`.
+export function mutating(self, method, ...args) {
+  const box = self.value;
+  if (box instanceof Existential) return mutating(ref(() => box.$value, (value) => box.$value = value), method, ...args);
+  return self.value[method](self, ...args);
+}
+
+// Defines a stored type property whose initial value is computed on first access, like Swift's `static let`.
+export function lazyStatic(type, name, initialize, isConstant) {
+  const store = (value) => Object.defineProperty(type, name, { value, writable: !isConstant, configurable: false });
+  Object.defineProperty(type, name, {
+    configurable: true,
+    get() { return store(initialize.call(type))[name]; },
+    set(newValue) {
+      if (isConstant) throw new TypeError(`cannot assign to '${name}': it is a 'let' constant`);
+      store(newValue);
+    },
+  });
+}
+
+// Protocols are values so that casts can name them. Their extensions add members to the protocol's
+// `prototype`, which conforming types that lack them receive as defaults. Conforming types record a
+// witness table per protocol, mapping requirement signatures to the members satisfying them;
+// subclasses inherit the tables of their superclass through the constructor's prototype chain.
+// A conditional conformance also records a predicate deciding whether an instance meets its
+// `where` clause.
+class Protocol {
+  constructor(name) {
+    this.$name = name;
+    this.prototype = {};
+  }
+}
+
+export function protocol(name) {
+  return new Protocol(name);
+}
+
+// The standard library protocols whose requirements the runtime calls, and `Codable` and
+// `CodingKey`, which types list as well.
+export const Equatable = protocol("Equatable");
+export const Hashable = protocol("Hashable");
+export const Comparable = protocol("Comparable");
+export const Encodable = protocol("Encodable");
+export const Decodable = protocol("Decodable");
+export const Codable = protocol("Codable");
+export const CodingKey = protocol("CodingKey");
+export const CustomStringConvertible = protocol("CustomStringConvertible");
+export const CustomDebugStringConvertible = protocol("CustomDebugStringConvertible");
+
+export function conform(type, protocol, witnesses, condition) {
+  if (!Object.hasOwn(type, "$conformances")) type.$conformances = new Map(type.$conformances);
+  type.$conformances.set(protocol.$name, witnesses);
+  if (condition) {
+    if (!Object.hasOwn(type, "$conditions")) type.$conditions = new Map(type.$conditions);
+    type.$conditions.set(protocol.$name, condition);
+  }
+  installDefaults(type.prototype, protocol.prototype);
+  installDefaults(type, protocol);
+}
+
+// Members the type declares or inherits are kept; its extensions emitted later replace defaults.
+function installDefaults(target, source) {
+  for (const key of Reflect.ownKeys(source)) {
+    if (key === "$name" || key === "prototype" || key in target) continue;
+    Object.defineProperty(target, key, Object.getOwnPropertyDescriptor(source, key));
+  }
+}
+
+export function witnesses(value, protocol) {
+  if (value === null || value === undefined) return undefined;
+  if (value instanceof Existential) return value.$witnesses.get(protocol) ?? witnesses(value.$value, protocol);
+  const type = Object(value).constructor;
+  const condition = type.$conditions?.get(protocol);
+  if (condition && !condition(value)) return undefined;
+  return type.$conformances?.get(protocol);
+}
+
+// Generic types are erased, so a conditional conformance checks the values an instance stores for
+// a type parameter instead of its type argument: each element of an array, and nil for any type.
+export function satisfies(value, type) {
+  if (value === null || value === undefined) return true;
+  if (Array.isArray(value)) return value.every((element) => satisfies(element, type));
+  return cast(value, type) !== null;
+}
+
+// Reads a property that a protocol extension provides without it being a requirement, which
+// Swift dispatches statically, ignoring any property of the same name on the dynamic type.
+export function protocolGet(protocol, name, self) {
+  return Reflect.get(protocol.prototype, name, self);
+}
+
+// The 64-bit integer types are represented by BigInts, and the others by numbers. `Int` is a
+// number limited to the integers it represents exactly, unless the program was compiled to make it
+// a BigInt. A cast to an integer type succeeds for values of the same representation in its range.
+function integerType(bits, signed, big) {
+  const min = signed ? -(2n ** BigInt(bits - 1)) : 0n;
+  const max = (signed ? 2n ** BigInt(bits - 1) : 2n ** BigInt(bits)) - 1n;
+  return big ? { bits, signed, big, min, max } : { bits, signed, big, min: Number(min), max: Number(max) };
+}
+
+const integerTypes = {
+  Int: { bits: 64, signed: true, big: false, min: Number.MIN_SAFE_INTEGER, max: Number.MAX_SAFE_INTEGER },
+  Int8: integerType(8, true, false),
+  Int16: integerType(16, true, false),
+  Int32: integerType(32, true, false),
+  Int64: integerType(64, true, true),
+  UInt: integerType(64, false, true),
+  UInt8: integerType(8, false, false),
+  UInt16: integerType(16, false, false),
+  UInt32: integerType(32, false, false),
+  UInt64: integerType(64, false, true),
+};
+
+// Called first by programs compiled with `Int` represented by BigInts.
+export function useBigIntForInt() {
+  integerTypes.Int = integerType(64, true, true);
+}
+
+// `Int8.min`, `UInt64.max` and the like, in the type's representation. `Int.max` is the largest
+// integer a number holds exactly, unless `Int` is a BigInt.
+export function integerBound(type, bound) {
+  return integerTypes[type][bound];
+}
+
+function isInteger(value, type) {
+  const { big, min, max } = integerTypes[type];
+  return (big ? typeof value === "bigint" : Number.isInteger(value)) && value >= min && value <= max;
+}
+
+// Integer arithmetic traps on overflow and division by zero, as in Swift, rather than losing
+// precision or producing infinities. Operands have the same type, named by `type`.
+function checked(value, type) {
+  if (!isInteger(value, type)) throw new Error(`arithmetic overflow: the result doesn't fit in ${type}`);
+  return value;
+}
+
+export function add(left, right, type) {
+  return checked(left + right, type);
+}
+
+export function subtract(left, right, type) {
+  return checked(left - right, type);
+}
+
+export function multiply(left, right, type) {
+  return checked(left * right, type);
+}
+
+export function negate(value, type) {
+  return checked(-value, type);
+}
+
+// Integer division truncates toward zero; BigInt division already does.
+export function divide(left, right, type) {
+  if (right == 0) throw new Error("division by zero");
+  return checked(integerTypes[type].big ? left / right : Math.trunc(left / right), type);
+}
+
+export function remainder(left, right, type) {
+  if (right == 0) throw new Error("division by zero in remainder operation");
+  // `Int.min % -1` traps in Swift, since the matching division overflows.
+  if (right == -1 && left == integerTypes[type].min) throw new Error("arithmetic overflow in remainder operation");
+  return left % right;
+}
+
+// The wrapping operators `&+`, `&-` and `&*` discard the bits that don't fit, computed as BigInts
+// so that no bits are lost to floating point first.
+function wrapped(value, type) {
+  const { bits, signed, big, min, max } = integerTypes[type];
+  const result = signed ? BigInt.asIntN(bits, value) : BigInt.asUintN(bits, value);
+  if (big) return result;
+  // Only `Int` has bits that a number can't hold exactly.
+  if (result < min || result > max) throw new Error(`${result} doesn't fit in ${type} represented as a number`);
+  return Number(result);
+}
+
+export function wrappingAdd(left, right, type) {
+  return wrapped(BigInt(left) + BigInt(right), type);
+}
+
+export function wrappingSubtract(left, right, type) {
+  return wrapped(BigInt(left) - BigInt(right), type);
+}
+
+export function wrappingMultiply(left, right, type) {
+  return wrapped(BigInt(left) * BigInt(right), type);
+}
+
+// Bitwise operators work on the two's complement bits of the type, as BigInts since JS bitwise
+// operators on numbers are limited to 32 bits.
+export function bitwiseAnd(left, right, type) {
+  return wrapped(BigInt(left) & BigInt(right), type);
+}
+
+export function bitwiseOr(left, right, type) {
+  return wrapped(BigInt(left) | BigInt(right), type);
+}
+
+export function bitwiseXor(left, right, type) {
+  return wrapped(BigInt(left) ^ BigInt(right), type);
+}
+
+export function bitwiseNot(value, type) {
+  return wrapped(~BigInt(value), type);
+}
+
+// Swift's smart shifts: a negative amount shifts the other way, and shifting by the bit width or
+// more leaves no bits but the sign. The amount may be of any integer type. Bits shifted out on the
+// left are discarded, without trapping.
+export function shiftLeft(value, amount, type) {
+  const bits = BigInt(integerTypes[type].bits);
+  const shift = BigInt(amount);
+  if (shift < 0n) return shiftRight(value, -shift, type);
+  return wrapped(shift >= bits ? 0n : BigInt(value) << shift, type);
+}
+
+export function shiftRight(value, amount, type) {
+  const bits = BigInt(integerTypes[type].bits);
+  const shift = BigInt(amount);
+  if (shift < 0n) return shiftLeft(value, -shift, type);
+  // Values are in range, so BigInt's arithmetic shift is also the logical one for unsigned types.
+  return wrapped(BigInt(value) >> (shift >= bits ? bits : shift), type);
+}
+
+// The masking shifts `&<<` and `&>>` only use the low bits of the amount, as most CPUs do.
+export function maskingShiftLeft(value, amount, type) {
+  const bits = integerTypes[type].bits;
+  return wrapped(BigInt(value) << (BigInt(amount) & BigInt(bits - 1)), type);
+}
+
+export function maskingShiftRight(value, amount, type) {
+  const bits = integerTypes[type].bits;
+  return wrapped(BigInt(value) >> (BigInt(amount) & BigInt(bits - 1)), type);
+}
+
+// Converts a number or BigInt to an integer type with one of its initializers. Converting a value
+// that doesn't fit traps, unless the initializer says otherwise.
+function integer(value, type, initializer = "$init$_") {
+  const { big, min, max } = integerTypes[type];
+  const isWhole = typeof value === "bigint" || Number.isInteger(value);
+  const truncated = typeof value === "bigint" ? value : Math.trunc(Number(value));
+  const represent = (value) => (big ? BigInt(value) : Number(value));
+  switch (initializer) {
+    // `bitPattern:` converts between the signed and unsigned types of the same width.
+    case "$init$truncatingIfNeeded": case "$init$bitPattern": return wrapped(BigInt(truncated), type);
+    case "$init$clamping": return truncated < min ? represent(min) : truncated > max ? represent(max) : represent(truncated);
+    case "$init$exactly": return isWhole && truncated >= min && truncated <= max ? represent(truncated) : null;
+    default:
+      if (Number.isNaN(truncated) || truncated < min || truncated > max) {
+        throw new Error(`${describe(value)} cannot be converted to ${type} because it is outside the representable range`);
+      }
+      return represent(truncated);
+  }
+}
+
+// Strings are JS strings, whose characters are extended grapheme clusters as in Swift: a flag or a
+// family emoji is one character of several scalars. A `Character` is a string of one cluster, and a
+// `String.Index` the UTF-16 offset where a character starts. The `utf8`, `utf16` and
+// `unicodeScalars` views are arrays of code units or single-scalar strings.
+const segmenter = new Intl.Segmenter(undefined, { granularity: "grapheme" });
+let segmented = { string: "", offsets: [0] };
+
+// The offsets where the characters of a string start, then its length. Indexing walks one string at
+// a time, so the last string segmented is kept.
+function boundaries(string) {
+  if (segmented.string !== string) {
+    const offsets = Array.from(segmenter.segment(string), (segment) => segment.index);
+    offsets.push(string.length);
+    segmented = { string, offsets };
+  }
+  return segmented.offsets;
+}
+
+// Converts a count or offset to an `Int`, which may be represented by a BigInt.
+export function toInt(length) {
+  return integerTypes.Int.big ? BigInt(length) : length;
+}
+
+// The position of an index among the character boundaries; an index that isn't one traps.
+function position(string, index) {
+  const found = boundaries(string).indexOf(Number(index));
+  if (found < 0) throw new Error("String index is out of bounds");
+  return found;
+}
+
+function indexAt(string, position) {
+  const offsets = boundaries(string);
+  if (position < 0 || position >= offsets.length) throw new Error("String index is out of bounds");
+  return offsets[position];
+}
+
+export function characters(string) {
+  return Array.from(segmenter.segment(string), (segment) => segment.segment);
+}
+
+export function characterCount(string) {
+  return toInt(boundaries(string).length - 1);
+}
+
+export function startIndex(string) {
+  return 0;
+}
+
+export function endIndex(string) {
+  return string.length;
+}
+
+export function indexAfter(string, index) {
+  return indexAt(string, position(string, index) + 1);
+}
+
+export function indexBefore(string, index) {
+  return indexAt(string, position(string, index) - 1);
+}
+
+export function indexOffsetBy(string, index, distance) {
+  return indexAt(string, position(string, index) + Number(distance));
+}
+
+export function characterAt(string, index) {
+  const start = position(string, index);
+  if (start === boundaries(string).length - 1) throw new Error("String index is out of bounds");
+  return string.slice(boundaries(string)[start], boundaries(string)[start + 1]);
+}
+
+export function firstCharacter(string) {
+  return string.length === 0 ? null : characterAt(string, 0);
+}
+
+export function lastCharacter(string) {
+  return string.length === 0 ? null : characterAt(string, indexBefore(string, string.length));
+}
+
+export function hasPrefix(string, prefix) {
+  const [whole, part] = [characters(string), characters(prefix)];
+  return part.length <= whole.length && part.every((character, i) => compareStrings(character, whole[i]) === 0);
+}
+
+export function hasSuffix(string, suffix) {
+  const [whole, part] = [characters(string).reverse(), characters(suffix).reverse()];
+  return part.length <= whole.length && part.every((character, i) => compareStrings(character, whole[i]) === 0);
+}
+
+export function uppercased(string) {
+  return string.toUpperCase();
+}
+
+export function lowercased(string) {
+  return string.toLowerCase();
+}
+
+export function utf8(string) {
+  return Array.from(new TextEncoder().encode(string));
+}
+
+export function utf16(string) {
+  return Array.from({ length: string.length }, (_, i) => string.charCodeAt(i));
+}
+
+export function unicodeScalars(string) {
+  return Array.from(string);
+}
+
+export function scalarValue(scalar) {
+  return scalar.codePointAt(0);
+}
+
+export function isASCII(character) {
+  return /^[\x00-\x7f]*$/.test(character);
+}
+
+// "\r\n" is a single character, whose ASCII value is the newline's.
+export function asciiValue(character) {
+  if (character === "\r\n") return 10;
+  return character.length === 1 && isASCII(character) ? character.charCodeAt(0) : null;
+}
+
+// Swift compares strings by canonical equivalence: "é" is equal whether written as one scalar or as
+// "e" and a combining accent. Strings order by the scalars of their composed forms.
+export function compareStrings(left, right) {
+  if (left === right) return 0;
+  const [a, b] = [Array.from(left.normalize("NFC")), Array.from(right.normalize("NFC"))];
+  for (let i = 0; i < Math.min(a.length, b.length); i++) {
+    if (a[i] !== b[i]) return a[i].codePointAt(0) - b[i].codePointAt(0);
+  }
+  return a.length - b.length;
+}
+
+// Swift's `==`, which calls a type's `Equatable` witness. Collections and tuples compare their
+// elements; values of types without a witness, class instances among them, compare by identity.
+export function equals(left, right) {
+  left = open(left);
+  right = open(right);
+  if (typeof left === "string" && typeof right === "string") return compareStrings(left, right) === 0;
+  if (left === right) return true;
+  if (left === null || right === null || typeof left !== "object" || typeof right !== "object") return false;
+  if (Array.isArray(left)) {
+    return Array.isArray(right) && left.length === right.length && left.every((element, i) => equals(element, right[i]));
+  }
+  if (left instanceof Map) {
+    return right instanceof Map && left.size === right.size
+      && [...left].every(([key, value]) => right.has(key) && equals(value, right.get(key)));
+  }
+  if (left instanceof Set) return right instanceof Set && left.size === right.size && [...left].every((element) => right.has(element));
+  const witness = witnesses(left, "Equatable")?.["==(_:_:)"];
+  return witness !== undefined && left.constructor[witness](left, right);
+}
+
+// Swift's `<`, which calls a type's `Comparable` witness. Tuples and arrays compare
+// lexicographically, strings by their canonical form.
+export function less(left, right) {
+  left = open(left);
+  right = open(right);
+  if (typeof left === "string") return compareStrings(left, right) < 0;
+  if (Array.isArray(left)) {
+    for (let i = 0; i < Math.min(left.length, right.length); i++) {
+      if (less(left[i], right[i])) return true;
+      if (less(right[i], left[i])) return false;
+    }
+    return left.length < right.length;
+  }
+  const witness = left !== null && typeof left === "object" ? witnesses(left, "Comparable")?.["<(_:_:)"] : undefined;
+  if (witness !== undefined) return left.constructor[witness](left, right);
+  return left < right;
+}
+
+// Swift's `Hasher`, which `hash(into:)` witnesses feed the values they compare in `==`. Values
+// are combined as `hashInto` walks them, with FNV-1a over 32-bit words.
+export class Hasher {
+  $state = 0x811c9dc5;
+
+  $mix(word) {
+    this.$state = Math.imul(this.$state ^ word, 0x01000193) >>> 0;
+  }
+
+  $copy() {
+    return Object.assign(new Hasher(), this);
+  }
+}
+
+export function combine(hasher, value) {
+  hashInto(value, hasher);
+}
+
+export function finalize(hasher) {
+  return toInt(hasher.$state | 0);
+}
+
+// Instances without a `Hashable` witness hash by identity, as they compare.
+const identities = new WeakMap();
+let nextIdentity = 1;
+
+function hashInto(value, hasher) {
+  value = open(value);
+  if (value === null || value === undefined) return hasher.$mix(0);
+  switch (typeof value) {
+    case "boolean": return hasher.$mix(value ? 1 : 2);
+    case "string": {
+      // Canonically equivalent strings are equal, so they hash their normalized form.
+      const normalized = value.normalize("NFC");
+      for (let i = 0; i < normalized.length; i++) hasher.$mix(normalized.charCodeAt(i));
+      return hasher.$mix(normalized.length);
+    }
+    case "number": case "bigint": return hashInto(String(value === 0 ? 0 : value), hasher);
+  }
+  if (Array.isArray(value)) {
+    value.forEach((element) => hashInto(element, hasher));
+    return hasher.$mix(value.length);
+  }
+  if (value instanceof Map || value instanceof Set) {
+    // The order of a dictionary's or set's elements doesn't affect equality, so their hashes are
+    // summed.
+    let sum = 0;
+    for (const element of value) sum = (sum + hashValue(element)) >>> 0;
+    return hasher.$mix(sum);
+  }
+  const witness = witnesses(value, "Hashable")?.["hash(into:)"];
+  if (witness !== undefined) return value[witness]({ value: hasher });
+  if (!identities.has(value)) identities.set(value, nextIdentity++);
+  hasher.$mix(identities.get(value));
+}
+
+export function hashValue(value) {
+  const hasher = new Hasher();
+  hashInto(value, hasher);
+  return hasher.$state;
+}
+
+// Dictionaries and sets key their entries by Swift equality. Booleans, numbers and BigInts are JS
+// keys as they are; strings and other values are stored under the first key stored that equals
+// them, found among the keys of the same hash.
+function isPrimitive(key) {
+  return key === null || (typeof key !== "object" && typeof key !== "string");
+}
+
+class Keys {
+  $buckets = new Map();
+
+  // The key an entry equal to `key` is stored under, if any.
+  $find(key) {
+    if (isPrimitive(key)) return key;
+    return this.$buckets.get(hashValue(key))?.find((stored) => equals(stored, key)) ?? key;
+  }
+
+  $add(key) {
+    if (isPrimitive(key)) return key;
+    const hash = hashValue(key);
+    const bucket = this.$buckets.get(hash) ?? [];
+    const stored = bucket.find((candidate) => equals(candidate, key));
+    if (stored !== undefined) return stored;
+    // The key is a value, which mustn't change once stored.
+    key = copy(key);
+    bucket.push(key);
+    this.$buckets.set(hash, bucket);
+    return key;
+  }
+
+  $remove(key) {
+    if (isPrimitive(key)) return;
+    const hash = hashValue(key);
+    const bucket = this.$buckets.get(hash)?.filter((stored) => stored !== key) ?? [];
+    if (bucket.length === 0) this.$buckets.delete(hash);
+    else this.$buckets.set(hash, bucket);
+  }
+}
+
+export class HashMap extends Map {
+  $keys = new Keys();
+
+  constructor(entries = []) {
+    super();
+    for (const [key, value] of entries) this.set(key, value);
+  }
+
+  get(key) { return super.get(this.$keys.$find(key)); }
+  has(key) { return super.has(this.$keys.$find(key)); }
+  set(key, value) { return super.set(this.$keys.$add(key), value); }
+
+  delete(key) {
+    const stored = this.$keys.$find(key);
+    this.$keys.$remove(stored);
+    return super.delete(stored);
+  }
+
+  clear() {
+    this.$keys = new Keys();
+    super.clear();
+  }
+}
+
+export class HashSet extends Set {
+  $keys = new Keys();
+
+  constructor(elements = []) {
+    super();
+    for (const element of elements) this.add(element);
+  }
+
+  has(element) { return super.has(this.$keys.$find(element)); }
+  add(element) { return super.add(this.$keys.$add(element)); }
+
+  delete(element) {
+    const stored = this.$keys.$find(element);
+    this.$keys.$remove(stored);
+    return super.delete(stored);
+  }
+
+  clear() {
+    this.$keys = new Keys();
+    super.clear();
+  }
+}
+
+// Class instances are the objects that aren't structs, enum cases or collections.
+function isInstance(value) {
+  return value !== null && typeof value === "object" && typeof value.$copy !== "function" && !Array.isArray(value)
+    && !(value instanceof Map) && !(value instanceof Set) && !Array.isArray(value.$values);
+}
+
+export function cast(value, type) {
+  // Only a boxed value can be a `Double` or a `Float` that holds an integer.
+  const floatingPointType = value instanceof Existential ? value.$type : undefined;
+  if (type === "Any") return value;
+  if (isOptionalType(type)) return value === null ? null : cast(value, type.optional);
+  value = open(value);
+  // A protocol composition, which the value must satisfy in full.
+  if (Array.isArray(type)) return type.every((member) => cast(value, member) !== null) ? value : null;
+  if (type instanceof Protocol) return witnesses(value, type.$name) ? value : null;
+  if (typeof type === "function") return value instanceof type ? value : null;
+  if (Object.hasOwn(integerTypes, type)) return floatingPointType === undefined && isInteger(value, type) ? value : null;
+  switch (type) {
+    case "Double": case "Float": {
+      const isFloatingPoint = floatingPointType === undefined ? typeof value === "number" && !Number.isInteger(value) : floatingPointType === type;
+      return isFloatingPoint ? value : null;
+    }
+    case "AnyObject": return isInstance(value) ? value : null;
+    case "Bool": return typeof value === "boolean" ? value : null;
+    case "String": return typeof value === "string" ? value : null;
+    case "Character": return typeof value === "string" && boundaries(value).length === 2 ? value : null;
+    default: return value;
+  }
+}
+
+// A value of an existential type (`any P`, a composition, or a protocol used as a type) is boxed
+// with the witness tables of its protocols, found when the value was converted. Copying a box
+// copies the value inside, which may be a struct. A `Double` or `Float` also records its type,
+// which a JS number doesn't tell.
+class Existential {
+  constructor(value, witnesses, type) {
+    this.$value = value;
+    this.$witnesses = witnesses;
+    this.$type = type;
+  }
+
+  $copy() {
+    return new Existential(copy(this.$value), this.$witnesses, this.$type);
+  }
+}
+
+export function existential(value, ...protocols) {
+  if (value === null || value === undefined) return null;
+  const type = value instanceof Existential ? value.$type : undefined;
+  value = open(value);
+  return new Existential(value, new Map(protocols.map((protocol) => [protocol.$name, witnesses(value, protocol.$name)])), type);
+}
+
+// A `Double` or `Float` converted to `Any` or to an existential, boxed so that casts and
+// descriptions can tell `1.0` from `1`.
+export function floatingPoint(value, type) {
+  if (value === null || value === undefined) return null;
+  return new Existential(open(value), new Map(), type);
+}
+
+// The value inside an existential box, to access its members; other values are returned as is.
+export function open(value) {
+  return value instanceof Existential ? value.$value : value;
+}
+
+// Generic code receives the metadata of a type parameter where it needs one: a class, a protocol,
+// or the name of a standard library type. `T(...)` constructs a value of such a type.
+export function construct(type, initializer, ...args) {
+  if (typeof type === "function") {
+    // Value types initialize through static factories; classes allocate first.
+    return Object.hasOwn(type, initializer) ? type[initializer](...args) : new type()[initializer](...args);
+  }
+  if (Object.hasOwn(integerTypes, type)) return integer(args.length === 0 ? 0 : args[0], type, initializer);
+  // `String(decoding: data, as: UTF8.self)` replaces invalid UTF-8 with U+FFFD, as Swift does.
+  if (type === "String" && initializer === "$init$decoding$as") return new TextDecoder().decode(Uint8Array.from(args[0]));
+  switch (type) {
+    case "Double": return args.length === 0 ? 0 : Number(args[0]);
+    // `Float` values are numbers rounded to single precision where they are created.
+    case "Float": return args.length === 0 ? 0 : Math.fround(Number(args[0]));
+    case "Bool": return args.length === 0 ? false : Boolean(args[0]);
+    case "String": case "Character": return args.length === 0 ? "" : describe(args[0]);
+    default: return unsupported(`constructing a value of type ${describe(type)}`);
+  }
+}
+
+export function unsupported(feature) {
+  throw new Error(`unsupported: ${feature}`);
+}
+
+// Swift errors are thrown as the values they are. Runtime traps are JS `Error`s, which Swift code
+// can't catch: `catch` clauses, `try?` and `try!` pass them on.
+export function rethrowTraps(error) {
+  if (error instanceof Error) throw error;
+}
+
+// `try? expression`, which gives `nil` instead of the error the expression throws.
+export function tryOptional(evaluate) {
+  try {
+    return evaluate();
+  } catch (error) {
+    rethrowTraps(error);
+    return null;
+  }
+}
+
+// `try! expression`, which traps when the expression throws.
+export function tryForced(evaluate) {
+  try {
+    return evaluate();
+  } catch (error) {
+    rethrowTraps(error);
+    throw new Error(`'try!' expression unexpectedly raised an error: ${debugDescribe(error)}`);
+  }
+}
+
+// Deterministic deinitialization, for modules compiled with reference counting. Class instances
+// carry a `$retainCount` that starts at 1 for the expression that created them; variables,
+// properties and collection elements own one count each. Structs, enums and collections are
+// retained and released element-wise, since they own the instances they contain.
+const autoreleased = [];
+
+function isReference(value) {
+  return value !== null && typeof value === "object" && typeof value.$retainCount === "number";
+}
+
+function forEachOwned(value, action) {
+  if (value instanceof Existential) action(value.$value);
+  else if (Array.isArray(value)) value.forEach(action);
+  else if (value instanceof Map || value instanceof Set) value.forEach((element) => action(element));
+  else if (value !== null && typeof value === "object" && Array.isArray(value.$values)) value.$values.forEach(action);
+  else if (value !== null && typeof value === "object" && typeof value.$copy === "function") Object.values(value).forEach(action);
+}
+
+export function retain(value) {
+  if (isReference(value)) value.$retainCount += 1;
+  else forEachOwned(value, retain);
+  return value;
+}
+
+export function release(value) {
+  if (!isReference(value)) {
+    forEachOwned(value, release);
+  } else if (--value.$retainCount === 0) {
+    // NaN keeps the instance from being destroyed again while its deinit runs.
+    value.$retainCount = NaN;
+    value.$deinit?.();
+    for (const key of Object.keys(value)) release(value[key]);
+  }
+}
+
+// Stores an owned value over `old`, which is released once `value` holds its own count.
+export function replacing(old, value) {
+  release(old);
+  return value;
+}
+
+export function deleteValue(map, key) {
+  release(map.get(key));
+  map.delete(key);
+}
+
+// Owned temporaries used where a borrowed value is expected stay alive until the enclosing
+// full expression has been evaluated: `drain(pool(), expression)`.
+export function autorelease(value) {
+  autoreleased.push(value);
+  return value;
+}
+
+export function pool() {
+  return autoreleased.length;
+}
+
+export function drain(depth, value) {
+  for (const temporary of autoreleased.splice(depth).reverse()) release(temporary);
+  return value;
+}
+
+// `weak` references don't keep their instance alive, and read as nil once it has been deallocated:
+// collected, or destroyed by reference counting.
+export function weak(value) {
+  return value === null ? null : new WeakRef(value);
+}
+
+export function loadWeak(reference) {
+  const value = reference?.deref();
+  return value === undefined || isDeallocated(value) ? null : value;
+}
+
+// `unowned` references don't keep their instance alive either, and trap when read after it has
+// been deallocated. The wrapper keeps reference counting from releasing the instance with its holder.
+export function unowned(value) {
+  return value === null ? null : { instance: value };
+}
+
+export function loadUnowned(reference) {
+  if (reference === null) return null;
+  if (isDeallocated(reference.instance)) {
+    throw new Error("attempted to read an unowned reference but the object was already deallocated");
+  }
+  return reference.instance;
+}
+
+function isDeallocated(value) {
+  return Number.isNaN(value.$retainCount);
+}
+
+// Without reference counting, `deinit` runs when the garbage collector reclaims an instance. The
+// instance itself is gone by then, so the properties `deinit` reads live in a `$fields` store that
+// the instance's accessors write through; only that store is kept for `deinit`, which sees the
+// values they last held.
+const finalization = new FinalizationRegistry(({ prototype, fields }) => {
+  prototype.$deinit.call(Object.assign(Object.create(prototype), { $fields: fields }));
+});
+
+// The store of an instance, shared with the classes it inherits from, holding initial values.
+export function fieldStore(instance, fields) {
+  return Object.assign(instance.$fields ?? {}, fields);
+}
+
+export function track(instance) {
+  if (instance !== null) {
+    const prototype = Object.getPrototypeOf(instance);
+    finalization.register(instance, { prototype, fields: instance.$fields ?? {} });
+  }
+  return instance;
+}
@@ -0,0 +1,161 @@
+// Reflection: `Mirror`, and `dump`, which prints a value with the children its mirror shows, as
+// Swift's `dump` does. Values are reflected with their static types, described as `$rt.cast` takes
+// them, which tell what the JS values can't: that a value is optional, or a `Double`.
+import { customDescription, debugDescribe, floatingPoint, isOptionalType, open, printItems, reflectValue, standardCase, typeName } from "./swift-runtime.js";
+
+export class Mirror {
+  constructor(subject, type, subjectType, reflected) {
+    this.$subject = subject;
+    this.$type = type;
+    this.subjectType = subjectType;
+    this.$reflected = reflected;
+  }
+
+  toString() {
+    return `Mirror for ${typeName(this.subjectType)}`;
+  }
+
+  static DisplayStyle = class DisplayStyle {
+    static $reflection = { kind: "enum", members: () => ({}), qualifiedName: "Swift.Mirror.DisplayStyle" };
+    static struct = standardCase(DisplayStyle, "struct", []);
+    static class = standardCase(DisplayStyle, "class", []);
+    static enum = standardCase(DisplayStyle, "enum", []);
+    static tuple = standardCase(DisplayStyle, "tuple", []);
+    static optional = standardCase(DisplayStyle, "optional", []);
+    static collection = standardCase(DisplayStyle, "collection", []);
+    static dictionary = standardCase(DisplayStyle, "dictionary", []);
+    static set = standardCase(DisplayStyle, "set", []);
+  };
+}
+
+// `Mirror(reflecting:)`. The mirror of a class instance shows the stored properties of `subject`,
+// its class or one of its superclasses.
+export function makeMirror(value, type, subject) {
+  const reflected = reflectValue(value, type, subject);
+  return new Mirror(value, type, subject ?? subjectTypeOf(value, type), reflected);
+}
+
+// The dynamic type of the value: the class of an instance, whatever its static type.
+function subjectTypeOf(value, type) {
+  value = open(value);
+  if (!isOptionalType(type) && Object.hasOwn(value?.constructor ?? {}, "$reflection")) return value.constructor;
+  if (type !== undefined) return type;
+  switch (typeof value) {
+    case "string": return "String";
+    case "boolean": return "Bool";
+    case "number": return Number.isInteger(value) ? "Int" : "Double";
+    case "bigint": return "Int";
+    case "function": return "(Function)";
+    default: return value?.constructor ?? "Any";
+  }
+}
+
+// Children are `(label: String?, value: Any)` tuples, whose `Double` and `Float` values are boxed
+// with their types, as values converted to `Any` are.
+export function mirrorChildren(mirror) {
+  return mirror.$reflected.children.map(([label, value, type]) => [label, type === "Double" || type === "Float" ? floatingPoint(value, type) : value]);
+}
+
+export function childLabel(child) {
+  return child[0];
+}
+
+export function childValue(child) {
+  return child[1];
+}
+
+export function displayStyle(mirror) {
+  const { style } = mirror.$reflected;
+  return style === null ? null : Mirror.DisplayStyle[style];
+}
+
+export function subjectType(mirror) {
+  return mirror.subjectType;
+}
+
+export function superclassMirror(mirror) {
+  const { superclass } = mirror.$reflected;
+  return superclass === null ? null : makeMirror(mirror.$subject, undefined, superclass);
+}
+
+// `dump(_:name:indent:maxDepth:maxItems:)`, which prints the value, then its children indented
+// below it, and returns the value. Class instances are numbered as they're first reached, so that
+// those reached again print their number instead of their children.
+export function dump(value, type, name = null, indent = 0, maxDepth = Infinity, maxItems = Infinity) {
+  const lines = [];
+  const state = { remaining: Number(maxItems), visited: new Map() };
+  dumpValue(lines, state, value, type, name, Number(indent), Number(maxDepth));
+  printItems([lines.join("\n")], " ", "\n");
+  return value;
+}
+
+function dumpValue(lines, state, value, type, name, indent, maxDepth) {
+  if (state.remaining <= 0) return;
+  state.remaining -= 1;
+  const mirror = makeMirror(value, type);
+  const { children, superclass } = mirror.$reflected;
+  let line = `${" ".repeat(indent)}${bullet(children, maxDepth)} ${name === null ? "" : `${name}: `}${summary(mirror)}`;
+  const object = open(value);
+  if (mirror.$reflected.style === "class") {
+    const previous = state.visited.get(object);
+    if (previous !== undefined) {
+      lines.push(`${line} #${previous}`);
+      return;
+    }
+    state.visited.set(object, state.visited.size);
+    line += ` #${state.visited.size - 1}`;
+  }
+  lines.push(line);
+  if (maxDepth <= 0) return;
+  if (superclass !== null) dumpSuperclass(lines, state, makeMirror(value, undefined, superclass), indent + 2, maxDepth - 1);
+  dumpChildren(lines, state, children, indent, maxDepth);
+}
+
+// The stored properties a superclass declares, under a `super:` line naming it.
+function dumpSuperclass(lines, state, mirror, indent, maxDepth) {
+  if (state.remaining <= 0) return;
+  state.remaining -= 1;
+  const { children, superclass } = mirror.$reflected;
+  lines.push(`${" ".repeat(indent)}${bullet(children, maxDepth)} super: ${typeName(mirror.subjectType, true)}`);
+  if (maxDepth <= 0) return;
+  if (superclass !== null) dumpSuperclass(lines, state, makeMirror(mirror.$subject, undefined, superclass), indent + 2, maxDepth - 1);
+  dumpChildren(lines, state, children, indent, maxDepth);
+}
+
+// Children past `maxItems` are counted on one line instead.
+function dumpChildren(lines, state, children, indent, maxDepth) {
+  for (const [i, [label, child, type]] of children.entries()) {
+    if (state.remaining <= 0) {
+      const remainder = children.length - i;
+      lines.push(`${" ".repeat(indent + 4)}(${remainder}${i > 0 ? " more" : ""} ${remainder === 1 ? "child" : "children"})`);
+      return;
+    }
+    dumpValue(lines, state, child, type, label, indent + 2, maxDepth - 1);
+  }
+}
+
+function bullet(children, maxDepth) {
+  if (children.length === 0) return "-";
+  return maxDepth <= 0 ? "▹" : "▿";
+}
+
+// Collections print their counts; other values the description they give themselves, or the
+// qualified name of their type, with the case of an enum.
+function summary(mirror) {
+  const { style, children } = mirror.$reflected;
+  const count = (singular, plural) => `${children.length} ${children.length === 1 ? singular : plural}`;
+  switch (style) {
+    case "tuple": return `(${count("element", "elements")})`;
+    case "collection": return count("element", "elements");
+    case "dictionary": return count("key/value pair", "key/value pairs");
+    case "set": return count("member", "members");
+  }
+  const value = open(mirror.$subject);
+  const description = style !== "optional" && value !== null && typeof value === "object" ? customDescription(value, true) : undefined;
+  if (description !== undefined) return description;
+  switch (style) {
+    case "struct": case "class": return typeName(mirror.subjectType, true);
+    case "enum": return `${typeName(mirror.subjectType, true)}.${value.$case}`;
+    default: return debugDescribe(mirror.$subject, mirror.$type);
+  }
+}
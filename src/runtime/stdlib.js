@@ -0,0 +1,290 @@
+// The standard library members of arrays, dictionaries, sets and optionals, and its global
+// functions. Arrays are JS arrays, dictionaries `Map`s, sets `Set`s and tuples arrays; a method
+// takes its receiver first: `numbers.map { $0 * 2 }` is `map(numbers, ($0) => ...)`. Mutating
+// methods change the collection in place, since stores copy collections.
+import { HashMap, HashSet, equals, less, release, toInt } from "./swift-runtime.js";
+
+function size(collection) {
+  return collection instanceof Map || collection instanceof Set ? collection.size : collection.length;
+}
+
+function index(collection, position) {
+  const i = Number(position);
+  if (!Number.isInteger(i) || i < 0 || i >= collection.length) throw new Error("Index out of range");
+  return i;
+}
+
+// Members every collection has. Strings go through their characters in `core.js`; these are the
+// arrays of their views.
+export function count(collection) {
+  return toInt(size(collection));
+}
+
+export function isEmpty(collection) {
+  return size(collection) === 0;
+}
+
+export function first(collection) {
+  for (const element of collection) return element;
+  return null;
+}
+
+export function last(collection) {
+  return collection.length === 0 ? null : collection[collection.length - 1];
+}
+
+export function map(collection, transform) {
+  return Array.from(collection, (element) => transform(element));
+}
+
+export function compactMap(collection, transform) {
+  const result = [];
+  for (const element of collection) {
+    const value = transform(element);
+    if (value !== null && value !== undefined) result.push(value);
+  }
+  return result;
+}
+
+export function filter(collection, isIncluded) {
+  return collection.filter((element) => isIncluded(element));
+}
+
+export function filterSet(set, isIncluded) {
+  return new HashSet([...set].filter((element) => isIncluded(element)));
+}
+
+export function filterDictionary(map, isIncluded) {
+  return new HashMap([...map].filter((entry) => isIncluded(entry)));
+}
+
+export function reduce(collection, initial, combine) {
+  let result = initial;
+  for (const element of collection) result = combine(result, element);
+  return result;
+}
+
+export function forEach(collection, body) {
+  for (const element of collection) body(element);
+}
+
+export function sorted(collection, areInIncreasingOrder = less) {
+  return [...collection].sort((a, b) => (areInIncreasingOrder(a, b) ? -1 : areInIncreasingOrder(b, a) ? 1 : 0));
+}
+
+export function reversed(collection) {
+  return [...collection].reverse();
+}
+
+export function enumerated(collection) {
+  return Array.from(collection, (element, offset) => [toInt(offset), element]);
+}
+
+export function contains(collection, element) {
+  if (collection instanceof Set) return collection.has(element);
+  for (const candidate of collection) if (equals(candidate, element)) return true;
+  return false;
+}
+
+export function containsWhere(collection, predicate) {
+  for (const element of collection) if (predicate(element)) return true;
+  return false;
+}
+
+export function firstWhere(collection, predicate) {
+  for (const element of collection) if (predicate(element)) return element;
+  return null;
+}
+
+export function firstIndexOf(collection, element) {
+  const found = collection.findIndex((candidate) => equals(candidate, element));
+  return found < 0 ? null : toInt(found);
+}
+
+export function minElement(collection) {
+  return reduce(collection, null, (result, element) => (result === null || less(element, result) ? element : result));
+}
+
+export function maxElement(collection) {
+  return reduce(collection, null, (result, element) => (result === null || less(result, element) ? element : result));
+}
+
+export function joined(strings, separator = "") {
+  return strings.join(separator);
+}
+
+// Array mutation.
+export function append(array, element) {
+  array.push(element);
+}
+
+export function appendContentsOf(array, elements) {
+  for (const element of elements) array.push(element);
+}
+
+export function insertAt(array, element, position) {
+  const i = Number(position);
+  if (i !== array.length) index(array, i);
+  array.splice(i, 0, element);
+}
+
+export function removeAt(array, position) {
+  return array.splice(index(array, position), 1)[0];
+}
+
+export function removeLast(array) {
+  if (array.length === 0) throw new Error("Can't remove last element from an empty collection");
+  return array.pop();
+}
+
+export function removeAll(collection) {
+  if (Array.isArray(collection)) collection.splice(0).forEach(release);
+  else {
+    collection.forEach((element) => release(element));
+    collection.clear();
+  }
+}
+
+export function removeAllWhere(array, shouldBeRemoved) {
+  const kept = array.filter((element) => !shouldBeRemoved(element));
+  array.filter((element) => !kept.includes(element)).forEach(release);
+  array.splice(0, array.length, ...kept);
+}
+
+export function appendString(string, other) {
+  return string + other;
+}
+
+// Dictionaries.
+export function keys(map) {
+  return [...map.keys()];
+}
+
+export function values(map) {
+  return [...map.values()];
+}
+
+export function mapValues(map, transform) {
+  return new HashMap([...map].map(([key, value]) => [key, transform(value)]));
+}
+
+export function updateValue(map, value, key) {
+  const old = map.has(key) ? map.get(key) : null;
+  map.set(key, value);
+  return old;
+}
+
+export function removeValue(map, key) {
+  if (!map.has(key)) return null;
+  const old = map.get(key);
+  map.delete(key);
+  return old;
+}
+
+// `dictionary[key, default: value]`
+export function valueOrDefault(map, key, defaultValue) {
+  return map.has(key) ? map.get(key) : defaultValue;
+}
+
+// `dictionary[key, default: value] += 1` stores the updated value, or the default updated.
+export function modifyValue(map, key, defaultValue, update) {
+  map.set(key, update(valueOrDefault(map, key, defaultValue)));
+}
+
+// Sets.
+export function union(set, other) {
+  return new HashSet([...set, ...other]);
+}
+
+export function intersection(set, other) {
+  const elements = new HashSet(other);
+  return new HashSet([...set].filter((element) => elements.has(element)));
+}
+
+export function subtracting(set, other) {
+  const elements = new HashSet(other);
+  return new HashSet([...set].filter((element) => !elements.has(element)));
+}
+
+export function symmetricDifference(set, other) {
+  return union(subtracting(set, other), subtracting(new HashSet(other), set));
+}
+
+export function isSubset(set, other) {
+  const elements = new HashSet(other);
+  return [...set].every((element) => elements.has(element));
+}
+
+export function isSuperset(set, other) {
+  return [...other].every((element) => set.has(element));
+}
+
+export function isDisjoint(set, other) {
+  return ![...other].some((element) => set.has(element));
+}
+
+// `(inserted: Bool, memberAfterInsert: Element)`
+export function insert(set, element) {
+  if (set.has(element)) return [false, element];
+  set.add(element);
+  return [true, element];
+}
+
+export function remove(set, element) {
+  return set.delete(element) ? element : null;
+}
+
+export function formUnion(set, other) {
+  for (const element of other) set.add(element);
+}
+
+// Optionals are their wrapped value, or null.
+export function mapOptional(value, transform) {
+  return value === null ? null : transform(value);
+}
+
+export function flatMapOptional(value, transform) {
+  return value === null ? null : transform(value);
+}
+
+// Global functions.
+export function zip(left, right) {
+  const result = [];
+  for (let i = 0; i < Math.min(left.length, right.length); i++) result.push([left[i], right[i]]);
+  return result;
+}
+
+// Each value of a stride is `from + i * by`, so floating-point strides don't accumulate errors.
+function stride(from, end, by, includesEnd) {
+  if (by == 0) throw new Error("Stride size must not be zero");
+  const result = [];
+  const step = (i) => from + (typeof from === "bigint" ? BigInt(i) : i) * by;
+  for (let i = 0; ; i++) {
+    const value = step(i);
+    const before = by > 0 ? value < end : value > end;
+    if (!before && !(includesEnd && value == end)) return result;
+    result.push(value);
+  }
+}
+
+export function strideTo(from, to, by) {
+  return stride(from, to, by, false);
+}
+
+export function strideThrough(from, through, by) {
+  return stride(from, through, by, true);
+}
+
+export function min(first, ...rest) {
+  return rest.reduce((result, value) => (less(value, result) ? value : result), first);
+}
+
+export function max(first, ...rest) {
+  return rest.reduce((result, value) => (less(result, value) ? value : result), first);
+}
+
+export function abs(value) {
+  if (typeof value === "bigint") return value < 0n ? -value : value;
+  return Math.abs(value);
+}
+
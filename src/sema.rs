@@ -0,0 +1,2181 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use crate::stdlib::{self, StandardMember, StandardProtocol};
+use crate::syntax::{Declaration, Expression, FunctionTypeAttributes, Statement, StatementSequence, SwiftType};
+use crate::syntax::declaration::{
+    AssociatedTypeDeclaration,
+    ClassDeclaration,
+    EnumCase,
+    EnumDeclaration,
+    ExtensionDeclaration,
+    FunDeclaration,
+    FunctionParameter,
+    GenericRequirement,
+    GenericsDeclaration,
+    InitializerDeclaration,
+    InitializerRequirement,
+    MethodRequirement,
+    PropertyKind,
+    PropertyRequirement,
+    ProtocolDeclaration,
+    ReferenceOwnership,
+    StructDeclaration,
+    VariablePropertyDeclaration,
+};
+use crate::syntax::expression::{Argument, CallExpression, Closure, Literal, MemberAccessExpression, TrailingClosure, TryKind};
+use crate::syntax::statement::Pattern;
+
+/// A named type declared in the program.
+#[derive(Debug, Clone, Copy)]
+pub enum NominalType<'a> {
+    Struct(&'a StructDeclaration),
+    Enum(&'a EnumDeclaration),
+    Class(&'a ClassDeclaration),
+    Protocol(&'a ProtocolDeclaration),
+}
+
+impl<'a> NominalType<'a> {
+    pub fn name(&self) -> &'a str {
+        match self {
+            NominalType::Struct(decl) => &decl.name,
+            NominalType::Enum(decl) => &decl.name,
+            NominalType::Class(decl) => &decl.name,
+            NominalType::Protocol(decl) => &decl.name,
+        }
+    }
+    /// Structs and enums have value semantics; classes are reference types.
+    pub fn is_value_type(&self) -> bool {
+        matches!(self, NominalType::Struct(_) | NominalType::Enum(_))
+    }
+    pub fn properties(&self) -> &'a [VariablePropertyDeclaration] {
+        match self {
+            NominalType::Struct(decl) => &decl.properties,
+            NominalType::Enum(decl) => &decl.properties,
+            NominalType::Class(decl) => &decl.properties,
+            NominalType::Protocol(_) => &[],
+        }
+    }
+    pub fn methods(&self) -> &'a [FunDeclaration] {
+        match self {
+            NominalType::Struct(decl) => &decl.methods,
+            NominalType::Enum(decl) => &decl.methods,
+            NominalType::Class(decl) => &decl.methods,
+            NominalType::Protocol(_) => &[],
+        }
+    }
+    pub fn initializers(&self) -> &'a [InitializerDeclaration] {
+        match self {
+            NominalType::Struct(decl) => &decl.initializers,
+            NominalType::Enum(decl) => &decl.initializers,
+            NominalType::Class(decl) => &decl.initializers,
+            NominalType::Protocol(_) => &[],
+        }
+    }
+    /// The generic parameters and `where` clause of a type declaration.
+    pub fn generics(&self) -> Option<&'a GenericsDeclaration> {
+        match self {
+            NominalType::Struct(decl) => decl.generics.as_ref(),
+            NominalType::Enum(decl) => decl.generics.as_ref(),
+            NominalType::Class(decl) => decl.generics.as_ref(),
+            NominalType::Protocol(_) => None,
+        }
+    }
+    /// The protocols listed in the declaration; for a protocol, the ones it inherits.
+    pub fn conformances(&self) -> &'a [String] {
+        match self {
+            NominalType::Struct(decl) => &decl.conformances,
+            NominalType::Enum(decl) => &decl.conformances,
+            NominalType::Class(decl) => &decl.conformances,
+            NominalType::Protocol(decl) => &decl.inherited_protocols,
+        }
+    }
+}
+
+impl ProtocolRequirements for ProtocolDeclaration {
+    fn requirements(&self) -> Vec<Requirement<'_>> {
+        self.property_requirements
+            .iter()
+            .map(Requirement::Property)
+            .chain(self.method_requirements.iter().map(Requirement::Method))
+            .chain(self.initializer_requirements.iter().map(Requirement::Initializer))
+            .collect()
+    }
+}
+
+/// The requirements of a protocol, in declaration order.
+pub trait ProtocolRequirements {
+    fn requirements(&self) -> Vec<Requirement<'_>>;
+}
+
+/// Something a protocol requires of conforming types.
+#[derive(Debug, Clone, Copy)]
+pub enum Requirement<'a> {
+    Property(&'a PropertyRequirement),
+    Method(&'a MethodRequirement),
+    Initializer(&'a InitializerRequirement),
+}
+
+impl Requirement<'_> {
+    /// How Swift names the requirement, e.g. `area`, `move(by:)` or `init(name:)`.
+    pub fn signature(&self) -> String {
+        match self {
+            Requirement::Property(property) => property.name.clone(),
+            Requirement::Method(method) => signature(&method.name, labels(&method.parameters)),
+            Requirement::Initializer(initializer) => signature("init", labels(&initializer.parameters)),
+        }
+    }
+    /// Describes the requirement as Swift does when a conforming type lacks it,
+    /// e.g. `function 'area()' with type '() -> Double'`.
+    pub fn description(&self) -> String {
+        let name = self.signature();
+        match self {
+            Requirement::Property(property) => {
+                let accessors = if property.is_read_only { "get" } else { "get set" };
+                format!("property '{name}' with type '{}' {{ {accessors} }}", property.ty)
+            }
+            Requirement::Method(method) => {
+                let ty = function_type(&method.parameters, method.return_type.as_ref(), method.is_throwing);
+                let mutating = if method.is_mutating { "mutating " } else { "" };
+                format!("{mutating}function '{name}' with type '{ty}'")
+            }
+            Requirement::Initializer(initializer) => {
+                let parameters = initializer
+                    .parameters
+                    .iter()
+                    .map(|parameter| match &parameter.label {
+                        Some(label) => format!("{label}: {}", parameter.ty),
+                        None => parameter.ty.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("initializer '{name}' with type '({})'", parameters.join(", "))
+            }
+        }
+    }
+}
+
+/// The declaration satisfying a protocol requirement for a conforming type.
+#[derive(Debug, Clone, Copy)]
+pub enum Witness<'a> {
+    Property(&'a VariablePropertyDeclaration),
+    Method(&'a FunDeclaration),
+    Initializer(&'a InitializerDeclaration),
+}
+
+/// How a type conforms to a protocol: the type chosen for each associated type, and each
+/// requirement of the protocol, in declaration order, with the member witnessing it, if any.
+/// Requirements of inherited protocols belong to their own conformances.
+#[derive(Debug, Clone)]
+pub struct Conformance<'a> {
+    pub type_name: &'a str,
+    pub protocol: &'a ProtocolDeclaration,
+    pub associated_types: Vec<(&'a AssociatedTypeDeclaration, Option<SwiftType>)>,
+    pub witnesses: Vec<(Requirement<'a>, Option<Witness<'a>>)>,
+}
+
+impl<'a> Conformance<'a> {
+    /// The requirements no member of the type (or default implementation) satisfies.
+    pub fn missing(&self) -> impl Iterator<Item = Requirement<'a>> + '_ {
+        self.witnesses.iter().filter(|(_, witness)| witness.is_none()).map(|(requirement, _)| *requirement)
+    }
+}
+
+/// Types bound to generic parameters, or to the associated types of a protocol and `Self`,
+/// while matching types written in terms of them against concrete types.
+#[derive(Debug, Clone, Default)]
+pub struct Substitution {
+    parameters: Vec<String>,
+    bindings: HashMap<String, SwiftType>,
+}
+
+impl Substitution {
+    pub fn new(parameters: impl IntoIterator<Item = String>) -> Self {
+        Substitution { parameters: parameters.into_iter().collect(), bindings: HashMap::new() }
+    }
+    /// Binds the parameters of a generic declaration to explicit arguments, in order.
+    pub fn specializing(generics: &GenericsDeclaration, arguments: &[SwiftType]) -> Self {
+        let mut substitution = Substitution::new(generics.type_parameters.iter().map(|parameter| parameter.name.clone()));
+        for (parameter, argument) in generics.type_parameters.iter().zip(arguments) {
+            substitution.bind(&parameter.name, argument.clone());
+        }
+        substitution
+    }
+    pub fn bind(&mut self, name: &str, ty: SwiftType) {
+        self.bindings.insert(name.to_string(), ty);
+    }
+    pub fn get(&self, name: &str) -> Option<&SwiftType> {
+        self.bindings.get(name)
+    }
+    /// The parameter a type names: `T`, or `Element` as `Self.Element`.
+    fn parameter<'t>(&self, ty: &'t SwiftType) -> Option<&'t str> {
+        let name = match ty {
+            SwiftType::Custom(name) => name,
+            SwiftType::Nested(base, name) if matches!(base.as_ref(), SwiftType::Custom(base) if base == "Self") => name,
+            _ => return None,
+        };
+        self.parameters.iter().any(|parameter| parameter == name).then_some(name.as_str())
+    }
+    /// Matches a type written with parameters against a concrete type, binding the parameters it
+    /// reaches for the first time. Bindings made before a mismatch are kept.
+    pub fn unify(&mut self, pattern: &SwiftType, ty: &SwiftType) -> bool {
+        if let Some(name) = self.parameter(pattern) {
+            return match self.bindings.get(name) {
+                Some(bound) => bound == ty,
+                None => {
+                    self.bindings.insert(name.to_string(), ty.clone());
+                    true
+                }
+            };
+        }
+        match (pattern, ty) {
+            (SwiftType::Optional(pattern), SwiftType::Optional(ty))
+            | (SwiftType::ImplicitlyUnwrappedOptional(pattern), SwiftType::ImplicitlyUnwrappedOptional(ty))
+            | (SwiftType::Array(pattern), SwiftType::Array(ty))
+            | (SwiftType::Set(pattern), SwiftType::Set(ty))
+            | (SwiftType::Metatype(pattern), SwiftType::Metatype(ty))
+            | (SwiftType::ProtocolMetatype(pattern), SwiftType::ProtocolMetatype(ty)) => {
+                self.unify(pattern, ty)
+            }
+            (SwiftType::Generic(pattern_name, patterns), SwiftType::Generic(name, types)) => {
+                pattern_name == name && patterns.len() == types.len() && patterns.iter().zip(types).all(|(pattern, ty)| self.unify(pattern, ty))
+            }
+            (SwiftType::Dictionary(key_pattern, value_pattern), SwiftType::Dictionary(key, value)) => {
+                self.unify(key_pattern, key) && self.unify(value_pattern, value)
+            }
+            (SwiftType::Existential(pattern), SwiftType::Existential(ty)) | (SwiftType::Opaque(pattern), SwiftType::Opaque(ty)) => {
+                self.unify(pattern, ty)
+            }
+            (SwiftType::Tuple(patterns) | SwiftType::LabeledTuple(_, patterns), SwiftType::Tuple(types) | SwiftType::LabeledTuple(_, types))
+            | (SwiftType::Composition(patterns), SwiftType::Composition(types)) => {
+                patterns.len() == types.len() && patterns.iter().zip(types).all(|(pattern, ty)| self.unify(pattern, ty))
+            }
+            (
+                SwiftType::Function(pattern_parameters, pattern_result, pattern_attributes),
+                SwiftType::Function(parameters, result, attributes),
+            ) => {
+                pattern_attributes == attributes
+                    && pattern_parameters.len() == parameters.len()
+                    && pattern_parameters.iter().zip(parameters).all(|(pattern, ty)| self.unify(pattern, ty))
+                    && self.unify(pattern_result, result)
+            }
+            (pattern, ty) => pattern == ty,
+        }
+    }
+    /// True if a type mentions none of the parameters, nor an associated type of one.
+    pub fn is_concrete(&self, ty: &SwiftType) -> bool {
+        if self.parameter(ty).is_some() {
+            return false;
+        }
+        match ty {
+            SwiftType::Optional(inner)
+            | SwiftType::ImplicitlyUnwrappedOptional(inner)
+            | SwiftType::Array(inner)
+            | SwiftType::Set(inner)
+            | SwiftType::Metatype(inner)
+            | SwiftType::ProtocolMetatype(inner)
+            | SwiftType::Existential(inner)
+            | SwiftType::Opaque(inner) => {
+                self.is_concrete(inner)
+            }
+            SwiftType::Dictionary(key, value) => self.is_concrete(key) && self.is_concrete(value),
+            SwiftType::Tuple(elements)
+            | SwiftType::LabeledTuple(_, elements)
+            | SwiftType::Composition(elements)
+            | SwiftType::Generic(_, elements) => elements.iter().all(|element| self.is_concrete(element)),
+            SwiftType::Function(parameters, result, _) => {
+                parameters.iter().all(|parameter| self.is_concrete(parameter)) && self.is_concrete(result)
+            }
+            SwiftType::Nested(..) => false,
+            _ => true,
+        }
+    }
+    /// Replaces the bound parameters of a type.
+    pub fn apply(&self, ty: &SwiftType) -> SwiftType {
+        if let Some(bound) = self.parameter(ty).and_then(|name| self.bindings.get(name)) {
+            return bound.clone();
+        }
+        match ty {
+            SwiftType::Optional(wrapped) => SwiftType::Optional(Box::new(self.apply(wrapped))),
+            SwiftType::ImplicitlyUnwrappedOptional(wrapped) => SwiftType::ImplicitlyUnwrappedOptional(Box::new(self.apply(wrapped))),
+            SwiftType::Array(element) => SwiftType::Array(Box::new(self.apply(element))),
+            SwiftType::Set(element) => SwiftType::Set(Box::new(self.apply(element))),
+            SwiftType::Metatype(instance) => SwiftType::Metatype(Box::new(self.apply(instance))),
+            SwiftType::ProtocolMetatype(protocol) => SwiftType::ProtocolMetatype(Box::new(self.apply(protocol))),
+            SwiftType::Generic(name, arguments) => SwiftType::Generic(name.clone(), arguments.iter().map(|argument| self.apply(argument)).collect()),
+            SwiftType::Dictionary(key, value) => SwiftType::Dictionary(Box::new(self.apply(key)), Box::new(self.apply(value))),
+            SwiftType::Tuple(elements) => SwiftType::Tuple(elements.iter().map(|element| self.apply(element)).collect()),
+            SwiftType::LabeledTuple(labels, elements) => {
+                SwiftType::LabeledTuple(labels.clone(), elements.iter().map(|element| self.apply(element)).collect())
+            }
+            SwiftType::Function(parameters, result, attributes) => SwiftType::Function(
+                parameters.iter().map(|parameter| self.apply(parameter)).collect(),
+                Box::new(self.apply(result)),
+                *attributes,
+            ),
+            SwiftType::Nested(base, name) => SwiftType::Nested(Box::new(self.apply(base)), name.clone()),
+            SwiftType::Existential(constraint) => SwiftType::Existential(Box::new(self.apply(constraint))),
+            SwiftType::Opaque(constraint) => SwiftType::Opaque(Box::new(self.apply(constraint))),
+            SwiftType::Composition(types) => SwiftType::Composition(types.iter().map(|ty| self.apply(ty)).collect()),
+            ty => ty.clone(),
+        }
+    }
+}
+
+/// A member found by name lookup on a type.
+#[derive(Debug, Clone, Copy)]
+pub enum Member<'a> {
+    Property(&'a VariablePropertyDeclaration),
+    Method(&'a FunDeclaration),
+    Case(&'a EnumCase),
+}
+
+impl<'a> Member<'a> {
+    /// True for members accessed on the type rather than on an instance.
+    pub fn is_static(&self) -> bool {
+        match self {
+            Member::Property(property) => property.is_static || property.is_class,
+            Member::Method(method) => method.is_static || method.is_class,
+            Member::Case(_) => true,
+        }
+    }
+}
+
+/// Index of the top-level declarations of a program, used to resolve names
+/// during checking and code generation.
+#[derive(Debug, Default)]
+pub struct TypeTable<'a> {
+    types: HashMap<&'a str, NominalType<'a>>,
+    extensions: HashMap<&'a str, Vec<&'a ExtensionDeclaration>>,
+    functions: HashMap<&'a str, Vec<&'a FunDeclaration>>,
+    /// Properties whose types are being inferred from their initial values, as `(type, property)`.
+    inferring: RefCell<Vec<(String, String)>>,
+    /// Properties whose initial values refer back to themselves, so their types can't be inferred.
+    circular: RefCell<HashSet<(String, String)>>,
+}
+
+impl<'a> TypeTable<'a> {
+    pub fn new(program: &'a StatementSequence) -> Self {
+        let mut table = TypeTable::default();
+        for statement in program.statements() {
+            let Statement::Declaration(declaration) = statement else { continue };
+            match declaration.as_ref() {
+                Declaration::Struct(decl) => {
+                    table.types.insert(&decl.name, NominalType::Struct(decl));
+                }
+                Declaration::Enum(decl) => {
+                    table.types.insert(&decl.name, NominalType::Enum(decl));
+                }
+                Declaration::Class(decl) => {
+                    table.types.insert(&decl.name, NominalType::Class(decl));
+                }
+                Declaration::Protocol(decl) => {
+                    table.types.insert(&decl.name, NominalType::Protocol(decl));
+                }
+                Declaration::Extension(decl) => {
+                    table.extensions.entry(&decl.type_name).or_default().push(decl);
+                }
+                Declaration::Function(decl) => {
+                    table.functions.entry(&decl.name).or_default().push(decl);
+                }
+                _ => {}
+            }
+        }
+        table
+    }
+    pub fn nominal(&self, name: &str) -> Option<NominalType<'a>> {
+        self.types.get(name).copied()
+    }
+    pub fn extensions(&self, type_name: &str) -> &[&'a ExtensionDeclaration] {
+        self.extensions.get(type_name).map(Vec::as_slice).unwrap_or_default()
+    }
+    pub fn function(&self, name: &str) -> Option<&'a FunDeclaration> {
+        self.functions.get(name).and_then(|overloads| overloads.first().copied())
+    }
+    /// The superclass of a class; `None` as well for classes in an inheritance cycle, so that
+    /// lookups through superclasses terminate.
+    pub fn superclass(&self, type_name: &str) -> Option<&'a str> {
+        let superclass = self.declared_superclass(type_name)?;
+        (!self.is_cyclic(type_name)).then_some(superclass)
+    }
+    fn declared_superclass(&self, type_name: &str) -> Option<&'a str> {
+        match self.nominal(type_name)? {
+            NominalType::Class(decl) => decl.superclass.as_deref(),
+            _ => None,
+        }
+    }
+    /// True if following superclasses from `type_name` never reaches a root class.
+    pub fn is_cyclic(&self, type_name: &str) -> bool {
+        let mut current = self.declared_superclass(type_name);
+        for _ in 0..self.types.len() {
+            match current {
+                Some(name) => current = self.declared_superclass(name),
+                None => return false,
+            }
+        }
+        true
+    }
+    /// Looks up a member declared on the type, its extensions, or its superclasses, and failing
+    /// that, a default implementation from an extension of a protocol it conforms to.
+    pub fn member(&self, type_name: &str, name: &str) -> Option<Member<'a>> {
+        self.declared_or_inherited_member(type_name, name)
+            .or_else(|| self.default_member(type_name, name).map(|(_, member)| member))
+    }
+    /// The default implementation of a member that a type receives from an extension of a protocol
+    /// it conforms to, with that protocol. Extensions whose `where` clause the type fails don't apply.
+    fn default_member(&self, type_name: &str, name: &str) -> Option<(&'a str, Member<'a>)> {
+        self.conformances(type_name).into_iter().find_map(|protocol| {
+            let mut bindings = None;
+            self.extension_members(&protocol.name, name).find_map(|(extension, member)| {
+                if !extension.where_clause.is_empty() {
+                    let bindings = bindings.get_or_insert_with(|| self.protocol_bindings(type_name, protocol));
+                    if !self.unmet_requirements(&extension.where_clause, bindings).is_empty() {
+                        return None;
+                    }
+                }
+                Some((protocol.name.as_str(), member))
+            })
+        })
+    }
+    /// A default implementation a type would receive from a constrained protocol extension if it met
+    /// the extension's `where` clause, with the protocol and the requirements the type fails.
+    pub fn unavailable_default(&self, type_name: &str, name: &str) -> Option<(&'a str, Member<'a>, Vec<GenericRequirement>)> {
+        if self.member(type_name, name).is_some() {
+            return None;
+        }
+        self.conformances(type_name).into_iter().find_map(|protocol| {
+            let (extension, member) = self.extension_members(&protocol.name, name).next()?;
+            let unmet = self.unmet_requirements(&extension.where_clause, &self.protocol_bindings(type_name, protocol));
+            Some((protocol.name.as_str(), member, unmet))
+        })
+    }
+    /// A member that a generic type has only through extensions whose `where` clause its arguments
+    /// fail, with the requirements the first of them fails: `describe()` of `Box<Int>` when only
+    /// `extension Box: Describable where T: Describable` declares it.
+    pub fn unavailable_extension_member(&self, ty: &SwiftType, name: &str) -> Option<(Member<'a>, Vec<GenericRequirement>)> {
+        let SwiftType::Generic(type_name, arguments) = ty else { return None };
+        let nominal = self.nominal(type_name)?;
+        let declared = nominal.properties().iter().any(|property| property.name == name) || nominal.methods().iter().any(|method| method.name == name);
+        let substitution = Substitution::specializing(nominal.generics()?, arguments);
+        let mut unavailable = None;
+        for (extension, member) in self.extension_members(type_name, name) {
+            let unmet = self.unmet_requirements(&extension.where_clause, &substitution);
+            if unmet.is_empty() {
+                return None;
+            }
+            unavailable.get_or_insert((member, unmet));
+        }
+        unavailable.filter(|_| !declared)
+    }
+    /// The members named `name` of a type's extensions, with the extension declaring each.
+    fn extension_members<'s>(&'s self, type_name: &str, name: &'s str) -> impl Iterator<Item = (&'a ExtensionDeclaration, Member<'a>)> + 's {
+        self.extensions(type_name).iter().filter_map(move |extension| {
+            if let Some(property) = extension.properties.iter().find(|property| property.name == name) {
+                return Some((*extension, Member::Property(property)));
+            }
+            let method = extension.methods.iter().find(|method| method.name == name)?;
+            Some((*extension, Member::Method(method)))
+        })
+    }
+    fn declared_or_inherited_member(&self, type_name: &str, name: &str) -> Option<Member<'a>> {
+        if let Some(nominal) = self.nominal(type_name) {
+            if let Some(member) = Self::declared_member(nominal, name) {
+                return Some(member);
+            }
+        }
+        for extension in self.extensions(type_name) {
+            if let Some(property) = extension.properties.iter().find(|property| property.name == name) {
+                return Some(Member::Property(property));
+            }
+            if let Some(method) = extension.methods.iter().find(|method| method.name == name) {
+                return Some(Member::Method(method));
+            }
+        }
+        self.superclass(type_name).and_then(|superclass| self.declared_or_inherited_member(superclass, name))
+    }
+    /// The type (or extended type) that declares the member, looking through superclasses, or the
+    /// protocol whose extension provides it.
+    pub fn member_owner(&self, type_name: &str, name: &str) -> Option<&'a str> {
+        self.declared_or_inherited_owner(type_name, name)
+            .or_else(|| self.default_member(type_name, name).map(|(protocol, _)| protocol))
+    }
+    fn declared_or_inherited_owner(&self, type_name: &str, name: &str) -> Option<&'a str> {
+        if let Some(nominal) = self.nominal(type_name) {
+            if Self::declared_member(nominal, name).is_some() {
+                return Some(nominal.name());
+            }
+        }
+        let extension = self.extensions(type_name).iter().find(|extension| {
+            extension.properties.iter().any(|property| property.name == name)
+                || extension.methods.iter().any(|method| method.name == name)
+        });
+        if let Some(extension) = extension {
+            return Some(&extension.type_name);
+        }
+        self.declared_or_inherited_owner(self.superclass(type_name)?, name)
+    }
+    /// For a member reached through a value of static type `type_name`, the protocol whose extension
+    /// provides it when it isn't a requirement of any protocol the type conforms to. Such members
+    /// dispatch statically: the extension's implementation runs even if the dynamic type has its own.
+    pub fn statically_dispatched(&self, type_name: &str, name: &str) -> Option<&'a str> {
+        let owner = self.member_owner(type_name, name)?;
+        let Some(NominalType::Protocol(_)) = self.nominal(owner) else { return None };
+        let protocols = match self.nominal(type_name) {
+            Some(NominalType::Protocol(_)) => self.protocols(type_name),
+            _ => self.conformances(type_name),
+        };
+        let is_requirement = protocols.iter().any(|protocol| {
+            protocol.property_requirements.iter().any(|requirement| requirement.name == name)
+                || protocol.method_requirements.iter().any(|requirement| requirement.name == name)
+        });
+        (!is_requirement).then_some(owner)
+    }
+    fn declared_member(nominal: NominalType<'a>, name: &str) -> Option<Member<'a>> {
+        if let NominalType::Enum(decl) = nominal {
+            if let Some(case) = decl.cases.iter().find(|case| case.name == name) {
+                return Some(Member::Case(case));
+            }
+        }
+        if let Some(property) = nominal.properties().iter().find(|property| property.name == name) {
+            return Some(Member::Property(property));
+        }
+        nominal.methods().iter().find(|method| method.name == name).map(Member::Method)
+    }
+    /// The superclass method that a method of `type_name` with the same name and labels overrides.
+    pub fn overridden_method(&self, type_name: &str, method: &FunDeclaration) -> Option<&'a FunDeclaration> {
+        let is_type_method = method.is_static || method.is_class;
+        let mut ancestor = self.superclass(type_name);
+        while let Some(name) = ancestor {
+            let found = self
+                .nominal(name)
+                .into_iter()
+                .flat_map(|nominal| nominal.methods())
+                .chain(self.extensions(name).iter().flat_map(|extension| extension.methods.iter()))
+                .find(|candidate| {
+                    candidate.name == method.name
+                        && (candidate.is_static || candidate.is_class) == is_type_method
+                        && same_labels(&candidate.parameters, &method.parameters)
+                });
+            if found.is_some() {
+                return found;
+            }
+            ancestor = self.superclass(name);
+        }
+        None
+    }
+    /// The superclass property that a property of `type_name` with this name overrides.
+    pub fn overridden_property(&self, type_name: &str, name: &str) -> Option<&'a VariablePropertyDeclaration> {
+        match self.member(self.superclass(type_name)?, name)? {
+            Member::Property(property) => Some(property),
+            _ => None,
+        }
+    }
+    /// The designated superclass initializer that an initializer with these parameters overrides.
+    pub fn overridden_initializer(&self, type_name: &str, parameters: &[FunctionParameter]) -> Option<&'a InitializerDeclaration> {
+        self.initializers(self.superclass(type_name)?)
+            .into_iter()
+            .find(|initializer| !initializer.is_convenience && same_labels(&initializer.parameters, parameters))
+    }
+    /// True if a subclass overrides the property of `type_name`, which must then be reachable
+    /// through `super`: in JS, an instance field would shadow the overriding accessors.
+    pub fn is_overridden(&self, type_name: &str, name: &str) -> bool {
+        self.types.values().any(|nominal| match nominal {
+            NominalType::Class(decl) => {
+                decl.properties.iter().any(|property| property.name == name && property.is_override)
+                    && self.is_subclass(&decl.name, type_name)
+            }
+            _ => false,
+        })
+    }
+    /// True if `type_name` inherits, directly or not, from `ancestor`.
+    pub fn is_subclass(&self, type_name: &str, ancestor: &str) -> bool {
+        let mut current = self.superclass(type_name);
+        while let Some(name) = current {
+            if name == ancestor {
+                return true;
+            }
+            current = self.superclass(name);
+        }
+        false
+    }
+    /// Every initializer a type can be constructed with: its own, those of its extensions, and for
+    /// classes the ones inherited from the superclass. A subclass that declares no designated
+    /// initializer (and needs none, all its stored properties having defaults) inherits the designated
+    /// ones; once it provides all of those, it also inherits the convenience initializers.
+    pub fn initializers(&self, type_name: &str) -> Vec<&'a InitializerDeclaration> {
+        let Some(nominal) = self.nominal(type_name) else { return Vec::new() };
+        let mut initializers = nominal
+            .initializers()
+            .iter()
+            .chain(self.extensions(type_name).iter().flat_map(|extension| extension.initializers.iter()))
+            .collect::<Vec<_>>();
+        let Some(superclass) = self.superclass(type_name) else { return initializers };
+        let inherited = self.initializers(superclass);
+        let overrides = |initializers: &[&InitializerDeclaration], inherited: &InitializerDeclaration| {
+            initializers.iter().any(|initializer| same_labels(&initializer.parameters, &inherited.parameters))
+        };
+        let declares_designated = initializers.iter().any(|initializer| !initializer.is_convenience);
+        let needs_designated = nominal.properties().iter().any(|property| property.requires_initialization());
+        if !declares_designated && !needs_designated {
+            for initializer in inherited.iter().filter(|initializer| !initializer.is_convenience) {
+                if !overrides(&initializers, initializer) {
+                    initializers.push(initializer);
+                }
+            }
+        }
+        let provides_all = inherited
+            .iter()
+            .filter(|initializer| !initializer.is_convenience)
+            .all(|initializer| overrides(&initializers, initializer));
+        if provides_all {
+            for initializer in inherited.iter().filter(|initializer| initializer.is_convenience) {
+                if !overrides(&initializers, initializer) {
+                    initializers.push(initializer);
+                }
+            }
+        }
+        initializers
+    }
+    /// Finds the initializer of `type_name` matching the labels used at a call site.
+    /// Parameters with default values may be omitted by the caller.
+    pub fn initializer(&self, type_name: &str, labels: &[Option<&str>]) -> Option<&'a InitializerDeclaration> {
+        self.initializers(type_name)
+            .into_iter()
+            .find(|initializer| parameters_accept_labels(&initializer.parameters, labels))
+    }
+    /// The labels of a call constructing `type_name`, with its trailing closures labeled by the
+    /// parameters they fill in the initializer that accepts them.
+    pub fn construction_labels<'c>(&self, type_name: &str, call: &'c CallExpression) -> Vec<Option<&'c str>>
+    where
+        'a: 'c,
+    {
+        let initializer = self.initializers(type_name).into_iter().find_map(|initializer| {
+            let labels = call_labels(&initializer.parameters, &call.arguments, &call.trailing_closures);
+            parameters_accept_labels(&initializer.parameters, &labels).then_some(labels)
+        });
+        initializer.unwrap_or_else(|| call_labels(&[], &call.arguments, &call.trailing_closures))
+    }
+    /// The type of `T(...)`: optional when the initializer is failable, as is `init(rawValue:)`.
+    pub fn construction_type(&self, type_name: &str, labels: &[Option<&str>]) -> SwiftType {
+        let ty = SwiftType::Custom(type_name.to_string());
+        let is_failable = match self.initializer(type_name, labels) {
+            Some(initializer) => initializer.is_failable,
+            None => matches!(self.nominal(type_name), Some(NominalType::Enum(decl)) if decl.raw_type.is_some())
+                && labels == [Some("rawValue")],
+        };
+        if is_failable {
+            SwiftType::Optional(Box::new(ty))
+        } else {
+            ty
+        }
+    }
+    /// True when values of this type must be copied when stored into a new variable.
+    pub fn is_value_type(&self, ty: &SwiftType) -> bool {
+        match ty {
+            SwiftType::Array(_) | SwiftType::Dictionary(_, _) | SwiftType::Tuple(_) | SwiftType::LabeledTuple(..) => true,
+            SwiftType::Optional(wrapped) => self.is_value_type(wrapped),
+            SwiftType::Custom(name) => self.nominal(name).map(|nominal| nominal.is_value_type()).unwrap_or(false),
+            _ => false,
+        }
+    }
+    /// True when values of this type keep class instances alive: class types, and value types
+    /// that may contain them.
+    pub fn holds_references(&self, ty: &SwiftType) -> bool {
+        self.holds_references_within(ty, &mut Vec::new())
+    }
+    fn holds_references_within(&self, ty: &SwiftType, visiting: &mut Vec<String>) -> bool {
+        match ty {
+            SwiftType::Optional(wrapped)
+            | SwiftType::ImplicitlyUnwrappedOptional(wrapped)
+            | SwiftType::Array(wrapped)
+            | SwiftType::Set(wrapped) => self.holds_references_within(wrapped, visiting),
+            SwiftType::AnyObject => true,
+            SwiftType::Generic(name, _) => self.holds_references_within(&SwiftType::Custom(name.clone()), visiting),
+            // Keys are hashed by value and never owned.
+            SwiftType::Dictionary(_, value) => self.holds_references_within(value, visiting),
+            SwiftType::Tuple(elements) | SwiftType::LabeledTuple(_, elements) => {
+                elements.iter().any(|element| self.holds_references_within(element, visiting))
+            }
+            SwiftType::Custom(name) if visiting.contains(name) => false,
+            SwiftType::Custom(name) => {
+                visiting.push(name.clone());
+                let holds_references = match self.nominal(name) {
+                    Some(NominalType::Class(_)) => true,
+                    Some(NominalType::Struct(decl)) => decl
+                        .properties
+                        .iter()
+                        .filter(|property| !property.is_static && property.has_storage())
+                        .filter_map(|property| self.member_type(name, &property.name))
+                        .any(|ty| self.holds_references_within(&ty, visiting)),
+                    Some(NominalType::Enum(decl)) => decl
+                        .cases
+                        .iter()
+                        .flat_map(|case| &case.associated_values)
+                        .any(|value| self.holds_references_within(&value.ty, visiting)),
+                    _ => false,
+                };
+                visiting.pop();
+                holds_references
+            }
+            _ => false,
+        }
+    }
+    /// The protocols a type conforms to: those its declaration, its extensions and its superclasses
+    /// list, and the protocols they inherit from. Only protocols declared in the program are included.
+    pub fn conformances(&self, type_name: &str) -> Vec<&'a ProtocolDeclaration> {
+        let mut listed = self.nominal(type_name).map(|nominal| nominal.conformances()).unwrap_or_default().iter().collect::<Vec<_>>();
+        listed.extend(self.extensions(type_name).iter().flat_map(|extension| &extension.conformances));
+        let mut protocols = Vec::new();
+        for name in listed {
+            self.collect_protocols(name, &mut protocols);
+        }
+        if let Some(superclass) = self.superclass(type_name) {
+            for protocol in self.conformances(superclass) {
+                if !protocols.iter().any(|known: &&ProtocolDeclaration| known.name == protocol.name) {
+                    protocols.push(protocol);
+                }
+            }
+        }
+        protocols
+    }
+    /// The standard library protocols a type conforms to, as `conformances` finds those of the
+    /// program: `Hashable` and the `Equatable` it inherits for a type listing `Hashable`.
+    pub fn standard_conformances(&self, type_name: &str) -> Vec<&'static StandardProtocol> {
+        let mut listed = self.nominal(type_name).map(|nominal| nominal.conformances()).unwrap_or_default().iter().collect::<Vec<_>>();
+        listed.extend(self.extensions(type_name).iter().flat_map(|extension| &extension.conformances));
+        let mut protocols = listed.into_iter().flat_map(|name| stdlib::protocols(name)).collect::<Vec<_>>();
+        if let Some(superclass) = self.superclass(type_name) {
+            protocols.extend(self.standard_conformances(superclass));
+        }
+        let mut unique = Vec::new();
+        for protocol in protocols {
+            if !unique.iter().any(|known: &&StandardProtocol| known.name == protocol.name) {
+                unique.push(protocol);
+            }
+        }
+        unique
+    }
+    /// The protocol named, followed by the protocols it inherits from.
+    pub fn protocols(&self, name: &str) -> Vec<&'a ProtocolDeclaration> {
+        let mut protocols = Vec::new();
+        self.collect_protocols(name, &mut protocols);
+        protocols
+    }
+    fn collect_protocols(&self, name: &str, protocols: &mut Vec<&'a ProtocolDeclaration>) {
+        let Some(NominalType::Protocol(decl)) = self.nominal(name) else { return };
+        if protocols.iter().any(|known| known.name == decl.name) {
+            return;
+        }
+        protocols.push(decl);
+        for inherited in &decl.inherited_protocols {
+            self.collect_protocols(inherited, protocols);
+        }
+    }
+    /// True if the type conforms to the protocol, directly or through inheritance.
+    pub fn conforms_to(&self, type_name: &str, protocol: &str) -> bool {
+        self.conformances(type_name).iter().any(|decl| decl.name == protocol)
+    }
+    /// Resolves the witness of every requirement of `protocol` for `type_name`. Members of the type,
+    /// its extensions and superclasses come first, then default implementations from extensions of
+    /// the protocols it conforms to.
+    pub fn conformance(&self, type_name: &'a str, protocol: &'a ProtocolDeclaration) -> Conformance<'a> {
+        let requirements = protocol.requirements().into_iter();
+        let mut substitution = self.protocol_bindings(type_name, protocol);
+        let witnesses = requirements
+            .map(|requirement| (requirement, self.witness(type_name, requirement, &mut substitution)))
+            .collect();
+        let associated_types = protocol
+            .associated_types
+            .iter()
+            .map(|associated| (associated, substitution.get(&associated.name).cloned()))
+            .collect();
+        Conformance { type_name, protocol, associated_types, witnesses }
+    }
+    /// Binds `Self` and the associated types of a protocol and those it inherits for a conforming
+    /// type: a generic parameter of the same name, then the types of the witnesses of requirements
+    /// that mention them, then their defaults. The type's generic parameters are left open.
+    pub fn protocol_bindings(&self, type_name: &str, protocol: &ProtocolDeclaration) -> Substitution {
+        let protocols = self.protocols(&protocol.name);
+        let associated = protocols.iter().flat_map(|protocol| &protocol.associated_types).collect::<Vec<_>>();
+        let mut substitution = Substitution::new(associated.iter().map(|associated| associated.name.clone()));
+        substitution.parameters.push("Self".to_string());
+        substitution.bind("Self", SwiftType::Custom(type_name.to_string()));
+        let generics = self.nominal(type_name).and_then(|nominal| nominal.generics());
+        for parameter in generics.iter().flat_map(|generics| &generics.type_parameters) {
+            if associated.iter().any(|associated| associated.name == parameter.name) {
+                substitution.bind(&parameter.name, SwiftType::Custom(parameter.name.clone()));
+            } else {
+                substitution.parameters.push(parameter.name.clone());
+            }
+        }
+        for protocol in &protocols {
+            for requirement in protocol.requirements() {
+                self.witness(type_name, requirement, &mut substitution);
+            }
+        }
+        for associated in associated {
+            if let (None, Some(default)) = (substitution.get(&associated.name), &associated.default) {
+                let ty = substitution.apply(default);
+                substitution.bind(&associated.name, ty);
+            }
+        }
+        substitution
+    }
+    /// Finds the member satisfying a requirement, binding the associated types its type determines.
+    fn witness(&self, type_name: &str, requirement: Requirement<'a>, substitution: &mut Substitution) -> Option<Witness<'a>> {
+        let is_value_type = self.nominal(type_name).is_some_and(|nominal| nominal.is_value_type());
+        let lineage = self.lineage(type_name);
+        // Default implementations come from the protocol extensions whose `where` clause holds for
+        // the associated types bound so far, and are written in terms of the protocol's own types.
+        let defaults = self
+            .conformances(type_name)
+            .into_iter()
+            .flat_map(|protocol| self.extensions(&protocol.name))
+            .filter(|extension| self.unmet_requirements(&extension.where_clause, substitution).is_empty())
+            .collect::<Vec<_>>();
+        let mut matches = |is_concrete: bool, pairs: &[(&SwiftType, SwiftType)]| {
+            if !is_concrete {
+                return true;
+            }
+            let mut trial = substitution.clone();
+            let matched = pairs.iter().all(|(pattern, ty)| trial.unify(pattern, ty));
+            if matched {
+                *substitution = trial;
+            }
+            matched
+        };
+        let void = SwiftType::Tuple(Vec::new());
+        match requirement {
+            Requirement::Property(required) => lineage
+                .iter()
+                .flat_map(|provider| self.declared_properties(provider).map(|property| (property, true)))
+                .chain(defaults.iter().flat_map(|extension| extension.properties.iter().map(|property| (property, false))))
+                .find(|(property, is_concrete)| {
+                    property.name == required.name
+                        && !(property.is_static || property.is_class)
+                        && (required.is_read_only || property.is_settable())
+                        && match self.member_type(type_name, &property.name).filter(|_| *is_concrete) {
+                            Some(ty) => matches(true, &[(&required.ty, ty)]),
+                            None => true,
+                        }
+                })
+                .map(|(property, _)| Witness::Property(property)),
+            Requirement::Method(required) => lineage
+                .iter()
+                .flat_map(|provider| self.declared_methods(provider).map(|method| (method, true)))
+                .chain(defaults.iter().flat_map(|extension| extension.methods.iter().map(|method| (method, false))))
+                .find(|(method, is_concrete)| {
+                    let mut pairs = required
+                        .parameters
+                        .iter()
+                        .zip(&method.parameters)
+                        .map(|(required, parameter)| (&required.ty, parameter.ty.clone()))
+                        .collect::<Vec<_>>();
+                    pairs.push((required.return_type.as_ref().unwrap_or(&void), method.return_type.clone().unwrap_or(void.clone())));
+                    method.name == required.name
+                        && !(method.is_static || method.is_class)
+                        && (required.is_mutating || !method.is_mutating || !is_value_type)
+                        && same_labels(&method.parameters, &required.parameters)
+                        && method.parameters.iter().zip(&required.parameters).all(|(left, right)| left.is_inout == right.is_inout)
+                        && matches(*is_concrete, &pairs)
+                })
+                .map(|(method, _)| Witness::Method(method)),
+            Requirement::Initializer(required) => self
+                .initializers(type_name)
+                .into_iter()
+                .find(|initializer| {
+                    let pairs = required
+                        .parameters
+                        .iter()
+                        .zip(&initializer.parameters)
+                        .map(|(required, parameter)| (&required.ty, parameter.ty.clone()))
+                        .collect::<Vec<_>>();
+                    same_labels(&initializer.parameters, &required.parameters) && matches(true, &pairs)
+                })
+                .map(Witness::Initializer),
+        }
+    }
+    /// The property, method or initializer of a type, or one it inherits, witnessing a standard library
+    /// protocol's requirement. Operators have no argument labels, whatever their parameters are named.
+    pub fn standard_witness(&self, type_name: &str, requirement: &str) -> Option<Witness<'a>> {
+        if requirement.starts_with("init(") {
+            let initializer = self.initializers(type_name).into_iter().find(|initializer| signature("init", labels(&initializer.parameters)) == requirement);
+            return initializer.map(Witness::Initializer);
+        }
+        if !requirement.contains('(') {
+            let property = self.lineage(type_name).into_iter().flat_map(|provider| self.declared_properties(provider)).find(|property| {
+                property.name == requirement && !property.is_static
+            });
+            return property.map(Witness::Property);
+        }
+        let method = self.lineage(type_name).into_iter().flat_map(|provider| self.declared_methods(provider)).find(|method| {
+            let is_operator = !method.name.starts_with(|c: char| c.is_alphabetic() || c == '_');
+            let method_signature = match is_operator {
+                true => signature(&method.name, method.parameters.iter().map(|_| None)),
+                false => signature(&method.name, labels(&method.parameters)),
+            };
+            method_signature == requirement
+        });
+        method.map(Witness::Method)
+    }
+    /// The names a pattern binds, with their types as far as the type of the value it matches
+    /// determines them: `case .upc(let a, let b)` binds the types of the associated values.
+    pub fn pattern_bindings<'p>(&self, pattern: &'p Pattern, subject: Option<&SwiftType>) -> Vec<(&'p str, Option<SwiftType>)> {
+        let mut bindings = Vec::new();
+        self.collect_pattern_bindings(pattern, subject, &mut bindings);
+        bindings
+    }
+    fn collect_pattern_bindings<'p>(&self, pattern: &'p Pattern, subject: Option<&SwiftType>, bindings: &mut Vec<(&'p str, Option<SwiftType>)>) {
+        match pattern {
+            Pattern::Identifier(identifier) => bindings.push((&identifier.name, subject.cloned())),
+            Pattern::Tuple(tuple) => {
+                for (index, element) in tuple.elements.iter().enumerate() {
+                    let ty = match subject {
+                        Some(SwiftType::Tuple(types) | SwiftType::LabeledTuple(_, types)) => types.get(index),
+                        _ => None,
+                    };
+                    self.collect_pattern_bindings(element, ty, bindings);
+                }
+            }
+            Pattern::EnumCase(case) => {
+                let enum_name = case.enum_name.as_deref().or_else(|| subject.and_then(nominal_name));
+                // The associated values of generic enums are written with the enum's parameters.
+                let declared = match enum_name.and_then(|name| self.nominal(name)) {
+                    Some(NominalType::Enum(decl)) if decl.generics.is_none() => decl.cases.iter().find(|declared| declared.name == case.case_name),
+                    _ => None,
+                };
+                for (index, value) in case.associated_values.iter().enumerate() {
+                    let ty = declared.and_then(|declared| declared.associated_values.get(index)).map(|value| &value.ty);
+                    self.collect_pattern_bindings(value, ty, bindings);
+                }
+            }
+            Pattern::Literal(_) | Pattern::Wildcard | Pattern::TypePattern(_) => {}
+        }
+    }
+    /// The associated type `name` of a type through its conformances, e.g. `Int` for
+    /// `IntStack.Item`. `None` if the type conforms to no protocol declaring it.
+    pub fn associated_type(&self, type_name: &str, name: &str) -> Option<SwiftType> {
+        let protocol = self.conformances(type_name).into_iter().find(|protocol| {
+            protocol.associated_types.iter().any(|associated| associated.name == name)
+        })?;
+        self.protocol_bindings(type_name, protocol).get(name).cloned()
+    }
+    /// Looks up an associated type declared by a protocol or one it inherits.
+    pub fn associated_type_declaration(&self, protocol: &str, name: &str) -> Option<&'a AssociatedTypeDeclaration> {
+        self.protocols(protocol)
+            .into_iter()
+            .find_map(|protocol| protocol.associated_types.iter().find(|associated| associated.name == name))
+    }
+    /// Binds the generic parameters of a function or initializer from the types of the arguments of a
+    /// call, as far as they are known.
+    pub fn generic_arguments(
+        &self,
+        generics: &GenericsDeclaration,
+        parameters: &[FunctionParameter],
+        labels: &[Option<&str>],
+        arguments: &[Option<SwiftType>],
+    ) -> Substitution {
+        let mut substitution = Substitution::new(generics.type_parameters.iter().map(|parameter| parameter.name.clone()));
+        let mut arguments = labels.iter().zip(arguments).peekable();
+        for parameter in parameters {
+            let Some((_, argument)) = arguments.next_if(|(label, _)| **label == parameter.label.as_deref()) else { continue };
+            if let Some(argument) = argument {
+                let mut trial = substitution.clone();
+                if trial.unify(&parameter.ty, argument) {
+                    substitution = trial;
+                }
+            }
+        }
+        substitution
+    }
+    /// Resolves `T.Name` once `T` is a concrete type, through that type's conformances.
+    pub fn resolve_nested(&self, ty: &SwiftType) -> SwiftType {
+        match ty {
+            SwiftType::Nested(base, name) => {
+                let base = self.resolve_nested(base);
+                nominal_name(&base)
+                    .and_then(|type_name| self.associated_type(type_name, name))
+                    .unwrap_or_else(|| SwiftType::Nested(Box::new(base), name.clone()))
+            }
+            ty => ty.clone(),
+        }
+    }
+    /// The requirements a generic declaration places on its parameters, as `where` clause entries:
+    /// the constraints written after each parameter, then the `where` clause itself.
+    pub fn generic_requirements(generics: &GenericsDeclaration) -> Vec<GenericRequirement> {
+        let mut requirements = Vec::new();
+        for parameter in &generics.type_parameters {
+            for constraint in &parameter.constraints {
+                requirements.push(GenericRequirement::Conformance {
+                    subject: SwiftType::Custom(parameter.name.clone()),
+                    constraint: constraint.clone(),
+                });
+            }
+        }
+        requirements.extend(generics.where_clause.iter().cloned());
+        requirements
+    }
+    /// The requirements of a `where` clause that the bindings show to be unmet, with the bound types
+    /// substituted. Requirements on types still open, or on protocols of the standard library, count
+    /// as met.
+    pub fn unmet_requirements(&self, where_clause: &[GenericRequirement], substitution: &Substitution) -> Vec<GenericRequirement> {
+        let resolve = |ty: &SwiftType| self.resolve_nested(&substitution.apply(ty));
+        where_clause
+            .iter()
+            .filter_map(|requirement| match requirement {
+                GenericRequirement::Conformance { subject, constraint } => {
+                    let subject = resolve(subject);
+                    let is_unmet = substitution.is_concrete(&subject) && self.satisfies(&subject, constraint) == Some(false);
+                    is_unmet.then(|| GenericRequirement::Conformance { subject, constraint: constraint.clone() })
+                }
+                GenericRequirement::SameType { left, right } => {
+                    let (left, right) = (resolve(left), resolve(right));
+                    let is_unmet = substitution.is_concrete(&left) && substitution.is_concrete(&right) && left != right;
+                    is_unmet.then_some(GenericRequirement::SameType { left, right })
+                }
+            })
+            .collect()
+    }
+    /// The generic parameters of a function whose type metadata its body needs at run time. Calls
+    /// pass it as hidden leading arguments, one per parameter, in this order.
+    pub fn function_metadata(&self, function: &'a FunDeclaration) -> Vec<String> {
+        self.function_metadata_within(function, &mut Vec::new())
+    }
+    fn function_metadata_within(&self, function: &'a FunDeclaration, visiting: &mut Vec<usize>) -> Vec<String> {
+        let (Some(generics), Some(body)) = (&function.generics, &function.body) else { return Vec::new() };
+        let key = function as *const FunDeclaration as usize;
+        self.metadata_uses(generics, &[body.statements()], &[], key, visiting)
+    }
+    /// The generic parameters of a struct or class whose type metadata its members need at run
+    /// time. Its initializers take it as hidden leading arguments and store it on the instance.
+    pub fn type_metadata(&self, type_name: &str) -> Vec<String> {
+        self.type_metadata_within(type_name, &mut Vec::new())
+    }
+    fn type_metadata_within(&self, type_name: &str, visiting: &mut Vec<usize>) -> Vec<String> {
+        let Some(nominal @ (NominalType::Struct(_) | NominalType::Class(_))) = self.nominal(type_name) else { return Vec::new() };
+        let Some(generics) = nominal.generics() else { return Vec::new() };
+        let extensions = self.extensions(type_name);
+        let properties = nominal.properties().iter().chain(extensions.iter().flat_map(|extension| extension.properties.iter()));
+        let mut statements = Vec::new();
+        let mut expressions = Vec::new();
+        for property in properties.filter(|property| !property.is_static) {
+            match &property.kind {
+                PropertyKind::Stored(stored) => {
+                    expressions.extend(&stored.initial_value);
+                    for observer in [&stored.will_set, &stored.did_set].into_iter().flatten() {
+                        statements.push(observer.body.statements());
+                    }
+                }
+                PropertyKind::Computed(computed) => {
+                    for accessor in [Some(&computed.getter), computed.setter.as_ref()].into_iter().flatten() {
+                        statements.extend(accessor.body.as_ref().map(StatementSequence::statements));
+                    }
+                }
+            }
+        }
+        let methods = nominal.methods().iter().chain(extensions.iter().flat_map(|extension| extension.methods.iter()));
+        for method in methods.filter(|method| !(method.is_static || method.is_class)) {
+            statements.extend(method.body.as_ref().map(StatementSequence::statements));
+        }
+        let initializers = nominal.initializers().iter().chain(extensions.iter().flat_map(|extension| extension.initializers.iter()));
+        statements.extend(initializers.map(|initializer| initializer.body.statements()));
+        let key = generics as *const GenericsDeclaration as usize;
+        self.metadata_uses(generics, &statements, &expressions, key, visiting)
+    }
+    /// The generic parameters whose metadata code needs, since generic values are otherwise erased:
+    /// those it names as values (`T()`, `T.self`, `T.zero`) or casts to (`as? T`, `is T`), and all
+    /// of them where it calls a generic function or constructs a generic type that needs its own,
+    /// which may be bound to them.
+    fn metadata_uses(
+        &self,
+        generics: &GenericsDeclaration,
+        statements: &[&'a [Statement]],
+        expressions: &[&'a Expression],
+        key: usize,
+        visiting: &mut Vec<usize>,
+    ) -> Vec<String> {
+        if visiting.contains(&key) {
+            return Vec::new();
+        }
+        visiting.push(key);
+        let parameters = &generics.type_parameters;
+        let mut used = vec![false; parameters.len()];
+        let mut visit = |node: Node<'a>| {
+            let named = match node {
+                Node::Expression(Expression::Identifier(identifier)) => Some(identifier.name.as_str()),
+                Node::Expression(Expression::TypeCasting(cast)) => match &cast.target_type {
+                    SwiftType::Custom(name) => Some(name.as_str()),
+                    _ => None,
+                },
+                Node::Pattern(Pattern::TypePattern(pattern)) => match &pattern.ty {
+                    SwiftType::Custom(name) => Some(name.as_str()),
+                    _ => None,
+                },
+                Node::Expression(Expression::CallExpression(call)) => {
+                    let Expression::Identifier(callee) = call.callee.as_ref() else { return };
+                    let passes_metadata = match (self.function(&callee.name), self.nominal(&callee.name)) {
+                        (_, Some(_)) => !self.type_metadata_within(&callee.name, visiting).is_empty(),
+                        (Some(function), None) => !self.function_metadata_within(function, visiting).is_empty(),
+                        (None, None) => false,
+                    };
+                    if passes_metadata {
+                        used.iter_mut().for_each(|used| *used = true);
+                    }
+                    None
+                }
+                _ => None,
+            };
+            if let Some(position) = named.and_then(|name| parameters.iter().position(|parameter| parameter.name == name)) {
+                used[position] = true;
+            }
+        };
+        for statements in statements {
+            visit_statements(statements, &mut visit);
+        }
+        for expression in expressions {
+            visit_expression(expression, &mut visit);
+        }
+        visiting.pop();
+        parameters.iter().zip(used).filter(|(_, used)| *used).map(|(parameter, _)| parameter.name.clone()).collect()
+    }
+    /// True if a concrete type satisfies a conformance constraint: it conforms to the protocol, or
+    /// is or inherits from the class. `None` when this can't be decided, as for the protocols of
+    /// the standard library.
+    pub fn satisfies(&self, ty: &SwiftType, constraint: &SwiftType) -> Option<bool> {
+        match (ty, constraint) {
+            (_, SwiftType::Composition(constraints)) => {
+                let results = constraints.iter().map(|constraint| self.satisfies(ty, constraint)).collect::<Option<Vec<_>>>()?;
+                return Some(results.into_iter().all(|satisfied| satisfied));
+            }
+            // An existential opens to its dynamic type, which meets its own constraints.
+            (SwiftType::Existential(inner) | SwiftType::Opaque(inner), _) => return self.satisfies(inner, constraint),
+            (SwiftType::Composition(types), _) => {
+                return Some(types.iter().any(|ty| self.satisfies(ty, constraint) == Some(true)));
+            }
+            _ => {}
+        }
+        let SwiftType::Custom(constraint) = constraint else { return None };
+        let type_name = nominal_name(ty)?;
+        match self.nominal(constraint)? {
+            NominalType::Protocol(_) => Some(self.conforms_to(type_name, constraint) || type_name == constraint),
+            NominalType::Class(_) => Some(type_name == constraint || self.is_subclass(type_name, constraint)),
+            _ => Some(false),
+        }
+    }
+    /// The type followed by its superclasses.
+    fn lineage<'t>(&self, type_name: &'t str) -> Vec<&'t str>
+    where
+        'a: 't,
+    {
+        let mut lineage = vec![type_name];
+        let mut current = self.superclass(type_name);
+        while let Some(name) = current {
+            lineage.push(name);
+            current = self.superclass(name);
+        }
+        lineage
+    }
+    /// Properties declared by a type (or protocol) and its extensions, without inherited ones.
+    fn declared_properties(&self, type_name: &str) -> impl Iterator<Item = &'a VariablePropertyDeclaration> + '_ {
+        let nominal = self.nominal(type_name).map(|nominal| nominal.properties()).unwrap_or_default();
+        nominal.iter().chain(self.extensions(type_name).iter().flat_map(|extension| extension.properties.iter()))
+    }
+    /// Methods declared by a type (or protocol) and its extensions, without inherited ones.
+    fn declared_methods(&self, type_name: &str) -> impl Iterator<Item = &'a FunDeclaration> + '_ {
+        let nominal = self.nominal(type_name).map(|nominal| nominal.methods()).unwrap_or_default();
+        nominal.iter().chain(self.extensions(type_name).iter().flat_map(|extension| extension.methods.iter()))
+    }
+    /// True for class types, `AnyObject`, and protocols only classes can adopt.
+    pub fn is_class_bound(&self, ty: &SwiftType) -> bool {
+        let SwiftType::Custom(name) = ty else { return false };
+        match self.nominal(name) {
+            Some(NominalType::Class(_)) => true,
+            Some(NominalType::Protocol(decl)) => decl.inherited_protocols.iter().any(|inherited| {
+                inherited == "class" || self.is_class_bound(&SwiftType::Custom(inherited.clone()))
+            }),
+            _ => name == "AnyObject",
+        }
+    }
+    /// True if some class of the program declares a `deinit`.
+    pub fn has_deinitializers(&self) -> bool {
+        self.types.values().any(|nominal| matches!(nominal, NominalType::Class(decl) if decl.deinitializer.is_some()))
+    }
+    /// True if the class or one of its superclasses declares a `deinit`.
+    pub fn has_deinitializer(&self, type_name: &str) -> bool {
+        let mut current = Some(type_name);
+        while let Some(name) = current {
+            if matches!(self.nominal(name), Some(NominalType::Class(decl)) if decl.deinitializer.is_some()) {
+                return true;
+            }
+            current = self.superclass(name);
+        }
+        false
+    }
+    /// The types a class gives the generic parameters of one of its superclasses, through the
+    /// superclass declarations in between: `Element` is `Int` for `class IntStack: Stack<Int>`.
+    pub fn superclass_substitution(&self, type_name: &str, ancestor: &str) -> Substitution {
+        let mut substitution = Substitution::default();
+        let mut current = type_name;
+        while current != ancestor {
+            let (Some(NominalType::Class(decl)), Some(superclass)) = (self.nominal(current), self.superclass(current)) else { break };
+            let parameters = self
+                .nominal(superclass)
+                .and_then(|nominal| nominal.generics())
+                .map(|generics| generics.type_parameters.iter().map(|parameter| parameter.name.clone()).collect::<Vec<_>>())
+                .unwrap_or_default();
+            let mut next = Substitution::new(parameters.clone());
+            for (parameter, ty) in parameters.iter().zip(&decl.superclass_arguments) {
+                next.bind(parameter, substitution.apply(ty));
+            }
+            substitution = next;
+            current = superclass;
+        }
+        substitution
+    }
+    /// The class declaring an initializer that `type_name` has, itself or a superclass it inherits from.
+    pub fn initializer_owner(&self, type_name: &str, initializer: &InitializerDeclaration) -> Option<&'a str> {
+        let mut current = Some(type_name);
+        while let Some(name) = current {
+            let nominal = self.nominal(name)?;
+            let declared = nominal
+                .initializers()
+                .iter()
+                .chain(self.extensions(name).iter().flat_map(|extension| extension.initializers.iter()));
+            if declared.into_iter().any(|declared| std::ptr::eq(declared, initializer)) {
+                return Some(nominal.name());
+            }
+            current = self.superclass(name);
+        }
+        None
+    }
+    /// The stored properties declared by a class that its `deinit`, or that of a subclass, reads.
+    pub fn deinit_reads(&self, type_name: &str) -> Vec<&'a str> {
+        let Some(NominalType::Class(decl)) = self.nominal(type_name) else { return Vec::new() };
+        let mut names = HashSet::new();
+        let mut reads_all = false;
+        for nominal in self.types.values() {
+            let NominalType::Class(class) = nominal else { continue };
+            if class.name != decl.name && !self.is_subclass(&class.name, &decl.name) {
+                continue;
+            }
+            match self.deinitializer_reads(class) {
+                Some(reads) => names.extend(reads),
+                None => reads_all = true,
+            }
+        }
+        decl.properties
+            .iter()
+            .filter(|property| !property.is_static && property.has_storage())
+            .filter(|property| reads_all || names.contains(property.name.as_str()))
+            .map(|property| property.name.as_str())
+            .collect()
+    }
+    /// The names of the stored properties a class's own `deinit` reads, or `None` when it may read
+    /// any: calling a method, reading a computed property or using `self` as a value.
+    pub fn deinitializer_reads(&self, class: &'a ClassDeclaration) -> Option<HashSet<&'a str>> {
+        let mut names = HashSet::new();
+        let Some(deinitializer) = &class.deinitializer else { return Some(names) };
+        let mut reads_all = false;
+        // Every `self` should be the target of a member access naming a stored property.
+        let (mut selves, mut accesses) = (0, 0);
+        visit_statements(deinitializer.body.statements(), &mut |node| {
+            let Node::Expression(expression) = node else { return };
+            let name = match expression {
+                Expression::SelfExpression | Expression::SuperExpression => {
+                    selves += 1;
+                    return;
+                }
+                Expression::MemberAccess(access) if matches!(access.target.as_ref(), Expression::SelfExpression) => {
+                    accesses += 1;
+                    &access.member
+                }
+                Expression::Identifier(identifier) => &identifier.name,
+                _ => return,
+            };
+            match self.member(&class.name, name) {
+                Some(Member::Property(property)) if property.stored().is_some() => {
+                    names.insert(name.as_str());
+                }
+                Some(Member::Property(_) | Member::Method(_)) => reads_all = true,
+                _ => {}
+            }
+        });
+        (!reads_all && selves == accesses).then_some(names)
+    }
+    pub fn member_type(&self, type_name: &str, name: &str) -> Option<SwiftType> {
+        let Some(member) = self.member(type_name, name) else { return self.requirement_type(type_name, name) };
+        match member {
+            Member::Property(property) if property.is_override && property.ty.is_none() => {
+                self.member_type(self.superclass(self.member_owner(type_name, name)?)?, name)
+            }
+            Member::Property(property) => property.ty.clone().or_else(|| {
+                let initial_value = property.stored()?.initial_value.as_ref()?;
+                let key = (type_name.to_string(), name.to_string());
+                if self.inferring.borrow().contains(&key) {
+                    self.circular.borrow_mut().insert(key);
+                    return None;
+                }
+                self.inferring.borrow_mut().push(key);
+                let ty = self.infer(&Scope::default(), Some(type_name), initial_value);
+                self.inferring.borrow_mut().pop();
+                ty
+            }),
+            Member::Method(method) => {
+                Some(function_type(&method.parameters, method.return_type.as_ref(), method.is_throwing || method.is_rethrowing))
+            }
+            Member::Case(_) => Some(SwiftType::Custom(type_name.to_string())),
+        }
+    }
+    /// The type of a member reached through a value of type `ty`. Existential and opaque types offer
+    /// the members of their constraints; a composition those of each of its types.
+    pub fn value_member_type(&self, ty: &SwiftType, name: &str) -> Option<SwiftType> {
+        match ty {
+            SwiftType::Existential(constraint) | SwiftType::Opaque(constraint) => self.value_member_type(constraint, name),
+            SwiftType::Composition(types) => types.iter().find_map(|ty| self.value_member_type(ty, name)),
+            // `pair.0`, or `pair.key`
+            SwiftType::Tuple(_) | SwiftType::LabeledTuple(..) => tuple_index(ty, name).and_then(|index| tuple_elements(ty).get(index)).cloned(),
+            // Members of the standard library types, unless an extension declares one of that name.
+            ty => nominal_name(ty)
+                .and_then(|type_name| self.member_type(type_name, name))
+                .or_else(|| stdlib::property_type(ty, name)),
+        }
+    }
+    /// The protocols whose witness tables a value of an existential type carries: `any P`, a
+    /// composition, or a protocol used as a type. `None` for other types, opaque ones included,
+    /// whose values aren't boxed.
+    pub fn existential_protocols<'t>(&self, ty: &'t SwiftType) -> Option<Vec<&'t str>> {
+        match ty {
+            SwiftType::Existential(constraint) => self.existential_protocols(constraint).or_else(|| Some(Vec::new())),
+            SwiftType::Composition(types) => Some(
+                types
+                    .iter()
+                    .filter_map(|ty| self.existential_protocols(ty))
+                    .flatten()
+                    .collect(),
+            ),
+            SwiftType::Custom(name) if matches!(self.nominal(name), Some(NominalType::Protocol(_))) => Some(vec![name]),
+            _ => None,
+        }
+    }
+    /// The type of a requirement of a protocol, or of the protocols it inherits from.
+    pub fn requirement_type(&self, protocol: &str, name: &str) -> Option<SwiftType> {
+        let Some(NominalType::Protocol(_)) = self.nominal(protocol) else { return None };
+        self.protocols(protocol).into_iter().find_map(|decl| {
+            if let Some(property) = decl.property_requirements.iter().find(|property| property.name == name) {
+                return Some(property.ty.clone());
+            }
+            let method = decl.method_requirements.iter().find(|method| method.name == name)?;
+            Some(function_type(&method.parameters, method.return_type.as_ref(), method.is_throwing))
+        })
+    }
+    /// True when inferring the type of a property from its initial value leads back to the property.
+    pub fn is_circular(&self, type_name: &str, name: &str) -> bool {
+        self.circular.borrow().contains(&(type_name.to_string(), name.to_string()))
+    }
+    /// Best-effort static type of an expression; `None` when it can't be determined locally.
+    pub fn infer(&self, scope: &Scope, self_type: Option<&str>, expression: &Expression) -> Option<SwiftType> {
+        match expression {
+            Expression::SelfExpression => self_type.map(|name| SwiftType::Custom(name.to_string())),
+            Expression::SuperExpression => self_type
+                .and_then(|name| self.superclass(name))
+                .map(|name| SwiftType::Custom(name.to_string())),
+            Expression::Identifier(identifier) => {
+                if let Some(binding) = scope.lookup(&identifier.name) {
+                    return binding.ty.clone();
+                }
+                self_type.and_then(|name| self.member_type(name, &identifier.name))
+            }
+            Expression::Literal(literal) => match literal {
+                Literal::Integer(_) => Some(SwiftType::Integer),
+                Literal::Float(_) => Some(SwiftType::Double),
+                Literal::Bool(_) => Some(SwiftType::Bool),
+                Literal::String(_) => Some(SwiftType::String),
+                Literal::Character(_) => Some(SwiftType::Character),
+                Literal::Nil => None,
+            },
+            Expression::BinaryExpression(binary) => match binary.operator.symbol.as_str() {
+                "==" | "!=" | "===" | "!==" | "<" | ">" | "<=" | ">=" | "&&" | "||" => Some(SwiftType::Bool),
+                "??" => self.infer(scope, self_type, &binary.right),
+                // A literal operand takes the type of the other one, as in `1 + count`.
+                _ if matches!(binary.left.as_ref(), Expression::Literal(_)) => self
+                    .infer(scope, self_type, &binary.right)
+                    .or_else(|| self.infer(scope, self_type, &binary.left)),
+                _ => self
+                    .infer(scope, self_type, &binary.left)
+                    .or_else(|| self.infer(scope, self_type, &binary.right)),
+            },
+            Expression::UnaryExpression(unary) => match unary.operator.symbol.as_str() {
+                "!" => Some(SwiftType::Bool),
+                _ => self.infer(scope, self_type, &unary.operand),
+            },
+            Expression::CallExpression(call) => match call.callee.as_ref() {
+                Expression::Identifier(identifier) if scope.lookup(&identifier.name).is_none() => {
+                    if self.nominal(&identifier.name).is_some() {
+                        let labels = self.construction_labels(&identifier.name, call);
+                        return Some(self.construction_type(&identifier.name, &labels));
+                    }
+                    if let Some(ty) = standard_value_type(&identifier.name) {
+                        // `Int8(exactly: value)` is nil when the value doesn't fit.
+                        return Some(match call.arguments.as_slice() {
+                            [argument] if argument.label.as_deref() == Some("exactly") => SwiftType::Optional(Box::new(ty)),
+                            _ => ty,
+                        });
+                    }
+                    if let Some(SwiftType::Function(_, result, _)) =
+                        self_type.and_then(|name| self.member_type(name, &identifier.name))
+                    {
+                        return Some(*result);
+                    }
+                    if self.function(&identifier.name).is_none() {
+                        let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+                        let function = stdlib::global_function(&identifier.name, &labels)?;
+                        return self.standard_call_result(scope, self_type, function, None, call);
+                    }
+                    let function = self.function(&identifier.name)?;
+                    let result = function.return_type.clone().unwrap_or(SwiftType::Tuple(Vec::new()));
+                    let Some(generics) = &function.generics else { return Some(result) };
+                    let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+                    let arguments = call
+                        .arguments
+                        .iter()
+                        .map(|argument| self.infer(scope, self_type, &argument.value))
+                        .collect::<Vec<_>>();
+                    let substitution = self.generic_arguments(generics, &function.parameters, &labels, &arguments);
+                    Some(self.resolve_nested(&substitution.apply(&result)))
+                }
+                Expression::MemberAccess(access) => {
+                    let member_type = match self.type_reference(scope, &access.target) {
+                        Some(type_name) if access.member == "init" => {
+                            let labels = self.construction_labels(type_name, call);
+                            return Some(self.construction_type(type_name, &labels));
+                        }
+                        Some(type_name) => self.member_type(type_name, &access.member),
+                        None => {
+                            let receiver = self.infer(scope, self_type, &access.target)?;
+                            if let Some(member) = self.standard_method(&receiver, &access.member, call) {
+                                return self.standard_call_result(scope, self_type, member, Some(&receiver), call);
+                            }
+                            self.value_member_type(&receiver, &access.member)
+                        }
+                    };
+                    match member_type? {
+                        SwiftType::Function(_, result, _) => Some(*result),
+                        ty => Some(ty),
+                    }
+                }
+                callee => match self.infer(scope, self_type, callee)? {
+                    SwiftType::Function(_, result, _) => Some(*result),
+                    _ => None,
+                },
+            },
+            Expression::Closure(closure) => Some(SwiftType::Function(
+                closure
+                    .parameters
+                    .iter()
+                    .map(|parameter| parameter.type_annotation.clone())
+                    .collect::<Option<Vec<_>>>()?,
+                Box::new(closure.return_type.as_deref().cloned().unwrap_or_else(SwiftType::void)),
+                FunctionTypeAttributes::default(),
+            )),
+            Expression::Subscript(subscript) => match self.infer(scope, self_type, &subscript.target)? {
+                SwiftType::Array(element) => Some(*element),
+                SwiftType::Dictionary(_, value) if subscript.default_value.is_some() => Some(*value),
+                SwiftType::Dictionary(_, value) => Some(SwiftType::Optional(value)),
+                SwiftType::String => Some(SwiftType::Character),
+                _ => None,
+            },
+            Expression::Conditional(conditional) => self
+                .infer(scope, self_type, &conditional.true_expression)
+                .or_else(|| self.infer(scope, self_type, &conditional.false_expression)),
+            Expression::Tuple(tuple) => tuple
+                .elements
+                .iter()
+                .map(|element| self.infer(scope, self_type, element))
+                .collect::<Option<Vec<_>>>()
+                .map(SwiftType::Tuple),
+            Expression::Array(array) => array
+                .elements
+                .first()
+                .and_then(|element| self.infer(scope, self_type, element))
+                .map(|element| SwiftType::Array(Box::new(element))),
+            Expression::Dictionary(dictionary) => {
+                let (key, value) = dictionary.elements.first()?;
+                Some(SwiftType::Dictionary(
+                    Box::new(self.infer(scope, self_type, key)?),
+                    Box::new(self.infer(scope, self_type, value)?),
+                ))
+            }
+            Expression::MemberAccess(access) => {
+                if let Some(type_name) = self.type_reference(scope, &access.target) {
+                    if access.member == "self" {
+                        return Some(match self.nominal(type_name) {
+                            Some(NominalType::Protocol(_)) => SwiftType::ProtocolMetatype(Box::new(SwiftType::Custom(type_name.to_string()))),
+                            _ => SwiftType::Metatype(Box::new(SwiftType::Custom(type_name.to_string()))),
+                        });
+                    }
+                    return self.member_type(type_name, &access.member);
+                }
+                if access.member == "self" {
+                    // `Int.self`, `Date.self` and `UTF8.self` name standard library types, which have no declaration.
+                    if let Expression::Identifier(identifier) = access.target.as_ref() {
+                        let ty = SwiftType::named(&identifier.name);
+                        let is_standard = !matches!(ty, SwiftType::Custom(_)) || stdlib::has_members(&ty) || stdlib::is_runtime_type(&identifier.name);
+                        if scope.lookup(&identifier.name).is_none() && is_standard {
+                            return Some(SwiftType::Metatype(Box::new(ty)));
+                        }
+                    }
+                    return self.infer(scope, self_type, &access.target);
+                }
+                if let Some(integer) = integer_bound(scope, access) {
+                    return Some(integer);
+                }
+                let target = self.infer(scope, self_type, &access.target)?;
+                self.value_member_type(&target, &access.member)
+            }
+            Expression::TypeCasting(cast) => Some(cast.target_type.clone()),
+            Expression::Assignment(_) => Some(SwiftType::Tuple(Vec::new())),
+            Expression::PatternMatch(_) => Some(SwiftType::Bool),
+            Expression::StringInterpolation(_) => Some(SwiftType::String),
+            Expression::KeyPath(_) => None,
+            Expression::TypeExpression(ty) => Some(SwiftType::Metatype(Box::new(ty.clone()))),
+            Expression::Try(expression) => {
+                let ty = self.infer(scope, self_type, &expression.expression)?;
+                Some(match (expression.kind, ty) {
+                    // `try?` doesn't wrap an optional result in another.
+                    (TryKind::Optional, ty @ SwiftType::Optional(_)) => ty,
+                    (TryKind::Optional, ty) => SwiftType::Optional(Box::new(ty)),
+                    (_, ty) => ty,
+                })
+            }
+        }
+    }
+    /// Whether a call can throw: always, when it calls a throwing function, method or initializer,
+    /// or only when it passes a throwing function to one that `rethrows`. The standard library's
+    /// functions taking closures rethrow their errors.
+    pub fn call_throwing(&self, scope: &Scope, self_type: Option<&str>, call: &CallExpression) -> Option<Throwing> {
+        let labels = call.arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+        let declared = |function: &FunDeclaration| match (function.is_throwing, function.is_rethrowing) {
+            (true, _) => Some(Throwing::Always),
+            (false, true) => Some(Throwing::Rethrows),
+            (false, false) => None,
+        };
+        let initializer = |type_name: &str| {
+            let labels = self.construction_labels(type_name, call);
+            self.initializer(type_name, &labels).filter(|initializer| initializer.is_throwing).map(|_| Throwing::Always)
+        };
+        let standard = |member: &StandardMember| match (member.ty)() {
+            SwiftType::Function(_, _, attributes) if attributes.is_throwing => Some(Throwing::Always),
+            SwiftType::Function(parameters, ..) if parameters.iter().any(|parameter| matches!(parameter, SwiftType::Function(..))) => {
+                Some(Throwing::Rethrows)
+            }
+            _ => None,
+        };
+        let function_type = |ty: Option<SwiftType>| match ty {
+            Some(SwiftType::Function(_, _, attributes)) if attributes.is_throwing => Some(Throwing::Always),
+            _ => None,
+        };
+        match call.callee.as_ref() {
+            Expression::Identifier(identifier) if scope.lookup(&identifier.name).is_none() => {
+                if self.nominal(&identifier.name).is_some() {
+                    return initializer(&identifier.name);
+                }
+                if let Some(member) = self_type.and_then(|name| self.member(name, &identifier.name)) {
+                    return match member {
+                        Member::Method(method) => declared(method),
+                        _ => function_type(self_type.and_then(|name| self.member_type(name, &identifier.name))),
+                    };
+                }
+                if let Some(function) = self.function(&identifier.name) {
+                    return declared(function);
+                }
+                stdlib::global_function(&identifier.name, &labels).and_then(standard)
+            }
+            Expression::MemberAccess(access) => {
+                match delegation_call(call) {
+                    Some((Delegation::ToSelf, _)) => return self_type.and_then(initializer),
+                    Some((Delegation::ToSuper, _)) => return self_type.and_then(|name| self.superclass(name)).and_then(initializer),
+                    None => {}
+                }
+                if let Some(type_name) = self.type_reference(scope, &access.target) {
+                    return match self.member(type_name, &access.member) {
+                        _ if access.member == "init" => initializer(type_name),
+                        Some(Member::Method(method)) => declared(method),
+                        _ => None,
+                    };
+                }
+                let receiver = self.infer(scope, self_type, &access.target)?;
+                if let Some(member) = self.standard_method(&receiver, &access.member, call) {
+                    return standard(member);
+                }
+                match nominal_name(&receiver).and_then(|type_name| self.member(type_name, &access.member)) {
+                    Some(Member::Method(method)) => declared(method),
+                    _ => function_type(self.value_member_type(&receiver, &access.member)),
+                }
+            }
+            callee => function_type(self.infer(scope, self_type, callee)),
+        }
+    }
+    /// The standard library method a call reaches through a value of type `receiver`, unless an
+    /// extension of the type declares a member of that name.
+    pub fn standard_method(&self, receiver: &SwiftType, name: &str, call: &CallExpression) -> Option<&'static StandardMember> {
+        if nominal_name(receiver).is_some_and(|type_name| self.member_type(type_name, name).is_some()) {
+            return None;
+        }
+        let labels = call.arguments.iter().map(|argument| argument.label.as_deref());
+        let trailing = call.trailing_closures.iter().map(|closure| closure.label.as_deref());
+        let labels = labels.chain(trailing).collect::<Vec<_>>();
+        // The first trailing closure drops the label of the parameter it's passed as: `sorted { ... }`
+        // calls `sorted(by:)`.
+        let first_trailing = call.arguments.len();
+        stdlib::members(receiver, name).find(|member| {
+            let expected = member.labels();
+            expected.len() == labels.len()
+                && expected.iter().zip(&labels).enumerate().all(|(i, (expected, label))| {
+                    expected == label || (i == first_trailing && label.is_none() && !call.trailing_closures.is_empty())
+                })
+        })
+    }
+    /// The type of a standard library function or method at a call: its function type with the
+    /// receiver's generic arguments, and the method's own as far as the arguments determine them.
+    /// Closure arguments are typed by the parameters they are passed as.
+    pub fn standard_call_type(
+        &self,
+        scope: &Scope,
+        self_type: Option<&str>,
+        member: &StandardMember,
+        receiver: Option<&SwiftType>,
+        call: &CallExpression,
+    ) -> SwiftType {
+        let mut substitution = stdlib::substitution(receiver);
+        let ty = (member.ty)();
+        let SwiftType::Function(parameters, ..) = &ty else { return substitution.apply(&ty) };
+        let arguments = call
+            .arguments
+            .iter()
+            .map(|argument| &argument.value)
+            .chain(call.trailing_closures.iter().map(|closure| &closure.closure));
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            let parameter = substitution.apply(parameter);
+            let argument = match (argument, &parameter) {
+                (Expression::Closure(closure), SwiftType::Function(context, result, _)) => {
+                    // A closure returning `T` is passed where one returning `T?` is expected.
+                    self.closure_type(scope, self_type, closure, context).map(|ty| match (ty, result.as_ref()) {
+                        (SwiftType::Function(parameters, value, attributes), SwiftType::Optional(_)) if !matches!(*value, SwiftType::Optional(_)) => {
+                            SwiftType::Function(parameters, Box::new(SwiftType::Optional(value)), attributes)
+                        }
+                        (ty, _) => ty,
+                    })
+                }
+                _ => self.infer(scope, self_type, argument),
+            };
+            let mut trial = substitution.clone();
+            if argument.is_some_and(|argument| trial.unify(&parameter, &argument)) {
+                substitution = trial;
+            }
+        }
+        substitution.apply(&ty)
+    }
+    fn standard_call_result(
+        &self,
+        scope: &Scope,
+        self_type: Option<&str>,
+        member: &StandardMember,
+        receiver: Option<&SwiftType>,
+        call: &CallExpression,
+    ) -> Option<SwiftType> {
+        let SwiftType::Function(_, result, _) = self.standard_call_type(scope, self_type, member, receiver, call) else { return None };
+        // A result still written with a method's parameter wasn't determined by the arguments.
+        stdlib::substitution(None).is_concrete(&result).then_some(*result)
+    }
+    /// The type of a closure passed as a parameter of function type `(context) -> _`, whose
+    /// result is that of its expression when it has a single one.
+    pub fn closure_type(&self, scope: &Scope, self_type: Option<&str>, closure: &Closure, context: &[SwiftType]) -> Option<SwiftType> {
+        let (parameters, _) = closure_parameters(closure, context);
+        let mut inner = scope.clone();
+        inner.push();
+        for (name, ty) in &parameters {
+            inner.declare(name, Binding { ty: ty.clone(), is_constant: true, is_inout: false, ownership: ReferenceOwnership::Strong });
+        }
+        let result = match (&closure.return_type, closure.body.as_slice()) {
+            (Some(result), _) => *result.clone(),
+            (None, [Statement::Expression(expression)]) => self.infer(&inner, self_type, expression)?,
+            (None, [Statement::Return(statement)]) => match &statement.expression {
+                Some(expression) => self.infer(&inner, self_type, expression)?,
+                None => SwiftType::void(),
+            },
+            (None, _) => SwiftType::void(),
+        };
+        let parameters = parameters.into_iter().map(|(_, ty)| ty).collect::<Option<Vec<_>>>()?;
+        Some(SwiftType::Function(parameters, Box::new(result), FunctionTypeAttributes::default()))
+    }
+    /// If the expression names a type (e.g. the `Point` in `Point.origin`), returns its name.
+    pub fn type_reference<'e>(&self, scope: &Scope, expression: &'e Expression) -> Option<&'e str> {
+        match expression {
+            Expression::Identifier(identifier)
+                if scope.lookup(&identifier.name).is_none() && self.nominal(&identifier.name).is_some() =>
+            {
+                Some(&identifier.name)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The name used to look up members of a type, e.g. `Int` for `SwiftType::Integer`.
+pub fn nominal_name(ty: &SwiftType) -> Option<&str> {
+    match ty {
+        SwiftType::Integer => Some("Int"),
+        SwiftType::SizedInteger(integer) => Some(integer.name()),
+        SwiftType::Double => Some("Double"),
+        SwiftType::Float => Some("Float"),
+        SwiftType::Bool => Some("Bool"),
+        SwiftType::String => Some("String"),
+        SwiftType::Character => Some("Character"),
+        SwiftType::Custom(name) | SwiftType::Generic(name, _) => Some(name),
+        SwiftType::Existential(constraint) | SwiftType::Opaque(constraint) => nominal_name(constraint),
+        SwiftType::ImplicitlyUnwrappedOptional(wrapped) => nominal_name(wrapped),
+        _ => None,
+    }
+}
+
+/// The type of a function with these parameters and result; variadic parameters are written as
+/// their element type.
+pub fn function_type(parameters: &[FunctionParameter], result: Option<&SwiftType>, is_throwing: bool) -> SwiftType {
+    SwiftType::Function(
+        parameters.iter().map(|parameter| parameter.ty.clone()).collect(),
+        Box::new(result.cloned().unwrap_or_else(SwiftType::void)),
+        FunctionTypeAttributes { is_throwing, ..FunctionTypeAttributes::default() },
+    )
+}
+
+/// The standard library value type a name refers to, like `Double` or `UInt8`, which values are
+/// converted to by calling it.
+pub fn standard_value_type(name: &str) -> Option<SwiftType> {
+    match SwiftType::named(name) {
+        ty @ (SwiftType::Integer
+        | SwiftType::SizedInteger(_)
+        | SwiftType::Double
+        | SwiftType::Float
+        | SwiftType::Bool
+        | SwiftType::String
+        | SwiftType::Character) => Some(ty),
+        _ => None,
+    }
+}
+
+/// The integer type whose bound an access like `Int.max` or `UInt8.min` reads, unless a local
+/// shadows the type's name.
+pub fn integer_bound(scope: &Scope, access: &MemberAccessExpression) -> Option<SwiftType> {
+    let Expression::Identifier(identifier) = access.target.as_ref() else { return None };
+    if !matches!(access.member.as_str(), "min" | "max") || scope.lookup(&identifier.name).is_some() {
+        return None;
+    }
+    Some(SwiftType::named(&identifier.name)).filter(|ty| matches!(ty, SwiftType::Integer | SwiftType::SizedInteger(_)))
+}
+
+/// The names and types of a closure's parameters, when it is passed as a function taking the
+/// `context` types: the types written for them, or else the context's. A closure without
+/// parameters uses `$0`, `$1`, ... for them. True if the closure names the elements of a single tuple
+/// parameter, as in `dictionary.map { key, value in ... }`.
+pub fn closure_parameters(closure: &Closure, context: &[SwiftType]) -> (Vec<(String, Option<SwiftType>)>, bool) {
+    if closure.parameters.is_empty() {
+        let parameters = context.iter().enumerate().map(|(index, ty)| (format!("${index}"), Some(ty.clone())));
+        return (parameters.collect(), false);
+    }
+    let (context, destructures) = match context {
+        [SwiftType::Tuple(elements) | SwiftType::LabeledTuple(_, elements)] if closure.parameters.len() > 1 && elements.len() == closure.parameters.len() => {
+            (elements.as_slice(), true)
+        }
+        context => (context, false),
+    };
+    let parameters = closure
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(index, parameter)| {
+            let ty = parameter.type_annotation.clone().or_else(|| context.get(index).cloned());
+            (parameter.name.clone(), ty)
+        })
+        .collect();
+    (parameters, destructures)
+}
+
+/// True for operators like `+=` that assign to their left operand.
+pub fn is_compound_assignment(operator: &str) -> bool {
+    operator.len() >= 2
+        && operator.ends_with('=')
+        && !matches!(operator, "==" | "!=" | "<=" | ">=" | "===" | "!==")
+}
+
+/// True if a call with the given argument labels can bind to these parameters.
+pub fn parameters_accept_labels(parameters: &[FunctionParameter], labels: &[Option<&str>]) -> bool {
+    let mut labels = labels.iter().peekable();
+    for parameter in parameters {
+        match labels.peek() {
+            Some(label) if **label == parameter.label.as_deref() => {
+                labels.next();
+                if parameter.is_variadic {
+                    while labels.peek() == Some(&&None) {
+                        labels.next();
+                    }
+                }
+            }
+            _ if parameter.default_value.is_some() || parameter.is_variadic => {}
+            _ => return false,
+        }
+    }
+    labels.next().is_none()
+}
+
+/// The labels a call binds its arguments with. A labeled trailing closure fills the next parameter
+/// with its label; the unlabeled first one fills the next parameter taking a function, or else the
+/// next one without a default value.
+pub fn call_labels<'c>(parameters: &'c [FunctionParameter], arguments: &'c [Argument], trailing: &'c [TrailingClosure]) -> Vec<Option<&'c str>> {
+    let mut labels = arguments.iter().map(|argument| argument.label.as_deref()).collect::<Vec<_>>();
+    // The first parameter after those the explicit arguments fill.
+    let mut next = 0;
+    let mut explicit = labels.iter().peekable();
+    for (index, parameter) in parameters.iter().enumerate() {
+        if explicit.peek().is_none() {
+            break;
+        }
+        if explicit.next_if(|label| **label == parameter.label.as_deref()).is_some() {
+            if parameter.is_variadic {
+                while explicit.next_if(|label| label.is_none()).is_some() {}
+            }
+            next = index + 1;
+        }
+    }
+    for closure in trailing {
+        let remaining = parameters.get(next..).unwrap_or_default();
+        let position = match &closure.label {
+            Some(label) => remaining.iter().position(|parameter| parameter.label.as_deref() == Some(label.as_str())),
+            None => remaining
+                .iter()
+                .position(|parameter| matches!(parameter.ty, SwiftType::Function(..)))
+                .or_else(|| remaining.iter().position(|parameter| parameter.default_value.is_none())),
+        };
+        match position {
+            Some(position) => {
+                labels.push(remaining[position].label.as_deref());
+                next += position + 1;
+            }
+            None => {
+                labels.push(closure.label.as_deref());
+                next = parameters.len();
+            }
+        }
+    }
+    labels
+}
+
+/// The types of a tuple's elements, or none for another type.
+pub fn tuple_elements(ty: &SwiftType) -> &[SwiftType] {
+    match ty {
+        SwiftType::Tuple(elements) | SwiftType::LabeledTuple(_, elements) => elements,
+        _ => &[],
+    }
+}
+
+/// The element of a tuple a member names, by its position or label: `pair.0`, `pair.key`.
+pub fn tuple_index(ty: &SwiftType, member: &str) -> Option<usize> {
+    let index = match ty {
+        SwiftType::LabeledTuple(labels, _) => labels.iter().position(|label| label == member),
+        _ => None,
+    };
+    index.or_else(|| member.parse().ok()).filter(|index| *index < tuple_elements(ty).len())
+}
+
+/// How Swift names a function in diagnostics, e.g. `init(x:y:)` or `move(_:by:)`.
+pub fn signature<'l>(name: &str, labels: impl IntoIterator<Item = Option<&'l str>>) -> String {
+    let labels = labels.into_iter().map(|label| format!("{}:", label.unwrap_or("_"))).collect::<String>();
+    format!("{name}({labels})")
+}
+
+fn labels(parameters: &[FunctionParameter]) -> impl Iterator<Item = Option<&str>> {
+    parameters.iter().map(|parameter| parameter.label.as_deref())
+}
+
+/// True if two parameter lists have the same labels and types, as a witness of a protocol requirement must.
+pub fn same_parameters(left: &[FunctionParameter], right: &[FunctionParameter]) -> bool {
+    same_labels(left, right)
+        && left.iter().zip(right).all(|(left, right)| left.ty == right.ty && left.is_inout == right.is_inout)
+}
+
+/// True if two parameter lists have the same argument labels, as an override of an initializer must.
+pub fn same_labels(left: &[FunctionParameter], right: &[FunctionParameter]) -> bool {
+    left.len() == right.len() && left.iter().zip(right).all(|(left, right)| left.label == right.label)
+}
+
+/// The initializer an initializer body hands off to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delegation {
+    /// `self.init(...)`, from a convenience initializer or a delegating value type initializer.
+    ToSelf,
+    /// `super.init(...)`, from a designated initializer of a subclass.
+    ToSuper,
+}
+
+/// If the expression is a call to `self.init(...)` or `super.init(...)`, possibly marked with
+/// `try`, which one it is.
+pub fn delegation(expression: &Expression) -> Option<(Delegation, &CallExpression)> {
+    match expression {
+        Expression::CallExpression(call) => delegation_call(call),
+        Expression::Try(expression) => delegation(&expression.expression),
+        _ => None,
+    }
+}
+
+pub fn delegation_call(call: &CallExpression) -> Option<(Delegation, &CallExpression)> {
+    let Expression::MemberAccess(access) = call.callee.as_ref() else { return None };
+    match access.target.as_ref() {
+        _ if access.member != "init" => None,
+        Expression::SelfExpression => Some((Delegation::ToSelf, call)),
+        Expression::SuperExpression => Some((Delegation::ToSuper, call)),
+        _ => None,
+    }
+}
+
+/// How a call can throw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Throwing {
+    /// The callee throws.
+    Always,
+    /// The callee `rethrows`: the call throws if it passes a throwing function.
+    Rethrows,
+}
+
+/// A node reached by `visit_statements`.
+#[derive(Debug, Clone, Copy)]
+pub enum Node<'b> {
+    Expression(&'b Expression),
+    Pattern(&'b Pattern),
+}
+
+/// Calls `visit` on every expression and `case` pattern in the statements, outer ones first,
+/// including those in closures and nested functions but not in nested type declarations.
+pub fn visit_statements<'b>(statements: &'b [Statement], visit: &mut impl FnMut(Node<'b>)) {
+    for statement in statements {
+        match statement {
+            Statement::Break(_) | Statement::Continue(_) => {}
+            Statement::Expression(expression) => visit_expression(expression, visit),
+            Statement::Declaration(declaration) => match declaration.as_ref() {
+                Declaration::Var(var) => var.initial_value.iter().for_each(|value| visit_expression(value, visit)),
+                Declaration::Let(constant) => constant.initial_value.iter().for_each(|value| visit_expression(value, visit)),
+                Declaration::Function(function) => {
+                    for default_value in function.parameters.iter().filter_map(|parameter| parameter.default_value.as_ref()) {
+                        visit_expression(default_value, visit);
+                    }
+                    if let Some(body) = &function.body {
+                        visit_statements(body.statements(), visit);
+                    }
+                }
+                _ => {}
+            },
+            Statement::Return(statement) => statement.expression.iter().for_each(|value| visit_expression(value, visit)),
+            Statement::If(statement) => {
+                visit_expression(&statement.condition, visit);
+                visit_statements(statement.body.statements(), visit);
+                if let Some(else_body) = &statement.else_body {
+                    visit_statements(else_body.statements(), visit);
+                }
+            }
+            Statement::ForLoop(statement) => {
+                visit_expression(&statement.range.0, visit);
+                visit_expression(&statement.range.1, visit);
+                visit_statements(statement.body.statements(), visit);
+            }
+            Statement::ForIn(statement) => {
+                visit_expression(&statement.sequence, visit);
+                visit_statements(statement.body.statements(), visit);
+            }
+            Statement::WhileLoop(statement) => {
+                visit_expression(&statement.condition, visit);
+                visit_statements(statement.body.statements(), visit);
+            }
+            Statement::RepeatWhileLoop(statement) => {
+                visit_statements(statement.body.statements(), visit);
+                visit_expression(&statement.condition, visit);
+            }
+            Statement::Switch(statement) => {
+                visit_expression(&statement.expression, visit);
+                for case in &statement.cases {
+                    case.patterns.iter().for_each(|pattern| visit_pattern(pattern, visit));
+                    case.guard_expression.iter().for_each(|guard| visit_expression(guard, visit));
+                    visit_statements(case.body.statements(), visit);
+                }
+                if let Some(default_case) = &statement.default_case {
+                    visit_statements(default_case.statements(), visit);
+                }
+            }
+            Statement::Guard(statement) => {
+                visit_expression(&statement.condition, visit);
+                visit_statements(statement.body.statements(), visit);
+            }
+            Statement::Throw(statement) => visit_expression(&statement.expression, visit),
+            Statement::DoCatch(statement) => {
+                visit_statements(statement.body.statements(), visit);
+                for clause in &statement.catch_clauses {
+                    clause.pattern.iter().for_each(|pattern| visit_pattern(pattern, visit));
+                    clause.guard_expression.iter().for_each(|guard| visit_expression(guard, visit));
+                    visit_statements(clause.body.statements(), visit);
+                }
+            }
+            Statement::Assignment(statement) => {
+                visit_expression(&statement.target, visit);
+                visit_expression(&statement.value, visit);
+            }
+        }
+    }
+}
+
+/// Calls `visit` on an expression and every expression and pattern within it, outer ones first.
+pub fn visit_expression<'b>(expression: &'b Expression, visit: &mut impl FnMut(Node<'b>)) {
+    visit(Node::Expression(expression));
+    match expression {
+        Expression::SelfExpression
+        | Expression::SuperExpression
+        | Expression::Identifier(_)
+        | Expression::Literal(_)
+        | Expression::KeyPath(_)
+        | Expression::TypeExpression(_) => {}
+        Expression::BinaryExpression(binary) => {
+            visit_expression(&binary.left, visit);
+            visit_expression(&binary.right, visit);
+        }
+        Expression::UnaryExpression(unary) => visit_expression(&unary.operand, visit),
+        Expression::StringInterpolation(interpolation) => interpolation.values().for_each(|value| visit_expression(value, visit)),
+        Expression::CallExpression(call) => {
+            visit_expression(&call.callee, visit);
+            call.arguments.iter().for_each(|argument| visit_expression(&argument.value, visit));
+            call.trailing_closures.iter().for_each(|closure| visit_expression(&closure.closure, visit));
+        }
+        Expression::Closure(closure) => {
+            closure.capture_list.iter().filter_map(|item| item.value.as_ref()).for_each(|value| visit_expression(value, visit));
+            visit_statements(&closure.body, visit);
+        }
+        Expression::Subscript(subscript) => {
+            visit_expression(&subscript.target, visit);
+            visit_expression(&subscript.index, visit);
+        }
+        Expression::Conditional(conditional) => {
+            visit_expression(&conditional.condition, visit);
+            visit_expression(&conditional.true_expression, visit);
+            visit_expression(&conditional.false_expression, visit);
+        }
+        Expression::Tuple(tuple) => tuple.elements.iter().for_each(|element| visit_expression(element, visit)),
+        Expression::Array(array) => array.elements.iter().for_each(|element| visit_expression(element, visit)),
+        Expression::Dictionary(dictionary) => {
+            for (key, value) in &dictionary.elements {
+                visit_expression(key, visit);
+                visit_expression(value, visit);
+            }
+        }
+        Expression::MemberAccess(access) => visit_expression(&access.target, visit),
+        Expression::TypeCasting(cast) => visit_expression(&cast.expression, visit),
+        Expression::PatternMatch(pattern_match) => {
+            visit_expression(&pattern_match.pattern, visit);
+            visit_expression(&pattern_match.expression, visit);
+        }
+        Expression::Assignment(assignment) => {
+            visit_expression(&assignment.target, visit);
+            visit_expression(&assignment.value, visit);
+        }
+        Expression::Try(expression) => visit_expression(&expression.expression, visit),
+    }
+}
+
+fn visit_pattern<'b>(pattern: &'b Pattern, visit: &mut impl FnMut(Node<'b>)) {
+    visit(Node::Pattern(pattern));
+    match pattern {
+        Pattern::Tuple(tuple) => tuple.elements.iter().for_each(|element| visit_pattern(element, visit)),
+        Pattern::EnumCase(case) => case.associated_values.iter().for_each(|value| visit_pattern(value, visit)),
+        _ => {}
+    }
+}
+
+/// The `self.init` and `super.init` calls made by an initializer body, in source order,
+/// including those nested in conditionals and loops.
+pub fn delegations(body: &StatementSequence) -> Vec<(Delegation, &CallExpression)> {
+    fn visit<'b>(statements: &'b [Statement], found: &mut Vec<(Delegation, &'b CallExpression)>) {
+        for statement in statements {
+            match statement {
+                Statement::Expression(expression) => found.extend(delegation(expression)),
+                Statement::If(statement) => {
+                    visit(statement.body.statements(), found);
+                    if let Some(else_body) = &statement.else_body {
+                        visit(else_body.statements(), found);
+                    }
+                }
+                Statement::Guard(statement) => visit(statement.body.statements(), found),
+                Statement::WhileLoop(statement) => visit(statement.body.statements(), found),
+                Statement::RepeatWhileLoop(statement) => visit(statement.body.statements(), found),
+                Statement::ForLoop(statement) => visit(statement.body.statements(), found),
+                Statement::ForIn(statement) => visit(statement.body.statements(), found),
+                Statement::Switch(statement) => {
+                    for case in &statement.cases {
+                        visit(case.body.statements(), found);
+                    }
+                    if let Some(default_case) = &statement.default_case {
+                        visit(default_case.statements(), found);
+                    }
+                }
+                Statement::DoCatch(statement) => {
+                    visit(statement.body.statements(), found);
+                    for clause in &statement.catch_clauses {
+                        visit(clause.body.statements(), found);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    let mut found = Vec::new();
+    visit(body.statements(), &mut found);
+    found
+}
+
+/// A local variable, constant, or parameter.
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub ty: Option<SwiftType>,
+    /// True for `let` constants and ordinary parameters.
+    pub is_constant: bool,
+    /// True for `inout` parameters, which are passed as references.
+    pub is_inout: bool,
+    /// `weak` and `unowned` variables hold their instance in a wrapper that doesn't keep it alive.
+    pub ownership: ReferenceOwnership,
+}
+
+/// Lexical scopes of local bindings, innermost last.
+#[derive(Debug, Default, Clone)]
+pub struct Scope {
+    frames: Vec<HashMap<String, Binding>>,
+}
+
+impl Scope {
+    pub fn push(&mut self) {
+        self.frames.push(HashMap::new());
+    }
+    pub fn pop(&mut self) {
+        self.frames.pop();
+    }
+    pub fn declare(&mut self, name: &str, binding: Binding) {
+        if self.frames.is_empty() {
+            self.push();
+        }
+        if let Some(frame) = self.frames.last_mut() {
+            frame.insert(name.to_string(), binding);
+        }
+    }
+    pub fn lookup(&self, name: &str) -> Option<&Binding> {
+        self.lookup_frame(name).map(|(_, binding)| binding)
+    }
+    /// Like `lookup`, also returning the index of the frame declaring the binding, outermost first.
+    pub fn lookup_frame(&self, name: &str) -> Option<(usize, &Binding)> {
+        self.frames.iter().enumerate().rev().find_map(|(index, frame)| Some((index, frame.get(name)?)))
+    }
+    /// The number of frames pushed.
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+}
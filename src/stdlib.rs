@@ -0,0 +1,487 @@
+use crate::sema::Substitution;
+use crate::syntax::{FunctionTypeAttributes, IntegerType, SwiftType};
+
+/// A function, property or method of the standard library, which has no declaration in the
+/// program. The runtime implements it as a function taking the value, then the arguments.
+pub struct StandardMember {
+    /// The type declaring the member, spelled as in Swift: `String.UTF8View`, or `Array` for the
+    /// arrays of any element. Empty for global functions.
+    pub owner: &'static str,
+    /// The member's name, with its argument labels for a method: `count` or `index(after:)`.
+    pub signature: &'static str,
+    /// The runtime function implementing the member.
+    pub function: &'static str,
+    /// The type of a property, or the function type of a method. Members of generic types are
+    /// written with their parameters, `Element`, `Key`, `Value` and `Wrapped`, and generic methods
+    /// with their own, `T` and `U`.
+    pub ty: fn() -> SwiftType,
+    /// True for mutating methods, which need a mutable receiver.
+    pub is_mutating: bool,
+}
+
+impl StandardMember {
+    /// The name of the member without its argument labels.
+    pub fn name(&self) -> &'static str {
+        self.signature.split('(').next().unwrap_or(self.signature)
+    }
+
+    /// The argument labels of a method, `None` for the unlabeled ones.
+    pub fn labels(&self) -> Vec<Option<&'static str>> {
+        let Some(labels) = self.signature.strip_prefix(self.name()) else { return Vec::new() };
+        let labels = labels.trim_start_matches('(').trim_end_matches(')');
+        labels.split_terminator(':').map(|label| (label != "_").then_some(label)).collect()
+    }
+}
+
+const fn property(owner: &'static str, signature: &'static str, function: &'static str, ty: fn() -> SwiftType) -> StandardMember {
+    StandardMember { owner, signature, function, ty, is_mutating: false }
+}
+
+const fn method(owner: &'static str, signature: &'static str, function: &'static str, ty: fn() -> SwiftType) -> StandardMember {
+    StandardMember { owner, signature, function, ty, is_mutating: false }
+}
+
+const fn mutating(owner: &'static str, signature: &'static str, function: &'static str, ty: fn() -> SwiftType) -> StandardMember {
+    StandardMember { owner, signature, function, ty, is_mutating: true }
+}
+
+const MEMBERS: &[StandardMember] = &[
+    // Strings are collections of characters, which are extended grapheme clusters.
+    property("String", "count", "characterCount", || SwiftType::Integer),
+    property("String", "isEmpty", "isEmpty", || SwiftType::Bool),
+    property("String", "startIndex", "startIndex", string_index),
+    property("String", "endIndex", "endIndex", string_index),
+    property("String", "first", "firstCharacter", || optional(SwiftType::Character)),
+    property("String", "last", "lastCharacter", || optional(SwiftType::Character)),
+    method("String", "index(after:)", "indexAfter", || function(vec![string_index()], string_index())),
+    method("String", "index(before:)", "indexBefore", || function(vec![string_index()], string_index())),
+    method("String", "index(_:offsetBy:)", "indexOffsetBy", || function(vec![string_index(), SwiftType::Integer], string_index())),
+    method("String", "hasPrefix(_:)", "hasPrefix", || function(vec![SwiftType::String], SwiftType::Bool)),
+    method("String", "hasSuffix(_:)", "hasSuffix", || function(vec![SwiftType::String], SwiftType::Bool)),
+    method("String", "uppercased()", "uppercased", || function(vec![], SwiftType::String)),
+    method("String", "lowercased()", "lowercased", || function(vec![], SwiftType::String)),
+    mutating("String", "append(_:)", "appendString", || function(vec![SwiftType::String], SwiftType::void())),
+    property("String", "utf8", "utf8", || nested("String", "UTF8View")),
+    property("String", "utf16", "utf16", || nested("String", "UTF16View")),
+    property("String", "unicodeScalars", "unicodeScalars", || nested("String", "UnicodeScalarView")),
+    property("Character", "isASCII", "isASCII", || SwiftType::Bool),
+    property("Character", "asciiValue", "asciiValue", || optional(uint(IntegerType::UInt8))),
+    property("Character", "utf8", "utf8", || nested("Character", "UTF8View")),
+    property("Character", "utf16", "utf16", || nested("Character", "UTF16View")),
+    property("Character", "unicodeScalars", "unicodeScalars", || nested("Character", "UnicodeScalarView")),
+    property("Unicode.Scalar", "value", "scalarValue", || uint(IntegerType::UInt32)),
+    // The views of strings and characters are arrays of their code units or scalars.
+    property("String.UTF8View", "count", "count", || SwiftType::Integer),
+    property("String.UTF16View", "count", "count", || SwiftType::Integer),
+    property("String.UnicodeScalarView", "count", "count", || SwiftType::Integer),
+    property("String.UTF8View", "first", "first", || optional(uint(IntegerType::UInt8))),
+    property("String.UTF16View", "first", "first", || optional(uint(IntegerType::UInt16))),
+    property("String.UnicodeScalarView", "first", "first", || optional(unicode_scalar())),
+    property("String.UTF8View", "last", "last", || optional(uint(IntegerType::UInt8))),
+    property("String.UTF16View", "last", "last", || optional(uint(IntegerType::UInt16))),
+    property("String.UnicodeScalarView", "last", "last", || optional(unicode_scalar())),
+    property("Character.UTF8View", "count", "count", || SwiftType::Integer),
+    property("Character.UTF16View", "count", "count", || SwiftType::Integer),
+    property("Character.UnicodeScalarView", "count", "count", || SwiftType::Integer),
+    // Arrays.
+    property("Array", "count", "count", || SwiftType::Integer),
+    property("Array", "isEmpty", "isEmpty", || SwiftType::Bool),
+    property("Array", "first", "first", || optional(element())),
+    property("Array", "last", "last", || optional(element())),
+    method("Array", "map(_:)", "map", || function(vec![function(vec![element()], parameter("T"))], array(parameter("T")))),
+    method("Array", "compactMap(_:)", "compactMap", || function(vec![function(vec![element()], optional(parameter("T")))], array(parameter("T")))),
+    method("Array", "filter(_:)", "filter", || function(vec![predicate()], array(element()))),
+    method("Array", "reduce(_:_:)", "reduce", || function(vec![parameter("T"), function(vec![parameter("T"), element()], parameter("T"))], parameter("T"))),
+    method("Array", "forEach(_:)", "forEach", || function(vec![function(vec![element()], SwiftType::void())], SwiftType::void())),
+    method("Array", "sorted()", "sorted", || function(vec![], array(element()))),
+    method("Array", "sorted(by:)", "sorted", || function(vec![ordering()], array(element()))),
+    method("Array", "reversed()", "reversed", || function(vec![], array(element()))),
+    method("Array", "enumerated()", "enumerated", || function(vec![], array(labeled(&["offset", "element"], vec![SwiftType::Integer, element()])))),
+    method("Array", "contains(_:)", "contains", || function(vec![element()], SwiftType::Bool)),
+    method("Array", "contains(where:)", "containsWhere", || function(vec![predicate()], SwiftType::Bool)),
+    method("Array", "first(where:)", "firstWhere", || function(vec![predicate()], optional(element()))),
+    method("Array", "firstIndex(of:)", "firstIndexOf", || function(vec![element()], optional(SwiftType::Integer))),
+    method("Array", "min()", "minElement", || function(vec![], optional(element()))),
+    method("Array", "max()", "maxElement", || function(vec![], optional(element()))),
+    method("Array", "joined(separator:)", "joined", || function(vec![SwiftType::String], SwiftType::String)),
+    mutating("Array", "append(_:)", "append", || function(vec![element()], SwiftType::void())),
+    mutating("Array", "append(contentsOf:)", "appendContentsOf", || function(vec![array(element())], SwiftType::void())),
+    mutating("Array", "insert(_:at:)", "insertAt", || function(vec![element(), SwiftType::Integer], SwiftType::void())),
+    mutating("Array", "remove(at:)", "removeAt", || function(vec![SwiftType::Integer], element())),
+    mutating("Array", "removeLast()", "removeLast", || function(vec![], element())),
+    mutating("Array", "removeAll()", "removeAll", || function(vec![], SwiftType::void())),
+    mutating("Array", "removeAll(where:)", "removeAllWhere", || function(vec![predicate()], SwiftType::void())),
+    // Dictionaries, whose elements are key-value tuples.
+    property("Dictionary", "count", "count", || SwiftType::Integer),
+    property("Dictionary", "isEmpty", "isEmpty", || SwiftType::Bool),
+    property("Dictionary", "keys", "keys", || array(parameter("Key"))),
+    property("Dictionary", "values", "values", || array(parameter("Value"))),
+    method("Dictionary", "mapValues(_:)", "mapValues", || function(vec![function(vec![parameter("Value")], parameter("T"))], dictionary(parameter("Key"), parameter("T")))),
+    method("Dictionary", "map(_:)", "map", || function(vec![function(vec![element()], parameter("T"))], array(parameter("T")))),
+    method("Dictionary", "filter(_:)", "filterDictionary", || function(vec![predicate()], dictionary(parameter("Key"), parameter("Value")))),
+    method("Dictionary", "reduce(_:_:)", "reduce", || function(vec![parameter("T"), function(vec![parameter("T"), element()], parameter("T"))], parameter("T"))),
+    method("Dictionary", "forEach(_:)", "forEach", || function(vec![function(vec![element()], SwiftType::void())], SwiftType::void())),
+    method("Dictionary", "sorted(by:)", "sorted", || function(vec![ordering()], array(element()))),
+    method("Dictionary", "contains(where:)", "containsWhere", || function(vec![predicate()], SwiftType::Bool)),
+    method("Dictionary", "first(where:)", "firstWhere", || function(vec![predicate()], optional(element()))),
+    mutating("Dictionary", "updateValue(_:forKey:)", "updateValue", || function(vec![parameter("Value"), parameter("Key")], optional(parameter("Value")))),
+    mutating("Dictionary", "removeValue(forKey:)", "removeValue", || function(vec![parameter("Key")], optional(parameter("Value")))),
+    mutating("Dictionary", "removeAll()", "removeAll", || function(vec![], SwiftType::void())),
+    // Sets.
+    property("Set", "count", "count", || SwiftType::Integer),
+    property("Set", "isEmpty", "isEmpty", || SwiftType::Bool),
+    property("Set", "first", "first", || optional(element())),
+    method("Set", "contains(_:)", "contains", || function(vec![element()], SwiftType::Bool)),
+    method("Set", "union(_:)", "union", || function(vec![set()], set())),
+    method("Set", "intersection(_:)", "intersection", || function(vec![set()], set())),
+    method("Set", "subtracting(_:)", "subtracting", || function(vec![set()], set())),
+    method("Set", "symmetricDifference(_:)", "symmetricDifference", || function(vec![set()], set())),
+    method("Set", "isSubset(of:)", "isSubset", || function(vec![set()], SwiftType::Bool)),
+    method("Set", "isSuperset(of:)", "isSuperset", || function(vec![set()], SwiftType::Bool)),
+    method("Set", "isDisjoint(with:)", "isDisjoint", || function(vec![set()], SwiftType::Bool)),
+    method("Set", "map(_:)", "map", || function(vec![function(vec![element()], parameter("T"))], array(parameter("T")))),
+    method("Set", "filter(_:)", "filterSet", || function(vec![predicate()], set())),
+    method("Set", "reduce(_:_:)", "reduce", || function(vec![parameter("T"), function(vec![parameter("T"), element()], parameter("T"))], parameter("T"))),
+    method("Set", "forEach(_:)", "forEach", || function(vec![function(vec![element()], SwiftType::void())], SwiftType::void())),
+    method("Set", "sorted()", "sorted", || function(vec![], array(element()))),
+    method("Set", "sorted(by:)", "sorted", || function(vec![ordering()], array(element()))),
+    method("Set", "contains(where:)", "containsWhere", || function(vec![predicate()], SwiftType::Bool)),
+    method("Set", "first(where:)", "firstWhere", || function(vec![predicate()], optional(element()))),
+    mutating("Set", "insert(_:)", "insert", || function(vec![element()], labeled(&["inserted", "memberAfterInsert"], vec![SwiftType::Bool, element()]))),
+    mutating("Set", "remove(_:)", "remove", || function(vec![element()], optional(element()))),
+    mutating("Set", "formUnion(_:)", "formUnion", || function(vec![set()], SwiftType::void())),
+    mutating("Set", "removeAll()", "removeAll", || function(vec![], SwiftType::void())),
+    // Optionals, represented by their value or `null`.
+    method("Optional", "map(_:)", "mapOptional", || function(vec![function(vec![parameter("Wrapped")], parameter("T"))], optional(parameter("T")))),
+    method("Optional", "flatMap(_:)", "flatMapOptional", || function(vec![function(vec![parameter("Wrapped")], optional(parameter("T")))], optional(parameter("T")))),
+    // The hasher `hash(into:)` feeds.
+    mutating("Hasher", "combine(_:)", "combine", || function(vec![parameter("T")], SwiftType::void())),
+    method("Hasher", "finalize()", "finalize", || function(vec![], SwiftType::Integer)),
+    // Encoders and the containers `encode(to:)` stores values in. Keyed containers are generic over
+    // their `CodingKey` type, `Key`; nested keyed containers take theirs as `T`.
+    property("Encoder", "codingPath", "codingPath", coding_path),
+    method("Encoder", "container(keyedBy:)", "keyedEncodingContainer", || function(vec![metatype(parameter("Key"))], keyed("KeyedEncodingContainer", parameter("Key")))),
+    method("Encoder", "unkeyedContainer()", "unkeyedEncodingContainer", || function(vec![], SwiftType::named("UnkeyedEncodingContainer"))),
+    method("Encoder", "singleValueContainer()", "singleValueEncodingContainer", || function(vec![], SwiftType::named("SingleValueEncodingContainer"))),
+    property("KeyedEncodingContainer", "codingPath", "codingPath", coding_path),
+    mutating("KeyedEncodingContainer", "encode(_:forKey:)", "encodeForKey", || throwing(vec![parameter("T"), parameter("Key")], SwiftType::void())),
+    mutating("KeyedEncodingContainer", "encodeIfPresent(_:forKey:)", "encodeIfPresentForKey", || throwing(vec![optional(parameter("T")), parameter("Key")], SwiftType::void())),
+    mutating("KeyedEncodingContainer", "encodeNil(forKey:)", "encodeNilForKey", || throwing(vec![parameter("Key")], SwiftType::void())),
+    mutating("KeyedEncodingContainer", "nestedContainer(keyedBy:forKey:)", "nestedContainerForKey", || function(vec![metatype(parameter("T")), parameter("Key")], keyed("KeyedEncodingContainer", parameter("T")))),
+    mutating("KeyedEncodingContainer", "nestedUnkeyedContainer(forKey:)", "nestedUnkeyedContainerForKey", || function(vec![parameter("Key")], SwiftType::named("UnkeyedEncodingContainer"))),
+    mutating("KeyedEncodingContainer", "superEncoder()", "superCoder", || function(vec![], SwiftType::named("Encoder"))),
+    property("UnkeyedEncodingContainer", "codingPath", "codingPath", coding_path),
+    property("UnkeyedEncodingContainer", "count", "containerCount", || SwiftType::Integer),
+    mutating("UnkeyedEncodingContainer", "encode(_:)", "encodeValue", || throwing(vec![parameter("T")], SwiftType::void())),
+    mutating("UnkeyedEncodingContainer", "encodeNil()", "encodeNil", || throwing(vec![], SwiftType::void())),
+    mutating("UnkeyedEncodingContainer", "nestedContainer(keyedBy:)", "nestedContainer", || function(vec![metatype(parameter("T"))], keyed("KeyedEncodingContainer", parameter("T")))),
+    mutating("UnkeyedEncodingContainer", "nestedUnkeyedContainer()", "nestedUnkeyedContainer", || function(vec![], SwiftType::named("UnkeyedEncodingContainer"))),
+    property("SingleValueEncodingContainer", "codingPath", "codingPath", coding_path),
+    mutating("SingleValueEncodingContainer", "encode(_:)", "encodeValue", || throwing(vec![parameter("T")], SwiftType::void())),
+    mutating("SingleValueEncodingContainer", "encodeNil()", "encodeNil", || throwing(vec![], SwiftType::void())),
+    property("CodingKey", "stringValue", "stringValue", || SwiftType::String),
+    property("CodingKey", "intValue", "intValue", || optional(SwiftType::Integer)),
+    // Decoders and the containers `init(from:)` reads values from.
+    property("Decoder", "codingPath", "codingPath", coding_path),
+    method("Decoder", "container(keyedBy:)", "keyedDecodingContainer", || throwing(vec![metatype(parameter("Key"))], keyed("KeyedDecodingContainer", parameter("Key")))),
+    method("Decoder", "unkeyedContainer()", "unkeyedDecodingContainer", || throwing(vec![], SwiftType::named("UnkeyedDecodingContainer"))),
+    method("Decoder", "singleValueContainer()", "singleValueDecodingContainer", || throwing(vec![], SwiftType::named("SingleValueDecodingContainer"))),
+    property("KeyedDecodingContainer", "codingPath", "codingPath", coding_path),
+    property("KeyedDecodingContainer", "allKeys", "allKeys", || array(parameter("Key"))),
+    method("KeyedDecodingContainer", "contains(_:)", "containsKey", || function(vec![parameter("Key")], SwiftType::Bool)),
+    method("KeyedDecodingContainer", "decode(_:forKey:)", "decodeForKey", || throwing(vec![metatype(parameter("T")), parameter("Key")], parameter("T"))),
+    method("KeyedDecodingContainer", "decodeIfPresent(_:forKey:)", "decodeIfPresentForKey", || throwing(vec![metatype(parameter("T")), parameter("Key")], optional(parameter("T")))),
+    method("KeyedDecodingContainer", "decodeNil(forKey:)", "decodeNilForKey", || throwing(vec![parameter("Key")], SwiftType::Bool)),
+    method("KeyedDecodingContainer", "nestedContainer(keyedBy:forKey:)", "nestedContainerForKey", || throwing(vec![metatype(parameter("T")), parameter("Key")], keyed("KeyedDecodingContainer", parameter("T")))),
+    method("KeyedDecodingContainer", "nestedUnkeyedContainer(forKey:)", "nestedUnkeyedContainerForKey", || throwing(vec![parameter("Key")], SwiftType::named("UnkeyedDecodingContainer"))),
+    method("KeyedDecodingContainer", "superDecoder()", "superCoder", || throwing(vec![], SwiftType::named("Decoder"))),
+    property("UnkeyedDecodingContainer", "codingPath", "codingPath", coding_path),
+    property("UnkeyedDecodingContainer", "count", "containerCount", || optional(SwiftType::Integer)),
+    property("UnkeyedDecodingContainer", "isAtEnd", "isAtEnd", || SwiftType::Bool),
+    property("UnkeyedDecodingContainer", "currentIndex", "currentIndex", || SwiftType::Integer),
+    mutating("UnkeyedDecodingContainer", "decode(_:)", "decodeValue", || throwing(vec![metatype(parameter("T"))], parameter("T"))),
+    mutating("UnkeyedDecodingContainer", "decodeIfPresent(_:)", "decodeIfPresent", || throwing(vec![metatype(parameter("T"))], optional(parameter("T")))),
+    mutating("UnkeyedDecodingContainer", "decodeNil()", "decodeNil", || throwing(vec![], SwiftType::Bool)),
+    mutating("UnkeyedDecodingContainer", "nestedContainer(keyedBy:)", "nestedContainer", || throwing(vec![metatype(parameter("T"))], keyed("KeyedDecodingContainer", parameter("T")))),
+    mutating("UnkeyedDecodingContainer", "nestedUnkeyedContainer()", "nestedUnkeyedContainer", || throwing(vec![], SwiftType::named("UnkeyedDecodingContainer"))),
+    property("SingleValueDecodingContainer", "codingPath", "codingPath", coding_path),
+    method("SingleValueDecodingContainer", "decode(_:)", "decodeValue", || throwing(vec![metatype(parameter("T"))], parameter("T"))),
+    method("SingleValueDecodingContainer", "decodeNil()", "decodeNil", || function(vec![], SwiftType::Bool)),
+    property("DecodingError.Context", "codingPath", "codingPath", coding_path),
+    property("DecodingError.Context", "debugDescription", "contextDescription", || SwiftType::String),
+    property("EncodingError.Context", "codingPath", "codingPath", coding_path),
+    property("EncodingError.Context", "debugDescription", "contextDescription", || SwiftType::String),
+    // JSON coding, whose strategies are set by assigning these properties.
+    property("JSONEncoder", "outputFormatting", "outputFormatting", || nested("JSONEncoder", "OutputFormatting")),
+    property("JSONEncoder", "keyEncodingStrategy", "keyEncodingStrategy", || nested("JSONEncoder", "KeyEncodingStrategy")),
+    property("JSONEncoder", "dateEncodingStrategy", "dateEncodingStrategy", || nested("JSONEncoder", "DateEncodingStrategy")),
+    method("JSONEncoder", "encode(_:)", "encodeJSON", || throwing(vec![parameter("T")], data())),
+    property("JSONDecoder", "keyDecodingStrategy", "keyDecodingStrategy", || nested("JSONDecoder", "KeyDecodingStrategy")),
+    property("JSONDecoder", "dateDecodingStrategy", "dateDecodingStrategy", || nested("JSONDecoder", "DateDecodingStrategy")),
+    method("JSONDecoder", "decode(_:from:)", "decodeJSON", || throwing(vec![metatype(parameter("T")), data()], parameter("T"))),
+    // `Data` is an array of bytes, and `Date` an instant.
+    property("Data", "count", "count", || SwiftType::Integer),
+    property("Data", "isEmpty", "isEmpty", || SwiftType::Bool),
+    property("Date", "timeIntervalSince1970", "timeIntervalSince1970", || SwiftType::Double),
+    method("Date", "timeIntervalSince(_:)", "timeIntervalSince", || function(vec![date()], SwiftType::Double)),
+    // Children are the labels and values of a mirror's stored properties, elements or payload.
+    property("Mirror", "children", "mirrorChildren", || array(nested("Mirror", "Child"))),
+    property("Mirror", "displayStyle", "displayStyle", || optional(nested("Mirror", "DisplayStyle"))),
+    property("Mirror", "subjectType", "subjectType", || metatype(SwiftType::Any)),
+    property("Mirror", "superclassMirror", "superclassMirror", || optional(SwiftType::named("Mirror"))),
+    property("Mirror.Child", "label", "childLabel", || optional(SwiftType::String)),
+    property("Mirror.Child", "value", "childValue", || SwiftType::Any),
+];
+
+const FUNCTIONS: &[StandardMember] = &[
+    method("", "print(_:separator:terminator:)", "printItems", || function(vec![SwiftType::Any, SwiftType::String, SwiftType::String], SwiftType::void())),
+    method("", "debugPrint(_:separator:terminator:)", "printItems", || function(vec![SwiftType::Any, SwiftType::String, SwiftType::String], SwiftType::void())),
+    method("", "dump(_:name:indent:maxDepth:maxItems:)", "dump", || {
+        let options = vec![optional(SwiftType::String), SwiftType::Integer, SwiftType::Integer, SwiftType::Integer];
+        function([vec![parameter("T")], options].concat(), parameter("T"))
+    }),
+    method("", "zip(_:_:)", "zip", || function(vec![array(parameter("T")), array(parameter("U"))], array(SwiftType::Tuple(vec![parameter("T"), parameter("U")])))),
+    // Strides are arrays of their values.
+    method("", "stride(from:to:by:)", "strideTo", || function(vec![parameter("T"), parameter("T"), parameter("T")], array(parameter("T")))),
+    method("", "stride(from:through:by:)", "strideThrough", || function(vec![parameter("T"), parameter("T"), parameter("T")], array(parameter("T")))),
+    method("", "min(_:_:)", "min", || function(vec![parameter("T"), parameter("T")], parameter("T"))),
+    method("", "max(_:_:)", "max", || function(vec![parameter("T"), parameter("T")], parameter("T"))),
+    method("", "abs(_:)", "abs", || function(vec![parameter("T")], parameter("T"))),
+    // Initializers of standard library types that aren't conversions between values.
+    method("", "JSONEncoder()", "makeJSONEncoder", || function(vec![], SwiftType::named("JSONEncoder"))),
+    method("", "JSONDecoder()", "makeJSONDecoder", || function(vec![], SwiftType::named("JSONDecoder"))),
+    method("", "Data(_:)", "makeData", || function(vec![parameter("T")], data())),
+    method("", "Date()", "now", || function(vec![], date())),
+    method("", "Date(timeIntervalSince1970:)", "dateSince1970", || function(vec![SwiftType::Double], date())),
+    method("", "Mirror(reflecting:)", "makeMirror", || function(vec![SwiftType::Any], SwiftType::named("Mirror"))),
+];
+
+/// The standard library types the runtime exports a value for, which holds their nested types and
+/// static members: `JSONEncoder.OutputFormatting.prettyPrinted`, `DecodingError.dataCorrupted(_:)`.
+const TYPES: &[&str] = &["JSONEncoder", "JSONDecoder", "DecodingError", "EncodingError", "UTF8", "Mirror"];
+
+/// A protocol of the standard library whose witnesses the runtime calls, e.g. those of `Hashable`
+/// to key dictionaries by Swift equality.
+pub struct StandardProtocol {
+    pub name: &'static str,
+    pub inherited: &'static [&'static str],
+    /// The signatures of the requirements, or names of property requirements. Operators like
+    /// `==(_:_:)` are static methods.
+    pub requirements: &'static [&'static str],
+}
+
+const PROTOCOLS: &[StandardProtocol] = &[
+    StandardProtocol { name: "Equatable", inherited: &[], requirements: &["==(_:_:)"] },
+    StandardProtocol { name: "Hashable", inherited: &["Equatable"], requirements: &["hash(into:)"] },
+    StandardProtocol { name: "Comparable", inherited: &["Equatable"], requirements: &["<(_:_:)"] },
+    StandardProtocol { name: "Encodable", inherited: &[], requirements: &["encode(to:)"] },
+    StandardProtocol { name: "Decodable", inherited: &[], requirements: &["init(from:)"] },
+    StandardProtocol { name: "Codable", inherited: &["Encodable", "Decodable"], requirements: &[] },
+    // Coding keys are named by their `String` raw value, or else their case name.
+    StandardProtocol { name: "CodingKey", inherited: &[], requirements: &[] },
+    StandardProtocol { name: "CustomStringConvertible", inherited: &[], requirements: &["description"] },
+    StandardProtocol { name: "CustomDebugStringConvertible", inherited: &[], requirements: &["debugDescription"] },
+];
+
+/// The standard library protocol of this name followed by those it inherits, e.g. `Hashable` then
+/// `Equatable`. Empty for other names.
+pub fn protocols(name: &str) -> Vec<&'static StandardProtocol> {
+    let Some(protocol) = PROTOCOLS.iter().find(|protocol| protocol.name == name) else { return Vec::new() };
+    let inherited = protocol.inherited.iter().flat_map(|name| protocols(name));
+    std::iter::once(protocol).chain(inherited).collect()
+}
+
+/// True if the runtime exports a value named after this standard library type.
+pub fn is_runtime_type(name: &str) -> bool {
+    TYPES.contains(&name)
+}
+
+/// The member of a standard library type with this signature, e.g. `index(after:)`.
+pub fn member(ty: &SwiftType, signature: &str) -> Option<&'static StandardMember> {
+    let owner = owner(ty);
+    MEMBERS.iter().find(|member| member.owner == owner && member.signature == signature)
+}
+
+/// The members of a standard library type with this name, with any labels.
+pub fn members<'n>(ty: &SwiftType, name: &'n str) -> impl Iterator<Item = &'static StandardMember> + 'n {
+    let owner = owner(ty);
+    MEMBERS.iter().filter(move |member| member.owner == owner && member.name() == name)
+}
+
+/// True if the standard library declares members for values of this type.
+pub fn has_members(ty: &SwiftType) -> bool {
+    let owner = owner(ty);
+    MEMBERS.iter().any(|member| member.owner == owner)
+}
+
+/// True if the standard library declares a member of this name, with any labels, for values of
+/// this type.
+pub fn has_member(ty: &SwiftType, name: &str) -> bool {
+    members(ty, name).next().is_some()
+}
+
+/// The type of a property of a standard library type.
+pub fn property_type(ty: &SwiftType, name: &str) -> Option<SwiftType> {
+    let member = member(ty, name)?;
+    Some(substitution(Some(ty)).apply(&(member.ty)()))
+}
+
+/// The global function a call with these argument labels reaches.
+pub fn global_function(name: &str, labels: &[Option<&str>]) -> Option<&'static StandardMember> {
+    FUNCTIONS.iter().find(|function| function.name() == name && accepts(function, labels))
+}
+
+/// True if the standard library declares a global function of this name, with any labels.
+pub fn has_global_function(name: &str) -> bool {
+    FUNCTIONS.iter().any(|function| function.name() == name)
+}
+
+/// True if a call with these labels matches the function's parameters. `print` and `debugPrint`
+/// take any number of items, and `min` and `max` two or more values; the labeled parameters of
+/// these and of `dump` have default values, so calls may leave any of them out.
+fn accepts(function: &StandardMember, labels: &[Option<&str>]) -> bool {
+    let parameters = function.labels();
+    let is_defaulted = |labels: &[Option<&str>]| {
+        let mut defaults = parameters.iter().skip(1);
+        labels.iter().all(|label| label.is_some() && defaults.any(|parameter| parameter == label))
+    };
+    match function.name() {
+        "print" | "debugPrint" => is_defaulted(&labels[labels.iter().take_while(|label| label.is_none()).count()..]),
+        "min" | "max" => labels.len() >= 2 && labels.iter().all(Option::is_none),
+        "dump" => labels.split_first().is_some_and(|(value, options)| value.is_none() && is_defaulted(options)),
+        _ => labels == parameters.as_slice(),
+    }
+}
+
+/// Binds the generic parameters members of a standard library type are written with to the
+/// type's arguments, leaving those of generic methods to be inferred from a call's arguments.
+pub fn substitution(receiver: Option<&SwiftType>) -> Substitution {
+    let mut substitution = Substitution::new(["Element", "Key", "Value", "Wrapped", "T", "U"].map(str::to_string));
+    match receiver {
+        Some(SwiftType::Dictionary(key, value)) => {
+            substitution.bind("Key", *key.clone());
+            substitution.bind("Value", *value.clone());
+        }
+        Some(SwiftType::Optional(wrapped)) => substitution.bind("Wrapped", *wrapped.clone()),
+        Some(SwiftType::Generic(container, arguments)) if container.starts_with("Keyed") => {
+            if let [key] = arguments.as_slice() {
+                substitution.bind("Key", key.clone());
+            }
+        }
+        _ => {}
+    }
+    if let Some(element) = receiver.and_then(element_type) {
+        substitution.bind("Element", element);
+    }
+    substitution
+}
+
+/// The type of the elements a `for`-`in` loop over a standard library sequence binds.
+pub fn element_type(ty: &SwiftType) -> Option<SwiftType> {
+    match ty {
+        SwiftType::Array(element) | SwiftType::Set(element) => Some(*element.clone()),
+        SwiftType::Dictionary(key, value) => Some(labeled(&["key", "value"], vec![*key.clone(), *value.clone()])),
+        SwiftType::String => Some(SwiftType::Character),
+        SwiftType::Nested(_, view) => match view.as_str() {
+            "UTF8View" => Some(uint(IntegerType::UInt8)),
+            "UTF16View" => Some(uint(IntegerType::UInt16)),
+            "UnicodeScalarView" => Some(unicode_scalar()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The name members of a type are listed under: the type's own, or that of a generic collection.
+fn owner(ty: &SwiftType) -> String {
+    match ty {
+        SwiftType::Array(_) => "Array".to_string(),
+        SwiftType::Dictionary(..) => "Dictionary".to_string(),
+        SwiftType::Set(_) => "Set".to_string(),
+        SwiftType::Optional(_) => "Optional".to_string(),
+        SwiftType::Generic(name, _) => name.clone(),
+        SwiftType::Existential(constraint) => owner(constraint),
+        ty => ty.to_string(),
+    }
+}
+
+fn function(parameters: Vec<SwiftType>, result: SwiftType) -> SwiftType {
+    SwiftType::Function(parameters, Box::new(result), FunctionTypeAttributes::default())
+}
+
+fn throwing(parameters: Vec<SwiftType>, result: SwiftType) -> SwiftType {
+    SwiftType::Function(parameters, Box::new(result), FunctionTypeAttributes { is_throwing: true, ..FunctionTypeAttributes::default() })
+}
+
+fn parameter(name: &str) -> SwiftType {
+    SwiftType::Custom(name.to_string())
+}
+
+fn element() -> SwiftType {
+    parameter("Element")
+}
+
+fn predicate() -> SwiftType {
+    function(vec![element()], SwiftType::Bool)
+}
+
+fn ordering() -> SwiftType {
+    function(vec![element(), element()], SwiftType::Bool)
+}
+
+fn labeled(labels: &[&str], elements: Vec<SwiftType>) -> SwiftType {
+    SwiftType::LabeledTuple(labels.iter().map(|label| label.to_string()).collect(), elements)
+}
+
+fn array(element: SwiftType) -> SwiftType {
+    SwiftType::Array(Box::new(element))
+}
+
+fn set() -> SwiftType {
+    SwiftType::Set(Box::new(element()))
+}
+
+fn dictionary(key: SwiftType, value: SwiftType) -> SwiftType {
+    SwiftType::Dictionary(Box::new(key), Box::new(value))
+}
+
+fn string_index() -> SwiftType {
+    SwiftType::Nested(Box::new(SwiftType::String), "Index".to_string())
+}
+
+fn optional(ty: SwiftType) -> SwiftType {
+    SwiftType::Optional(Box::new(ty))
+}
+
+fn uint(integer: IntegerType) -> SwiftType {
+    SwiftType::SizedInteger(integer)
+}
+
+fn unicode_scalar() -> SwiftType {
+    SwiftType::Nested(Box::new(SwiftType::Custom("Unicode".to_string())), "Scalar".to_string())
+}
+
+fn nested(owner: &str, name: &str) -> SwiftType {
+    SwiftType::Nested(Box::new(SwiftType::named(owner)), name.to_string())
+}
+
+fn metatype(instance: SwiftType) -> SwiftType {
+    SwiftType::Metatype(Box::new(instance))
+}
+
+fn data() -> SwiftType {
+    SwiftType::named("Data")
+}
+
+fn date() -> SwiftType {
+    SwiftType::named("Date")
+}
+
+fn coding_path() -> SwiftType {
+    array(SwiftType::Existential(Box::new(SwiftType::named("CodingKey"))))
+}
+
+/// A keyed container of this kind, for keys of type `key`.
+fn keyed(container: &str, key: SwiftType) -> SwiftType {
+    SwiftType::Generic(container.to_string(), vec![key])
+}
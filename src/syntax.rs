@@ -9,6 +9,8 @@ pub enum Expression {
     Identifier(expression::Identifier),
     /// Corresponds to literals in Swift. Example: `5`, `"Hello"`, `true`
     Literal(expression::Literal),
+    /// Corresponds to string literals with interpolated values in Swift. Example: `"Hello \(name)!"`
+    StringInterpolation(expression::StringInterpolationExpression),
     /// Corresponds to binary expressions in Swift. Example: `a + b`
     BinaryExpression(Box<expression::BinaryExpression>),
     /// Corresponds to unary expressions in Swift. Example: `-a`, `!flag`
@@ -35,8 +37,12 @@ pub enum Expression {
     PatternMatch(Box<expression::PatternMatchExpression>),
     /// Corresponds to key path expressions in Swift. Example: `\Person.name`
     KeyPath(expression::KeyPathExpression),
+    /// Corresponds to a type used as a value in Swift, other than a plain name. Example: `[String].self`
+    TypeExpression(SwiftType),
     /// Corresponds to assignment expressions in Swift. Example: `a = b`
     Assignment(Box<expression::AssignmentExpression>),
+    /// Corresponds to `try` expressions in Swift. Example: `try load()`, `try? load()`, `try! load()`
+    Try(Box<expression::TryExpression>),
 }
 
 pub mod expression {
@@ -64,7 +70,8 @@ pub mod expression {
         Float(f64),
         Bool(bool),
         String(String),
-        Character(char),
+        /// A single extended grapheme cluster, which may span several unicode scalars. Example: `"🇯🇵"`
+        Character(String),
         /// Represents `nil`.
         Nil,
     }
@@ -122,9 +129,20 @@ pub mod expression {
         pub parameters: Vec<ClosureParameter>, // Closure parameters, possibly with types.
         pub return_type: Option<Box<SwiftType>>, // Optional return type of the closure.
         pub is_escaping: bool, // True if the closure is marked with `@escaping`.
+        pub capture_list: Vec<CaptureListItem>, // Values captured when the closure is created, e.g. `[weak self, y = expr]`.
         pub body: Vec<super::Statement>, // The body of the closure as a sequence of statements.
     }
 
+    /// An entry of a closure capture list. Examples: `weak self`, `unowned x`, `y = expr`
+    #[derive(Debug, Clone)]
+    pub struct CaptureListItem {
+        pub ownership: super::declaration::ReferenceOwnership,
+        /// The name the value is bound to inside the closure.
+        pub name: String,
+        /// The captured value; `None` captures the variable of the same name, or `self`.
+        pub value: Option<Expression>,
+    }
+
     #[derive(Debug, Clone)]
     pub struct ClosureParameter {
         /// The name of the parameter.
@@ -141,11 +159,13 @@ pub mod expression {
         pub value: Box<Expression>,
     }
 
-    /// Represents a subscript expression in Swift. Example: `array[0]`
+    /// Represents a subscript expression in Swift. Examples: `array[0]`, `counts[word, default: 0]`
     #[derive(Debug, Clone)]
     pub struct SubscriptExpression {
         pub target: Box<Expression>,
         pub index: Box<Expression>,
+        /// The value a dictionary subscript gives for a missing key, instead of `nil`.
+        pub default_value: Option<Box<Expression>>,
     }
 
     /// Represents a conditional expression in Swift. Example: `a > b ? a : b`
@@ -188,6 +208,24 @@ pub mod expression {
         pub target_type: SwiftType,
     }
 
+    /// Represents a `try` expression in Swift, covering every call to its right. Example: `try? load(path)`
+    #[derive(Debug, Clone)]
+    pub struct TryExpression {
+        pub kind: TryKind,
+        pub expression: Box<Expression>,
+    }
+
+    /// How a `try` expression handles an error thrown from it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TryKind {
+        /// `try` propagates the error to the enclosing `do` or throwing function.
+        Propagating,
+        /// `try?` evaluates to `nil` instead.
+        Optional,
+        /// `try!` traps.
+        Forced,
+    }
+
     /// Represents a pattern match expression in Swift. Example: `case .some(let x)`
     #[derive(Debug, Clone)]
     pub struct PatternMatchExpression {
@@ -201,6 +239,29 @@ pub mod expression {
         pub type_name: Option<String>,
         pub path: Vec<String>,
     }
+
+    /// Represents a string literal with interpolated values in Swift. Example: `"\(x) plus \(y)"`
+    #[derive(Debug, Clone)]
+    pub struct StringInterpolationExpression {
+        pub segments: Vec<InterpolationSegment>,
+    }
+
+    impl StringInterpolationExpression {
+        /// The interpolated values, in order.
+        pub fn values(&self) -> impl Iterator<Item = &Expression> {
+            self.segments.iter().filter_map(|segment| match segment {
+                InterpolationSegment::Text(_) => None,
+                InterpolationSegment::Expression(expression) => Some(expression),
+            })
+        }
+    }
+
+    /// A part of an interpolated string literal: literal text, or the value of `\(...)`.
+    #[derive(Debug, Clone)]
+    pub enum InterpolationSegment {
+        Text(String),
+        Expression(Expression),
+    }
 }
 
 
@@ -222,6 +283,8 @@ pub enum Statement {
     If(Box<statement::IfStatement>),
     /// Corresponds to `for` loop in Swift.
     ForLoop(Box<statement::ForLoopStatement>),
+    /// Corresponds to `for-in` loop over a sequence in Swift.
+    ForIn(Box<statement::ForInStatement>),
     /// Corresponds to `while` loop in Swift.
     WhileLoop(Box<statement::WhileLoopStatement>),
     /// Corresponds to `repeat-while` loop in Swift.
@@ -243,6 +306,19 @@ pub enum Statement {
 #[derive(Debug, Clone)]
 pub struct StatementSequence(Vec<Statement>);
 
+impl StatementSequence {
+    pub fn new(statements: Vec<Statement>) -> Self {
+        StatementSequence(statements)
+    }
+    /// The statements in source order.
+    pub fn statements(&self) -> &[Statement] {
+        &self.0
+    }
+    pub fn statements_mut(&mut self) -> &mut Vec<Statement> {
+        &mut self.0
+    }
+}
+
 pub mod statement {
     use super::{Expression, StatementSequence};
 
@@ -280,6 +356,14 @@ pub mod statement {
         pub body: StatementSequence,
     }
 
+    /// Represents a `for-in` loop over the elements of a sequence in Swift. Example: `for c in name { ... }`
+    #[derive(Debug, Clone)]
+    pub struct ForInStatement {
+        pub variable: String,
+        pub sequence: Box<Expression>,
+        pub body: StatementSequence,
+    }
+
     /// Represents a `while` loop in Swift. Example: `while a > b { ... }`
     #[derive(Debug, Clone)]
     pub struct WhileLoopStatement {
@@ -368,7 +452,25 @@ pub mod statement {
     #[derive(Debug, Clone)]
     pub struct DoCatchStatement {
         pub body: StatementSequence,
-        pub catch_body: Vec<super::Statement>,
+        /// Tried in order; an error no clause matches propagates.
+        pub catch_clauses: Vec<CatchClause>,
+    }
+
+    /// A `catch` clause. Example: `catch VendingError.insufficientFunds(let needed) where needed > 1 { ... }`
+    #[derive(Debug, Clone)]
+    pub struct CatchClause {
+        /// The pattern the error must match; without one, the clause catches any error and binds it to `error`.
+        pub pattern: Option<Pattern>,
+        pub guard_expression: Option<Box<Expression>>,
+        pub body: StatementSequence,
+    }
+
+    impl CatchClause {
+        /// True if the clause catches every error: it has no pattern, or one that binds or ignores
+        /// the whole error, and no `where` clause.
+        pub fn is_exhaustive(&self) -> bool {
+            self.guard_expression.is_none() && matches!(self.pattern, None | Some(Pattern::Identifier(_) | Pattern::Wildcard))
+        }
     }
 
     /// Represents an assignment statement in Swift. Example: `a = b`
@@ -382,11 +484,15 @@ pub mod statement {
 
 
 /// A Swift type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SwiftType {
-    /// Represents an integer type in Swift. Example: `Int`
+    /// Represents the platform-width integer type in Swift. Example: `Int`
     Integer,
-    /// Represents a floating point type in Swift. Example: `Double`
+    /// Represents a fixed-width integer type, or `UInt`. Example: `UInt8`
+    SizedInteger(IntegerType),
+    /// Represents a double-precision floating point type in Swift. Example: `Double`
+    Double,
+    /// Represents a single-precision floating point type in Swift. Example: `Float`
     Float,
     /// Represents a boolean type in Swift. Example: `Bool`
     Bool,
@@ -396,16 +502,387 @@ pub enum SwiftType {
     Character,
     /// Represents an optional type in Swift. Example: `Int?`
     Optional(Box<SwiftType>),
+    /// Represents an optional that is unwrapped wherever it is used. Example: `Int!`
+    ImplicitlyUnwrappedOptional(Box<SwiftType>),
     /// Represents an array type in Swift. Example: `[Int]`
     Array(Box<SwiftType>),
     /// Represents a dictionary type in Swift. Example: `[String: Int]`
     Dictionary(Box<SwiftType>, Box<SwiftType>),
-    /// Represents a tuple type in Swift. Example: `(Int, String)`
+    /// Represents a set type in Swift. Example: `Set<Int>`
+    Set(Box<SwiftType>),
+    /// Represents a tuple type in Swift; the empty tuple is `Void`. Example: `(Int, String)`
     Tuple(Vec<SwiftType>),
-    /// Represents a function type in Swift. Example: `(Int, String) -> Bool`
-    Function(Vec<SwiftType>, Box<SwiftType>),
-    /// Represents a custom type in Swift. Example: `MyClass`
+    /// Represents a tuple type whose elements have labels, followed by their types.
+    /// Example: `(key: String, value: Int)`
+    LabeledTuple(Vec<String>, Vec<SwiftType>),
+    /// Represents a function type in Swift. Example: `(Int, String) async throws -> Bool`
+    Function(Vec<SwiftType>, Box<SwiftType>, FunctionTypeAttributes),
+    /// Represents the type of any value. Example: `Any`
+    Any,
+    /// Represents the type of any class instance. Example: `AnyObject`
+    AnyObject,
+    /// Represents the type of functions that never return. Example: `Never`
+    Never,
+    /// Represents the type of a type, whose value is the type itself. Example: `Int.Type`
+    Metatype(Box<SwiftType>),
+    /// Represents the type of a protocol itself, rather than of conforming types. Example: `Shape.Protocol`
+    ProtocolMetatype(Box<SwiftType>),
+    /// Represents a custom type in Swift, a generic parameter, or `Self` in protocols. Example: `MyClass`
     Custom(String),
+    /// Represents a generic type applied to type arguments. Example: `Result<String, Error>`
+    Generic(String, Vec<SwiftType>),
+    /// Represents a type named through another type. Example: `T.Element`, `Self.Index`
+    Nested(Box<SwiftType>, String),
+    /// Represents a boxed value of any type conforming to a protocol or composition. Example: `any Shape`
+    Existential(Box<SwiftType>),
+    /// Represents a single concrete type the declaration hides behind its constraints. Example: `some View`
+    Opaque(Box<SwiftType>),
+    /// Represents a type conforming to several protocols, and maybe inheriting from a class. Example: `Codable & Equatable`
+    Composition(Vec<SwiftType>),
+}
+
+impl SwiftType {
+    /// The type a name refers to when no declaration shadows it: the standard library types that
+    /// have their own variant, or a custom type.
+    pub fn named(name: &str) -> SwiftType {
+        match name {
+            "Int" => SwiftType::Integer,
+            "Double" => SwiftType::Double,
+            "Float" => SwiftType::Float,
+            "Bool" => SwiftType::Bool,
+            "String" => SwiftType::String,
+            "Character" => SwiftType::Character,
+            "Any" => SwiftType::Any,
+            "AnyObject" => SwiftType::AnyObject,
+            "Never" => SwiftType::Never,
+            "Void" => SwiftType::Tuple(Vec::new()),
+            name => match IntegerType::ALL.into_iter().find(|integer| integer.name() == name) {
+                Some(integer) => SwiftType::SizedInteger(integer),
+                None => SwiftType::Custom(name.to_string()),
+            },
+        }
+    }
+    /// The type a function without a declared result returns.
+    pub fn void() -> SwiftType {
+        SwiftType::Tuple(Vec::new())
+    }
+}
+
+/// The integer types of the standard library besides `Int`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerType {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    /// The unsigned platform-width integer, which is 64 bits wide like `Int`.
+    UInt,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+}
+
+impl IntegerType {
+    pub const ALL: [IntegerType; 9] = [
+        IntegerType::Int8, IntegerType::Int16, IntegerType::Int32, IntegerType::Int64,
+        IntegerType::UInt, IntegerType::UInt8, IntegerType::UInt16, IntegerType::UInt32, IntegerType::UInt64,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntegerType::Int8 => "Int8",
+            IntegerType::Int16 => "Int16",
+            IntegerType::Int32 => "Int32",
+            IntegerType::Int64 => "Int64",
+            IntegerType::UInt => "UInt",
+            IntegerType::UInt8 => "UInt8",
+            IntegerType::UInt16 => "UInt16",
+            IntegerType::UInt32 => "UInt32",
+            IntegerType::UInt64 => "UInt64",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            IntegerType::Int8 | IntegerType::UInt8 => 8,
+            IntegerType::Int16 | IntegerType::UInt16 => 16,
+            IntegerType::Int32 | IntegerType::UInt32 => 32,
+            IntegerType::Int64 | IntegerType::UInt | IntegerType::UInt64 => 64,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, IntegerType::Int8 | IntegerType::Int16 | IntegerType::Int32 | IntegerType::Int64)
+    }
+}
+
+/// Attributes written on a function type. `@escaping` only appears on parameter types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FunctionTypeAttributes {
+    pub is_async: bool,
+    pub is_throwing: bool,
+    pub is_escaping: bool,
+}
+
+/// Spells the type as Swift source does, for diagnostics.
+impl std::fmt::Display for SwiftType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |types: &[SwiftType]| types.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+        // Postfix forms apply to a whole function type or composition in parentheses.
+        let operand = |ty: &SwiftType| match ty {
+            SwiftType::Function(..) | SwiftType::Composition(_) | SwiftType::Existential(_) | SwiftType::Opaque(_) => format!("({ty})"),
+            ty => ty.to_string(),
+        };
+        match self {
+            SwiftType::Integer => write!(f, "Int"),
+            SwiftType::SizedInteger(integer) => write!(f, "{}", integer.name()),
+            SwiftType::Double => write!(f, "Double"),
+            SwiftType::Float => write!(f, "Float"),
+            SwiftType::Bool => write!(f, "Bool"),
+            SwiftType::String => write!(f, "String"),
+            SwiftType::Character => write!(f, "Character"),
+            SwiftType::Optional(wrapped) => write!(f, "{}?", operand(wrapped)),
+            SwiftType::ImplicitlyUnwrappedOptional(wrapped) => write!(f, "{}!", operand(wrapped)),
+            SwiftType::Array(element) => write!(f, "[{element}]"),
+            SwiftType::Dictionary(key, value) => write!(f, "[{key}: {value}]"),
+            SwiftType::Set(element) => write!(f, "Set<{element}>"),
+            SwiftType::Tuple(elements) => write!(f, "({})", list(elements)),
+            SwiftType::LabeledTuple(labels, elements) => {
+                let elements = labels.iter().zip(elements).map(|(label, element)| format!("{label}: {element}"));
+                write!(f, "({})", elements.collect::<Vec<_>>().join(", "))
+            }
+            SwiftType::Function(parameters, result, attributes) => {
+                if attributes.is_escaping {
+                    write!(f, "@escaping ")?;
+                }
+                write!(f, "({})", list(parameters))?;
+                if attributes.is_async {
+                    write!(f, " async")?;
+                }
+                if attributes.is_throwing {
+                    write!(f, " throws")?;
+                }
+                write!(f, " -> {result}")
+            }
+            SwiftType::Any => write!(f, "Any"),
+            SwiftType::AnyObject => write!(f, "AnyObject"),
+            SwiftType::Never => write!(f, "Never"),
+            SwiftType::Metatype(instance) => write!(f, "{}.Type", operand(instance)),
+            SwiftType::ProtocolMetatype(protocol) => write!(f, "{}.Protocol", operand(protocol)),
+            SwiftType::Custom(name) => write!(f, "{name}"),
+            SwiftType::Generic(name, arguments) => write!(f, "{name}<{}>", list(arguments)),
+            SwiftType::Nested(base, name) => write!(f, "{base}.{name}"),
+            SwiftType::Existential(constraint) => write!(f, "any {constraint}"),
+            SwiftType::Opaque(constraint) => write!(f, "some {constraint}"),
+            SwiftType::Composition(types) => {
+                write!(f, "{}", types.iter().map(ToString::to_string).collect::<Vec<_>>().join(" & "))
+            }
+        }
+    }
+}
+
+/// Parses a type as Swift source spells it: `[String: Int]?`, `(Int) async throws -> Bool`,
+/// `Set<UInt8>`, `any Shape & Named` or `T.Type`.
+impl std::str::FromStr for SwiftType {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let parse = || {
+            let mut parser = TypeParser { tokens: type_tokens(source)?, position: 0 };
+            let ty = parser.parse_type()?;
+            match parser.peek() {
+                None => Ok(ty),
+                Some(token) => Err(format!("unexpected '{token}'")),
+            }
+        };
+        parse().map_err(|message: String| format!("{message} in type '{source}'"))
+    }
+}
+
+/// Splits a type into names, `->`, and single punctuation characters.
+fn type_tokens(source: &str) -> Result<Vec<&str>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = source.trim_start();
+    while let Some(character) = rest.chars().next() {
+        let length = if character.is_alphanumeric() || character == '_' {
+            rest.find(|character: char| !character.is_alphanumeric() && character != '_').unwrap_or(rest.len())
+        } else if rest.starts_with("->") {
+            2
+        } else if "()[]<>,:?!.&@".contains(character) {
+            1
+        } else {
+            return Err(format!("unexpected '{character}'"));
+        };
+        tokens.push(&rest[..length]);
+        rest = rest[length..].trim_start();
+    }
+    Ok(tokens)
+}
+
+fn is_identifier(token: &str) -> bool {
+    token.starts_with(|character: char| character.is_alphabetic() || character == '_')
+}
+
+struct TypeParser<'s> {
+    tokens: Vec<&'s str>,
+    position: usize,
+}
+
+impl<'s> TypeParser<'s> {
+    fn peek(&self) -> Option<&'s str> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<&'s str> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let is_next = self.peek() == Some(token);
+        if is_next {
+            self.position += 1;
+        }
+        is_next
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        match self.peek() {
+            _ if self.eat(token) => Ok(()),
+            Some(found) => Err(format!("expected '{token}' but found '{found}'")),
+            None => Err(format!("expected '{token}'")),
+        }
+    }
+
+    /// A type, after `@escaping`, `any` or `some` when it has them.
+    fn parse_type(&mut self) -> Result<SwiftType, String> {
+        if self.eat("@") {
+            self.expect("escaping")?;
+            return match self.parse_type()? {
+                SwiftType::Function(parameters, result, attributes) => {
+                    Ok(SwiftType::Function(parameters, result, FunctionTypeAttributes { is_escaping: true, ..attributes }))
+                }
+                ty => Err(format!("'@escaping' applied to non-function type '{ty}'")),
+            };
+        }
+        if self.eat("any") {
+            return Ok(SwiftType::Existential(Box::new(self.parse_composition()?)));
+        }
+        if self.eat("some") {
+            return Ok(SwiftType::Opaque(Box::new(self.parse_composition()?)));
+        }
+        self.parse_composition()
+    }
+
+    /// A type, or several joined by `&`.
+    fn parse_composition(&mut self) -> Result<SwiftType, String> {
+        let first = self.parse_postfix()?;
+        if self.peek() != Some("&") {
+            return Ok(first);
+        }
+        let mut types = vec![first];
+        while self.eat("&") {
+            types.push(self.parse_postfix()?);
+        }
+        Ok(SwiftType::Composition(types))
+    }
+
+    /// A type followed by any of `?`, `!`, `.Type`, `.Protocol` and the names of nested types.
+    fn parse_postfix(&mut self) -> Result<SwiftType, String> {
+        let mut ty = self.parse_primary()?;
+        loop {
+            ty = if self.eat("?") {
+                SwiftType::Optional(Box::new(ty))
+            } else if self.eat("!") {
+                SwiftType::ImplicitlyUnwrappedOptional(Box::new(ty))
+            } else if self.eat(".") {
+                match self.next() {
+                    Some("Type") => SwiftType::Metatype(Box::new(ty)),
+                    Some("Protocol") => SwiftType::ProtocolMetatype(Box::new(ty)),
+                    Some(name) if is_identifier(name) => SwiftType::Nested(Box::new(ty), name.to_string()),
+                    _ => return Err("expected a member type name after '.'".to_string()),
+                }
+            } else {
+                return Ok(ty);
+            };
+        }
+    }
+
+    /// A named type, with generic arguments when it has them, or a type in brackets or parentheses.
+    /// The generic forms of the types with their own syntax, like `Array<Int>`, are that type.
+    fn parse_primary(&mut self) -> Result<SwiftType, String> {
+        match self.next() {
+            Some("(") => self.parse_parenthesized(),
+            Some("[") => {
+                let element = self.parse_type()?;
+                let ty = match self.eat(":") {
+                    true => SwiftType::Dictionary(Box::new(element), Box::new(self.parse_type()?)),
+                    false => SwiftType::Array(Box::new(element)),
+                };
+                self.expect("]")?;
+                Ok(ty)
+            }
+            Some(name) if is_identifier(name) => {
+                if !self.eat("<") {
+                    return Ok(SwiftType::named(name));
+                }
+                let mut arguments = vec![self.parse_type()?];
+                while self.eat(",") {
+                    arguments.push(self.parse_type()?);
+                }
+                self.expect(">")?;
+                Ok(match (name, arguments.as_slice()) {
+                    ("Optional", [wrapped]) => SwiftType::Optional(Box::new(wrapped.clone())),
+                    ("Array", [element]) => SwiftType::Array(Box::new(element.clone())),
+                    ("Set", [element]) => SwiftType::Set(Box::new(element.clone())),
+                    ("Dictionary", [key, value]) => SwiftType::Dictionary(Box::new(key.clone()), Box::new(value.clone())),
+                    _ => SwiftType::Generic(name.to_string(), arguments),
+                })
+            }
+            Some(token) => Err(format!("expected a type but found '{token}'")),
+            None => Err("expected a type".to_string()),
+        }
+    }
+
+    /// A tuple, or the parameters of a function type, after the opening parenthesis. Labels are
+    /// kept when every element of a tuple has one, and otherwise skipped; a single unlabeled
+    /// element is just that type.
+    fn parse_parenthesized(&mut self) -> Result<SwiftType, String> {
+        let mut elements = Vec::new();
+        let mut labels = Vec::new();
+        if !self.eat(")") {
+            loop {
+                // `label:`, or `_ name:` in a function type.
+                let ahead = |offset: usize| self.tokens.get(self.position + offset).copied();
+                let (label, label_length) = match (ahead(0), ahead(1), ahead(2)) {
+                    (Some(label), Some(":"), _) if is_identifier(label) => (Some(label), 2),
+                    (Some(label), Some(name), Some(":")) if is_identifier(label) && is_identifier(name) => (Some(label), 3),
+                    _ => (None, 0),
+                };
+                labels.push(label);
+                self.position += label_length;
+                elements.push(self.parse_type()?);
+                if self.eat(")") {
+                    break;
+                }
+                self.expect(",")?;
+            }
+        }
+        let is_async = self.eat("async");
+        let is_throwing = self.eat("throws");
+        if is_async || is_throwing || self.peek() == Some("->") {
+            self.expect("->")?;
+            let result = self.parse_type()?;
+            return Ok(SwiftType::Function(elements, Box::new(result), FunctionTypeAttributes { is_async, is_throwing, is_escaping: false }));
+        }
+        match labels.iter().copied().collect::<Option<Vec<_>>>() {
+            Some(labels) if !labels.is_empty() => Ok(SwiftType::LabeledTuple(labels.into_iter().map(str::to_string).collect(), elements)),
+            _ if elements.len() == 1 && labels == [None] => Ok(elements.remove(0)),
+            _ => Ok(SwiftType::Tuple(elements)),
+        }
+    }
 }
 
 /// A Swift declaration.
@@ -458,6 +935,12 @@ pub mod declaration {
         pub parameters: Vec<FunctionParameter>,
         pub return_type: Option<SwiftType>,
         pub is_throwing: bool, // True if the function can throw an error, false otherwise.
+        pub is_rethrowing: bool, // True for `rethrows` functions, which throw only errors their function arguments throw.
+        pub is_static: bool, // True for `static func` type methods.
+        pub is_class: bool, // True for overridable `class func` type methods; only valid in classes.
+        pub is_mutating: bool, // True for `mutating func` methods of structs and enums.
+        pub is_override: bool, // True for methods marked `override`, replacing a superclass method.
+        pub is_final: bool, // True for `final` methods, which subclasses cannot override.
         pub access_control: AccessControl, // The access level of the function.
         pub body: Option<StatementSequence>, // Optional body; for protocol method requirements, this may be None.
     }
@@ -466,20 +949,34 @@ pub mod declaration {
 
     /// Represents generic type parameters and their constraints in a function.
     /// Swift code example:
-    /// ```
+    /// ```swift
     /// func add<T: Numeric>(a: T, b: T) -> T { ... }
+    /// func allItemsMatch<C1: Container, C2: Container>(_ a: C1, _ b: C2) -> Bool
+    ///     where C1.Item == C2.Item, C1.Item: Equatable { ... }
     /// ```
     #[derive(Debug, Clone)]
     pub struct GenericsDeclaration {
         pub type_parameters: Vec<TypeParameter>,
+        /// Requirements of the `where` clause.
+        pub where_clause: Vec<GenericRequirement>,
     }
 
-    /// Represents a single generic type parameter and its optional constraint.
+    /// Represents a single generic type parameter and its constraints.
     /// For instance, `T: Numeric` in `func add<T: Numeric>(a: T, b: T) -> T { ... }`
     #[derive(Debug, Clone)]
     pub struct TypeParameter {
         pub name: String,
-        pub constraint: Option<SwiftType>, // Simplified; real-world might require a more complex representation.
+        /// Protocols the parameter conforms to, or the class it inherits from. Example: `T: Codable & Equatable`
+        pub constraints: Vec<SwiftType>,
+    }
+
+    /// A requirement of a `where` clause.
+    #[derive(Debug, Clone)]
+    pub enum GenericRequirement {
+        /// Example: `T.Item: Equatable`
+        Conformance { subject: SwiftType, constraint: SwiftType },
+        /// Example: `C1.Item == C2.Item`, `Element == Int`
+        SameType { left: SwiftType, right: SwiftType },
     }
 
     /// Represents a function parameter in Swift, including support for labels, default values, and variadic parameters.
@@ -491,8 +988,8 @@ pub mod declaration {
         pub internal_name: String,
         /// The type of the parameter.
         pub ty: SwiftType,
-        /// The default value of the parameter, represented as a String.
-        pub default_value: Option<String>,
+        /// The default value of the parameter, if any.
+        pub default_value: Option<Expression>,
         /// True if the parameter is variadic, false otherwise.
         pub is_variadic: bool,
         /// True if the parameter is an inout parameter, allowing modification of passed argument.
@@ -503,7 +1000,7 @@ pub mod declaration {
     
     /// Represents a struct declaration in Swift, including support for generics, protocol conformance, and initializers.
     /// Swift code example:
-    /// ```
+    /// ```swift
     /// struct Point<T: Numeric>: CustomStringConvertible {
     ///     var x: T, y: T
     ///
@@ -523,7 +1020,7 @@ pub mod declaration {
         pub generics: Option<GenericsDeclaration>,
         /// Protocol names to which the struct conforms.
         pub conformances: Vec<String>,
-        /// Includes variables and constants.
+        /// Includes stored and computed properties, both instance and type (`static`) properties.
         pub properties: Vec<VariablePropertyDeclaration>,
         /// Includes functions and computed properties.
         pub methods: Vec<FunDeclaration>,
@@ -533,7 +1030,7 @@ pub mod declaration {
     
     /// Represents an enum declaration in Swift, including associated values, generics, and raw values.
     /// Swift code example:
-    /// ```
+    /// ```swift
     /// enum Barcode {
     ///     case upc(Int, Int, Int, Int)
     ///     case qrCode(String)
@@ -547,14 +1044,27 @@ pub mod declaration {
     ///     case none
     ///     case some(Wrapped)
     /// }
+    ///
+    /// enum Beverage: CaseIterable {
+    ///     case coffee, tea, juice
+    ///     func describe() -> String { ... }
+    /// }
     /// ```
     #[derive(Debug, Clone)]
     pub struct EnumDeclaration {
         pub name: String,
         pub generics: Option<GenericsDeclaration>,
+        /// Protocol names to which the enum conforms.
+        pub conformances: Vec<String>,
         pub cases: Vec<EnumCase>,
         /// // For enums with raw values
         pub raw_type: Option<SwiftType>,
+        /// Computed properties and type properties; enums cannot have stored instance properties.
+        pub properties: Vec<VariablePropertyDeclaration>,
+        /// Includes functions, `mutating` methods, and type methods.
+        pub methods: Vec<FunDeclaration>,
+        /// Initializers.
+        pub initializers: Vec<InitializerDeclaration>,
     }
 
     /// Represents a single case in an enum. Enum cases in Swift can have associated values.
@@ -579,7 +1089,7 @@ pub mod declaration {
     
     /// Represents a class declaration in Swift, including generics, inheritance, protocol conformance, initializers, and deinitializers.
     /// Swift code example:
-    /// ```
+    /// ```swift
     /// class Vehicle {
     ///     var numberOfWheels: Int
     ///     var description: String {
@@ -615,11 +1125,15 @@ pub mod declaration {
         pub generics: Option<GenericsDeclaration>,
         /// Optional superclass name for inheritance.
         pub superclass: Option<String>,
+        /// The type arguments of a generic superclass. Example: `Int` in `class IntStack: Stack<Int>`
+        pub superclass_arguments: Vec<SwiftType>,
+        /// True for `final` classes, which cannot be subclassed.
+        pub is_final: bool,
         /// Protocols the class conforms to.
         pub conformances: Vec<String>,
-        /// Includes variables and constants.
+        /// Includes stored and computed properties, both instance and type (`static`) properties.
         pub properties: Vec<VariablePropertyDeclaration>,
-        /// Includes functions, computed properties, and overrides.
+        /// Includes functions, computed properties, overrides, and `static`/`class` methods.
         pub methods: Vec<FunDeclaration>,
         /// Initializers.
         pub initializers: Vec<InitializerDeclaration>,
@@ -630,7 +1144,7 @@ pub mod declaration {
     
     /// Represents a protocol declaration in Swift, including method requirements, property requirements, initializer requirements, and protocol inheritance.
     /// Swift code example:
-    /// ```
+    /// ```swift
     /// protocol FullyNamed {
     ///     var fullName: String { get }
     /// }
@@ -652,11 +1166,24 @@ pub mod declaration {
         pub name: String,
         /// Names of inherited protocols
         pub inherited_protocols: Vec<String>,
+        /// Placeholder types that conforming types choose. Requirements refer to them by name, and to
+        /// the conforming type as `Self`.
+        pub associated_types: Vec<AssociatedTypeDeclaration>,
         pub property_requirements: Vec<PropertyRequirement>,
         pub method_requirements: Vec<MethodRequirement>,
         pub initializer_requirements: Vec<InitializerRequirement>,
     }
 
+    /// Represents an associated type of a Swift protocol. Example: `associatedtype Item: Equatable = Int`
+    #[derive(Debug, Clone)]
+    pub struct AssociatedTypeDeclaration {
+        pub name: String,
+        /// Protocols the chosen type must conform to.
+        pub constraints: Vec<SwiftType>,
+        /// The type used when a conforming type doesn't determine one.
+        pub default: Option<SwiftType>,
+    }
+
     /// Represents a property requirement in a Swift protocol.
     #[derive(Debug, Clone)]
     pub struct PropertyRequirement {
@@ -674,6 +1201,8 @@ pub mod declaration {
         pub return_type: Option<SwiftType>,
         /// True for mutating methods in value types.
         pub is_mutating: bool,
+        /// True for methods that can throw an error.
+        pub is_throwing: bool,
     }
 
     /// Represents an initializer requirement in a Swift protocol.
@@ -687,7 +1216,7 @@ pub mod declaration {
     /// and protocol conformances.
     /// 
     /// Swift code example:
-    /// ```
+    /// ```swift
     /// extension Double {
     ///     var km: Double { return self * 1_000.0 }
     ///     var m: Double { return self }
@@ -711,8 +1240,10 @@ pub mod declaration {
         pub type_name: String,
         /// Protocols the extension conforms to
         pub conformances: Vec<String>,
-        /// Computed properties added by the extension
-        pub properties: Vec<VarDeclaration>,
+        /// Requirements of the `where` clause. Example: `extension Container where Item: Equatable`
+        pub where_clause: Vec<GenericRequirement>,
+        /// Computed properties and stored type properties added by the extension
+        pub properties: Vec<VariablePropertyDeclaration>,
         /// Methods added by the extension
         pub methods: Vec<FunDeclaration>,
         /// Initializers added by the extension
@@ -723,7 +1254,7 @@ pub mod declaration {
     /// Represents a type alias declaration in Swift.
     /// A type alias allows you to provide a new name for an existing type.
     /// Swift code example:
-    /// ```
+    /// ```swift
     /// typealias Point = (Int, Int)
     /// typealias CompletionHandler = (Result<String, Error>) -> Void
     /// ```
@@ -762,6 +1293,7 @@ pub mod declaration {
         pub name: String,
         pub ty: Option<SwiftType>,
         pub initial_value: Option<Expression>,
+        pub ownership: ReferenceOwnership,
     }
 
     /// Represents a variable declaration in Swift. Example: `var a: Int = 5`
@@ -770,14 +1302,144 @@ pub mod declaration {
         pub name: String,
         pub ty: Option<SwiftType>,
         pub initial_value: Option<Expression>,
+        pub ownership: ReferenceOwnership,
     }
 
-    /// Represents a variable declaration, specifically for computed properties in this context.
-    /// Computed properties in extensions can't store a value; they must provide a getter and optionally a setter.
+    /// How a variable refers to the class instance it holds. Example: `weak var delegate: Delegate?`
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum ReferenceOwnership {
+        /// Keeps the instance alive.
+        #[default]
+        Strong,
+        /// Doesn't keep the instance alive, and becomes `nil` once it is deallocated.
+        Weak,
+        /// Doesn't keep the instance alive, which must outlive the reference.
+        Unowned,
+    }
+
+    impl ReferenceOwnership {
+        /// The modifier keyword, for diagnostics.
+        pub fn keyword(self) -> &'static str {
+            match self {
+                ReferenceOwnership::Strong => "strong",
+                ReferenceOwnership::Weak => "weak",
+                ReferenceOwnership::Unowned => "unowned",
+            }
+        }
+    }
+
+    /// Represents a property of a type, either stored or computed.
+    /// Swift code example:
+    /// ```swift
+    /// class StepCounter {
+    ///     private(set) var resets = 0
+    ///     lazy var history: [Int] = loadHistory()
+    ///     var totalSteps: Int = 0 {
+    ///         willSet(newTotalSteps) { print("About to set totalSteps to \(newTotalSteps)") }
+    ///         didSet { print("Added \(totalSteps - oldValue) steps") }
+    ///     }
+    ///     var isIdle: Bool { return totalSteps == 0 }
+    ///     static let maximum = 100_000
+    /// }
+    /// ```
     #[derive(Debug, Clone)]
     pub struct VariablePropertyDeclaration {
         pub name: String,
-        pub ty: SwiftType,
+        /// The declared type; stored properties may leave it to be inferred from the initial value.
+        pub ty: Option<SwiftType>,
+        pub kind: PropertyKind,
+        /// True for stored `let` properties.
+        pub is_constant: bool,
+        /// `weak` or `unowned` for stored properties not keeping their instance alive.
+        pub ownership: ReferenceOwnership,
+        /// True for `static` type properties.
+        pub is_static: bool,
+        /// True for overridable `class var` computed type properties; only valid in classes.
+        pub is_class: bool,
+        /// True for properties marked `override`. An overriding property is either computed, or
+        /// stored with only observers, in which case it uses the superclass storage.
+        pub is_override: bool,
+        /// True for `final` properties, which subclasses cannot override.
+        pub is_final: bool,
+        /// The access level of the property.
+        pub access_control: AccessControl,
+        /// A more restrictive access level for the setter, e.g. `private(set)`.
+        pub setter_access_control: Option<AccessControl>,
+    }
+
+    impl VariablePropertyDeclaration {
+        pub fn stored(&self) -> Option<&StoredProperty> {
+            match &self.kind {
+                PropertyKind::Stored(stored) => Some(stored),
+                PropertyKind::Computed(_) => None,
+            }
+        }
+        /// True for stored properties that declare their own storage, unlike observing overrides.
+        pub fn has_storage(&self) -> bool {
+            self.stored().is_some() && !self.is_override
+        }
+        pub fn is_lazy(&self) -> bool {
+            self.stored().is_some_and(|stored| stored.is_lazy)
+        }
+        /// True for stored properties with `willSet` or `didSet` observers.
+        pub fn is_observed(&self) -> bool {
+            self.stored().map(|stored| stored.will_set.is_some() || stored.did_set.is_some()).unwrap_or(false)
+        }
+        /// True for stored properties that hold a value before any initializer runs: those with an
+        /// initial value, and optional `var`s, which start out as `nil`.
+        pub fn is_default_initialized(&self) -> bool {
+            match self.stored() {
+                Some(stored) => {
+                    stored.initial_value.is_some() || (!self.is_constant && matches!(self.ty, Some(SwiftType::Optional(_))))
+                }
+                None => false,
+            }
+        }
+        /// True for stored instance properties that every designated initializer must assign.
+        pub fn requires_initialization(&self) -> bool {
+            !self.is_static && self.has_storage() && !self.is_lazy() && !self.is_default_initialized()
+        }
+        /// True if the property can be assigned at all (ignoring access control).
+        pub fn is_settable(&self) -> bool {
+            match &self.kind {
+                PropertyKind::Stored(_) => !self.is_constant,
+                PropertyKind::Computed(computed) => computed.setter.is_some(),
+            }
+        }
+    }
+
+    /// Whether a property stores its value or computes it.
+    #[derive(Debug, Clone)]
+    pub enum PropertyKind {
+        Stored(StoredProperty),
+        Computed(Box<ComputedProperty>),
+    }
+
+    /// A property backed by storage. Example: `var speed: Int = 0`
+    #[derive(Debug, Clone)]
+    pub struct StoredProperty {
+        /// Required for `static` and `lazy` properties.
+        pub initial_value: Option<Expression>,
+        /// True for `lazy var`, whose initial value is evaluated on first access.
+        pub is_lazy: bool,
+        /// Runs before a new value is stored; binds the new value (default name `newValue`).
+        pub will_set: Option<PropertyObserver>,
+        /// Runs after a new value is stored; binds the previous value (default name `oldValue`).
+        pub did_set: Option<PropertyObserver>,
+    }
+
+    /// A `willSet` or `didSet` observer. Example: `willSet(newTotalSteps) { ... }`
+    #[derive(Debug, Clone)]
+    pub struct PropertyObserver {
+        /// The explicit name of the observer's parameter, if any.
+        pub parameter_name: Option<String>,
+        pub body: StatementSequence,
+    }
+
+    /// A property computed by a getter and an optional setter.
+    /// Computed properties in extensions can't store a value; they must provide a getter and optionally a setter.
+    #[derive(Debug, Clone)]
+    pub struct ComputedProperty {
         // The getter function for the computed property
         pub getter: FunDeclaration,
         // The setter function for the computed property, if any
@@ -792,8 +1454,14 @@ pub mod declaration {
         pub body: StatementSequence,
         // True for failable initializers (`init?`), false otherwise.
         pub is_failable: bool,
+        // True for initializers that can throw an error (`init() throws`), false otherwise.
+        pub is_throwing: bool,
         // True for convenience initializers, false for designated initializers.
         pub is_convenience: bool,
+        // True for `required` initializers, which every subclass must provide.
+        pub is_required: bool,
+        // True for initializers marked `override`, replacing a designated superclass initializer.
+        pub is_override: bool,
         // The access level of the initializer.
         pub access_control: AccessControl,
     }
@@ -806,7 +1474,8 @@ pub mod declaration {
     }
 
     /// Represents the accessibility level of the initializer, corresponding to Swift's access control keywords.
-    #[derive(Debug, Clone, PartialEq)]
+    /// Ordered from least to most restrictive.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
     pub enum AccessControl {
         Public,
         Internal,
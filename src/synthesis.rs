@@ -0,0 +1,618 @@
+use crate::sema::{NominalType, TypeTable};
+use crate::stdlib::{self, StandardProtocol};
+use crate::syntax::{Declaration, Expression, Statement, StatementSequence, SwiftType};
+use crate::syntax::declaration::{
+    AccessControl,
+    ClassDeclaration,
+    EnumCase,
+    EnumDeclaration,
+    FunDeclaration,
+    FunctionParameter,
+    InitializerDeclaration,
+    LetDeclaration,
+    ReferenceOwnership,
+    StructDeclaration,
+    TypeParameter,
+    VarDeclaration,
+    VariablePropertyDeclaration,
+};
+use crate::syntax::expression::{
+    Argument,
+    BinaryExpression,
+    CallExpression,
+    Identifier,
+    InfixIdentifier,
+    Literal,
+    MemberAccessExpression,
+    TryExpression,
+    TryKind,
+    TupleExpression,
+};
+use crate::syntax::statement::{
+    AssignmentStatement,
+    Case,
+    EnumCasePattern,
+    Pattern,
+    ReturnStatement,
+    SwitchStatement,
+    TuplePattern,
+};
+
+/// The members Swift declares implicitly for a type, and the declarations accompanying them.
+#[derive(Default)]
+struct Implicit {
+    initializers: Vec<InitializerDeclaration>,
+    methods: Vec<FunDeclaration>,
+    /// Declared after the type, like the coding keys its `Codable` conformance derives.
+    declarations: Vec<Declaration>,
+}
+
+/// Adds the members Swift declares implicitly, so that later passes see them as ordinary declarations.
+pub fn synthesize(program: &mut StatementSequence) {
+    let implicit = {
+        let table = TypeTable::new(program);
+        program
+            .statements()
+            .iter()
+            .map(|statement| {
+                let Statement::Declaration(declaration) = statement else { return Implicit::default() };
+                let mut implicit = Implicit::default();
+                match declaration.as_ref() {
+                    Declaration::Struct(decl) => {
+                        implicit.initializers.extend(memberwise_initializer(&table, decl));
+                        implicit.methods = derived_methods(&table, &decl.name);
+                    }
+                    Declaration::Enum(decl) => implicit.methods = derived_methods(&table, &decl.name),
+                    Declaration::Class(decl) => implicit.initializers.extend(default_initializer(decl)),
+                    _ => return implicit,
+                }
+                coding_members(&table, declaration, &mut implicit);
+                implicit
+            })
+            .collect::<Vec<_>>()
+    };
+    let statements = std::mem::take(program.statements_mut());
+    for (mut statement, implicit) in statements.into_iter().zip(implicit) {
+        if let Statement::Declaration(declaration) = &mut statement {
+            match declaration.as_mut() {
+                Declaration::Struct(decl) => {
+                    decl.initializers.extend(implicit.initializers);
+                    decl.methods.extend(implicit.methods);
+                }
+                Declaration::Enum(decl) => decl.methods.extend(implicit.methods),
+                Declaration::Class(decl) => {
+                    decl.initializers.extend(implicit.initializers);
+                    decl.methods.extend(implicit.methods);
+                }
+                _ => {}
+            }
+        }
+        program.statements_mut().push(statement);
+        let declarations = implicit.declarations.into_iter().map(|declaration| Statement::Declaration(Box::new(declaration)));
+        program.statements_mut().extend(declarations);
+    }
+}
+
+/// The implicit `init()` of a root class whose stored properties all have default values.
+/// Subclasses inherit their superclass initializers instead.
+fn default_initializer(decl: &ClassDeclaration) -> Option<InitializerDeclaration> {
+    let needs_initializer = decl.properties.iter().any(|property| property.requires_initialization());
+    if decl.superclass.is_some() || !decl.initializers.is_empty() || needs_initializer {
+        return None;
+    }
+    Some(InitializerDeclaration {
+        generics: None,
+        parameters: Vec::new(),
+        body: StatementSequence::new(Vec::new()),
+        is_failable: false,
+        is_throwing: false,
+        is_convenience: false,
+        is_required: false,
+        is_override: false,
+        access_control: AccessControl::Internal,
+    })
+}
+
+/// The implicit `init` of a struct without explicit initializers, taking each stored property in
+/// declaration order. Properties with a default value become defaulted parameters, except `let`
+/// constants with an initial value, which keep it; `lazy` properties initialize on first access.
+/// The initializer is `internal` unless a property or setter is more restrictive.
+fn memberwise_initializer(table: &TypeTable, decl: &StructDeclaration) -> Option<InitializerDeclaration> {
+    if !decl.initializers.is_empty() {
+        return None;
+    }
+    let mut parameters = Vec::new();
+    let mut body = Vec::new();
+    let mut access_control = AccessControl::Internal;
+    for property in decl.properties.iter().filter(|property| !property.is_static) {
+        let Some(stored) = property.stored() else { continue };
+        if stored.is_lazy || (property.is_constant && stored.initial_value.is_some()) {
+            continue;
+        }
+        access_control = access_control
+            .max(property.access_control)
+            .max(property.setter_access_control.unwrap_or(AccessControl::Internal));
+        let ty = table
+            .member_type(&decl.name, &property.name)
+            .unwrap_or_else(|| SwiftType::Custom("Any".to_string()));
+        parameters.push(FunctionParameter {
+            label: Some(property.name.clone()),
+            internal_name: property.name.clone(),
+            ty,
+            default_value: match &stored.initial_value {
+                Some(value) => Some(value.clone()),
+                None if property.is_default_initialized() => Some(Expression::Literal(Literal::Nil)),
+                None => None,
+            },
+            is_variadic: false,
+            is_inout: false,
+        });
+        body.push(Statement::Assignment(AssignmentStatement {
+            target: Box::new(Expression::MemberAccess(Box::new(MemberAccessExpression {
+                target: Box::new(Expression::SelfExpression),
+                member: property.name.clone(),
+            }))),
+            value: Box::new(Expression::Identifier(Identifier { name: property.name.clone() })),
+        }));
+    }
+    Some(InitializerDeclaration {
+        generics: None,
+        parameters,
+        body: StatementSequence::new(body),
+        is_failable: false,
+        is_throwing: false,
+        is_convenience: false,
+        is_required: false,
+        is_override: false,
+        access_control,
+    })
+}
+
+/// The `==`, `hash(into:)` and `<` Swift derives for a struct or enum conforming to `Equatable`,
+/// `Hashable` or `Comparable` without declaring them, when its stored properties or associated
+/// values all conform to the protocol too. `<` is only derived for enums without associated values.
+fn derived_methods(table: &TypeTable, type_name: &str) -> Vec<FunDeclaration> {
+    let Some(nominal) = table.nominal(type_name) else { return Vec::new() };
+    let mut methods = Vec::new();
+    for protocol in table.standard_conformances(type_name) {
+        for requirement in protocol.requirements {
+            if table.standard_witness(type_name, requirement).is_some() || !members_conform(table, nominal, protocol.name) {
+                continue;
+            }
+            let method = match (nominal, *requirement) {
+                (NominalType::Struct(decl), "==(_:_:)") => Some(struct_equality(table, decl)),
+                (NominalType::Struct(decl), "hash(into:)") => Some(struct_hash(table, decl)),
+                (NominalType::Enum(decl), "==(_:_:)") => Some(enum_equality(decl)),
+                (NominalType::Enum(decl), "hash(into:)") => Some(enum_hash(decl)),
+                (NominalType::Enum(decl), "<(_:_:)") if decl.cases.iter().all(|case| case.associated_values.is_empty()) => Some(enum_ordering(decl)),
+                _ => None,
+            };
+            methods.extend(method);
+        }
+    }
+    methods
+}
+
+/// The `encode(to:)` and `init(from:)` Swift derives for a struct or class listing `Encodable` or
+/// `Decodable` without declaring them, when the properties it codes conform too. Properties are
+/// coded in a keyed container by the cases of the enum `<Type>CodingKeys`, which a type declares to
+/// rename or leave out properties, and otherwise derives with a case per stored property; types
+/// can't nest declarations here, so the keys are named after their type rather than nested in it.
+/// Optional properties are left out when nil, and decoded as nil when missing.
+fn coding_members(table: &TypeTable, declaration: &Declaration, implicit: &mut Implicit) {
+    let (type_name, properties, is_required) = match declaration {
+        Declaration::Struct(decl) => (&decl.name, &decl.properties, false),
+        // Subclasses inherit the coding of their superclass.
+        Declaration::Class(decl) if decl.superclass.is_none() => (&decl.name, &decl.properties, !decl.is_final),
+        _ => return,
+    };
+    let Some(nominal) = table.nominal(type_name) else { return };
+    let declared = declared_protocols(table, type_name);
+    let derives = |requirement: &str, protocol: &str| {
+        declared.iter().any(|declared| declared.name == protocol) && table.standard_witness(type_name, requirement).is_none()
+    };
+    let (encodes, decodes) = (derives("encode(to:)", "Encodable"), derives("init(from:)", "Decodable"));
+    if !encodes && !decodes {
+        return;
+    }
+    let keys_name = format!("{type_name}CodingKeys");
+    let declared_keys = match table.nominal(&keys_name) {
+        Some(NominalType::Enum(decl)) => Some(decl),
+        _ => None,
+    };
+    let coded = stored_properties(table, type_name, properties)
+        .into_iter()
+        .filter(|(name, _)| !properties.iter().any(|property| property.name == *name && property.is_lazy()))
+        .filter(|(name, _)| declared_keys.is_none_or(|keys| keys.cases.iter().any(|case| case.name == *name)))
+        .collect::<Vec<_>>();
+    let parameters = nominal.generics().map(|generics| generics.type_parameters.as_slice()).unwrap_or_default();
+    let all_conform = |protocol: &str| coded.iter().all(|(_, ty)| conforms(table, parameters, ty, protocol));
+    let mut derived = false;
+    if encodes && all_conform("Encodable") {
+        implicit.methods.push(encode_method(&keys_name, &coded));
+        derived = true;
+    }
+    if decodes && all_conform("Decodable") {
+        // `let` constants with an initial value keep it.
+        let keeps_value = |name: &str| {
+            properties.iter().any(|property| property.name == name && property.is_constant && property.is_default_initialized())
+        };
+        let decoded = coded.iter().filter(|(name, _)| !keeps_value(name)).cloned().collect::<Vec<_>>();
+        implicit.initializers.push(decoding_initializer(&keys_name, &decoded, is_required));
+        derived = true;
+    }
+    if derived && declared_keys.is_none() {
+        implicit.declarations.push(Declaration::Enum(Box::new(coding_keys(&keys_name, &coded))));
+    }
+}
+
+/// True if code generation derives the enum's witness of `encode(to:)` or `init(from:)`, as it
+/// does `rawValue`: an enum with a raw type codes its raw value, and others a keyed container with
+/// the case name as its only key, holding the associated values by label, or `_0`, `_1`, ...
+pub fn derives_coding(table: &TypeTable, type_name: &str, requirement: &str) -> bool {
+    let protocol = match requirement {
+        "encode(to:)" => "Encodable",
+        "init(from:)" => "Decodable",
+        _ => return false,
+    };
+    let Some(nominal @ NominalType::Enum(_)) = table.nominal(type_name) else { return false };
+    declared_protocols(table, type_name).iter().any(|declared| declared.name == protocol)
+        && table.standard_witness(type_name, requirement).is_none()
+        && members_conform(table, nominal, protocol)
+}
+
+/// The standard library protocols the type's declaration and extensions list, and those they
+/// inherit; unlike `standard_conformances`, not those of its superclass, whose coding it inherits.
+fn declared_protocols(table: &TypeTable, type_name: &str) -> Vec<&'static StandardProtocol> {
+    let listed = table.nominal(type_name).map(|nominal| nominal.conformances()).unwrap_or_default();
+    let extensions = table.extensions(type_name).iter().flat_map(|extension| &extension.conformances);
+    listed.iter().chain(extensions).flat_map(|name| stdlib::protocols(name)).collect()
+}
+
+/// ```swift
+/// func encode(to encoder: Encoder) throws {
+///     var container = encoder.container(keyedBy: PointCodingKeys.self)
+///     try container.encode(self.x, forKey: PointCodingKeys.x)
+///     try container.encodeIfPresent(self.label, forKey: PointCodingKeys.label)
+/// }
+/// ```
+fn encode_method(keys_name: &str, coded: &[(String, SwiftType)]) -> FunDeclaration {
+    let container = call(member(identifier("encoder"), "container"), vec![("keyedBy", type_value(keys_name))]);
+    let mut body = vec![variable("container", container, false)];
+    for (name, ty) in coded {
+        let method = if is_optional(ty) { "encodeIfPresent" } else { "encode" };
+        let value = member(Expression::SelfExpression, name);
+        let encode = call(member(identifier("container"), method), vec![("", value), ("forKey", member(identifier(keys_name), name))]);
+        body.push(Statement::Expression(Box::new(attempt(encode))));
+    }
+    let encoder = parameter(Some("to"), "encoder", SwiftType::Custom("Encoder".to_string()));
+    FunDeclaration { is_throwing: true, ..method("encode", vec![encoder], None, false, body) }
+}
+
+/// ```swift
+/// init(from decoder: Decoder) throws {
+///     let container = try decoder.container(keyedBy: PointCodingKeys.self)
+///     self.x = try container.decode(Int.self, forKey: PointCodingKeys.x)
+///     self.label = try container.decodeIfPresent(String.self, forKey: PointCodingKeys.label)
+/// }
+/// ```
+fn decoding_initializer(keys_name: &str, decoded: &[(String, SwiftType)], is_required: bool) -> InitializerDeclaration {
+    let container = call(member(identifier("decoder"), "container"), vec![("keyedBy", type_value(keys_name))]);
+    let mut body = vec![variable("container", attempt(container), true)];
+    for (name, ty) in decoded {
+        let (method, ty) = match ty {
+            SwiftType::Optional(wrapped) | SwiftType::ImplicitlyUnwrappedOptional(wrapped) => ("decodeIfPresent", wrapped.as_ref().clone()),
+            ty => ("decode", ty.clone()),
+        };
+        let arguments = vec![("", Expression::TypeExpression(ty)), ("forKey", member(identifier(keys_name), name))];
+        body.push(Statement::Assignment(AssignmentStatement {
+            target: Box::new(member(Expression::SelfExpression, name)),
+            value: Box::new(attempt(call(member(identifier("container"), method), arguments))),
+        }));
+    }
+    InitializerDeclaration {
+        generics: None,
+        parameters: vec![parameter(Some("from"), "decoder", SwiftType::Custom("Decoder".to_string()))],
+        body: StatementSequence::new(body),
+        is_failable: false,
+        is_throwing: true,
+        is_convenience: false,
+        is_required,
+        is_override: false,
+        access_control: AccessControl::Internal,
+    }
+}
+
+/// `enum PointCodingKeys: CodingKey { case x, y }`
+fn coding_keys(keys_name: &str, coded: &[(String, SwiftType)]) -> EnumDeclaration {
+    EnumDeclaration {
+        name: keys_name.to_string(),
+        generics: None,
+        conformances: vec!["CodingKey".to_string()],
+        cases: coded.iter().map(|(name, _)| EnumCase { name: name.clone(), associated_values: Vec::new(), raw_value: None }).collect(),
+        raw_type: None,
+        properties: Vec::new(),
+        methods: Vec::new(),
+        initializers: Vec::new(),
+    }
+}
+
+fn is_optional(ty: &SwiftType) -> bool {
+    matches!(ty, SwiftType::Optional(_) | SwiftType::ImplicitlyUnwrappedOptional(_))
+}
+
+/// True if a type declared in the program can key a `Set` or `Dictionary`: it lists `Hashable` or
+/// a protocol inheriting it, or is an enum without associated values.
+pub fn is_hashable(table: &TypeTable, type_name: &str) -> bool {
+    conforms(table, &[], &SwiftType::Custom(type_name.to_string()), "Hashable")
+}
+
+/// True if the stored properties of a struct, or the associated values of an enum, all conform to
+/// the protocol.
+fn members_conform(table: &TypeTable, nominal: NominalType, protocol: &str) -> bool {
+    let generics = nominal.generics();
+    let conforms = |ty: &SwiftType| conforms(table, generics.map(|generics| generics.type_parameters.as_slice()).unwrap_or_default(), ty, protocol);
+    match nominal {
+        NominalType::Struct(decl) => stored_properties(table, &decl.name, &decl.properties).iter().all(|(_, ty)| conforms(ty)),
+        NominalType::Class(decl) => stored_properties(table, &decl.name, &decl.properties).iter().all(|(_, ty)| conforms(ty)),
+        NominalType::Enum(decl) => decl.cases.iter().flat_map(|case| &case.associated_values).all(|value| conforms(&value.ty)),
+        _ => false,
+    }
+}
+
+/// True if values of the type conform to a standard library protocol: the standard types that do,
+/// their collections and optionals, types listing the protocol or one inheriting it, enums without
+/// associated values for `Equatable` and `Hashable`, and type parameters constrained to it.
+fn conforms(table: &TypeTable, parameters: &[TypeParameter], ty: &SwiftType, protocol: &str) -> bool {
+    let lists = |type_name: &str| table.standard_conformances(type_name).iter().any(|listed| listed.name == protocol);
+    let is_coding = matches!(protocol, "Encodable" | "Decodable");
+    match ty {
+        SwiftType::Integer | SwiftType::SizedInteger(_) | SwiftType::Double | SwiftType::Float | SwiftType::String => true,
+        SwiftType::Character => !is_coding,
+        SwiftType::Bool => protocol != "Comparable",
+        SwiftType::Optional(wrapped) | SwiftType::Array(wrapped) | SwiftType::Set(wrapped) => {
+            protocol != "Comparable" && conforms(table, parameters, wrapped, protocol)
+        }
+        SwiftType::Dictionary(_, value) => protocol != "Comparable" && conforms(table, parameters, value, protocol),
+        SwiftType::Custom(name) => match parameters.iter().find(|parameter| parameter.name == *name) {
+            Some(parameter) => parameter.constraints.iter().any(|constraint| match constraint {
+                SwiftType::Custom(constraint) => stdlib::protocols(constraint).iter().any(|inherited| inherited.name == protocol),
+                _ => false,
+            }),
+            None => match table.nominal(name) {
+                Some(NominalType::Enum(decl)) if !is_coding && protocol != "Comparable" && decl.cases.iter().all(|case| case.associated_values.is_empty()) => true,
+                Some(_) => lists(name),
+                // `Date` is also `Comparable`, and `Data` isn't.
+                None => name == "Date" || (name == "Data" && protocol != "Comparable"),
+            },
+        },
+        SwiftType::Generic(name, _) => lists(name),
+        _ => false,
+    }
+}
+
+/// The instance properties of a struct or class that have storage, with their types.
+fn stored_properties(table: &TypeTable, type_name: &str, properties: &[VariablePropertyDeclaration]) -> Vec<(String, SwiftType)> {
+    properties
+        .iter()
+        .filter(|property| !property.is_static && property.has_storage())
+        .map(|property| {
+            let ty = table.member_type(type_name, &property.name).unwrap_or(SwiftType::Any);
+            (property.name.clone(), ty)
+        })
+        .collect()
+}
+
+/// `static func == (lhs: Point, rhs: Point) -> Bool { return lhs.x == rhs.x && lhs.y == rhs.y }`
+fn struct_equality(table: &TypeTable, decl: &StructDeclaration) -> FunDeclaration {
+    let comparisons = stored_properties(table, &decl.name, &decl.properties)
+        .into_iter()
+        .map(|(name, _)| binary(member(identifier("lhs"), &name), "==", member(identifier("rhs"), &name)));
+    let result = comparisons.reduce(|all, comparison| binary(all, "&&", comparison)).unwrap_or(Expression::Literal(Literal::Bool(true)));
+    operator("==", &decl.name, vec![return_statement(result)])
+}
+
+/// `func hash(into hasher: inout Hasher) { hasher.combine(x); hasher.combine(y) }`
+fn struct_hash(table: &TypeTable, decl: &StructDeclaration) -> FunDeclaration {
+    let combines = stored_properties(table, &decl.name, &decl.properties)
+        .into_iter()
+        .map(|(name, _)| combine(member(Expression::SelfExpression, &name)))
+        .collect();
+    hash_method(combines)
+}
+
+/// Compares the case of each side, then their associated values:
+///
+/// ```swift
+/// switch (lhs, rhs) {
+/// case (.upc(let l0, let l1), .upc(let r0, let r1)): return l0 == r0 && l1 == r1
+/// case (.none, .none): return true
+/// default: return false
+/// }
+/// ```
+fn enum_equality(decl: &EnumDeclaration) -> FunDeclaration {
+    let cases = decl
+        .cases
+        .iter()
+        .map(|case| {
+            let count = case.associated_values.len();
+            let comparisons = (0..count).map(|index| binary(identifier(&format!("l{index}")), "==", identifier(&format!("r{index}"))));
+            let result = comparisons.reduce(|all, comparison| binary(all, "&&", comparison)).unwrap_or(Expression::Literal(Literal::Bool(true)));
+            let pair = Pattern::Tuple(TuplePattern { elements: vec![case_pattern(&case.name, "l", count), case_pattern(&case.name, "r", count)] });
+            Case { patterns: vec![pair], guard_expression: None, body: StatementSequence::new(vec![return_statement(result)]) }
+        })
+        .collect();
+    operator("==", &decl.name, vec![switch_pair(cases)])
+}
+
+/// Combines the index of the case, then its associated values.
+fn enum_hash(decl: &EnumDeclaration) -> FunDeclaration {
+    let cases = decl
+        .cases
+        .iter()
+        .enumerate()
+        .map(|(index, case)| {
+            let count = case.associated_values.len();
+            let index = Expression::Literal(Literal::Integer(index as i64));
+            let values = (0..count).map(|value| combine(identifier(&format!("v{value}"))));
+            let body = std::iter::once(combine(index)).chain(values).collect();
+            Case { patterns: vec![case_pattern(&case.name, "v", count)], guard_expression: None, body: StatementSequence::new(body) }
+        })
+        .collect();
+    let switch = SwitchStatement { expression: Box::new(Expression::SelfExpression), cases, default_case: None };
+    hash_method(vec![Statement::Switch(Box::new(switch))])
+}
+
+/// Orders the cases as they are declared: `(.a, .b), (.a, .c), (.b, .c)` are less.
+fn enum_ordering(decl: &EnumDeclaration) -> FunDeclaration {
+    let pairs = decl
+        .cases
+        .iter()
+        .enumerate()
+        .flat_map(|(index, left)| decl.cases[index + 1..].iter().map(move |right| (left, right)))
+        .map(|(left, right)| Pattern::Tuple(TuplePattern { elements: vec![case_pattern(&left.name, "", 0), case_pattern(&right.name, "", 0)] }))
+        .collect::<Vec<_>>();
+    let less = Expression::Literal(Literal::Bool(true));
+    let cases = match pairs.is_empty() {
+        true => Vec::new(),
+        false => vec![Case { patterns: pairs, guard_expression: None, body: StatementSequence::new(vec![return_statement(less)]) }],
+    };
+    operator("<", &decl.name, vec![switch_pair(cases)])
+}
+
+/// `switch (lhs, rhs)`, with a `default` returning false.
+fn switch_pair(cases: Vec<Case>) -> Statement {
+    let pair = Expression::Tuple(Box::new(TupleExpression { elements: vec![identifier("lhs"), identifier("rhs")] }));
+    let default = StatementSequence::new(vec![return_statement(Expression::Literal(Literal::Bool(false)))]);
+    Statement::Switch(Box::new(SwitchStatement { expression: Box::new(pair), cases, default_case: Some(default) }))
+}
+
+/// `.name(let prefix0, let prefix1, ...)`
+fn case_pattern(name: &str, prefix: &str, count: usize) -> Pattern {
+    let values = (0..count).map(|index| Pattern::Identifier(Identifier { name: format!("{prefix}{index}") })).collect();
+    Pattern::EnumCase(EnumCasePattern { enum_name: None, case_name: name.to_string(), associated_values: values })
+}
+
+/// `static func <operator>(lhs: Self, rhs: Self) -> Bool`
+fn operator(name: &str, type_name: &str, body: Vec<Statement>) -> FunDeclaration {
+    let operand = |name: &str| FunctionParameter {
+        label: None,
+        internal_name: name.to_string(),
+        ty: SwiftType::Custom(type_name.to_string()),
+        default_value: None,
+        is_variadic: false,
+        is_inout: false,
+    };
+    method(name, vec![operand("lhs"), operand("rhs")], Some(SwiftType::Bool), true, body)
+}
+
+fn hash_method(body: Vec<Statement>) -> FunDeclaration {
+    let hasher = FunctionParameter {
+        label: Some("into".to_string()),
+        internal_name: "hasher".to_string(),
+        ty: SwiftType::Custom("Hasher".to_string()),
+        default_value: None,
+        is_variadic: false,
+        is_inout: true,
+    };
+    method("hash", vec![hasher], None, false, body)
+}
+
+fn method(name: &str, parameters: Vec<FunctionParameter>, return_type: Option<SwiftType>, is_static: bool, body: Vec<Statement>) -> FunDeclaration {
+    FunDeclaration {
+        name: name.to_string(),
+        generics: None,
+        parameters,
+        return_type,
+        is_throwing: false,
+        is_rethrowing: false,
+        is_static,
+        is_class: false,
+        is_mutating: false,
+        is_override: false,
+        is_final: false,
+        access_control: AccessControl::Internal,
+        body: Some(StatementSequence::new(body)),
+    }
+}
+
+/// `hasher.combine(value)`
+fn combine(value: Expression) -> Statement {
+    let call = CallExpression {
+        callee: Box::new(member(identifier("hasher"), "combine")),
+        arguments: vec![Argument { label: None, value, is_variadic: false, is_inout: false }],
+        generic_type_arguments: None,
+        trailing_closures: Vec::new(),
+    };
+    Statement::Expression(Box::new(Expression::CallExpression(Box::new(call))))
+}
+
+/// `callee(label: value, ...)`, with an empty label for unlabeled arguments.
+fn call(callee: Expression, arguments: Vec<(&str, Expression)>) -> Expression {
+    let arguments = arguments
+        .into_iter()
+        .map(|(label, value)| Argument { label: (!label.is_empty()).then(|| label.to_string()), value, is_variadic: false, is_inout: false })
+        .collect();
+    let call = CallExpression { callee: Box::new(callee), arguments, generic_type_arguments: None, trailing_closures: Vec::new() };
+    Expression::CallExpression(Box::new(call))
+}
+
+/// `try expression`
+fn attempt(expression: Expression) -> Expression {
+    Expression::Try(Box::new(TryExpression { kind: TryKind::Propagating, expression: Box::new(expression) }))
+}
+
+/// `let name = value`, or `var name = value`.
+fn variable(name: &str, value: Expression, is_constant: bool) -> Statement {
+    let declaration = match is_constant {
+        true => Declaration::Let(Box::new(LetDeclaration {
+            name: name.to_string(),
+            ty: None,
+            initial_value: Some(value),
+            ownership: ReferenceOwnership::Strong,
+        })),
+        false => Declaration::Var(Box::new(VarDeclaration {
+            name: name.to_string(),
+            ty: None,
+            initial_value: Some(value),
+            ownership: ReferenceOwnership::Strong,
+        })),
+    };
+    Statement::Declaration(Box::new(declaration))
+}
+
+fn parameter(label: Option<&str>, name: &str, ty: SwiftType) -> FunctionParameter {
+    FunctionParameter {
+        label: label.map(str::to_string),
+        internal_name: name.to_string(),
+        ty,
+        default_value: None,
+        is_variadic: false,
+        is_inout: false,
+    }
+}
+
+/// `Type.self`
+fn type_value(type_name: &str) -> Expression {
+    member(identifier(type_name), "self")
+}
+
+fn return_statement(value: Expression) -> Statement {
+    Statement::Return(Box::new(ReturnStatement { expression: Some(Box::new(value)) }))
+}
+
+fn binary(left: Expression, operator: &str, right: Expression) -> Expression {
+    Expression::BinaryExpression(Box::new(BinaryExpression {
+        left: Box::new(left),
+        operator: InfixIdentifier { symbol: operator.to_string() },
+        right: Box::new(right),
+    }))
+}
+
+fn member(target: Expression, name: &str) -> Expression {
+    Expression::MemberAccess(Box::new(MemberAccessExpression { target: Box::new(target), member: name.to_string() }))
+}
+
+fn identifier(name: &str) -> Expression {
+    Expression::Identifier(Identifier { name: name.to_string() })
+}
@@ -0,0 +1,99 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::FunDeclaration;
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+/// `Container` has an associated `Item` that `item()` returns, and `Labelled` refines it with a
+/// `Label` defaulting to `String`. `first(_:)` returns a container's `Item`, and `sameKind(_:_:)`
+/// takes two containers of the same `Item`.
+fn declarations() -> Vec<Statement> {
+    let container = protocol("Container", &[], vec![("size", SwiftType::Integer, true)], vec![("item", vec![], Some(named("Item")))], vec![]);
+    let labelled = protocol("Labelled", &["Container"], vec![], vec![("label", vec![], Some(named("Label")))], vec![]);
+    let first = FunDeclaration {
+        generics: Some(generics(vec![("C", vec![named("Container")])], vec![])),
+        ..function("first", vec![param(None, "c", named("C"))], Some(nested("C", "Item")), vec![ret(call(member(ident("c"), "item"), vec![]))])
+    };
+    let same_kind = FunDeclaration {
+        generics: Some(generics(vec![("A", vec![named("Container")]), ("B", vec![])], vec![
+            conformance(named("B"), "Container"),
+            same_type(nested("A", "Item"), nested("B", "Item")),
+        ])),
+        ..function("sameKind", vec![param(None, "a", named("A")), param(None, "b", named("B"))], Some(SwiftType::Bool), vec![ret(boolean(true))])
+    };
+    vec![
+        with_associated_types(container, vec![("Item", vec![], None)]),
+        with_associated_types(labelled, vec![("Label", vec![], Some(SwiftType::String))]),
+        boxed("IntBox", SwiftType::Integer, vec![]),
+        boxed("StrBox", SwiftType::String, vec![]),
+        function_declaration(first),
+        function_declaration(same_kind),
+    ]
+}
+
+/// A container of `value`, whose `Item` is inferred from `item()`.
+fn boxed(name: &str, ty: SwiftType, mut methods: Vec<FunDeclaration>) -> Statement {
+    let properties = vec![stored("value", Some(ty.clone()), None), computed("size", SwiftType::Integer, vec![ret(int(1))])];
+    methods.insert(0, function("item", vec![], Some(ty), vec![ret(ident("value"))]));
+    conforming(structure(name, properties, methods, vec![]), &["Container"])
+}
+
+fn new_box(name: &str, value: Expression) -> Expression {
+    call(ident(name), vec![labeled("value", value)])
+}
+
+#[test]
+fn associated_types_are_inferred_from_witnesses_and_defaults() {
+    let generic_box = conforming(boxed("Box", named("T"), vec![function("label", vec![], Some(SwiftType::String), vec![ret(string("box"))])]), &["Labelled"]);
+    let mut statements = declarations();
+    statements.extend([
+        with_generics(generic_box, generics(vec![("T", vec![])], vec![])),
+        let_("i", new_box("IntBox", int(3))),
+        let_("r", call(ident("first"), vec![arg(ident("i"))])),
+        print(vec![binary(ident("r"), "+", int(1)), call(ident("sameKind"), vec![arg(ident("i")), arg(new_box("IntBox", int(5)))])]),
+        print(vec![call(ident("first"), vec![arg(new_box("Box", string("hi")))]), call(member(new_box("Box", int(1)), "label"), vec![])]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    assert_eq!(output, "4 true\nhi box\n");
+}
+
+#[test]
+fn conformances_need_every_associated_type_and_calls_meet_the_where_clause() {
+    let mut statements = declarations();
+    statements.extend([
+        conforming(structure("Bad", vec![], vec![], vec![]), &["Container"]),
+        structure("Plain", vec![], vec![], vec![]),
+        expression(call(ident("sameKind"), vec![arg(new_box("IntBox", int(1))), arg(new_box("StrBox", string("x")))])),
+        expression(call(ident("first"), vec![arg(call(ident("Plain"), vec![]))])),
+    ]);
+    assert_eq!(errors(&program(statements)), [
+        "type 'Bad' does not conform to protocol 'Container'; protocol requires nested type 'Item'",
+        "type 'Bad' does not conform to protocol 'Container'; protocol requires property 'size' with type 'Int' { get }",
+        "type 'Bad' does not conform to protocol 'Container'; protocol requires function 'item()' with type '() -> Item'",
+        "global function 'sameKind(_:_:)' requires the types 'Int' and 'String' be equivalent",
+        "global function 'first(_:)' requires that 'Plain' conform to 'Container'",
+    ]);
+}
+
+#[test]
+fn generic_requirements_must_name_protocols_and_member_types_that_exist() {
+    let constrained = FunDeclaration {
+        generics: Some(generics(vec![("T", vec![named("IntBox")])], vec![conformance(named("U"), "Container")])),
+        ..function("g", vec![], None, vec![])
+    };
+    let unknown_member = FunDeclaration {
+        generics: Some(generics(vec![("T", vec![named("Container")])], vec![same_type(nested("T", "Foo"), SwiftType::Integer)])),
+        ..function("h", vec![], None, vec![])
+    };
+    let mut statements = declarations();
+    statements.extend([
+        function_declaration(constrained),
+        function_declaration(unknown_member),
+        with_generics(extension("IntBox", &[], vec![], vec![]), generics(vec![], vec![conformance(named("Self"), "Container")])),
+    ]);
+    assert_eq!(errors(&program(statements)), [
+        "type 'T' constrained to non-protocol, non-class type 'IntBox'",
+        "cannot find type 'U' in scope",
+        "'Foo' is not a member type of type 'T'",
+        "trailing 'where' clause for extension of non-generic type 'IntBox'",
+    ]);
+}
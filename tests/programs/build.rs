@@ -0,0 +1,504 @@
+//! Shorthands for building syntax trees, with every optional part left out.
+
+use swift_oxide::syntax::declaration::*;
+use swift_oxide::syntax::expression::*;
+use swift_oxide::syntax::statement::*;
+use swift_oxide::syntax::*;
+
+pub fn named(name: &str) -> SwiftType {
+    SwiftType::Custom(name.into())
+}
+
+/// A member type, like `C.Item`.
+pub fn nested(base: &str, name: &str) -> SwiftType {
+    SwiftType::Nested(Box::new(SwiftType::Custom(base.into())), name.into())
+}
+
+/// A fixed-width integer type, like `UInt8`.
+pub fn sized(integer: IntegerType) -> SwiftType {
+    SwiftType::SizedInteger(integer)
+}
+
+pub fn optional(ty: SwiftType) -> SwiftType {
+    SwiftType::Optional(Box::new(ty))
+}
+
+/// An existential, like `any Shape`.
+pub fn existential(name: &str) -> SwiftType {
+    SwiftType::Existential(Box::new(named(name)))
+}
+
+/// An opaque type, like `some Shape`.
+pub fn opaque(name: &str) -> SwiftType {
+    SwiftType::Opaque(Box::new(named(name)))
+}
+
+/// A protocol composition, like `Named & Shape`.
+pub fn composition(names: &[&str]) -> SwiftType {
+    SwiftType::Composition(names.iter().map(|name| named(name)).collect())
+}
+
+pub fn program(statements: Vec<Statement>) -> StatementSequence {
+    StatementSequence::new(statements)
+}
+
+pub fn ident(name: &str) -> Expression {
+    Expression::Identifier(Identifier { name: name.into() })
+}
+
+pub fn boolean(value: bool) -> Expression {
+    Expression::Literal(Literal::Bool(value))
+}
+
+pub fn int(value: i64) -> Expression {
+    Expression::Literal(Literal::Integer(value))
+}
+
+pub fn float(value: f64) -> Expression {
+    Expression::Literal(Literal::Float(value))
+}
+
+pub fn string(value: &str) -> Expression {
+    Expression::Literal(Literal::String(value.into()))
+}
+
+pub fn character(value: &str) -> Expression {
+    Expression::Literal(Literal::Character(value.into()))
+}
+
+pub fn nil() -> Expression {
+    Expression::Literal(Literal::Nil)
+}
+
+pub fn this() -> Expression {
+    Expression::SelfExpression
+}
+
+pub fn superclass() -> Expression {
+    Expression::SuperExpression
+}
+
+pub fn interpolation(segments: Vec<InterpolationSegment>) -> Expression {
+    Expression::StringInterpolation(StringInterpolationExpression { segments })
+}
+
+pub fn text_segment(text: &str) -> InterpolationSegment {
+    InterpolationSegment::Text(text.into())
+}
+
+pub fn value_segment(value: Expression) -> InterpolationSegment {
+    InterpolationSegment::Expression(value)
+}
+
+pub fn tuple(elements: Vec<Expression>) -> Expression {
+    Expression::Tuple(Box::new(TupleExpression { elements }))
+}
+
+pub fn binary(left: Expression, operator: &str, right: Expression) -> Expression {
+    Expression::BinaryExpression(Box::new(BinaryExpression { left: Box::new(left), operator: InfixIdentifier { symbol: operator.into() }, right: Box::new(right) }))
+}
+
+pub fn unary(operator: &str, operand: Expression) -> Expression {
+    Expression::UnaryExpression(Box::new(UnaryExpression { operator: UnaryIdentifier { symbol: operator.into() }, operand: Box::new(operand) }))
+}
+
+pub fn member(target: Expression, member: &str) -> Expression {
+    Expression::MemberAccess(Box::new(MemberAccessExpression { target: Box::new(target), member: member.into() }))
+}
+
+pub fn array(elements: Vec<Expression>) -> Expression {
+    Expression::Array(Box::new(ArrayExpression { elements }))
+}
+
+pub fn subscript(target: Expression, index: Expression) -> Expression {
+    Expression::Subscript(Box::new(SubscriptExpression { target: Box::new(target), index: Box::new(index), default_value: None }))
+}
+
+/// A dictionary subscript with a default, like `counts[word, default: 0]`.
+pub fn subscript_or(target: Expression, index: Expression, default: Expression) -> Expression {
+    Expression::Subscript(Box::new(SubscriptExpression { target: Box::new(target), index: Box::new(index), default_value: Some(Box::new(default)) }))
+}
+
+pub fn dictionary(elements: Vec<(Expression, Expression)>) -> Expression {
+    Expression::Dictionary(Box::new(DictionaryExpression { elements }))
+}
+
+/// A conditional cast, `as?`.
+pub fn cast(value: Expression, ty: SwiftType) -> Expression {
+    Expression::TypeCasting(Box::new(TypeCastingExpression { expression: Box::new(value), target_type: ty }))
+}
+
+pub fn arg(value: Expression) -> Argument {
+    Argument { label: None, value, is_variadic: false, is_inout: false }
+}
+
+pub fn labeled(label: &str, value: Expression) -> Argument {
+    Argument { label: Some(label.into()), ..arg(value) }
+}
+
+/// An `&value` argument to an `inout` parameter.
+pub fn inout(value: Expression) -> Argument {
+    Argument { is_inout: true, ..arg(value) }
+}
+
+pub fn call(callee: Expression, arguments: Vec<Argument>) -> Expression {
+    Expression::CallExpression(Box::new(CallExpression { callee: Box::new(callee), arguments, generic_type_arguments: None, trailing_closures: vec![] }))
+}
+
+/// A call with explicit generic arguments, like `Stack<Int>()`.
+pub fn specialized(callee: Expression, types: Vec<SwiftType>, arguments: Vec<Argument>) -> Expression {
+    Expression::CallExpression(Box::new(CallExpression { callee: Box::new(callee), arguments, generic_type_arguments: Some(types), trailing_closures: vec![] }))
+}
+
+pub fn trailing(callee: Expression, arguments: Vec<Argument>, closure: Expression) -> Expression {
+    let trailing_closures = vec![TrailingClosure { label: None, closure }];
+    Expression::CallExpression(Box::new(CallExpression { callee: Box::new(callee), arguments, generic_type_arguments: None, trailing_closures }))
+}
+
+pub fn closure(body: Vec<Statement>) -> Expression {
+    closure_of(&[], body)
+}
+
+/// A closure with parameters named but not annotated, like `{ a, b in a + b }`.
+pub fn closure_of(parameters: &[&str], body: Vec<Statement>) -> Expression {
+    let parameters = parameters.iter().map(|name| ClosureParameter { name: (*name).into(), type_annotation: None }).collect();
+    Expression::Closure(Box::new(Closure { parameters, return_type: None, is_escaping: false, capture_list: vec![], body }))
+}
+
+/// Gives a closure built with `closure` a capture list of (ownership, name, value) items.
+pub fn capturing(mut closure: Expression, items: Vec<(ReferenceOwnership, &str, Option<Expression>)>) -> Expression {
+    if let Expression::Closure(closure) = &mut closure {
+        closure.capture_list = items.into_iter().map(|(ownership, name, value)| CaptureListItem { ownership, name: name.into(), value }).collect();
+    }
+    closure
+}
+
+pub fn try_(kind: TryKind, expression: Expression) -> Expression {
+    Expression::Try(Box::new(TryExpression { kind, expression: Box::new(expression) }))
+}
+
+pub fn expression(expression: Expression) -> Statement {
+    Statement::Expression(Box::new(expression))
+}
+
+pub fn print(items: Vec<Expression>) -> Statement {
+    expression(call(ident("print"), items.into_iter().map(arg).collect()))
+}
+
+pub fn assign(target: Expression, value: Expression) -> Statement {
+    Statement::Assignment(AssignmentStatement { target: Box::new(target), value: Box::new(value) })
+}
+
+pub fn ret(value: Expression) -> Statement {
+    Statement::Return(Box::new(ReturnStatement { expression: Some(Box::new(value)) }))
+}
+
+pub fn return_() -> Statement {
+    Statement::Return(Box::new(ReturnStatement { expression: None }))
+}
+
+pub fn throw_(value: Expression) -> Statement {
+    Statement::Throw(Box::new(ThrowStatement { expression: Box::new(value) }))
+}
+
+pub fn guard(condition: Expression, body: Vec<Statement>) -> Statement {
+    Statement::Guard(Box::new(GuardStatement { condition: Box::new(condition), body: StatementSequence::new(body) }))
+}
+
+pub fn while_(condition: Expression, body: Vec<Statement>) -> Statement {
+    Statement::WhileLoop(Box::new(WhileLoopStatement { condition: Box::new(condition), body: StatementSequence::new(body) }))
+}
+
+pub fn for_in(variable: &str, sequence: Expression, body: Vec<Statement>) -> Statement {
+    Statement::ForIn(Box::new(ForInStatement { variable: variable.into(), sequence: Box::new(sequence), body: StatementSequence::new(body) }))
+}
+
+pub fn break_() -> Statement {
+    Statement::Break(BreakStatement { label: None })
+}
+
+pub fn if_(condition: Expression, body: Vec<Statement>, else_body: Option<Vec<Statement>>) -> Statement {
+    Statement::If(Box::new(IfStatement { condition: Box::new(condition), body: StatementSequence::new(body), else_body: else_body.map(StatementSequence::new) }))
+}
+
+pub fn do_catch(body: Vec<Statement>, catch_clauses: Vec<CatchClause>) -> Statement {
+    Statement::DoCatch(Box::new(DoCatchStatement { body: StatementSequence::new(body), catch_clauses }))
+}
+
+/// A `catch` clause; without a pattern, it catches any error as `error`.
+pub fn catch(pattern: Option<Pattern>, body: Vec<Statement>) -> CatchClause {
+    CatchClause { pattern, guard_expression: None, body: StatementSequence::new(body) }
+}
+
+pub fn switch(subject: Expression, cases: Vec<(Pattern, Vec<Statement>)>) -> Statement {
+    switch_default(subject, cases, None)
+}
+
+pub fn switch_default(subject: Expression, cases: Vec<(Pattern, Vec<Statement>)>, default: Option<Vec<Statement>>) -> Statement {
+    let cases = cases.into_iter().map(|(pattern, body)| Case { patterns: vec![pattern], guard_expression: None, body: StatementSequence::new(body) }).collect();
+    Statement::Switch(Box::new(SwitchStatement { expression: Box::new(subject), cases, default_case: default.map(StatementSequence::new) }))
+}
+
+pub fn literal_pattern(value: i64) -> Pattern {
+    Pattern::Literal(LiteralPattern { value: Literal::Integer(value) })
+}
+
+pub fn string_pattern(value: &str) -> Pattern {
+    Pattern::Literal(LiteralPattern { value: Literal::String(value.into()) })
+}
+
+pub fn binding(name: &str) -> Pattern {
+    Pattern::Identifier(Identifier { name: name.into() })
+}
+
+pub fn type_pattern(ty: SwiftType) -> Pattern {
+    Pattern::TypePattern(TypePattern { ty })
+}
+
+pub fn case_pattern(case_name: &str, bindings: &[&str]) -> Pattern {
+    let associated_values = bindings.iter().map(|name| binding(name)).collect();
+    Pattern::EnumCase(EnumCasePattern { enum_name: None, case_name: case_name.into(), associated_values })
+}
+
+pub fn declaration(declaration: Declaration) -> Statement {
+    Statement::Declaration(Box::new(declaration))
+}
+
+pub fn var(name: &str, value: Expression) -> Statement {
+    declaration(Declaration::Var(Box::new(VarDeclaration { name: name.into(), ty: None, initial_value: Some(value), ownership: ReferenceOwnership::Strong })))
+}
+
+/// Declares a `weak` or `unowned` variable.
+pub fn owned_var(name: &str, ty: Option<SwiftType>, value: Expression, ownership: ReferenceOwnership) -> Statement {
+    declaration(Declaration::Var(Box::new(VarDeclaration { name: name.into(), ty, initial_value: Some(value), ownership })))
+}
+
+pub fn typed_var(name: &str, ty: SwiftType, value: Expression) -> Statement {
+    declaration(Declaration::Var(Box::new(VarDeclaration { name: name.into(), ty: Some(ty), initial_value: Some(value), ownership: ReferenceOwnership::Strong })))
+}
+
+pub fn let_(name: &str, value: Expression) -> Statement {
+    declaration(Declaration::Let(Box::new(LetDeclaration { name: name.into(), ty: None, initial_value: Some(value), ownership: ReferenceOwnership::Strong })))
+}
+
+pub fn typed_let(name: &str, ty: SwiftType, value: Expression) -> Statement {
+    declaration(Declaration::Let(Box::new(LetDeclaration { name: name.into(), ty: Some(ty), initial_value: Some(value), ownership: ReferenceOwnership::Strong })))
+}
+
+/// A variable declared without an initial value.
+pub fn declare_var(name: &str, ty: SwiftType) -> Statement {
+    declaration(Declaration::Var(Box::new(VarDeclaration { name: name.into(), ty: Some(ty), initial_value: None, ownership: ReferenceOwnership::Strong })))
+}
+
+/// A constant declared without an initial value.
+pub fn declare_let(name: &str, ty: SwiftType) -> Statement {
+    declaration(Declaration::Let(Box::new(LetDeclaration { name: name.into(), ty: Some(ty), initial_value: None, ownership: ReferenceOwnership::Strong })))
+}
+
+pub fn param(label: Option<&str>, name: &str, ty: SwiftType) -> FunctionParameter {
+    FunctionParameter { label: label.map(Into::into), internal_name: name.into(), ty, default_value: None, is_variadic: false, is_inout: false }
+}
+
+pub fn function(name: &str, parameters: Vec<FunctionParameter>, return_type: Option<SwiftType>, body: Vec<Statement>) -> FunDeclaration {
+    FunDeclaration {
+        name: name.into(),
+        generics: None,
+        parameters,
+        return_type,
+        is_throwing: false,
+        is_rethrowing: false,
+        is_static: false,
+        is_class: false,
+        is_mutating: false,
+        is_override: false,
+        is_final: false,
+        access_control: AccessControl::Internal,
+        body: Some(StatementSequence::new(body)),
+    }
+}
+
+pub fn initializer(parameters: Vec<FunctionParameter>, body: Vec<Statement>) -> InitializerDeclaration {
+    InitializerDeclaration {
+        generics: None,
+        parameters,
+        body: StatementSequence::new(body),
+        is_failable: false,
+        is_throwing: false,
+        is_convenience: false,
+        is_required: false,
+        is_override: false,
+        access_control: AccessControl::Internal,
+    }
+}
+
+pub fn stored(name: &str, ty: Option<SwiftType>, initial_value: Option<Expression>) -> VariablePropertyDeclaration {
+    VariablePropertyDeclaration {
+        name: name.into(),
+        ty,
+        kind: PropertyKind::Stored(StoredProperty { initial_value, is_lazy: false, will_set: None, did_set: None }),
+        is_constant: false,
+        ownership: ReferenceOwnership::Strong,
+        is_static: false,
+        is_class: false,
+        is_override: false,
+        is_final: false,
+        access_control: AccessControl::Internal,
+        setter_access_control: None,
+    }
+}
+
+pub fn computed(name: &str, ty: SwiftType, getter: Vec<Statement>) -> VariablePropertyDeclaration {
+    let getter = function("get", vec![], Some(ty.clone()), getter);
+    VariablePropertyDeclaration { kind: PropertyKind::Computed(Box::new(ComputedProperty { getter, setter: None })), ..stored(name, Some(ty), None) }
+}
+
+/// A computed property whose setter takes the implicit `newValue`.
+pub fn settable(name: &str, ty: SwiftType, getter: Vec<Statement>, setter: Vec<Statement>) -> VariablePropertyDeclaration {
+    let getter = function("get", vec![], Some(ty.clone()), getter);
+    let setter = Some(function("set", vec![], None, setter));
+    VariablePropertyDeclaration { kind: PropertyKind::Computed(Box::new(ComputedProperty { getter, setter })), ..stored(name, Some(ty), None) }
+}
+
+pub fn observer(parameter_name: Option<&str>, body: Vec<Statement>) -> PropertyObserver {
+    PropertyObserver { parameter_name: parameter_name.map(Into::into), body: StatementSequence::new(body) }
+}
+
+pub fn function_declaration(function: FunDeclaration) -> Statement {
+    declaration(Declaration::Function(Box::new(function)))
+}
+
+pub fn structure(name: &str, properties: Vec<VariablePropertyDeclaration>, methods: Vec<FunDeclaration>, initializers: Vec<InitializerDeclaration>) -> Statement {
+    declaration(Declaration::Struct(Box::new(StructDeclaration { name: name.into(), generics: None, conformances: vec![], properties, methods, initializers })))
+}
+
+pub fn class(name: &str, superclass: Option<&str>, properties: Vec<VariablePropertyDeclaration>, methods: Vec<FunDeclaration>, initializers: Vec<InitializerDeclaration>) -> Statement {
+    declaration(Declaration::Class(Box::new(ClassDeclaration {
+        name: name.into(),
+        generics: None,
+        superclass: superclass.map(Into::into),
+        superclass_arguments: vec![],
+        is_final: false,
+        conformances: vec![],
+        properties,
+        methods,
+        initializers,
+        deinitializer: None,
+    })))
+}
+
+/// Gives a class declared with `class` a `deinit`.
+pub fn with_deinit(mut class: Statement, body: Vec<Statement>) -> Statement {
+    if let Statement::Declaration(declaration) = &mut class {
+        if let Declaration::Class(class) = declaration.as_mut() {
+            class.deinitializer = Some(DeinitializerDeclaration { body: StatementSequence::new(body) });
+        }
+    }
+    class
+}
+
+/// Gives a class declared with `class` the generic arguments of its superclass.
+pub fn with_superclass_arguments(mut class: Statement, arguments: Vec<SwiftType>) -> Statement {
+    if let Statement::Declaration(declaration) = &mut class {
+        if let Declaration::Class(class) = declaration.as_mut() {
+            class.superclass_arguments = arguments;
+        }
+    }
+    class
+}
+
+pub fn enumeration(name: &str, cases: Vec<EnumCase>, raw_type: Option<SwiftType>, properties: Vec<VariablePropertyDeclaration>, methods: Vec<FunDeclaration>) -> Statement {
+    declaration(Declaration::Enum(Box::new(EnumDeclaration { name: name.into(), generics: None, conformances: vec![], cases, raw_type, properties, methods, initializers: vec![] })))
+}
+
+pub fn extension(type_name: &str, conformances: &[&str], properties: Vec<VariablePropertyDeclaration>, methods: Vec<FunDeclaration>) -> Statement {
+    declaration(Declaration::Extension(Box::new(ExtensionDeclaration {
+        type_name: type_name.into(),
+        conformances: conformances.iter().map(|name| (*name).into()).collect(),
+        where_clause: vec![],
+        properties,
+        methods,
+        initializers: vec![],
+    })))
+}
+
+/// Declares a protocol with properties as (name, type, is read-only), methods as (name, parameters,
+/// return type), and initializers by their parameters.
+pub fn protocol(
+    name: &str,
+    inherited: &[&str],
+    properties: Vec<(&str, SwiftType, bool)>,
+    methods: Vec<(&str, Vec<FunctionParameter>, Option<SwiftType>)>,
+    initializers: Vec<Vec<FunctionParameter>>,
+) -> Statement {
+    let property_requirements = properties.into_iter().map(|(name, ty, is_read_only)| PropertyRequirement { name: name.into(), ty, is_read_only }).collect();
+    let method_requirements = methods
+        .into_iter()
+        .map(|(name, parameters, return_type)| MethodRequirement { name: name.into(), parameters, return_type, is_mutating: false, is_throwing: false })
+        .collect();
+    declaration(Declaration::Protocol(Box::new(ProtocolDeclaration {
+        name: name.into(),
+        inherited_protocols: inherited.iter().map(|name| (*name).into()).collect(),
+        associated_types: vec![],
+        property_requirements,
+        method_requirements,
+        initializer_requirements: initializers.into_iter().map(|parameters| InitializerRequirement { parameters }).collect(),
+    })))
+}
+
+/// Gives a protocol declared with `protocol` associated types, as (name, constraints, default).
+pub fn with_associated_types(mut protocol: Statement, associated_types: Vec<(&str, Vec<SwiftType>, Option<SwiftType>)>) -> Statement {
+    if let Statement::Declaration(declaration) = &mut protocol {
+        if let Declaration::Protocol(protocol) = declaration.as_mut() {
+            protocol.associated_types =
+                associated_types.into_iter().map(|(name, constraints, default)| AssociatedTypeDeclaration { name: name.into(), constraints, default }).collect();
+        }
+    }
+    protocol
+}
+
+/// Generic parameters as (name, constraints), with a `where` clause.
+pub fn generics(parameters: Vec<(&str, Vec<SwiftType>)>, where_clause: Vec<GenericRequirement>) -> GenericsDeclaration {
+    let type_parameters = parameters.into_iter().map(|(name, constraints)| TypeParameter { name: name.into(), constraints }).collect();
+    GenericsDeclaration { type_parameters, where_clause }
+}
+
+/// Makes a struct, class or enum declaration generic, or gives an extension a `where` clause.
+pub fn with_generics(mut declaration: Statement, generics: GenericsDeclaration) -> Statement {
+    if let Statement::Declaration(declaration) = &mut declaration {
+        match declaration.as_mut() {
+            Declaration::Struct(declaration) => declaration.generics = Some(generics),
+            Declaration::Class(declaration) => declaration.generics = Some(generics),
+            Declaration::Enum(declaration) => declaration.generics = Some(generics),
+            Declaration::Extension(declaration) => declaration.where_clause = generics.where_clause,
+            _ => {}
+        }
+    }
+    declaration
+}
+
+pub fn conformance(subject: SwiftType, protocol: &str) -> GenericRequirement {
+    GenericRequirement::Conformance { subject, constraint: SwiftType::Custom(protocol.into()) }
+}
+
+pub fn same_type(left: SwiftType, right: SwiftType) -> GenericRequirement {
+    GenericRequirement::SameType { left, right }
+}
+
+/// Makes a struct, class or enum declaration adopt protocols.
+pub fn conforming(mut nominal: Statement, protocols: &[&str]) -> Statement {
+    let conformances = protocols.iter().map(|name| (*name).into()).collect();
+    if let Statement::Declaration(declaration) = &mut nominal {
+        match declaration.as_mut() {
+            Declaration::Struct(declaration) => declaration.conformances = conformances,
+            Declaration::Class(declaration) => declaration.conformances = conformances,
+            Declaration::Enum(declaration) => declaration.conformances = conformances,
+            _ => {}
+        }
+    }
+    nominal
+}
+
+pub fn case(name: &str, associated_values: Vec<SwiftType>) -> EnumCase {
+    let associated_values = associated_values.into_iter().map(|ty| EnumAssociatedValue { label: None, ty }).collect();
+    EnumCase { name: name.into(), associated_values, raw_value: None }
+}
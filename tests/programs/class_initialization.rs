@@ -0,0 +1,141 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{InitializerDeclaration, VariablePropertyDeclaration};
+use swift_oxide::syntax::expression::Argument;
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+fn super_init(arguments: Vec<Argument>) -> Statement {
+    expression(call(member(superclass(), "init"), arguments))
+}
+
+fn self_init(arguments: Vec<Argument>) -> Statement {
+    expression(call(member(this(), "init"), arguments))
+}
+
+/// A class with a required designated initializer and a convenience one delegating to it.
+fn vehicle() -> Statement {
+    let required = InitializerDeclaration {
+        is_required: true,
+        ..initializer(vec![param(Some("wheels"), "wheels", SwiftType::Integer)], vec![assign(member(this(), "wheels"), ident("wheels"))])
+    };
+    let convenience = InitializerDeclaration { is_convenience: true, ..initializer(vec![], vec![self_init(vec![labeled("wheels", int(4))])]) };
+    let properties = vec![stored("wheels", Some(SwiftType::Integer), None), stored("name", None, Some(string("vehicle")))];
+    class("Vehicle", None, properties, vec![function("describe", vec![], Some(SwiftType::String), vec![ret(ident("name"))])], vec![required, convenience])
+}
+
+/// A class whose failable initializer fails below absolute zero.
+fn temperature() -> Statement {
+    let celsius = VariablePropertyDeclaration { is_constant: true, ..stored("c", Some(SwiftType::Double), None) };
+    let body = vec![if_(binary(ident("c"), "<", float(-273.0)), vec![ret(nil())], None), assign(member(this(), "c"), ident("c"))];
+    class("Temp", None, vec![celsius], vec![], vec![InitializerDeclaration { is_failable: true, ..initializer(vec![param(Some("c"), "c", SwiftType::Double)], body) }])
+}
+
+fn is_nil(construction: Expression) -> Expression {
+    binary(construction, "==", nil())
+}
+
+#[test]
+fn subclasses_overriding_every_designated_initializer_inherit_the_convenience_ones() {
+    let bicycle = class(
+        "Bicycle",
+        Some("Vehicle"),
+        vec![stored("gears", Some(SwiftType::Integer), None)],
+        vec![],
+        vec![
+            initializer(vec![param(Some("gears"), "gears", SwiftType::Integer)], vec![
+                assign(member(this(), "gears"), ident("gears")),
+                super_init(vec![labeled("wheels", int(2))]),
+                assign(ident("name"), string("bike")),
+            ]),
+            InitializerDeclaration {
+                is_required: true,
+                ..initializer(vec![param(Some("wheels"), "wheels", SwiftType::Integer)], vec![
+                    assign(ident("gears"), int(1)),
+                    super_init(vec![labeled("wheels", ident("wheels"))]),
+                ])
+            },
+        ],
+    );
+    let program = program(vec![
+        vehicle(),
+        bicycle,
+        class("Tandem", Some("Bicycle"), vec![], vec![], vec![]),
+        print(vec![
+            member(call(ident("Vehicle"), vec![]), "wheels"),
+            member(call(ident("Bicycle"), vec![]), "wheels"),
+            member(call(ident("Bicycle"), vec![]), "gears"),
+            member(call(ident("Bicycle"), vec![labeled("gears", int(21))]), "name"),
+            member(call(ident("Tandem"), vec![]), "gears"),
+        ]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "4 4 1 bike 1\n");
+}
+
+#[test]
+fn failable_initializers_propagate_failure_through_super_init() {
+    let kelvin = InitializerDeclaration {
+        is_failable: true,
+        ..initializer(vec![param(Some("k"), "k", SwiftType::Double)], vec![super_init(vec![labeled("c", binary(ident("k"), "-", float(273.0)))])])
+    };
+    let program = program(vec![
+        temperature(),
+        class("Kelvin", Some("Temp"), vec![], vec![], vec![kelvin]),
+        print(vec![
+            is_nil(call(ident("Temp"), vec![labeled("c", float(-300.0))])),
+            is_nil(call(ident("Kelvin"), vec![labeled("k", float(300.0))])),
+            is_nil(call(ident("Kelvin"), vec![labeled("k", float(-1.0))])),
+        ]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "true false true\n");
+}
+
+#[test]
+fn initializers_must_follow_the_delegation_and_two_phase_rules() {
+    let premature = initializer(vec![param(Some("z"), "z", SwiftType::Integer)], vec![
+        expression(call(ident("describe"), vec![])),
+        super_init(vec![labeled("wheels", int(1))]),
+    ]);
+    let program = program(vec![
+        vehicle(),
+        temperature(),
+        class("Bad", Some("Vehicle"), vec![stored("z", Some(SwiftType::Integer), None)], vec![], vec![premature]),
+        class("Empty", None, vec![], vec![], vec![initializer(vec![], vec![ret(nil())])]),
+        class("Warm", Some("Temp"), vec![], vec![], vec![initializer(vec![], vec![super_init(vec![labeled("c", float(1.0))])])]),
+        class("NoInit", None, vec![stored("q", Some(SwiftType::Integer), None)], vec![], vec![]),
+    ]);
+    assert_eq!(errors(&program), [
+        "'required' initializer 'init(wheels:)' must be provided by subclass of 'Vehicle'",
+        "'self' used in method call 'describe' before 'super.init' call",
+        "only a failable initializer can return 'nil'",
+        "a non-failable initializer cannot delegate to failable initializer 'init(c:)' written with 'init?'",
+        "class 'NoInit' has no initializers",
+        "property 'self.z' not initialized at super.init call",
+    ]);
+}
+
+#[test]
+fn delegation_must_match_the_kind_of_initializer() {
+    let convenience = InitializerDeclaration {
+        is_convenience: true,
+        ..initializer(vec![param(Some("a"), "a", SwiftType::Integer)], vec![assign(member(this(), "a"), ident("a"))])
+    };
+    let delegating = initializer(vec![], vec![self_init(vec![labeled("v", int(0))])]);
+    let value = structure(
+        "Value",
+        vec![stored("v", Some(SwiftType::Integer), None)],
+        vec![],
+        vec![initializer(vec![param(Some("v"), "v", SwiftType::Integer)], vec![assign(member(this(), "v"), ident("v"))]), InitializerDeclaration { is_convenience: true, ..delegating }],
+    );
+    let program = program(vec![
+        class("Conv", None, vec![stored("a", None, Some(int(0)))], vec![], vec![initializer(vec![], vec![]), convenience]),
+        value,
+        class("Designated", None, vec![], vec![], vec![initializer(vec![], vec![]), initializer(vec![param(Some("x"), "x", SwiftType::Integer)], vec![self_init(vec![])])]),
+    ]);
+    assert_eq!(errors(&program), [
+        "'self.init' isn't called on all paths before returning from initializer",
+        "delegating initializers in structs are not marked with 'convenience'",
+        "designated initializer for 'Designated' cannot delegate (with 'self.init'); did you mean this to be a convenience initializer?",
+    ]);
+}
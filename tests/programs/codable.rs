@@ -0,0 +1,279 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{EnumAssociatedValue, EnumCase, FunDeclaration, InitializerDeclaration, VariablePropertyDeclaration};
+use swift_oxide::syntax::expression::{Argument, TryKind};
+use swift_oxide::syntax::{Expression, IntegerType, Statement, SwiftType};
+
+fn attempt(expression: Expression) -> Expression {
+    try_(TryKind::Propagating, expression)
+}
+
+fn method(target: Expression, name: &str, arguments: Vec<Argument>) -> Expression {
+    call(member(target, name), arguments)
+}
+
+fn data(json: &str) -> Expression {
+    call(ident("Data"), vec![arg(member(string(json), "utf8"))])
+}
+
+fn text(data: Expression) -> Expression {
+    call(ident("String"), vec![labeled("decoding", data), labeled("as", member(ident("UTF8"), "self"))])
+}
+
+/// `decoder.decode(T.self, from: Data(json.utf8))`, without the `try`.
+fn decode(decoder: Expression, ty: Expression, json: &str) -> Expression {
+    method(decoder, "decode", vec![arg(ty), labeled("from", data(json))])
+}
+
+/// Prints what an expression gives, or the error it throws.
+fn print_or_error(expression: Expression) -> Statement {
+    do_catch(vec![print(vec![attempt(expression)])], vec![catch(None, vec![print(vec![ident("error")])])])
+}
+
+fn constant(property: VariablePropertyDeclaration) -> VariablePropertyDeclaration {
+    VariablePropertyDeclaration { is_constant: true, ..property }
+}
+
+fn raw(name: &str, value: &str) -> EnumCase {
+    EnumCase { raw_value: Some(string(value)), ..case(name, vec![]) }
+}
+
+fn point(x: i64, y: i64) -> Expression {
+    call(ident("Point"), vec![labeled("x", int(x)), labeled("y", int(y))])
+}
+
+fn point_declaration() -> Statement {
+    let properties = vec![stored("x", Some(SwiftType::Integer), None), stored("y", Some(SwiftType::Integer), None)];
+    conforming(structure("Point", properties, vec![], vec![]), &["Codable", "Equatable"])
+}
+
+/// `Role` codes its raw value and `Shape` its case name, while `Celsius` codes itself as a single
+/// value and `User` renames `name` through its `CodingKeys`.
+fn declarations() -> Vec<Statement> {
+    let rect = EnumCase {
+        associated_values: vec![
+            EnumAssociatedValue { label: Some("width".into()), ty: SwiftType::Integer },
+            EnumAssociatedValue { label: Some("height".into()), ty: SwiftType::Integer },
+        ],
+        ..case("rect", vec![])
+    };
+    let decoded = InitializerDeclaration {
+        is_throwing: true,
+        ..initializer(vec![param(Some("from"), "decoder", named("Decoder"))], vec![
+            let_("c", attempt(method(ident("decoder"), "singleValueContainer", vec![]))),
+            assign(member(this(), "degrees"), attempt(method(ident("c"), "decode", vec![arg(member(ident("Double"), "self"))]))),
+        ])
+    };
+    let encode = FunDeclaration {
+        is_throwing: true,
+        ..function("encode", vec![param(Some("to"), "encoder", named("Encoder"))], None, vec![
+            var("c", method(ident("encoder"), "singleValueContainer", vec![])),
+            expression(attempt(method(ident("c"), "encode", vec![arg(binary(member(this(), "degrees"), "*", float(1.0)))]))),
+        ])
+    };
+    let memberwise = initializer(vec![param(Some("degrees"), "degrees", SwiftType::Double)], vec![assign(member(this(), "degrees"), ident("degrees"))]);
+    let keys = ["age", "email", "role", "tags", "location", "shapes", "joined", "temperature"];
+    let mut user_keys = vec![raw("name", "full_name")];
+    user_keys.extend(keys.iter().map(|key| case(key, vec![])));
+    let user_properties = vec![
+        constant(stored("name", Some(SwiftType::String), None)),
+        stored("age", Some(SwiftType::Integer), None),
+        stored("email", Some(optional(SwiftType::String)), None),
+        stored("role", Some(named("Role")), None),
+        stored("tags", Some(SwiftType::Array(Box::new(SwiftType::String))), None),
+        stored("location", Some(named("Point")), None),
+        stored("shapes", Some(SwiftType::Array(Box::new(named("Shape")))), None),
+        stored("joined", Some(named("Date")), None),
+        stored("temperature", Some(named("Celsius")), None),
+        stored("cache", Some(SwiftType::Integer), Some(int(0))),
+    ];
+    let balances = SwiftType::Dictionary(Box::new(SwiftType::String), Box::new(SwiftType::Double));
+    let account_properties = vec![stored("id", Some(SwiftType::Integer), Some(int(0))), stored("balances", Some(balances), Some(dictionary(vec![])))];
+    let profile_properties = vec![stored("firstName", Some(SwiftType::String), None), stored("homeURL", Some(optional(SwiftType::String)), None)];
+    vec![
+        point_declaration(),
+        conforming(enumeration("Role", vec![case("admin", vec![]), raw("guest", "visitor")], Some(SwiftType::String), vec![], vec![]), &["Codable"]),
+        conforming(enumeration("Shape", vec![case("circle", vec![named("Point")]), rect, case("empty", vec![])], None, vec![], vec![]), &["Codable"]),
+        conforming(structure("Celsius", vec![stored("degrees", Some(SwiftType::Double), None)], vec![encode], vec![memberwise, decoded]), &["Codable"]),
+        conforming(enumeration("UserCodingKeys", user_keys, Some(SwiftType::String), vec![], vec![]), &["CodingKey"]),
+        conforming(structure("User", user_properties, vec![], vec![]), &["Codable"]),
+        conforming(class("Account", None, account_properties, vec![], vec![]), &["Codable"]),
+        conforming(structure("Profile", profile_properties, vec![], vec![]), &["Codable"]),
+        let_("decoder", call(ident("JSONDecoder"), vec![])),
+    ]
+}
+
+fn strategy(coder: &str, kind: &str, name: &str) -> Expression {
+    member(member(ident(coder), kind), name)
+}
+
+#[test]
+fn synthesized_conformances_round_trip_through_json() {
+    let shapes = array(vec![
+        call(member(ident("Shape"), "circle"), vec![arg(point(0, 0))]),
+        call(member(ident("Shape"), "rect"), vec![labeled("width", int(2)), labeled("height", int(3))]),
+        member(ident("Shape"), "empty"),
+    ]);
+    let user = call(ident("User"), vec![
+        labeled("name", string("Ada")),
+        labeled("age", int(36)),
+        labeled("email", nil()),
+        labeled("role", member(ident("Role"), "guest")),
+        labeled("tags", array(vec![string("a/b"), string("\"q\"")])),
+        labeled("location", point(1, -2)),
+        labeled("shapes", shapes),
+        labeled("joined", call(ident("Date"), vec![labeled("timeIntervalSince1970", float(1000000000.0))])),
+        labeled("temperature", call(ident("Celsius"), vec![labeled("degrees", float(21.5))])),
+    ]);
+    let back = |name| member(ident("back"), name);
+    let encode = |value| text(attempt(method(call(ident("JSONEncoder"), vec![]), "encode", vec![arg(value)])));
+    let profile = call(ident("Profile"), vec![labeled("firstName", string("Grace")), labeled("homeURL", nil())]);
+    let mut statements = declarations();
+    statements.extend([
+        let_("encoder", call(ident("JSONEncoder"), vec![])),
+        assign(member(ident("encoder"), "outputFormatting"), array(vec![
+            strategy("JSONEncoder", "OutputFormatting", "prettyPrinted"),
+            strategy("JSONEncoder", "OutputFormatting", "sortedKeys"),
+        ])),
+        assign(member(ident("encoder"), "dateEncodingStrategy"), strategy("JSONEncoder", "DateEncodingStrategy", "iso8601")),
+        let_("user", user),
+        let_("data", attempt(method(ident("encoder"), "encode", vec![arg(ident("user"))]))),
+        print(vec![text(ident("data"))]),
+        assign(member(ident("decoder"), "dateDecodingStrategy"), strategy("JSONDecoder", "DateDecodingStrategy", "iso8601")),
+        let_("back", attempt(method(ident("decoder"), "decode", vec![arg(member(ident("User"), "self")), labeled("from", ident("data"))]))),
+        print(vec![
+            back("name"),
+            back("age"),
+            back("email"),
+            binary(back("role"), "==", member(ident("Role"), "guest")),
+            binary(back("location"), "==", member(ident("user"), "location")),
+            member(back("shapes"), "count"),
+            member(back("joined"), "timeIntervalSince1970"),
+            member(back("temperature"), "degrees"),
+        ]),
+        print(vec![encode(back("shapes"))]),
+        print(vec![encode(call(ident("Account"), vec![]))]),
+        print(vec![member(attempt(decode(call(ident("JSONDecoder"), vec![]), member(ident("Account"), "self"), r#"{"id": 7, "balances": {"usd": 1.5}}"#)), "id")]),
+        let_("snake", call(ident("JSONEncoder"), vec![])),
+        assign(member(ident("snake"), "keyEncodingStrategy"), strategy("JSONEncoder", "KeyEncodingStrategy", "convertToSnakeCase")),
+        print(vec![text(attempt(method(ident("snake"), "encode", vec![arg(profile)])))]),
+        let_("camel", call(ident("JSONDecoder"), vec![])),
+        assign(member(ident("camel"), "keyDecodingStrategy"), strategy("JSONDecoder", "KeyDecodingStrategy", "convertFromSnakeCase")),
+        print(vec![member(attempt(decode(ident("camel"), member(ident("Profile"), "self"), r#"{"first_name": "Grace", "home_url": "x"}"#)), "homeURL")]),
+        print(vec![attempt(decode(
+            ident("decoder"),
+            Expression::TypeExpression(SwiftType::Dictionary(Box::new(SwiftType::String), Box::new(SwiftType::Array(Box::new(SwiftType::Integer))))),
+            r#"{"a": [1, 2]}"#,
+        ))]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    let pretty = r#"{
+  "age" : 36,
+  "full_name" : "Ada",
+  "joined" : "2001-09-09T01:46:40Z",
+  "location" : {
+    "x" : 1,
+    "y" : -2
+  },
+  "role" : "visitor",
+  "shapes" : [
+    {
+      "circle" : {
+        "_0" : {
+          "x" : 0,
+          "y" : 0
+        }
+      }
+    },
+    {
+      "rect" : {
+        "height" : 3,
+        "width" : 2
+      }
+    },
+    {
+      "empty" : {}
+    }
+  ],
+  "tags" : [
+    "a\/b",
+    "\"q\""
+  ],
+  "temperature" : 21.5
+}
+"#;
+    let rest = r#"Ada 36 nil true true 3 1000000000.0 21.5
+[{"circle":{"_0":{"x":0,"y":0}}},{"rect":{"width":2,"height":3}},{"empty":{}}]
+{"id":0,"balances":{}}
+7
+{"first_name":"Grace"}
+nil
+["a": [1, 2]]
+"#;
+    assert_eq!(output, format!("{pretty}{rest}"));
+}
+
+#[test]
+fn failures_throw_the_errors_swift_does() {
+    let mut statements = declarations();
+    statements.extend([
+        print_or_error(decode(ident("decoder"), member(ident("Point"), "self"), r#"{"x": 1}"#)),
+        print_or_error(decode(ident("decoder"), member(ident("Point"), "self"), r#"{"x": "1", "y": 2}"#)),
+        print_or_error(decode(ident("decoder"), member(ident("Point"), "self"), r#"{"x": null, "y": 2}"#)),
+        print_or_error(decode(ident("decoder"), member(ident("Point"), "self"), "[1, 2")),
+        print_or_error(decode(ident("decoder"), member(ident("Role"), "self"), r#""nobody""#)),
+        print_or_error(decode(ident("decoder"), member(ident("Shape"), "self"), r#"{"circle": {}, "empty": {}}"#)),
+        print_or_error(decode(ident("decoder"), Expression::TypeExpression(SwiftType::Array(Box::new(sized(IntegerType::Int8)))), "[1, 300]")),
+        print_or_error(method(call(ident("JSONEncoder"), vec![]), "encode", vec![arg(array(vec![float(1.0), binary(float(0.0), "/", float(0.0))]))])),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    let expected = [
+        r#"keyNotFound(PointCodingKeys(stringValue: "y", intValue: nil), Swift.DecodingError.Context(codingPath: [], debugDescription: "No value associated with key PointCodingKeys(stringValue: \"y\", intValue: nil) (\"y\").", underlyingError: nil))"#,
+        r#"typeMismatch(Swift.Int, Swift.DecodingError.Context(codingPath: [PointCodingKeys(stringValue: "x", intValue: nil)], debugDescription: "Expected to decode Int but found a string instead.", underlyingError: nil))"#,
+        r#"valueNotFound(Swift.Int, Swift.DecodingError.Context(codingPath: [PointCodingKeys(stringValue: "x", intValue: nil)], debugDescription: "Expected Int value but found null instead.", underlyingError: nil))"#,
+        r#"dataCorrupted(Swift.DecodingError.Context(codingPath: [], debugDescription: "The given data was not valid JSON.", underlyingError: Optional(SyntaxError: Expected ',' or ']' after array element in JSON at position 5)))"#,
+        r#"dataCorrupted(Swift.DecodingError.Context(codingPath: [], debugDescription: "Cannot initialize Role from invalid String value nobody", underlyingError: nil))"#,
+        r#"typeMismatch(Shape, Swift.DecodingError.Context(codingPath: [], debugDescription: "Invalid number of keys found, expected one.", underlyingError: nil))"#,
+        r#"dataCorrupted(Swift.DecodingError.Context(codingPath: [_JSONKey(stringValue: "Index 1", intValue: 1)], debugDescription: "Parsed JSON number <300> does not fit in Int8.", underlyingError: nil))"#,
+        r#"invalidValue(nan, Swift.EncodingError.Context(codingPath: [_JSONKey(stringValue: "Index 1", intValue: 1)], debugDescription: "Unable to encode Double.nan directly in JSON.", underlyingError: nil))"#,
+    ];
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn large_integers_decode_exactly_or_fail_to_fit() {
+    let wide_properties = vec![stored("id", Some(sized(IntegerType::Int64)), None), stored("ratio", Some(SwiftType::Double), None)];
+    let decode_id = |ty: &str, json: &str| print_or_error(member(decode(call(ident("JSONDecoder"), vec![]), member(ident(ty), "self"), json), "id"));
+    let program = program(vec![
+        conforming(structure("Wide", wide_properties, vec![], vec![]), &["Codable"]),
+        conforming(structure("Narrow", vec![stored("id", Some(SwiftType::Integer), None)], vec![], vec![]), &["Codable"]),
+        decode_id("Wide", r#"{"id": 9007199254740993, "ratio": 12345678901234567890, "note": "9007199254740993"}"#),
+        decode_id("Wide", r#"{"id": -9223372036854775808, "ratio": 0.5}"#),
+        decode_id("Wide", r#"{"id": 9223372036854775808, "ratio": 0.5}"#),
+        decode_id("Narrow", r#"{"id": 9007199254740993}"#),
+        decode_id("Narrow", r#"{"id": 42}"#),
+    ]);
+    let Some(output) = output(&program) else { return };
+    let expected = [
+        "9007199254740993",
+        "-9223372036854775808",
+        r#"dataCorrupted(Swift.DecodingError.Context(codingPath: [WideCodingKeys(stringValue: "id", intValue: nil)], debugDescription: "Parsed JSON number <9223372036854775808> does not fit in Int64.", underlyingError: nil))"#,
+        r#"dataCorrupted(Swift.DecodingError.Context(codingPath: [NarrowCodingKeys(stringValue: "id", intValue: nil)], debugDescription: "Parsed JSON number <9007199254740993> does not fit in Int.", underlyingError: nil))"#,
+        "42",
+    ];
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn synthesis_needs_codable_members() {
+    let program = program(vec![
+        conforming(structure("Letter", vec![stored("value", Some(SwiftType::Character), None)], vec![], vec![]), &["Codable"]),
+        class("Node", None, vec![stored("id", Some(SwiftType::Integer), Some(int(0)))], vec![], vec![]),
+        conforming(structure("Holder", vec![stored("node", Some(named("Node")), None)], vec![], vec![]), &["Encodable"]),
+    ]);
+    assert_eq!(errors(&program), [
+        "type 'Letter' does not conform to protocol 'Encodable'",
+        "type 'Letter' does not conform to protocol 'Decodable'",
+        "type 'Holder' does not conform to protocol 'Encodable'",
+    ]);
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+use std::process::Command;
+use swift_oxide::cli::{parse_arguments, Arguments};
+use swift_oxide::options::IntRepresentation;
+
+fn parse(arguments: &[&str]) -> Result<Arguments, String> {
+    parse_arguments(arguments.iter().map(|argument| argument.to_string()))
+}
+
+#[test]
+fn int_option_chooses_the_representation() {
+    let cases = [
+        (&["out.js"][..], IntRepresentation::Number),
+        (&["--int", "bigint", "out.js"], IntRepresentation::BigInt),
+        (&["out.js", "--int=number"], IntRepresentation::Number),
+        (&["--int=bigint", "out.js"], IntRepresentation::BigInt),
+    ];
+    for (arguments, representation) in cases {
+        let parsed = parse(arguments).unwrap();
+        assert_eq!((parsed.options.int_representation, parsed.output), (representation, PathBuf::from("out.js")), "{arguments:?}");
+    }
+    let errors = [
+        (&["--int", "int64", "out.js"][..], "invalid value 'int64' for '--int': expected 'number' or 'bigint'"),
+        (&["out.js", "--int"], "missing value for '--int'"),
+        (&["--width", "out.js"], "unknown option '--width'"),
+        (&[], "missing output file"),
+        (&["a.js", "b.js"], "expected one output file"),
+    ];
+    for (arguments, message) in errors {
+        assert_eq!(parse(arguments).unwrap_err(), message, "{arguments:?}");
+    }
+}
+
+#[test]
+fn compiler_writes_a_module_for_the_chosen_representation() {
+    let directory = std::env::temp_dir().join(format!("swift-oxide-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let compiler = || Command::new(env!("CARGO_BIN_EXE_swift-oxide"));
+    let bigint = compiler().args(["--int", "bigint"]).arg(directory.join("bigint.js")).output().unwrap();
+    let number = compiler().arg(directory.join("number.js")).output().unwrap();
+    let invalid = compiler().args(["--int", "huge"]).arg(directory.join("huge.js")).output().unwrap();
+    let read = |name: &str| std::fs::read_to_string(directory.join(name));
+    let (bigint_module, number_module, runtime) = (read("bigint.js"), read("number.js"), read("swift-runtime.js"));
+    let invalid_module = directory.join("huge.js").exists();
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert!(bigint.status.success() && number.status.success());
+    assert!(bigint_module.unwrap().contains("$rt.useBigIntForInt();"));
+    assert!(!number_module.unwrap().contains("useBigIntForInt"));
+    assert!(runtime.unwrap().contains("export function useBigIntForInt()"));
+    assert_eq!(invalid.status.code(), Some(2));
+    assert!(!invalid_module);
+    assert_eq!(
+        String::from_utf8(invalid.stderr).unwrap(),
+        "error: invalid value 'huge' for '--int': expected 'number' or 'bigint'\nusage: swift-oxide [--int number|bigint] <output.js>\n",
+    );
+}
@@ -0,0 +1,59 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+/// `Box<T>` is `Describable` when `T` is, and containers of `Int` get `doubled()` while every
+/// container gets `tag()`.
+fn declarations() -> Vec<Statement> {
+    let describe = |body| function("describe", vec![], Some(SwiftType::String), vec![ret(body)]);
+    let boxed = |name: &str, ty: SwiftType| {
+        let item = function("item", vec![], Some(ty.clone()), vec![ret(ident("value"))]);
+        conforming(structure(name, vec![stored("value", Some(ty), None)], vec![item], vec![]), &["Container"])
+    };
+    let generic_box = structure("Box", vec![stored("value", Some(named("T")), None)], vec![], vec![]);
+    let box_description = binary(binary(string("box("), "+", call(member(ident("value"), "describe"), vec![])), "+", string(")"));
+    let doubled = function("doubled", vec![], Some(SwiftType::Integer), vec![ret(binary(call(ident("item"), vec![]), "*", int(2)))]);
+    vec![
+        protocol("Describable", &[], vec![], vec![("describe", vec![], Some(SwiftType::String))], vec![]),
+        with_associated_types(protocol("Container", &[], vec![], vec![("item", vec![], Some(named("Item")))], vec![]), vec![("Item", vec![], None)]),
+        boxed("IntBox", SwiftType::Integer),
+        boxed("StrBox", SwiftType::String),
+        with_generics(generic_box, generics(vec![("T", vec![])], vec![])),
+        with_generics(extension("Box", &["Describable"], vec![], vec![describe(box_description)]), generics(vec![], vec![conformance(named("T"), "Describable")])),
+        conforming(structure("Name", vec![stored("n", Some(SwiftType::String), None)], vec![describe(ident("n"))], vec![]), &["Describable"]),
+        with_generics(extension("Container", &[], vec![], vec![doubled]), generics(vec![], vec![same_type(named("Item"), SwiftType::Integer)])),
+        extension("Container", &[], vec![], vec![function("tag", vec![], Some(SwiftType::String), vec![ret(string("any"))])]),
+    ]
+}
+
+fn new(name: &str, label: &str, value: Expression) -> Expression {
+    call(ident(name), vec![labeled(label, value)])
+}
+
+#[test]
+fn conditional_conformances_hold_only_for_matching_arguments() {
+    let mut statements = declarations();
+    statements.extend([
+        typed_let("named", SwiftType::Generic("Box".into(), vec![named("Name")]), new("Box", "value", new("Name", "n", string("a")))),
+        print(vec![call(member(new("IntBox", "value", int(3)), "doubled"), vec![]), call(member(new("StrBox", "value", string("x")), "tag"), vec![])]),
+        print(vec![
+            binary(cast(ident("named"), named("Describable")), "!=", nil()),
+            binary(cast(new("Box", "value", int(5)), named("Describable")), "!=", nil()),
+            call(member(ident("named"), "describe"), vec![]),
+        ]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    assert_eq!(output, "6 any\ntrue false box(a)\n");
+}
+
+#[test]
+fn constrained_extension_members_need_their_requirements_met() {
+    let mut statements = declarations();
+    statements.push(expression(call(member(new("StrBox", "value", string("x")), "doubled"), vec![])));
+    statements.push(typed_let("ints", SwiftType::Generic("Box".into(), vec![SwiftType::Integer]), new("Box", "value", int(5))));
+    statements.push(expression(call(member(ident("ints"), "describe"), vec![])));
+    assert_eq!(errors(&program(statements)), [
+        "referencing instance method 'doubled()' on 'Container' requires the types 'String' and 'Int' be equivalent",
+        "referencing instance method 'describe()' on 'Box' requires that 'Int' conform to 'Describable'",
+    ]);
+}
@@ -0,0 +1,85 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::VariablePropertyDeclaration;
+use swift_oxide::syntax::SwiftType;
+
+#[test]
+fn values_initialized_on_every_path_can_be_used() {
+    let y = VariablePropertyDeclaration { is_constant: true, ..stored("y", Some(SwiftType::Integer), None) };
+    let point = structure(
+        "P",
+        vec![stored("x", Some(SwiftType::Integer), None), y],
+        vec![function("sum", vec![], Some(SwiftType::Integer), vec![ret(binary(ident("x"), "+", ident("y")))])],
+        vec![initializer(vec![param(Some("v"), "v", SwiftType::Integer)], vec![
+            if_(binary(ident("v"), ">", int(0)), vec![assign(ident("x"), ident("v"))], Some(vec![assign(ident("x"), int(0))])),
+            assign(member(this(), "y"), int(3)),
+            print(vec![call(ident("sum"), vec![])]),
+        ])],
+    );
+    let program = program(vec![
+        let_("flag", boolean(true)),
+        declare_let("a", SwiftType::Integer),
+        if_(ident("flag"), vec![assign(ident("a"), int(1))], Some(vec![assign(ident("a"), int(2))])),
+        declare_var("b", SwiftType::Integer),
+        switch_default(ident("a"), vec![(literal_pattern(1), vec![assign(ident("b"), int(10))])], Some(vec![assign(ident("b"), int(20))])),
+        function_declaration(function("run", vec![], None, vec![
+            declare_var("c", SwiftType::Integer),
+            guard(ident("flag"), vec![return_()]),
+            assign(ident("c"), binary(ident("a"), "+", ident("b"))),
+            print(vec![ident("a"), ident("b"), ident("c")]),
+        ])),
+        expression(call(ident("run"), vec![])),
+        point,
+        expression(call(ident("P"), vec![labeled("v", int(4))])),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "1 10 11\n7\n");
+}
+
+#[test]
+fn locals_must_be_initialized_before_use_and_constants_only_once() {
+    let program = program(vec![
+        let_("flag", boolean(true)),
+        declare_let("d", SwiftType::Integer),
+        if_(ident("flag"), vec![assign(ident("d"), int(1))], None),
+        print(vec![ident("d")]),
+        declare_let("e", SwiftType::Integer),
+        assign(ident("e"), int(1)),
+        assign(ident("e"), int(2)),
+        declare_var("f", SwiftType::Integer),
+        expression(closure(vec![print(vec![ident("f")])])),
+        declare_let("g", SwiftType::Integer),
+        while_(ident("flag"), vec![assign(ident("g"), int(1)), break_()]),
+    ]);
+    assert_eq!(errors(&program), [
+        "constant 'd' used before being initialized",
+        "immutable value 'e' may only be initialized once",
+        "variable 'f' captured by a closure before being initialized",
+        "immutable value 'g' may only be initialized once",
+    ]);
+}
+
+#[test]
+fn initializers_must_initialize_every_stored_property_before_using_self() {
+    let properties = || vec![stored("x", Some(SwiftType::Integer), None), stored("y", Some(SwiftType::Integer), None)];
+    let early_use = initializer(vec![], vec![assign(ident("x"), int(1)), expression(call(ident("m"), vec![])), assign(ident("y"), int(1))]);
+    let partial = initializer(vec![param(Some("flag"), "flag", SwiftType::Bool)], vec![assign(ident("x"), int(1)), if_(ident("flag"), vec![assign(ident("y"), int(1))], None)]);
+    let early_read = initializer(vec![param(Some("z"), "z", SwiftType::Integer)], vec![print(vec![ident("y")]), assign(ident("x"), ident("z")), assign(ident("y"), ident("z"))]);
+    let k = || stored("k", Some(SwiftType::Integer), None);
+    let program = program(vec![
+        structure("Q", properties(), vec![function("m", vec![], None, vec![])], vec![early_use, partial, early_read]),
+        class("Base", None, vec![], vec![], vec![initializer(vec![], vec![])]),
+        class("Late", Some("Base"), vec![k()], vec![], vec![initializer(vec![param(Some("flag"), "flag", SwiftType::Bool)], vec![
+            if_(ident("flag"), vec![assign(ident("k"), int(1))], None),
+            expression(call(member(superclass(), "init"), vec![])),
+        ])]),
+        class("Missing", None, vec![k()], vec![], vec![initializer(vec![], vec![])]),
+    ]);
+    assert_eq!(errors(&program), [
+        "'self' used before all stored properties are initialized",
+        "return from initializer without initializing all stored properties",
+        "variable 'self.y' used before being initialized",
+        "property 'self.k' not initialized at super.init call",
+        "return from initializer without initializing all stored properties",
+    ]);
+}
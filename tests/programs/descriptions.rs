@@ -0,0 +1,101 @@
+use crate::build::*;
+use crate::output;
+use swift_oxide::syntax::declaration::{EnumAssociatedValue, EnumCase};
+use swift_oxide::syntax::{Expression, SwiftType};
+
+fn point(x: i64, y: i64) -> Expression {
+    call(ident("Point"), vec![labeled("x", int(x)), labeled("y", int(y))])
+}
+
+fn temperature(value: Expression) -> Expression {
+    call(ident("Temp"), vec![labeled("c", value)])
+}
+
+#[test]
+fn values_print_as_swift_describes_them() {
+    let rect = EnumCase {
+        associated_values: vec![
+            EnumAssociatedValue { label: Some("width".into()), ty: SwiftType::Double },
+            EnumAssociatedValue { label: Some("height".into()), ty: SwiftType::Double },
+        ],
+        ..case("rect", vec![])
+    };
+    let point_properties = vec![stored("x", Some(SwiftType::Integer), None), stored("y", Some(SwiftType::Integer), None)];
+    let line_properties = vec![stored("from", Some(named("Point")), None), stored("to", Some(named("Point")), None)];
+    let celsius = computed("description", SwiftType::String, vec![ret(interpolation(vec![value_segment(ident("c")), text_segment("°C")]))]);
+    let cat = computed("description", SwiftType::String, vec![ret(string("a cat"))]);
+    let doubles = SwiftType::Dictionary(Box::new(SwiftType::String), Box::new(SwiftType::Double));
+    let program = program(vec![
+        structure("Point", point_properties, vec![], vec![]),
+        structure("Line", line_properties, vec![], vec![]),
+        enumeration("Barcode", vec![case("upc", vec![SwiftType::Integer; 4]), case("qrCode", vec![SwiftType::String])], None, vec![], vec![]),
+        enumeration("Shape", vec![rect, case("none", vec![])], None, vec![], vec![]),
+        class("Dog", None, vec![stored("name", None, Some(string("Rex")))], vec![], vec![]),
+        conforming(structure("Temp", vec![stored("c", Some(SwiftType::Double), None), celsius], vec![], vec![]), &["CustomStringConvertible"]),
+        conforming(class("Cat", None, vec![cat], vec![], vec![]), &["CustomStringConvertible"]),
+        let_("p", point(10, 20)),
+        print(vec![ident("p")]),
+        typed_let("o", optional(SwiftType::Integer), int(5)),
+        print(vec![ident("o")]),
+        print(vec![dictionary(vec![(string("a"), int(1))])]),
+        print(vec![
+            float(1.0),
+            binary(float(0.1), "+", float(0.2)),
+            float(1e16),
+            float(0.00001),
+            binary(float(100.0), "/", float(3.0)),
+            float(1e15),
+            float(-0.0001),
+        ]),
+        print(vec![call(member(ident("Barcode"), "upc"), vec![arg(int(1)), arg(int(2)), arg(int(3)), arg(int(4))])]),
+        print(vec![array(vec![call(member(ident("Barcode"), "qrCode"), vec![arg(string("x\"y'\n"))])])]),
+        print(vec![
+            call(member(ident("Shape"), "rect"), vec![labeled("width", int(2)), labeled("height", float(3.5))]),
+            member(ident("Shape"), "none"),
+            array(vec![member(ident("Shape"), "none")]),
+        ]),
+        print(vec![tuple(vec![int(1), string("a")])]),
+        print(vec![call(ident("Line"), vec![labeled("from", ident("p")), labeled("to", point(0, 0))])]),
+        print(vec![call(ident("Dog"), vec![]), array(vec![call(ident("Dog"), vec![])])]),
+        print(vec![array(vec![temperature(int(21))]), temperature(float(1.5))]),
+        print(vec![interpolation(vec![
+            text_segment("p is "),
+            value_segment(ident("p")),
+            text_segment(", o is "),
+            value_segment(ident("o")),
+            text_segment(", t is "),
+            value_segment(temperature(int(3))),
+            text_segment(", half is "),
+            value_segment(binary(float(1.0), "/", float(2.0))),
+        ])]),
+        print(vec![call(ident("String"), vec![labeled("describing", call(ident("Cat"), vec![]))]), call(ident("String"), vec![arg(float(2.0))])]),
+        typed_let("names", SwiftType::Array(Box::new(optional(SwiftType::String))), array(vec![string("a"), nil()])),
+        print(vec![ident("names")]),
+        typed_let("f", SwiftType::Float, float(0.1)),
+        typed_let("big", SwiftType::Float, float(1e8)),
+        print(vec![ident("f"), ident("big")]),
+        typed_let("empty", doubles.clone(), dictionary(vec![])),
+        typed_let("scores", doubles, dictionary(vec![(string("a"), int(1))])),
+        print(vec![ident("empty"), ident("scores"), array(vec![])]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    let expected = [
+        "Point(x: 10, y: 20)",
+        "Optional(5)",
+        "[\"a\": 1]",
+        "1.0 0.30000000000000004 1e+16 1e-05 33.333333333333336 1000000000000000.0 -0.0001",
+        "upc(1, 2, 3, 4)",
+        "[main.Barcode.qrCode(\"x\\\"y\\'\\n\")]",
+        "rect(width: 2.0, height: 3.5) none [main.Shape.none]",
+        "(1, \"a\")",
+        "Line(from: main.Point(x: 10, y: 20), to: main.Point(x: 0, y: 0))",
+        "main.Dog [main.Dog]",
+        "[21.0°C] 1.5°C",
+        "p is Point(x: 10, y: 20), o is Optional(5), t is 3.0°C, half is 0.5",
+        "a cat 2.0",
+        "[Optional(\"a\"), nil]",
+        "0.1 1e+08",
+        "[:] [\"a\": 1.0] []",
+    ];
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+}
@@ -0,0 +1,186 @@
+use crate::build::*;
+use crate::{compile, errors, output, run_trapping};
+use swift_oxide::options::Options;
+use swift_oxide::syntax::declaration::{EnumAssociatedValue, EnumCase, FunDeclaration, InitializerDeclaration, VariablePropertyDeclaration};
+use swift_oxide::syntax::expression::{Argument, TryKind};
+use swift_oxide::syntax::statement::{CatchClause, EnumCasePattern, Pattern};
+use swift_oxide::syntax::{Expression, FunctionTypeAttributes, Statement, SwiftType};
+
+fn parse_error(case_name: &str, arguments: Vec<Argument>) -> Expression {
+    let case = member(ident("ParseError"), case_name);
+    if arguments.is_empty() { case } else { call(case, arguments) }
+}
+
+fn parse_error_pattern(case_name: &str, associated_values: Vec<Pattern>) -> Pattern {
+    Pattern::EnumCase(EnumCasePattern { enum_name: Some("ParseError".into()), case_name: case_name.into(), associated_values })
+}
+
+fn check(n: i64, line: i64) -> Expression {
+    call(ident("check"), vec![arg(int(n)), labeled("line", int(line))])
+}
+
+fn throwing_function() -> SwiftType {
+    SwiftType::Function(vec![SwiftType::Integer], Box::new(SwiftType::Integer), FunctionTypeAttributes { is_throwing: true, ..Default::default() })
+}
+
+fn constant(name: &str) -> VariablePropertyDeclaration {
+    VariablePropertyDeclaration { is_constant: true, ..stored(name, Some(SwiftType::Integer), None) }
+}
+
+/// `check(_:line:)` throws a `ParseError` or a `Limit` for out-of-range numbers, `apply(_:to:)`
+/// rethrows, and `Port.init` throws for negative ports.
+fn declarations() -> Vec<Statement> {
+    let invalid = EnumCase { associated_values: vec![EnumAssociatedValue { label: Some("line".into()), ty: SwiftType::Integer }], ..case("invalid", vec![]) };
+    let check = function("check", vec![param(None, "n", SwiftType::Integer), param(Some("line"), "line", SwiftType::Integer)], Some(SwiftType::Integer), vec![
+        if_(binary(ident("n"), "==", int(0)), vec![throw_(parse_error("empty", vec![]))], None),
+        if_(binary(ident("n"), "<", int(0)), vec![throw_(parse_error("invalid", vec![labeled("line", ident("line"))]))], None),
+        if_(binary(ident("n"), ">", int(999)), vec![throw_(parse_error("tooLong", vec![arg(ident("n"))]))], None),
+        if_(binary(ident("n"), ">", int(100)), vec![throw_(call(ident("Limit"), vec![labeled("max", int(100))]))], None),
+        ret(ident("n")),
+    ]);
+    let apply = function(
+        "apply",
+        vec![param(None, "f", throwing_function()), param(Some("to"), "v", SwiftType::Integer)],
+        Some(SwiftType::Integer),
+        vec![ret(try_(TryKind::Propagating, call(ident("f"), vec![arg(ident("v"))])))],
+    );
+    let port = InitializerDeclaration {
+        is_throwing: true,
+        ..initializer(vec![param(None, "v", SwiftType::Integer)], vec![
+            if_(binary(ident("v"), "<", int(0)), vec![throw_(parse_error("invalid", vec![labeled("line", ident("v"))]))], None),
+            assign(member(this(), "value"), ident("v")),
+        ])
+    };
+    vec![
+        conforming(enumeration("ParseError", vec![case("empty", vec![]), invalid, case("tooLong", vec![SwiftType::Integer])], None, vec![], vec![]), &["Error"]),
+        conforming(structure("Limit", vec![constant("max")], vec![], vec![]), &["Error"]),
+        function_declaration(FunDeclaration { is_throwing: true, ..check }),
+        function_declaration(FunDeclaration { is_rethrowing: true, ..apply }),
+        structure("Port", vec![constant("value")], vec![], vec![port]),
+    ]
+}
+
+#[test]
+fn errors_are_thrown_caught_by_pattern_and_rethrown() {
+    let doubled = closure_of(&["x"], vec![ret(binary(ident("x"), "*", int(2)))]);
+    let checked = closure_of(&["x"], vec![ret(try_(TryKind::Propagating, call(ident("check"), vec![arg(ident("x")), labeled("line", int(9))])))]);
+    let mut statements = declarations();
+    statements.extend([
+        for_in("n", array(vec![int(12), int(0), int(-1), int(-5), int(5000), int(500)]), vec![do_catch(
+            vec![
+                let_("value", try_(TryKind::Propagating, call(ident("check"), vec![arg(ident("n")), labeled("line", binary(ident("n"), "*", int(-1)))]))),
+                print(vec![string("parsed"), ident("value")]),
+            ],
+            vec![
+                catch(Some(parse_error_pattern("empty", vec![])), vec![print(vec![string("empty")])]),
+                CatchClause {
+                    guard_expression: Some(Box::new(binary(ident("line"), ">", int(2)))),
+                    ..catch(Some(parse_error_pattern("invalid", vec![binding("line")])), vec![print(vec![string("invalid at line"), ident("line")])])
+                },
+                catch(Some(parse_error_pattern("tooLong", vec![binding("count")])), vec![print(vec![string("too long:"), ident("count")])]),
+                catch(None, vec![print(vec![string("other:"), ident("error")])]),
+            ],
+        )]),
+        print(vec![try_(TryKind::Optional, check(7, 1))]),
+        print(vec![try_(TryKind::Optional, check(0, 1))]),
+        print(vec![try_(TryKind::Forced, check(42, 1))]),
+        print(vec![call(ident("apply"), vec![arg(doubled), labeled("to", int(4))])]),
+        do_catch(
+            vec![print(vec![try_(TryKind::Propagating, call(ident("apply"), vec![arg(checked), labeled("to", int(1000))]))])],
+            vec![catch(Some(binding("e")), vec![print(vec![string("rethrown:"), ident("e")])])],
+        ),
+        do_catch(
+            vec![do_catch(vec![expression(try_(TryKind::Propagating, check(0, 1)))], vec![catch(
+                Some(parse_error_pattern("tooLong", vec![Pattern::Wildcard])),
+                vec![print(vec![string("inner")])],
+            )])],
+            vec![catch(None, vec![print(vec![string("outer:"), ident("error")])])],
+        ),
+        do_catch(
+            vec![
+                let_("port", try_(TryKind::Propagating, call(ident("Port"), vec![arg(int(-3))]))),
+                print(vec![member(ident("port"), "value")]),
+            ],
+            vec![
+                catch(Some(type_pattern(named("Limit"))), vec![print(vec![string("limit")])]),
+                catch(None, vec![print(vec![string("port:"), ident("error")])]),
+            ],
+        ),
+        print(vec![member(try_(TryKind::Forced, call(ident("Port"), vec![arg(int(8080))])), "value")]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    let expected = [
+        "parsed 12",
+        "empty",
+        "other: invalid(line: 1)",
+        "invalid at line 5",
+        "too long: 5000",
+        "other: Limit(max: 100)",
+        "Optional(7)",
+        "nil",
+        "42",
+        "8",
+        "rethrown: tooLong(1000)",
+        "outer: empty",
+        "port: invalid(line: -3)",
+        "8080",
+    ];
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn throwing_calls_need_try_and_somewhere_to_throw_to() {
+    let quiet = function("quiet", vec![], None, vec![throw_(parse_error("empty", vec![])), expression(try_(TryKind::Propagating, check(1, 1)))]);
+    let partial = function("partial", vec![], None, vec![do_catch(
+        vec![expression(try_(TryKind::Propagating, check(1, 1)))],
+        vec![catch(Some(parse_error_pattern("empty", vec![])), vec![])],
+    )]);
+    let wrap = function("wrap", vec![param(None, "f", throwing_function())], None, vec![
+        expression(try_(TryKind::Propagating, check(1, 1))),
+        throw_(parse_error("empty", vec![])),
+    ]);
+    let mut statements = declarations();
+    statements.extend([
+        expression(check(1, 1)),
+        function_declaration(quiet),
+        function_declaration(partial),
+        function_declaration(FunDeclaration { is_rethrowing: true, ..wrap }),
+        expression(call(member(array(vec![int(1)]), "map"), vec![arg(closure_of(&["x"], vec![ret(try_(TryKind::Propagating, check(1, 1)))]))])),
+        expression(call(ident("Port"), vec![arg(int(1))])),
+    ]);
+    assert_eq!(errors(&program(statements)), [
+        "call can throw but is not marked with 'try'",
+        "error is not handled because the enclosing function is not declared 'throws'",
+        "errors thrown from here are not handled",
+        "errors thrown from here are not handled because the enclosing catch is not exhaustive",
+        "call can throw, but the error is not handled; a function declared 'rethrows' may only throw if its parameter does",
+        "a function declared 'rethrows' may only throw if its parameter does",
+        "call can throw but is not marked with 'try'",
+        "call can throw but is not marked with 'try'",
+    ]);
+}
+
+#[test]
+fn rethrowing_functions_only_throw_what_their_parameters_do() {
+    let call_with_one = |name| expression(try_(TryKind::Propagating, call(ident(name), vec![arg(int(1))])));
+    let relay = function("relay", vec![param(None, "f", throwing_function())], None, vec![
+        call_with_one("f"),
+        typed_let("g", throwing_function(), ident("f")),
+        call_with_one("g"),
+        if_(boolean(true), vec![typed_let("f", throwing_function(), ident("g")), call_with_one("f")], None),
+    ]);
+    let mut statements = declarations();
+    statements.push(function_declaration(FunDeclaration { is_rethrowing: true, ..relay }));
+    assert_eq!(errors(&program(statements)), [
+        "call can throw, but the error is not handled; a function declared 'rethrows' may only throw if its parameter does",
+        "call can throw, but the error is not handled; a function declared 'rethrows' may only throw if its parameter does",
+    ]);
+}
+
+#[test]
+fn forced_try_traps_when_an_error_is_thrown() {
+    let mut statements = declarations();
+    statements.extend([print(vec![try_(TryKind::Forced, check(7, 1))]), print(vec![try_(TryKind::Forced, check(0, 1))])]);
+    let Some((output, message)) = run_trapping(&compile(&program(statements), &Options::default())) else { return };
+    assert_eq!((output.as_str(), message.as_str()), ("7\n", "'try!' expression unexpectedly raised an error: main.ParseError.empty"));
+}
@@ -0,0 +1,89 @@
+use crate::build::*;
+use crate::{errors, output, warnings};
+use swift_oxide::options::Options;
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+/// `Circle` is both `Shape` and `Named`, while `Square` is only a `Shape`.
+fn shapes() -> Vec<Statement> {
+    let circle_draw = function("draw", vec![], Some(SwiftType::String), vec![ret(binary(string("circle "), "+", call(ident("String"), vec![arg(ident("r"))])))]);
+    let circle_properties = vec![stored("r", Some(SwiftType::Integer), None), computed("name", SwiftType::String, vec![ret(string("circle"))])];
+    let square_draw = function("draw", vec![], Some(SwiftType::String), vec![ret(string("square"))]);
+    vec![
+        protocol("Shape", &[], vec![], vec![("draw", vec![], Some(SwiftType::String))], vec![]),
+        protocol("Named", &[], vec![("name", SwiftType::String, true)], vec![], vec![]),
+        conforming(structure("Circle", circle_properties, vec![circle_draw], vec![]), &["Shape", "Named"]),
+        conforming(structure("Square", vec![], vec![square_draw], vec![]), &["Shape"]),
+    ]
+}
+
+fn circle(r: i64) -> Expression {
+    call(ident("Circle"), vec![labeled("r", int(r))])
+}
+
+fn square() -> Expression {
+    call(ident("Square"), vec![])
+}
+
+fn draw(shape: Expression) -> Expression {
+    call(member(shape, "draw"), vec![])
+}
+
+#[test]
+fn existentials_opaque_results_and_compositions_dispatch_through_their_conformances() {
+    let make = function("makeShape", vec![], Some(opaque("Shape")), vec![expression(circle(1))]);
+    let render = function("render", vec![param(None, "s", existential("Shape"))], Some(SwiftType::String), vec![ret(draw(ident("s")))]);
+    let both = function("both", vec![param(None, "v", composition(&["Named", "Shape"]))], Some(SwiftType::String), vec![ret(binary(
+        binary(member(ident("v"), "name"), "+", string(": ")),
+        "+",
+        draw(ident("v")),
+    ))]);
+    let mut statements = shapes();
+    statements.extend([
+        function_declaration(make),
+        function_declaration(render),
+        function_declaration(both),
+        typed_var("a", existential("Shape"), circle(2)),
+        var("b", ident("a")),
+        typed_let("shapes", SwiftType::Array(Box::new(existential("Shape"))), array(vec![circle(3), square()])),
+        print(vec![draw(call(ident("makeShape"), vec![])), call(ident("render"), vec![arg(square())]), call(ident("both"), vec![arg(circle(4))])]),
+        print(vec![draw(subscript(ident("shapes"), int(0))), draw(subscript(ident("shapes"), int(1)))]),
+        assign(ident("a"), square()),
+        print(vec![
+            draw(ident("b")),
+            draw(ident("a")),
+            binary(cast(ident("b"), named("Circle")), "!=", nil()),
+            binary(cast(ident("a"), named("Named")), "!=", nil()),
+        ]),
+        let_("n", cast(ident("b"), composition(&["Named", "Shape"]))),
+        print(vec![binary(ident("n"), "!=", nil()), call(ident("render"), vec![arg(ident("b"))])]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    assert_eq!(output, "circle 1 square circle: circle 4\ncircle 3 square\ncircle 2 square true false\ntrue circle 2\n");
+}
+
+#[test]
+fn opaque_results_need_one_underlying_type_that_conforms() {
+    let mixed = function("mixed", vec![], Some(opaque("Shape")), vec![if_(boolean(true), vec![ret(circle(1))], None), ret(square())]);
+    let mut statements = shapes();
+    statements.extend([
+        function_declaration(mixed),
+        function_declaration(function("nothing", vec![], Some(opaque("Shape")), vec![])),
+        function_declaration(function("number", vec![], Some(opaque("Shape")), vec![ret(int(5))])),
+        typed_var("x", SwiftType::Opaque(Box::new(SwiftType::Integer)), int(1)),
+        typed_var("z", composition(&["Circle", "Shape"]), circle(1)),
+    ]);
+    assert_eq!(errors(&program(statements)), [
+        "function declares an opaque return type, but the return statements in its body do not have matching underlying types",
+        "function declares an opaque return type, but has no return statements in its body from which to infer an underlying type",
+        "return type of global function 'number()' requires that 'Int' conform to 'Shape'",
+        "an 'opaque' type must specify only 'Any', 'AnyObject', protocols, and/or a base class",
+        "non-protocol, non-class type 'Circle' cannot be used within a protocol-constrained type",
+    ]);
+}
+
+#[test]
+fn any_has_no_effect_on_concrete_types() {
+    let mut statements = shapes();
+    statements.push(typed_var("y", existential("Circle"), circle(1)));
+    assert_eq!(warnings(&program(statements), &Options::default()), ["'any' has no effect on concrete type 'Circle'"]);
+}
@@ -0,0 +1,75 @@
+use crate::build::*;
+use crate::{compile, errors, run};
+use swift_oxide::options::{IntRepresentation, Options};
+use swift_oxide::syntax::{Expression, IntegerType};
+
+fn convert(type_name: &str, label: &str, value: Expression) -> Expression {
+    call(ident(type_name), vec![labeled(label, value)])
+}
+
+#[test]
+fn shifts_bitwise_operators_and_conversions_follow_swift_in_either_representation() {
+    let program = program(vec![
+        typed_let("flags", sized(IntegerType::UInt8), int(0b1010_0101)),
+        let_("shifted", binary(ident("flags"), "<<", int(4))),
+        let_("back", binary(ident("flags"), ">>", unary("-", int(2)))),
+        let_("gone", binary(ident("flags"), "<<", int(9))),
+        let_("masked", binary(ident("flags"), "&<<", int(9))),
+        let_("low", binary(ident("flags"), "&", int(0x0F))),
+        let_("inverted", unary("~", ident("flags"))),
+        print(vec![ident("shifted"), ident("back"), ident("gone"), ident("masked"), ident("low"), ident("inverted")]),
+        typed_let("negative", sized(IntegerType::Int32), unary("-", int(1))),
+        print(vec![binary(ident("negative"), ">>", int(40)), binary(ident("negative"), "|", int(6)), binary(ident("negative"), "^", int(1))]),
+        print(vec![
+            convert("UInt32", "bitPattern", ident("negative")),
+            convert("Int8", "truncatingIfNeeded", int(300)),
+            convert("UInt8", "clamping", unary("-", int(7))),
+            convert("Int8", "clamping", int(1000)),
+        ]),
+        print(vec![convert("Int8", "exactly", int(100)), convert("Int8", "exactly", int(200)), convert("Int16", "exactly", float(2.5))]),
+        var("counter", int(0)),
+        expression(binary(ident("counter"), "+=", int(41))),
+        expression(binary(ident("counter"), "<<=", int(1))),
+        typed_let("big", sized(IntegerType::Int64), binary(ident("counter"), "*", int(1000))),
+        let_("ratio", binary(call(ident("Double"), vec![arg(ident("counter"))]), "/", int(4))),
+        print(vec![
+            ident("counter"),
+            ident("big"),
+            ident("ratio"),
+            binary(ident("counter"), "&*", int(2)),
+            call(ident("Int"), vec![arg(float(3.9))]),
+            binary(int(1), "<<", int(40)),
+        ]),
+        switch_default(ident("counter"), vec![(literal_pattern(82), vec![print(vec![string("matched")])])], Some(vec![print(vec![string("missed")])])),
+    ]);
+    for int_representation in [IntRepresentation::Number, IntRepresentation::BigInt] {
+        let Some(output) = run(&compile(&program, &Options { int_representation, ..Options::default() })) else { return };
+        assert_eq!(
+            output,
+            "80 148 0 74 5 90\n-1 -1 -2\n4294967295 44 0 127\nOptional(100) nil nil\n82 82000 20.5 164 3 1099511627776\nmatched\n",
+            "with {int_representation:?}"
+        );
+    }
+}
+
+#[test]
+fn integer_literals_must_fit_the_type_they_initialize() {
+    let program = program(vec![
+        typed_let("x", sized(IntegerType::UInt8), int(256)),
+        typed_let("y", sized(IntegerType::UInt16), unary("-", int(1))),
+        typed_let("z", sized(IntegerType::Int8), unary("-", int(129))),
+        typed_let("w", sized(IntegerType::Int8), unary("-", int(128))),
+    ]);
+    assert_eq!(errors(&program), [
+        "integer literal '256' overflows when stored into 'UInt8'",
+        "negative integer '-1' overflows when stored into unsigned type 'UInt16'",
+        "integer literal '-129' overflows when stored into 'Int8'",
+    ]);
+}
+
+#[test]
+fn int_representations_parse_by_name() {
+    assert_eq!("number".parse(), Ok(IntRepresentation::Number));
+    assert_eq!("bigint".parse(), Ok(IntRepresentation::BigInt));
+    assert_eq!("int64".parse::<IntRepresentation>(), Err("invalid value 'int64' for '--int': expected 'number' or 'bigint'".to_string()));
+}
@@ -0,0 +1,137 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{FunDeclaration, FunctionParameter};
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+/// `Makeable` requires `init(seed:)`, which `Counter` implements by storing the seed in `n`.
+fn counter() -> Vec<Statement> {
+    let kind = FunDeclaration { is_static: true, ..function("kind", vec![], Some(SwiftType::String), vec![ret(string("counter"))]) };
+    let initializer = initializer(vec![param(Some("seed"), "seed", SwiftType::Integer)], vec![assign(ident("n"), ident("seed"))]);
+    vec![
+        protocol("Makeable", &[], vec![], vec![], vec![vec![param(Some("seed"), "seed", SwiftType::Integer)]]),
+        conforming(structure("Counter", vec![stored("n", Some(SwiftType::Integer), None)], vec![kind], vec![initializer]), &["Makeable"]),
+    ]
+}
+
+fn generic(function: FunDeclaration, constraints: Vec<SwiftType>) -> Statement {
+    function_declaration(FunDeclaration { generics: Some(generics(vec![("T", constraints)], vec![])), ..function })
+}
+
+fn seeded(name: &str, seed: i64) -> Expression {
+    call(ident(name), vec![labeled("seed", int(seed))])
+}
+
+/// `Holder<Element: Makeable>` holds an item and makes fresh ones; `CounterHolder` and
+/// `PlainHolder` subclass `Holder<Counter>`, or bare `Holder` when `arguments` is empty.
+fn holders(arguments: Vec<SwiftType>) -> Vec<Statement> {
+    let element = named("Element");
+    let fresh = function("fresh", vec![], Some(element.clone()), vec![ret(seeded("Element", 9))]);
+    let holder = class("Holder", None, vec![stored("item", Some(element.clone()), None)], vec![fresh], vec![initializer(
+        vec![param(Some("item"), "item", element)],
+        vec![assign(member(this(), "item"), ident("item"))],
+    )]);
+    let super_init = expression(call(member(superclass(), "init"), vec![labeled("item", call(ident("Counter"), vec![labeled("seed", ident("seed"))]))]));
+    let counter_holder = class("CounterHolder", Some("Holder"), vec![], vec![], vec![initializer(vec![param(Some("seed"), "seed", SwiftType::Integer)], vec![super_init])]);
+    let mut statements = counter();
+    statements.extend([
+        with_generics(holder, generics(vec![("Element", vec![named("Makeable")])], vec![])),
+        with_superclass_arguments(counter_holder, arguments.clone()),
+        with_superclass_arguments(class("PlainHolder", Some("Holder"), vec![], vec![], vec![]), arguments),
+        class("DeeperHolder", Some("CounterHolder"), vec![], vec![], vec![]),
+    ]);
+    statements
+}
+
+#[test]
+fn generic_functions_and_types_get_metadata_only_where_they_need_it() {
+    let t = || named("T");
+    let inout_param = |name| FunctionParameter { is_inout: true, ..param(None, name, t()) };
+    let make = function("make", vec![param(None, "sample", t())], Some(t()), vec![ret(seeded("T", 5))]);
+    let kind_of = function("kindOf", vec![param(None, "x", t())], Some(SwiftType::String), vec![ret(call(member(ident("T"), "kind"), vec![]))]);
+    let check = function(
+        "check",
+        vec![param(None, "v", SwiftType::Integer), param(None, "sample", t())],
+        Some(SwiftType::Bool),
+        vec![ret(binary(cast(ident("v"), t()), "!=", nil()))],
+    );
+    let swap = function("swapTwoValues", vec![inout_param("a"), inout_param("b")], None, vec![
+        let_("tmp", ident("a")),
+        assign(ident("a"), ident("b")),
+        assign(ident("b"), ident("tmp")),
+    ]);
+    let twice = function("twice", vec![param(None, "sample", t())], Some(SwiftType::Integer), vec![ret(binary(
+        member(call(ident("make"), vec![arg(ident("sample"))]), "n"),
+        "*",
+        int(2),
+    ))]);
+    let element = named("Element");
+    let stack = structure(
+        "Stack",
+        vec![stored("top", Some(element.clone()), None)],
+        vec![function("fresh", vec![], Some(element), vec![ret(seeded("Element", 9))])],
+        vec![initializer(vec![], vec![assign(ident("top"), seeded("Element", 0))])],
+    );
+    let mut statements = counter();
+    statements.extend([
+        generic(make, vec![named("Makeable")]),
+        generic(kind_of, vec![]),
+        generic(check, vec![]),
+        generic(swap, vec![]),
+        generic(twice, vec![named("Makeable")]),
+        with_generics(stack, generics(vec![("Element", vec![named("Makeable")])], vec![])),
+        var("a", int(1)),
+        var("b", int(2)),
+        expression(call(ident("swapTwoValues"), vec![inout(ident("a")), inout(ident("b"))])),
+        print(vec![ident("a"), ident("b")]),
+        let_("c", seeded("Counter", 1)),
+        print(vec![
+            member(call(ident("make"), vec![arg(ident("c"))]), "n"),
+            call(ident("kindOf"), vec![arg(ident("c"))]),
+            call(ident("check"), vec![arg(int(3)), arg(int(4))]),
+            call(ident("check"), vec![arg(int(3)), arg(string("s"))]),
+        ]),
+        let_("stack", specialized(ident("Stack"), vec![named("Counter")], vec![])),
+        print(vec![member(member(ident("stack"), "top"), "n"), member(call(member(ident("stack"), "fresh"), vec![]), "n"), call(ident("twice"), vec![arg(ident("c"))])]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    assert_eq!(output, "2 1\n5 counter true false\n0 9 10\n");
+}
+
+#[test]
+fn subclasses_of_generic_classes_pass_their_superclass_arguments() {
+    let forwarding = class("Forwarding", Some("Holder"), vec![], vec![], vec![initializer(vec![param(Some("seed"), "seed", SwiftType::Integer)], vec![expression(call(
+        member(superclass(), "init"),
+        vec![labeled("item", call(ident("T"), vec![labeled("seed", ident("seed"))]))],
+    ))])]);
+    let fresh_n = |name| member(call(member(ident(name), "fresh"), vec![]), "n");
+    let item_n = |name| member(member(ident(name), "item"), "n");
+    let mut statements = holders(vec![named("Counter")]);
+    statements.extend([
+        with_generics(with_superclass_arguments(forwarding, vec![named("T")]), generics(vec![("T", vec![named("Makeable")])], vec![])),
+        let_("a", seeded("CounterHolder", 1)),
+        let_("b", call(ident("PlainHolder"), vec![labeled("item", seeded("Counter", 2))])),
+        let_("c", seeded("DeeperHolder", 3)),
+        let_("f", specialized(ident("Forwarding"), vec![named("Counter")], vec![labeled("seed", int(4))])),
+        print(vec![item_n("a"), fresh_n("a"), item_n("b"), fresh_n("b"), fresh_n("c")]),
+        print(vec![item_n("f"), fresh_n("f")]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    assert_eq!(output, "1 9 2 9 9\n4 9\n");
+}
+
+#[test]
+fn generic_types_need_arguments_that_meet_their_constraints() {
+    let make = function("make", vec![param(None, "sample", named("T"))], Some(named("T")), vec![ret(seeded("T", 5))]);
+    let mut statements = holders(vec![]);
+    statements.extend([
+        generic(make, vec![named("Makeable")]),
+        expression(call(ident("make"), vec![arg(int(1))])),
+        expression(specialized(ident("Holder"), vec![SwiftType::String], vec![labeled("item", string("x"))])),
+    ]);
+    assert_eq!(errors(&program(statements)), [
+        "reference to generic type 'Holder' requires arguments in <...>; expected <Element>",
+        "reference to generic type 'Holder' requires arguments in <...>; expected <Element>",
+        "global function 'make(_:)' requires that 'Int' conform to 'Makeable'",
+        "generic class 'Holder' requires that 'String' conform to 'Makeable'",
+    ]);
+}
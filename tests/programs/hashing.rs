@@ -0,0 +1,74 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{FunDeclaration, FunctionParameter};
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+/// `Point` is `Hashable` through its own `==` and `hash(into:)`.
+fn point_declaration() -> Statement {
+    let equals = FunDeclaration {
+        is_static: true,
+        ..function("==", vec![param(None, "lhs", named("Point")), param(None, "rhs", named("Point"))], Some(SwiftType::Bool), vec![ret(binary(
+            binary(member(ident("lhs"), "x"), "==", member(ident("rhs"), "x")),
+            "&&",
+            binary(member(ident("lhs"), "y"), "==", member(ident("rhs"), "y")),
+        ))])
+    };
+    let hasher = FunctionParameter { is_inout: true, ..param(Some("into"), "hasher", named("Hasher")) };
+    let hash = function("hash", vec![hasher], None, vec![
+        expression(call(member(ident("hasher"), "combine"), vec![arg(member(this(), "x"))])),
+        expression(call(member(ident("hasher"), "combine"), vec![arg(member(this(), "y"))])),
+    ]);
+    let properties = vec![stored("x", Some(SwiftType::Integer), None), stored("y", Some(SwiftType::Integer), None)];
+    conforming(structure("Point", properties, vec![equals, hash], vec![]), &["Hashable"])
+}
+
+fn point(x: i64, y: i64) -> Expression {
+    call(ident("Point"), vec![labeled("x", int(x)), labeled("y", int(y))])
+}
+
+#[test]
+fn keys_are_compared_by_swift_equality() {
+    let program = program(vec![
+        point_declaration(),
+        typed_var("names", SwiftType::Dictionary(Box::new(named("Point")), Box::new(SwiftType::String)), dictionary(vec![
+            (point(0, 0), string("origin")),
+            (point(1, 2), string("a")),
+        ])),
+        print(vec![subscript(ident("names"), point(1, 2)), subscript(ident("names"), point(2, 1)), member(ident("names"), "count")]),
+        var("key", point(3, 3)),
+        assign(subscript(ident("names"), ident("key")), string("b")),
+        assign(member(ident("key"), "x"), int(4)),
+        assign(subscript(ident("names"), point(0, 0)), string("zero")),
+        print(vec![subscript(ident("names"), point(3, 3)), subscript(ident("names"), ident("key")), subscript(ident("names"), point(0, 0)), member(ident("names"), "count")]),
+        assign(subscript(ident("names"), point(1, 2)), nil()),
+        print(vec![member(ident("names"), "count"), binary(point(1, 2), "==", point(1, 2)), binary(point(1, 2), "!=", point(1, 3))]),
+        typed_var("seen", SwiftType::Set(Box::new(named("Point"))), array(vec![point(1, 1), point(1, 1), point(2, 2)])),
+        print(vec![
+            member(ident("seen"), "count"),
+            call(member(ident("seen"), "contains"), vec![arg(point(2, 2))]),
+            call(member(ident("seen"), "insert"), vec![arg(point(1, 1))]),
+        ]),
+        typed_var("words", SwiftType::Dictionary(Box::new(SwiftType::String), Box::new(SwiftType::Integer)), dictionary(vec![(string("caf\u{e9}"), int(1))])),
+        print(vec![subscript(ident("words"), string("cafe\u{301}")), call(member(member(ident("words"), "keys"), "contains"), vec![arg(string("cafe\u{301}"))])]),
+        typed_let("grid", SwiftType::Set(Box::new(SwiftType::Array(Box::new(SwiftType::Integer)))), array(vec![
+            array(vec![int(1), int(2)]),
+            array(vec![int(1), int(2)]),
+        ])),
+        print(vec![member(ident("grid"), "count"), binary(array(vec![point(1, 2)]), "==", array(vec![point(1, 2)]))]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(
+        output,
+        "Optional(\"a\") nil 2\nOptional(\"b\") nil Optional(\"zero\") 3\n2 true true\n\
+         2 true (inserted: false, memberAfterInsert: main.Point(x: 1, y: 1))\nOptional(1) true\n1 true\n"
+    );
+}
+
+#[test]
+fn keys_must_be_hashable() {
+    let program = program(vec![
+        structure("Plain", vec![], vec![], vec![]),
+        typed_let("set", SwiftType::Set(Box::new(named("Plain"))), array(vec![])),
+    ]);
+    assert_eq!(errors(&program), ["type 'Plain' does not conform to protocol 'Hashable'"]);
+}
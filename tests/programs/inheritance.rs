@@ -0,0 +1,108 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{FunDeclaration, InitializerDeclaration, PropertyKind, VariablePropertyDeclaration};
+use swift_oxide::syntax::{Declaration, Expression, Statement, SwiftType};
+
+fn vehicle() -> Statement {
+    let description = computed("description", SwiftType::String, vec![ret(binary(call(ident("String"), vec![arg(ident("numberOfWheels"))]), "+", string(" wheel(s)")))]);
+    let kind = FunDeclaration { is_class: true, ..function("kind", vec![], Some(SwiftType::String), vec![ret(string("vehicle"))]) };
+    class(
+        "Vehicle",
+        None,
+        vec![stored("numberOfWheels", Some(SwiftType::Integer), None), description],
+        vec![function("honk", vec![], Some(SwiftType::String), vec![ret(string("beep"))]), kind],
+        vec![initializer(vec![], vec![assign(ident("numberOfWheels"), int(0))])],
+    )
+}
+
+fn super_call(name: &str) -> Expression {
+    call(member(superclass(), name), vec![])
+}
+
+#[test]
+fn overrides_are_dispatched_dynamically_and_reach_the_superclass() {
+    let mut observed = VariablePropertyDeclaration { is_override: true, ..stored("numberOfWheels", None, None) };
+    if let PropertyKind::Stored(property) = &mut observed.kind {
+        property.did_set = Some(observer(None, vec![print(vec![string("wheels"), ident("oldValue"), string("->"), ident("numberOfWheels")])]));
+    }
+    let honk = FunDeclaration { is_override: true, ..function("honk", vec![], Some(SwiftType::String), vec![ret(binary(string("ring "), "+", super_call("honk")))]) };
+    let kind = FunDeclaration {
+        is_class: true,
+        is_override: true,
+        ..function("kind", vec![], Some(SwiftType::String), vec![ret(binary(string("bike/"), "+", super_call("kind")))])
+    };
+    let bicycle_init = InitializerDeclaration {
+        is_override: true,
+        ..initializer(vec![], vec![expression(super_call("init")), assign(ident("numberOfWheels"), int(2))])
+    };
+    let description = VariablePropertyDeclaration {
+        is_override: true,
+        ..computed("description", SwiftType::String, vec![ret(binary(binary(member(superclass(), "description"), "+", string(" in the color ")), "+", ident("color")))])
+    };
+    let hoverboard_init = initializer(vec![param(Some("color"), "color", SwiftType::String)], vec![
+        assign(member(this(), "color"), ident("color")),
+        expression(super_call("init")),
+    ]);
+    let vehicles = array(vec![call(ident("Vehicle"), vec![]), call(ident("Bicycle"), vec![]), call(ident("Hoverboard"), vec![labeled("color", string("silver"))])]);
+    let program = program(vec![
+        vehicle(),
+        class("Bicycle", Some("Vehicle"), vec![observed], vec![honk, kind], vec![bicycle_init]),
+        class("Hoverboard", Some("Vehicle"), vec![stored("color", Some(SwiftType::String), None), description], vec![], vec![hoverboard_init]),
+        let_("vehicles", vehicles),
+        print(vec![member(subscript(ident("vehicles"), int(1)), "description")]),
+        print(vec![member(subscript(ident("vehicles"), int(2)), "description")]),
+        print(vec![call(member(subscript(ident("vehicles"), int(1)), "honk"), vec![]), call(member(ident("Bicycle"), "kind"), vec![])]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "wheels 0 -> 2\n2 wheel(s)\n0 wheel(s) in the color silver\nring beep bike/vehicle\n");
+}
+
+#[test]
+fn overrides_must_be_marked_and_match_an_overridable_member() {
+    let stop = FunDeclaration { is_final: true, ..function("stop", vec![], None, vec![]) };
+    let car = class(
+        "Car",
+        Some("Vehicle"),
+        vec![
+            VariablePropertyDeclaration { is_override: true, ..stored("numberOfWheels", Some(SwiftType::Integer), Some(int(4))) },
+            VariablePropertyDeclaration { is_override: true, ..stored("speed", Some(SwiftType::Integer), Some(int(0))) },
+        ],
+        vec![
+            function("honk", vec![], Some(SwiftType::String), vec![ret(string("honk"))]),
+            FunDeclaration { is_override: true, ..function("fly", vec![], None, vec![]) },
+            stop,
+        ],
+        vec![],
+    );
+    let program = program(vec![
+        vehicle(),
+        car,
+        class("Sport", Some("Car"), vec![], vec![FunDeclaration { is_override: true, ..function("stop", vec![], None, vec![]) }], vec![]),
+        structure("S", vec![], vec![FunDeclaration { is_override: true, ..function("f", vec![], None, vec![]) }], vec![]),
+    ]);
+    assert_eq!(errors(&program), [
+        "cannot override with a stored property 'numberOfWheels'",
+        "property 'speed' does not override any property from its superclass",
+        "overriding declaration requires an 'override' keyword ('honk()')",
+        "method 'fly()' does not override any method from its superclass",
+        "instance method 'stop()' overrides a 'final' instance method",
+        "'override' can only be specified on class members",
+    ]);
+}
+
+#[test]
+fn final_classes_cannot_be_subclassed_and_inheritance_cannot_cycle() {
+    let mut leaf = class("Leaf", None, vec![], vec![], vec![]);
+    if let Statement::Declaration(declaration) = &mut leaf {
+        if let Declaration::Class(class) = declaration.as_mut() {
+            class.is_final = true;
+        }
+    }
+    let program = program(vec![
+        leaf,
+        class("Twig", Some("Leaf"), vec![], vec![], vec![]),
+        class("A", Some("B"), vec![], vec![], vec![]),
+        class("B", Some("A"), vec![], vec![], vec![]),
+    ]);
+    assert_eq!(errors(&program), ["inheritance from a final class 'Leaf'", "'A' inherits from itself", "'B' inherits from itself"]);
+}
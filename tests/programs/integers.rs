@@ -0,0 +1,85 @@
+use crate::build::*;
+use crate::{compile, run, run_trapping};
+use swift_oxide::options::{IntRepresentation, Options};
+use swift_oxide::syntax::{IntegerType, Statement};
+
+/// Division and remainder truncate, the `&` operators wrap, and 64-bit results stay exact.
+fn arithmetic() -> Vec<Statement> {
+    vec![
+        let_("a", binary(int(7), "/", int(2))),
+        let_("b", binary(unary("-", int(7)), "%", int(3))),
+        typed_let("c", sized(IntegerType::Int8), int(100)),
+        let_("d", binary(ident("c"), "&+", ident("c"))),
+        typed_let("e", sized(IntegerType::UInt8), int(3)),
+        let_("f", binary(ident("e"), "&-", int(5))),
+        typed_let("g", sized(IntegerType::Int64), int(9007199254740993)),
+        let_("h", binary(ident("g"), "*", int(1000))),
+        typed_var("i", sized(IntegerType::UInt64), binary(int(1), "+", int(2))),
+        expression(binary(ident("i"), "+=", int(4))),
+        let_("j", binary(ident("g"), "/", int(-2))),
+        let_("k", binary(int(10), "&*", int(3))),
+        print(vec![ident("a"), ident("b"), ident("d"), ident("f"), ident("h"), ident("i"), binary(ident("i"), "==", int(7)), ident("j"), ident("k")]),
+        typed_let("wide", sized(IntegerType::Int64), binary(ident("g"), "&*", ident("g"))),
+        print(vec![ident("wide"), call(ident("Double"), vec![arg(ident("i"))]), unary("-", ident("c"))]),
+    ]
+}
+
+const ARITHMETIC: &str = "3 -1 -56 254 9007199254740993000 7 true -4503599627370496 30\n18014398509481985 7.0 -100\n";
+
+#[test]
+fn integer_arithmetic_truncates_wraps_and_stays_exact_in_either_representation() {
+    for int_representation in [IntRepresentation::Number, IntRepresentation::BigInt] {
+        let options = Options { int_representation, ..Options::default() };
+        let Some(output) = run(&compile(&program(arithmetic()), &options)) else { return };
+        assert_eq!(output, ARITHMETIC, "with {int_representation:?}");
+    }
+}
+
+#[test]
+fn overflow_and_division_by_zero_trap() {
+    let cases = [
+        (binary(ident("c"), "+", ident("c")), "arithmetic overflow: the result doesn't fit in Int8"),
+        (binary(member(ident("Int"), "max"), "+", int(1)), "arithmetic overflow: the result doesn't fit in Int"),
+        (binary(member(ident("Int8"), "min"), "-", int(1)), "arithmetic overflow: the result doesn't fit in Int8"),
+        (binary(ident("a"), "/", binary(ident("a"), "-", int(3))), "division by zero"),
+        (binary(ident("i"), "-", int(8)), "arithmetic overflow: the result doesn't fit in UInt64"),
+    ];
+    for (trap, message) in cases {
+        let mut statements = arithmetic();
+        statements.push(print(vec![trap]));
+        let Some((output, trapped)) = run_trapping(&compile(&program(statements), &Options::default())) else { return };
+        assert_eq!((output.as_str(), trapped.as_str()), (ARITHMETIC, message));
+    }
+}
+
+#[test]
+fn integer_bounds_follow_the_representation_of_int() {
+    let bounds = || {
+        let bound = |ty: &str, bound: &str| member(ident(ty), bound);
+        vec![print(vec![
+            bound("Int", "max"),
+            bound("Int", "min"),
+            bound("Int8", "min"),
+            bound("UInt8", "max"),
+            bound("Int64", "max"),
+            bound("UInt64", "max"),
+        ])]
+    };
+    let Some(output) = run(&compile(&program(bounds()), &Options::default())) else { return };
+    assert_eq!(output, "9007199254740991 -9007199254740991 -128 255 9223372036854775807 18446744073709551615\n");
+    let options = Options { int_representation: IntRepresentation::BigInt, ..Options::default() };
+    let Some(output) = run(&compile(&program(bounds()), &options)) else { return };
+    assert_eq!(output, "9223372036854775807 -9223372036854775808 -128 255 9223372036854775807 18446744073709551615\n");
+    let mut statements = bounds();
+    statements.push(print(vec![binary(member(ident("Int"), "max"), "+", int(1))]));
+    let Some((_, trapped)) = run_trapping(&compile(&program(statements), &options)) else { return };
+    assert_eq!(trapped, "arithmetic overflow: the result doesn't fit in Int");
+}
+
+#[test]
+fn bigint_ints_go_past_the_range_doubles_hold_exactly() {
+    let statements = vec![print(vec![binary(int(9007199254740991), "+", int(1))])];
+    let options = Options { int_representation: IntRepresentation::BigInt, ..Options::default() };
+    let Some(output) = run(&compile(&program(statements), &options)) else { return };
+    assert_eq!(output, "9007199254740992\n");
+}
@@ -0,0 +1,102 @@
+use crate::build::*;
+use crate::{compile, run, run_collecting, warnings};
+use swift_oxide::options::{Lifetime, Options};
+use swift_oxide::syntax::declaration::{ReferenceOwnership, VariablePropertyDeclaration};
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+fn reference_counting() -> Options {
+    Options { lifetime: Lifetime::ReferenceCounting, ..Options::default() }
+}
+
+/// A class that prints when its instances are initialized and deinitialized.
+fn node() -> Statement {
+    let name = VariablePropertyDeclaration { is_constant: true, ..stored("name", Some(SwiftType::String), None) };
+    let initializer = initializer(vec![param(Some("name"), "name", SwiftType::String)], vec![
+        assign(member(this(), "name"), ident("name")),
+        print(vec![string("init"), ident("name")]),
+    ]);
+    let class = class("Node", None, vec![name, stored("next", Some(optional(named("Node"))), None)], vec![], vec![initializer]);
+    with_deinit(class, vec![print(vec![string("deinit"), ident("name")])])
+}
+
+fn new_node(name: &str) -> Expression {
+    call(ident("Node"), vec![labeled("name", string(name))])
+}
+
+#[test]
+fn reference_counting_deinitializes_when_the_last_reference_goes_away() {
+    let program = program(vec![
+        node(),
+        with_deinit(class("Sub", Some("Node"), vec![], vec![], vec![]), vec![print(vec![string("sub deinit"), ident("name")])]),
+        typed_var("a", optional(named("Node")), new_node("a")),
+        assign(ident("a"), nil()),
+        print(vec![string("after a")]),
+        function_declaration(function("scope", vec![], None, vec![let_("b", new_node("b")), print(vec![string("in scope")])])),
+        expression(call(ident("scope"), vec![])),
+        typed_var("c", optional(named("Node")), new_node("c")),
+        var("d", ident("c")),
+        assign(ident("c"), nil()),
+        print(vec![string("c cleared")]),
+        assign(ident("d"), nil()),
+        expression(new_node("temp")),
+        typed_var("nodes", SwiftType::Dictionary(Box::new(SwiftType::String), Box::new(named("Node"))), dictionary(vec![(string("x"), new_node("x"))])),
+        assign(subscript(ident("nodes"), string("x")), nil()),
+        function_declaration(function("chain", vec![], None, vec![let_("head", new_node("head")), assign(member(ident("head"), "next"), new_node("tail"))])),
+        expression(call(ident("chain"), vec![])),
+        function_declaration(function("sub", vec![], None, vec![
+            let_("x", call(ident("Sub"), vec![labeled("name", string("s"))])),
+            if_(boolean(true), vec![return_()], None),
+        ])),
+        expression(call(ident("sub"), vec![])),
+        print(vec![string("end")]),
+    ]);
+    let Some(output) = run(&compile(&program, &reference_counting())) else { return };
+    assert_eq!(
+        output,
+        "init a\ndeinit a\nafter a\ninit b\nin scope\ndeinit b\ninit c\nc cleared\ndeinit c\ninit temp\ndeinit temp\n\
+         init x\ndeinit x\ninit head\ninit tail\ndeinit head\ndeinit tail\ninit s\nsub deinit s\ndeinit s\nend\n"
+    );
+}
+
+#[test]
+fn finalization_runs_deinit_with_the_current_property_values() {
+    let parent = class(
+        "Parent",
+        None,
+        vec![stored("name", Some(SwiftType::String), None), stored("child", Some(optional(named("Child"))), None)],
+        vec![function("rename", vec![param(None, "n", SwiftType::String)], None, vec![assign(member(this(), "name"), ident("n"))])],
+        vec![initializer(vec![param(Some("name"), "name", SwiftType::String)], vec![assign(member(this(), "name"), ident("name"))])],
+    );
+    let back = VariablePropertyDeclaration { ownership: ReferenceOwnership::Unowned, ..stored("parent", Some(named("Parent")), None) };
+    let child = class("Child", None, vec![back], vec![], vec![initializer(vec![param(Some("parent"), "parent", named("Parent"))], vec![
+        assign(member(this(), "parent"), ident("parent")),
+    ])]);
+    let program = program(vec![
+        with_deinit(parent, vec![print(vec![string("deinit"), ident("name")])]),
+        child,
+        function_declaration(function("scope", vec![], None, vec![
+            let_("p", call(ident("Parent"), vec![labeled("name", string("first"))])),
+            assign(member(ident("p"), "child"), call(ident("Child"), vec![labeled("parent", ident("p"))])),
+            expression(call(member(ident("p"), "rename"), vec![arg(string("renamed"))])),
+        ])),
+        expression(call(ident("scope"), vec![])),
+        print(vec![string("after scope")]),
+    ]);
+    let Some(output) = run_collecting(&compile(&program, &Options::default())) else { return };
+    assert_eq!(output, "after scope\ndeinit renamed\n");
+}
+
+#[test]
+fn finalization_warns_about_late_deinit_and_the_references_it_keeps() {
+    let reader = class("Reader", None, vec![stored("next", Some(optional(named("Node"))), None)], vec![], vec![]);
+    let program = program(vec![node(), with_deinit(reader, vec![print(vec![member(ident("next"), "name")])])]);
+    assert_eq!(warnings(&program, &Options::default()), [
+        "'deinit' of 'Node' runs only once the garbage collector reclaims an instance, which may be late or never; \
+         compile with reference counting to run it when the last reference goes away",
+        "'deinit' of 'Reader' runs only once the garbage collector reclaims an instance, which may be late or never; \
+         compile with reference counting to run it when the last reference goes away",
+        "'deinit' of 'Reader' reads 'next', which keeps the instances it refers to alive until the instance is reclaimed; \
+         one that refers back to the instance keeps it from ever being reclaimed",
+    ]);
+    assert!(warnings(&program, &reference_counting()).is_empty());
+}
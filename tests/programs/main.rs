@@ -0,0 +1,122 @@
+//! Compiles small programs, built directly as syntax trees, and checks the diagnostics they get or
+//! what the generated JavaScript prints when run with Node.
+
+mod associated_types;
+mod build;
+mod class_initialization;
+mod codable;
+mod command_line;
+mod conditional_conformances;
+mod definite_initialization;
+mod descriptions;
+mod errors;
+mod existentials;
+mod fixed_width;
+mod generics;
+mod hashing;
+mod inheritance;
+mod integers;
+mod lifetimes;
+mod members;
+mod memberwise;
+mod properties;
+mod protocol_extensions;
+mod protocols;
+mod references;
+mod reflection;
+mod standard_library;
+mod strings;
+mod synthesized_conformances;
+mod types;
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use swift_oxide::diagnostics::Diagnostic;
+use swift_oxide::driver::{self, Compilation};
+use swift_oxide::options::Options;
+use swift_oxide::syntax::StatementSequence;
+
+/// Compiles a program that is expected to check without errors.
+fn compile(program: &StatementSequence, options: &Options) -> Compilation {
+    match driver::compile(program, options) {
+        Ok(compilation) => compilation,
+        Err(diagnostics) => {
+            let messages: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+            panic!("program did not compile:\n{}", messages.join("\n"))
+        }
+    }
+}
+
+/// The error messages for a program that is expected not to compile, in the order reported.
+fn errors(program: &StatementSequence) -> Vec<String> {
+    match driver::compile(program, &Options::default()) {
+        Ok(_) => panic!("program compiled, but errors were expected"),
+        Err(diagnostics) => diagnostics.into_iter().filter(Diagnostic::is_error).map(|diagnostic| diagnostic.message).collect(),
+    }
+}
+
+/// The warnings for a program that is expected to compile.
+fn warnings(program: &StatementSequence, options: &Options) -> Vec<String> {
+    compile(program, options).warnings.into_iter().map(|diagnostic| diagnostic.message).collect()
+}
+
+/// What a program prints with the default options. See `run`.
+fn output(program: &StatementSequence) -> Option<String> {
+    run(&compile(program, &Options::default()))
+}
+
+/// Writes a compiled program and its runtime to a fresh directory and runs it with Node, returning
+/// what it printed. Returns `None` when Node isn't installed, so that tests depending on it pass
+/// trivially instead of failing.
+fn run(compilation: &Compilation) -> Option<String> {
+    execute(compilation, false).map(stdout)
+}
+
+/// Like `run`, but has the garbage collector reclaim unreachable instances a few times after the
+/// program ends, so that `deinit`s run under finalization.
+fn run_collecting(compilation: &Compilation) -> Option<String> {
+    execute(compilation, true).map(stdout)
+}
+
+/// Like `run`, for a program expected to trap, returning what it printed before trapping and the
+/// message it trapped with.
+fn run_trapping(compilation: &Compilation) -> Option<(String, String)> {
+    let output = execute(compilation, false)?;
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!output.status.success(), "program did not trap:\n{stdout}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let message = stderr.lines().find_map(|line| line.strip_prefix("Error: ")).unwrap_or_else(|| panic!("no error in:\n{stderr}"));
+    Some((stdout, message.to_string()))
+}
+
+/// What a program that ran to completion printed.
+fn stdout(output: Output) -> String {
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "program failed:\n{stdout}{}", String::from_utf8_lossy(&output.stderr));
+    stdout
+}
+
+fn execute(compilation: &Compilation, collect: bool) -> Option<Output> {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let directory: PathBuf = std::env::temp_dir().join(format!("swift-oxide-test-{}-{}", std::process::id(), RUNS.fetch_add(1, Ordering::Relaxed)));
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(directory.join("package.json"), "{ \"type\": \"module\" }").unwrap();
+    compilation.write(&directory.join("main.js")).unwrap();
+    let mut command = Command::new("node");
+    if collect {
+        let collection = "await import(\"./main.js\");\n\
+            for (let i = 0; i < 10; i++) { globalThis.gc(); await new Promise((resolve) => setTimeout(resolve, 10)); }\n";
+        std::fs::write(directory.join("collect.js"), collection).unwrap();
+        command.arg("--expose-gc").arg(directory.join("collect.js"));
+    } else {
+        command.arg(directory.join("main.js"));
+    }
+    let result = command.output();
+    std::fs::remove_dir_all(&directory).unwrap();
+    match result {
+        Ok(output) => Some(output),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => panic!("could not run node: {error}"),
+    }
+}
@@ -0,0 +1,102 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{FunDeclaration, VariablePropertyDeclaration};
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+fn point(methods: Vec<FunDeclaration>) -> Statement {
+    let origin = VariablePropertyDeclaration { is_static: true, is_constant: true, ..stored("origin", None, Some(new_point(0, 0))) };
+    let initializer = initializer(
+        vec![param(Some("x"), "x", SwiftType::Integer), param(Some("y"), "y", SwiftType::Integer)],
+        vec![assign(member(this(), "x"), ident("x")), assign(member(this(), "y"), ident("y"))],
+    );
+    let properties = vec![stored("x", Some(SwiftType::Integer), None), stored("y", Some(SwiftType::Integer), None), origin];
+    structure("Point", properties, methods, vec![initializer])
+}
+
+fn new_point(x: i64, y: i64) -> Expression {
+    call(ident("Point"), vec![labeled("x", int(x)), labeled("y", int(y))])
+}
+
+fn move_by() -> FunDeclaration {
+    let body = vec![assign(member(this(), "x"), binary(member(this(), "x"), "+", ident("dx")))];
+    FunDeclaration { is_mutating: true, ..function("moveBy", vec![param(Some("x"), "dx", SwiftType::Integer)], None, body) }
+}
+
+#[test]
+fn mutating_methods_change_only_the_value_they_are_called_on() {
+    let square = FunDeclaration {
+        is_static: true,
+        ..function("square", vec![param(Some("of"), "n", SwiftType::Integer)], Some(SwiftType::Integer), vec![ret(binary(ident("n"), "*", ident("n")))])
+    };
+    let program = program(vec![
+        point(vec![move_by(), square]),
+        var("p", new_point(1, 2)),
+        var("q", ident("p")),
+        expression(call(member(ident("p"), "moveBy"), vec![labeled("x", int(5))])),
+        print(vec![member(ident("p"), "x"), member(ident("q"), "x")]),
+        print(vec![call(member(ident("Point"), "square"), vec![labeled("of", int(4))]), member(member(ident("Point"), "origin"), "y")]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "6 1\n16 0\n");
+}
+
+#[test]
+fn enums_have_methods_and_computed_properties() {
+    let toggle = FunDeclaration {
+        is_mutating: true,
+        ..function("toggle", vec![], None, vec![switch(this(), vec![
+            (case_pattern("off", &[]), vec![assign(this(), member(ident("Switch"), "on"))]),
+            (case_pattern("on", &[]), vec![assign(this(), member(ident("Switch"), "off"))]),
+        ])])
+    };
+    let is_on = computed("isOn", SwiftType::Bool, vec![ret(binary(this(), "==", member(ident("Switch"), "on")))]);
+    let value = function("value", vec![], Some(SwiftType::Integer), vec![switch(this(), vec![
+        (case_pattern("upc", &["a", "b"]), vec![ret(binary(binary(ident("a"), "*", int(100)), "+", ident("b")))]),
+        (case_pattern("qr", &["c"]), vec![ret(ident("c"))]),
+    ])]);
+    let program = program(vec![
+        enumeration("Switch", vec![case("off", vec![]), case("on", vec![])], Some(SwiftType::Integer), vec![is_on], vec![toggle]),
+        enumeration("Barcode", vec![case("upc", vec![SwiftType::Integer, SwiftType::Integer]), case("qr", vec![SwiftType::Integer])], None, vec![], vec![value]),
+        var("s", member(ident("Switch"), "off")),
+        expression(call(member(ident("s"), "toggle"), vec![])),
+        print(vec![member(ident("s"), "isOn"), member(ident("s"), "rawValue")]),
+        print(vec![call(member(call(member(ident("Barcode"), "upc"), vec![arg(int(1)), arg(int(2))]), "value"), vec![])]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "true 1\n102\n");
+}
+
+#[test]
+fn class_methods_and_static_properties_are_inherited() {
+    let make = FunDeclaration { is_class: true, ..function("make", vec![], Some(SwiftType::String), vec![ret(string("base"))]) };
+    let sub_make = FunDeclaration {
+        is_class: true,
+        is_override: true,
+        ..function("make", vec![], Some(SwiftType::String), vec![ret(binary(string("sub of "), "+", call(member(superclass(), "make"), vec![])))])
+    };
+    let shared = VariablePropertyDeclaration { is_static: true, ..stored("shared", None, Some(string("S"))) };
+    let program = program(vec![
+        class("Base", None, vec![shared], vec![make], vec![]),
+        class("Sub", Some("Base"), vec![], vec![sub_make], vec![]),
+        assign(member(ident("Base"), "shared"), string("T")),
+        print(vec![call(member(ident("Sub"), "make"), vec![]), member(ident("Sub"), "shared")]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "sub of base T\n");
+}
+
+#[test]
+fn mutation_needs_a_mutating_method_and_a_variable() {
+    let reset = function("reset", vec![], None, vec![assign(member(this(), "x"), int(0))]);
+    let program = program(vec![
+        point(vec![move_by(), reset]),
+        let_("p", new_point(1, 2)),
+        expression(call(member(ident("p"), "moveBy"), vec![labeled("x", int(1))])),
+        class("Counter", None, vec![], vec![FunDeclaration { is_mutating: true, ..function("bump", vec![], None, vec![]) }], vec![]),
+    ]);
+    assert_eq!(errors(&program), [
+        "cannot assign to property: 'self' is immutable (in 'Point'; mark the method 'mutating')",
+        "cannot use mutating member on immutable value: 'p' is a 'let' constant",
+        "'mutating' is not valid on instance methods in classes ('bump')",
+    ]);
+}
@@ -0,0 +1,63 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{AccessControl, VariablePropertyDeclaration};
+use swift_oxide::syntax::{Statement, SwiftType};
+
+/// A struct whose variables have default values, which its memberwise initializer makes optional
+/// arguments, and a constant with one, which it leaves out.
+fn size() -> Statement {
+    let tag = VariablePropertyDeclaration { is_constant: true, ..stored("tag", None, Some(string("pt"))) };
+    let zero = VariablePropertyDeclaration { is_static: true, is_constant: true, ..stored("zero", None, Some(int(0))) };
+    structure("Size", vec![stored("w", None, Some(int(1))), stored("h", None, Some(int(2))), tag, zero], vec![], vec![])
+}
+
+fn point() -> Statement {
+    structure("Point", vec![stored("x", Some(SwiftType::Integer), None), stored("y", Some(SwiftType::Integer), None)], vec![], vec![])
+}
+
+#[test]
+fn memberwise_initializers_take_the_stored_variables_in_order() {
+    let program = program(vec![
+        point(),
+        size(),
+        let_("p", call(ident("Point"), vec![labeled("x", int(10)), labeled("y", int(20))])),
+        let_("a", call(ident("Size"), vec![])),
+        let_("b", call(ident("Size"), vec![labeled("h", int(9))])),
+        let_("c", call(ident("Size"), vec![labeled("w", int(5)), labeled("h", int(6))])),
+        print(vec![member(ident("p"), "x"), member(ident("p"), "y")]),
+        print(vec![member(ident("a"), "w"), member(ident("a"), "h"), member(ident("b"), "w"), member(ident("b"), "h"), member(ident("c"), "w"), member(ident("c"), "tag")]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "10 20\n1 2 1 9 5 pt\n");
+}
+
+#[test]
+fn trailing_closures_fill_function_typed_members() {
+    let action = SwiftType::Function(vec![], Box::new(SwiftType::void()), Default::default());
+    let program = program(vec![
+        structure("Counter", vec![stored("step", Some(SwiftType::Integer), None), stored("report", Some(action), None)], vec![], vec![]),
+        let_("counter", trailing(ident("Counter"), vec![labeled("step", int(2))], closure(vec![print(vec![string("reported")])]))),
+        expression(call(member(ident("counter"), "report"), vec![])),
+        print(vec![member(ident("counter"), "step")]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "reported\n2\n");
+}
+
+#[test]
+fn calls_must_match_the_memberwise_initializer() {
+    let secret = VariablePropertyDeclaration { access_control: AccessControl::Private, ..stored("secret", Some(SwiftType::Integer), Some(int(0))) };
+    let program = program(vec![
+        point(),
+        size(),
+        structure("Hidden", vec![secret], vec![], vec![]),
+        expression(call(ident("Point"), vec![labeled("x", int(1))])),
+        expression(call(ident("Hidden"), vec![labeled("secret", int(1))])),
+        expression(call(ident("Size"), vec![labeled("tag", string("q"))])),
+    ]);
+    assert_eq!(errors(&program), [
+        "no initializer of 'Point' accepts the call 'Point(x:)'; candidates are: init(x:y:)",
+        "'init(secret:)' is inaccessible due to 'private' protection level",
+        "no initializer of 'Size' accepts the call 'Size(tag:)'; candidates are: init(w:h:)",
+    ]);
+}
@@ -0,0 +1,99 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{AccessControl, PropertyKind, VariablePropertyDeclaration};
+use swift_oxide::syntax::SwiftType;
+
+#[test]
+fn observers_run_on_assignment_but_not_during_initialization() {
+    let mut total = stored("totalSteps", Some(SwiftType::Integer), Some(int(0)));
+    if let PropertyKind::Stored(property) = &mut total.kind {
+        property.will_set = Some(observer(Some("newTotal"), vec![print(vec![string("about to set"), ident("newTotal")])]));
+        property.did_set = Some(observer(None, vec![
+            if_(binary(ident("totalSteps"), ">", int(1000)), vec![assign(ident("totalSteps"), int(1000))], None),
+            print(vec![string("added"), binary(ident("totalSteps"), "-", ident("oldValue"))]),
+        ]));
+    }
+    let program = program(vec![
+        class("StepCounter", None, vec![total], vec![], vec![initializer(vec![], vec![assign(ident("totalSteps"), int(5))])]),
+        let_("counter", call(ident("StepCounter"), vec![])),
+        assign(member(ident("counter"), "totalSteps"), int(200)),
+        assign(member(ident("counter"), "totalSteps"), int(5000)),
+        print(vec![member(ident("counter"), "totalSteps")]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "about to set 200\nadded 195\nabout to set 5000\nadded 800\n1000\n");
+}
+
+#[test]
+fn lazy_properties_are_initialized_once_on_first_access() {
+    let mut history = stored("history", None, Some(call(member(this(), "load"), vec![])));
+    if let PropertyKind::Stored(property) = &mut history.kind {
+        property.is_lazy = true;
+    }
+    let load = function("load", vec![], Some(SwiftType::String), vec![print(vec![string("loading")]), ret(string("H"))]);
+    let program = program(vec![
+        class("Log", None, vec![history], vec![load], vec![]),
+        let_("log", call(ident("Log"), vec![])),
+        print(vec![string("created")]),
+        print(vec![member(ident("log"), "history"), member(ident("log"), "history")]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "created\nloading\nH H\n");
+}
+
+#[test]
+fn computed_setters_and_extension_properties() {
+    let rect = structure(
+        "Rect",
+        vec![
+            stored("width", Some(SwiftType::Double), None),
+            stored("height", Some(SwiftType::Double), None),
+            settable("area", SwiftType::Double, vec![ret(binary(ident("width"), "*", ident("height")))], vec![
+                assign(ident("width"), binary(ident("newValue"), "/", ident("height"))),
+            ]),
+        ],
+        vec![],
+        vec![initializer(
+            vec![param(Some("width"), "w", SwiftType::Double), param(Some("height"), "h", SwiftType::Double)],
+            vec![assign(ident("width"), ident("w")), assign(ident("height"), ident("h"))],
+        )],
+    );
+    let unit = VariablePropertyDeclaration {
+        is_static: true,
+        is_constant: true,
+        ..stored("unit", None, Some(call(ident("Rect"), vec![labeled("width", float(1.0)), labeled("height", float(1.0))])))
+    };
+    let half = computed("half", SwiftType::Double, vec![ret(binary(ident("area"), "/", float(2.0)))]);
+    let program = program(vec![
+        rect,
+        extension("Rect", &[], vec![half, unit], vec![]),
+        var("r", call(ident("Rect"), vec![labeled("width", float(2.0)), labeled("height", float(3.0))])),
+        assign(member(ident("r"), "area"), float(12.0)),
+        print(vec![member(ident("r"), "width"), member(ident("r"), "half"), member(member(ident("Rect"), "unit"), "area")]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "4.0 6.0 1.0\n");
+}
+
+#[test]
+fn constants_private_setters_and_get_only_properties_cannot_be_assigned() {
+    let resets = VariablePropertyDeclaration { setter_access_control: Some(AccessControl::Private), ..stored("resets", None, Some(int(0))) };
+    let id = VariablePropertyDeclaration { is_constant: true, ..stored("id", None, Some(int(7))) };
+    let doubled = computed("doubled", SwiftType::Integer, vec![ret(binary(ident("resets"), "*", int(2)))]);
+    let program = program(vec![
+        class("Counter", None, vec![resets, id, doubled], vec![], vec![]),
+        structure("Size", vec![stored("width", Some(SwiftType::Integer), None)], vec![], vec![]),
+        let_("counter", call(ident("Counter"), vec![])),
+        assign(member(ident("counter"), "resets"), int(3)),
+        assign(member(ident("counter"), "id"), int(3)),
+        assign(member(ident("counter"), "doubled"), int(3)),
+        let_("size", call(ident("Size"), vec![labeled("width", int(1))])),
+        assign(member(ident("size"), "width"), int(2)),
+    ]);
+    assert_eq!(errors(&program), [
+        "cannot assign to property: 'resets' setter is inaccessible",
+        "cannot assign to property: 'id' is a 'let' constant",
+        "cannot assign to property: 'doubled' is a get-only property",
+        "cannot assign to property: 'size' is a 'let' constant",
+    ]);
+}
@@ -0,0 +1,63 @@
+use crate::build::*;
+use crate::output;
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+/// `Greeter` requires `greet()`, which its extension implements by default, and the extension adds
+/// `farewell()`, `both()` and `shout`, which aren't requirements.
+fn greeter() -> Vec<Statement> {
+    let greet = function("greet", vec![], Some(SwiftType::String), vec![ret(binary(string("hello "), "+", ident("name")))]);
+    let farewell = function("farewell", vec![], Some(SwiftType::String), vec![ret(binary(string("bye from extension, "), "+", member(this(), "name")))]);
+    let both = function("both", vec![], Some(SwiftType::String), vec![ret(binary(binary(call(ident("greet"), vec![]), "+", string(" / ")), "+", call(ident("farewell"), vec![])))]);
+    let shout = computed("shout", SwiftType::String, vec![ret(binary(call(ident("greet"), vec![]), "+", string("!")))]);
+    vec![
+        protocol("Greeter", &[], vec![("name", SwiftType::String, true)], vec![("greet", vec![], Some(SwiftType::String))], vec![]),
+        extension("Greeter", &[], vec![shout], vec![greet, farewell, both]),
+    ]
+}
+
+fn pirate() -> Statement {
+    let methods = vec![
+        function("greet", vec![], Some(SwiftType::String), vec![ret(binary(string("ahoy "), "+", ident("name")))]),
+        function("farewell", vec![], Some(SwiftType::String), vec![ret(string("arr, pirate farewell"))]),
+    ];
+    conforming(structure("Pirate", vec![stored("name", Some(SwiftType::String), None)], methods, vec![]), &["Greeter"])
+}
+
+fn invoke(target: &str, method: &str) -> Expression {
+    call(member(ident(target), method), vec![])
+}
+
+#[test]
+fn default_implementations_satisfy_requirements_a_type_leaves_out() {
+    let mut statements = greeter();
+    statements.extend([
+        conforming(structure("English", vec![stored("name", Some(SwiftType::String), None)], vec![], vec![]), &["Greeter"]),
+        class("Robot", None, vec![stored("name", Some(SwiftType::String), Some(string("r2")))], vec![], vec![]),
+        extension("Robot", &["Greeter"], vec![], vec![]),
+        let_("english", call(ident("English"), vec![labeled("name", string("ann"))])),
+        print(vec![invoke("english", "greet"), member(ident("english"), "shout")]),
+        print(vec![call(member(call(ident("Robot"), vec![]), "greet"), vec![])]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    assert_eq!(output, "hello ann hello ann!\nhello r2\n");
+}
+
+#[test]
+fn extension_members_that_are_not_requirements_dispatch_statically() {
+    let robot_shout = computed("shout", SwiftType::String, vec![ret(string("BEEP"))]);
+    let mut statements = greeter();
+    statements.extend([
+        pirate(),
+        class("Robot", None, vec![stored("name", Some(SwiftType::String), Some(string("r2")))], vec![], vec![]),
+        extension("Robot", &["Greeter"], vec![robot_shout], vec![]),
+        let_("pirate", call(ident("Pirate"), vec![labeled("name", string("jack"))])),
+        typed_let("greeter", named("Greeter"), ident("pirate")),
+        typed_let("robot", named("Greeter"), call(ident("Robot"), vec![])),
+        print(vec![invoke("pirate", "greet"), invoke("pirate", "farewell")]),
+        print(vec![invoke("greeter", "greet"), invoke("greeter", "farewell")]),
+        print(vec![invoke("pirate", "both")]),
+        print(vec![member(ident("robot"), "shout"), member(call(ident("Robot"), vec![]), "shout")]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    assert_eq!(output, "ahoy jack arr, pirate farewell\nahoy jack bye from extension, jack\nahoy jack / bye from extension, jack\nhello r2! BEEP\n");
+}
@@ -0,0 +1,92 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{FunDeclaration, InitializerDeclaration, VariablePropertyDeclaration};
+use swift_oxide::syntax::{Statement, SwiftType};
+
+/// `Named` requires a settable `name`; `Shape` inherits it and requires a read-only `area`,
+/// `describe(with:)` and `init(size:)`.
+fn protocols() -> Vec<Statement> {
+    vec![
+        protocol("Named", &[], vec![("name", SwiftType::String, false)], vec![], vec![]),
+        protocol(
+            "Shape",
+            &["Named"],
+            vec![("area", SwiftType::Double, true)],
+            vec![("describe", vec![param(Some("with"), "prefix", SwiftType::String)], Some(SwiftType::String))],
+            vec![vec![param(Some("size"), "size", SwiftType::Double)]],
+        ),
+    ]
+}
+
+fn describe(prefix_type: SwiftType) -> FunDeclaration {
+    function("describe", vec![param(Some("with"), "prefix", prefix_type)], Some(SwiftType::String), vec![ret(binary(ident("prefix"), "+", ident("name")))])
+}
+
+fn square() -> Statement {
+    let properties = vec![
+        stored("name", Some(SwiftType::String), Some(string("square"))),
+        stored("side", Some(SwiftType::Double), None),
+        computed("area", SwiftType::Double, vec![ret(binary(ident("side"), "*", ident("side")))]),
+    ];
+    let initializer = initializer(vec![param(Some("size"), "size", SwiftType::Double)], vec![assign(member(this(), "side"), ident("size"))]);
+    conforming(structure("Square", properties, vec![], vec![initializer]), &["Shape"])
+}
+
+/// A class conforming to `Shape` in an extension, which must satisfy `init(size:)` with a
+/// `required` initializer.
+fn circle(name: VariablePropertyDeclaration, is_required: bool) -> Vec<Statement> {
+    let initializer = initializer(vec![param(Some("size"), "size", SwiftType::Double)], vec![assign(member(this(), "radius"), ident("size"))]);
+    let area = computed("area", SwiftType::Double, vec![ret(binary(binary(float(3.0), "*", ident("radius")), "*", ident("radius")))]);
+    vec![
+        class("Circle", None, vec![name, stored("radius", Some(SwiftType::Double), None)], vec![], vec![InitializerDeclaration { is_required, ..initializer }]),
+        extension("Circle", &["Shape"], vec![area], if is_required { vec![describe(SwiftType::String)] } else { vec![] }),
+    ]
+}
+
+#[test]
+fn conformances_declared_on_types_and_extensions_are_witnessed() {
+    let mut statements = protocols();
+    statements.push(square());
+    statements.extend(circle(stored("name", Some(SwiftType::String), Some(string("circle"))), true));
+    statements.extend([
+        extension("Square", &[], vec![], vec![describe(SwiftType::String)]),
+        structure("Plain", vec![], vec![], vec![]),
+        let_("square", call(ident("Square"), vec![labeled("size", float(2.0))])),
+        let_("circle", call(ident("Circle"), vec![labeled("size", float(1.0))])),
+        print(vec![
+            member(ident("square"), "area"),
+            call(member(ident("square"), "describe"), vec![labeled("with", string("a "))]),
+            member(ident("circle"), "area"),
+            call(member(ident("circle"), "describe"), vec![labeled("with", string("a "))]),
+        ]),
+        print(vec![
+            binary(cast(ident("square"), named("Shape")), "!=", nil()),
+            binary(cast(ident("circle"), named("Named")), "!=", nil()),
+            binary(cast(call(ident("Plain"), vec![]), named("Shape")), "!=", nil()),
+        ]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    assert_eq!(output, "4.0 a square 3.0 a circle\ntrue true false\n");
+}
+
+#[test]
+fn every_requirement_needs_a_matching_witness() {
+    let mut statements = protocols();
+    statements.push(square());
+    statements.extend(circle(VariablePropertyDeclaration { is_constant: true, ..stored("name", Some(SwiftType::String), Some(string("circle"))) }, false));
+    statements.extend([
+        extension("Square", &[], vec![], vec![describe(SwiftType::Integer)]),
+        conforming(structure("Broken", vec![], vec![], vec![]), &["Shape", "Square"]),
+    ]);
+    assert_eq!(errors(&program(statements)), [
+        "type 'Square' does not conform to protocol 'Shape'; protocol requires function 'describe(with:)' with type '(String) -> String'",
+        "type 'Circle' does not conform to protocol 'Shape'; protocol requires function 'describe(with:)' with type '(String) -> String'",
+        "initializer requirement 'init(size:)' can only be satisfied by a 'required' initializer in non-final class 'Circle'",
+        "type 'Circle' does not conform to protocol 'Named'; protocol requires property 'name' with type 'String' { get set }",
+        "type 'Broken' does not conform to protocol 'Shape'; protocol requires property 'area' with type 'Double' { get }",
+        "type 'Broken' does not conform to protocol 'Shape'; protocol requires function 'describe(with:)' with type '(String) -> String'",
+        "type 'Broken' does not conform to protocol 'Shape'; protocol requires initializer 'init(size:)' with type '(size: Double)'",
+        "type 'Broken' does not conform to protocol 'Named'; protocol requires property 'name' with type 'String' { get set }",
+        "inheritance from non-protocol type 'Square'",
+    ]);
+}
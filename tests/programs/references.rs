@@ -0,0 +1,103 @@
+use crate::build::*;
+use crate::{compile, errors, output, run};
+use swift_oxide::options::{Lifetime, Options};
+use swift_oxide::syntax::declaration::ReferenceOwnership::{Strong, Unowned, Weak};
+use swift_oxide::syntax::declaration::VariablePropertyDeclaration;
+use swift_oxide::syntax::SwiftType;
+
+fn returning(ty: SwiftType) -> SwiftType {
+    SwiftType::Function(vec![], Box::new(ty), Default::default())
+}
+
+#[test]
+fn weak_references_become_nil_once_the_instance_is_deinitialized() {
+    let person = class(
+        "Person",
+        None,
+        vec![stored("name", Some(SwiftType::String), None), stored("apartment", Some(optional(named("Apartment"))), Some(nil()))],
+        vec![],
+        vec![initializer(vec![param(None, "name", SwiftType::String)], vec![assign(member(this(), "name"), ident("name"))])],
+    );
+    let apartment = class(
+        "Apartment",
+        None,
+        vec![
+            stored("unit", Some(SwiftType::String), None),
+            VariablePropertyDeclaration { ownership: Weak, ..stored("tenant", Some(optional(named("Person"))), None) },
+            VariablePropertyDeclaration { ownership: Unowned, ..stored("owner", Some(named("Person")), None) },
+        ],
+        vec![],
+        vec![initializer(vec![param(None, "unit", SwiftType::String), param(None, "owner", named("Person"))], vec![
+            assign(member(this(), "unit"), ident("unit")),
+            assign(member(this(), "owner"), ident("owner")),
+        ])],
+    );
+    let scope = function("scope", vec![], None, vec![
+        let_("landlord", call(ident("Person"), vec![arg(string("Landlord"))])),
+        let_("john", call(ident("Person"), vec![arg(string("John"))])),
+        let_("unit", call(ident("Apartment"), vec![arg(string("4A")), arg(ident("landlord"))])),
+        assign(member(ident("john"), "apartment"), ident("unit")),
+        assign(member(ident("unit"), "tenant"), ident("john")),
+        print(vec![member(member(ident("unit"), "tenant"), "name"), member(member(ident("unit"), "owner"), "name")]),
+    ]);
+    let program = program(vec![
+        with_deinit(person, vec![print(vec![string("deinit"), ident("name")])]),
+        with_deinit(apartment, vec![print(vec![string("deinit"), ident("unit")])]),
+        function_declaration(scope),
+        expression(call(ident("scope"), vec![])),
+        owned_var("gone", Some(optional(named("Person"))), call(ident("Person"), vec![arg(string("Temp"))]), Weak),
+        print(vec![binary(ident("gone"), "==", nil())]),
+    ]);
+    let options = Options { lifetime: Lifetime::ReferenceCounting, ..Options::default() };
+    let Some(output) = run(&compile(&program, &options)) else { return };
+    assert_eq!(output, "John Landlord\ndeinit John\ndeinit 4A\ndeinit Landlord\ndeinit Temp\ntrue\n");
+}
+
+#[test]
+fn capture_lists_bind_values_when_the_closure_is_created() {
+    let counter = class(
+        "Counter",
+        None,
+        vec![stored("count", None, Some(int(0)))],
+        vec![
+            function("unowned", vec![], Some(returning(SwiftType::Integer)), vec![ret(capturing(closure(vec![ret(member(this(), "count"))]), vec![(Unowned, "self", None)]))]),
+            function("weak", vec![], Some(returning(SwiftType::Bool)), vec![ret(capturing(closure(vec![ret(binary(this(), "==", nil()))]), vec![(Weak, "self", None)]))]),
+        ],
+        vec![],
+    );
+    let program = program(vec![
+        counter,
+        let_("counter", call(ident("Counter"), vec![])),
+        let_("read", call(member(ident("counter"), "unowned"), vec![])),
+        let_("released", call(member(ident("counter"), "weak"), vec![])),
+        assign(member(ident("counter"), "count"), int(5)),
+        var("y", int(1)),
+        let_("add", capturing(closure(vec![ret(binary(ident("y"), "+", ident("z")))]), vec![(Strong, "y", None), (Strong, "z", Some(int(10)))])),
+        assign(ident("y"), int(100)),
+        print(vec![call(ident("read"), vec![]), call(ident("released"), vec![]), call(ident("add"), vec![])]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "5 false 11\n");
+}
+
+#[test]
+fn weak_and_unowned_need_class_types_and_weak_an_optional_variable() {
+    let properties = vec![
+        VariablePropertyDeclaration { ownership: Weak, ..stored("a", Some(SwiftType::Integer), Some(int(0))) },
+        VariablePropertyDeclaration { ownership: Weak, ..stored("b", Some(named("K")), None) },
+        VariablePropertyDeclaration { ownership: Weak, is_constant: true, ..stored("c", Some(optional(named("K"))), Some(nil())) },
+    ];
+    let program = program(vec![
+        class("K", None, properties, vec![], vec![]),
+        owned_var("u", None, int(1), Unowned),
+        let_("x", capturing(closure(vec![]), vec![(Weak, "u", None)])),
+    ]);
+    assert_eq!(errors(&program), [
+        "class 'K' has no initializers",
+        "'weak' may only be applied to class and class-bound protocol types, not 'Int'",
+        "'weak' variable should have optional type 'K?'",
+        "'weak' must be a mutable variable, because it may change at runtime",
+        "'unowned' may only be applied to class and class-bound protocol types, not 'Int'",
+        "'weak' may only be applied to class and class-bound protocol types, not 'Int'",
+    ]);
+}
@@ -0,0 +1,159 @@
+use crate::build::*;
+use crate::output;
+use swift_oxide::syntax::declaration::{EnumAssociatedValue, EnumCase};
+use swift_oxide::syntax::expression::Argument;
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+fn point(x: i64, y: i64) -> Expression {
+    call(ident("Point"), vec![labeled("x", int(x)), labeled("y", int(y))])
+}
+
+fn line() -> Expression {
+    call(ident("Line"), vec![labeled("from", ident("p")), labeled("to", point(0, 0))])
+}
+
+fn dump(arguments: Vec<Argument>) -> Statement {
+    expression(call(ident("dump"), arguments))
+}
+
+/// `Dog` subclasses `Animal` and may befriend itself; `Token` describes itself differently for debugging.
+fn types() -> Vec<Statement> {
+    let rect = EnumCase {
+        associated_values: vec![
+            EnumAssociatedValue { label: Some("width".into()), ty: SwiftType::Double },
+            EnumAssociatedValue { label: Some("height".into()), ty: SwiftType::Double },
+        ],
+        ..case("rect", vec![])
+    };
+    let point_properties = vec![stored("x", Some(SwiftType::Integer), None), stored("y", Some(SwiftType::Integer), None)];
+    let line_properties = vec![stored("from", Some(named("Point")), None), stored("to", Some(named("Point")), None)];
+    let dog_properties = vec![stored("breed", None, Some(string("Lab"))), stored("friend", Some(optional(named("Dog"))), None)];
+    let token_properties = vec![
+        computed("debugDescription", SwiftType::String, vec![ret(string("Token<debug>"))]),
+        computed("description", SwiftType::String, vec![ret(string("token"))]),
+    ];
+    vec![
+        structure("Point", point_properties, vec![], vec![]),
+        structure("Line", line_properties, vec![], vec![]),
+        enumeration("Barcode", vec![case("upc", vec![SwiftType::Integer; 4]), case("none", vec![])], None, vec![], vec![]),
+        enumeration("Measurement", vec![case("weight", vec![SwiftType::Double])], None, vec![], vec![]),
+        enumeration("Shape", vec![rect], None, vec![], vec![]),
+        class("Animal", None, vec![stored("name", None, Some(string("Rex")))], vec![], vec![]),
+        class("Dog", Some("Animal"), dog_properties, vec![], vec![]),
+        conforming(structure("Token", token_properties, vec![], vec![]), &["CustomStringConvertible", "CustomDebugStringConvertible"]),
+        let_("p", point(10, 20)),
+        typed_let("o", optional(SwiftType::Integer), int(5)),
+        let_("dog", call(ident("Dog"), vec![])),
+        assign(member(ident("dog"), "friend"), ident("dog")),
+    ]
+}
+
+#[test]
+fn dump_prints_the_mirror_of_every_kind_of_value() {
+    let mut statements = types();
+    statements.extend([
+        dump(vec![arg(ident("p"))]),
+        dump(vec![arg(ident("o"))]),
+        dump(vec![arg(call(member(ident("Barcode"), "upc"), vec![arg(int(1)), arg(int(2)), arg(int(3)), arg(int(4))]))]),
+        dump(vec![arg(member(ident("Barcode"), "none"))]),
+        dump(vec![arg(call(member(ident("Measurement"), "weight"), vec![arg(float(2.5))]))]),
+        dump(vec![arg(call(member(ident("Shape"), "rect"), vec![labeled("width", float(2.0)), labeled("height", float(3.5))]))]),
+        dump(vec![arg(array(vec![string("a"), string("b")])), labeled("name", string("letters"))]),
+        dump(vec![arg(dictionary(vec![(string("a"), int(1))]))]),
+        dump(vec![arg(tuple(vec![int(1), string("a")]))]),
+        dump(vec![arg(line())]),
+        dump(vec![arg(line()), labeled("maxDepth", int(1))]),
+        dump(vec![arg(array(vec![int(1), int(2), int(3)])), labeled("maxItems", int(2))]),
+        dump(vec![arg(ident("dog"))]),
+        dump(vec![arg(call(ident("Token"), vec![]))]),
+        dump(vec![arg(string("hi")), labeled("indent", int(2))]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    let expected = [
+        "▿ main.Point",
+        "  - x: 10",
+        "  - y: 20",
+        "▿ Optional(5)",
+        "  - some: 5",
+        "▿ main.Barcode.upc",
+        "  ▿ upc: (4 elements)",
+        "    - .0: 1",
+        "    - .1: 2",
+        "    - .2: 3",
+        "    - .3: 4",
+        "- main.Barcode.none",
+        "▿ main.Measurement.weight",
+        "  - weight: 2.5",
+        "▿ main.Shape.rect",
+        "  ▿ rect: (2 elements)",
+        "    - width: 2.0",
+        "    - height: 3.5",
+        "▿ letters: 2 elements",
+        "  - \"a\"",
+        "  - \"b\"",
+        "▿ 1 key/value pair",
+        "  ▿ (2 elements)",
+        "    - key: \"a\"",
+        "    - value: 1",
+        "▿ (2 elements)",
+        "  - .0: 1",
+        "  - .1: \"a\"",
+        "▿ main.Line",
+        "  ▿ from: main.Point",
+        "    - x: 10",
+        "    - y: 20",
+        "  ▿ to: main.Point",
+        "    - x: 0",
+        "    - y: 0",
+        "▿ main.Line",
+        "  ▹ from: main.Point",
+        "  ▹ to: main.Point",
+        "▿ 3 elements",
+        "  - 1",
+        "    (2 more children)",
+        "▿ main.Dog #0",
+        "  ▿ super: main.Animal",
+        "    - name: \"Rex\"",
+        "  - breed: \"Lab\"",
+        "  ▿ friend: Optional(main.Dog)",
+        "    ▿ some: main.Dog #0",
+        "- Token<debug>",
+        "  - \"hi\"",
+    ];
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn debug_print_and_mirrors_reflect_values() {
+    let mut statements = types();
+    statements.extend([
+        expression(call(ident("debugPrint"), vec![
+            arg(string("hi")),
+            arg(int(1)),
+            arg(float(1.0)),
+            arg(ident("o")),
+            arg(ident("p")),
+            arg(call(ident("Token"), vec![])),
+        ])),
+        print(vec![call(ident("Token"), vec![])]),
+        let_("m", call(ident("Mirror"), vec![labeled("reflecting", ident("p"))])),
+        print(vec![member(ident("m"), "displayStyle"), member(ident("m"), "subjectType")]),
+        for_in("child", member(ident("m"), "children"), vec![print(vec![member(ident("child"), "label"), member(ident("child"), "value")])]),
+        let_("dm", call(ident("Mirror"), vec![labeled("reflecting", ident("dog"))])),
+        print(vec![member(member(ident("dm"), "children"), "count"), member(ident("dm"), "superclassMirror")]),
+        let_("r", call(ident("dump"), vec![arg(int(7))])),
+        print(vec![ident("r")]),
+    ]);
+    let Some(output) = output(&program(statements)) else { return };
+    let expected = [
+        "\"hi\" 1 1.0 Optional(5) main.Point(x: 10, y: 20) Token<debug>",
+        "token",
+        "Optional(Swift.Mirror.DisplayStyle.struct) Point",
+        "Optional(\"x\") 10",
+        "Optional(\"y\") 20",
+        "2 Optional(Mirror for Animal)",
+        "- 7",
+        "7",
+    ];
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+}
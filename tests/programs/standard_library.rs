@@ -0,0 +1,164 @@
+use crate::build::*;
+use crate::{compile, errors, output, run_trapping};
+use swift_oxide::options::Options;
+use swift_oxide::syntax::expression::Argument;
+use swift_oxide::syntax::{Expression, Statement, SwiftType};
+
+fn method(target: Expression, name: &str, arguments: Vec<Argument>) -> Expression {
+    call(member(target, name), arguments)
+}
+
+/// Calls a method with a trailing closure whose body is `body`, like `numbers.map { $0 * 2 }`.
+fn with_closure(target: Expression, name: &str, body: Expression) -> Expression {
+    trailing(member(target, name), vec![], closure(vec![expression(body)]))
+}
+
+fn first() -> Expression {
+    ident("$0")
+}
+
+fn dictionary_of_ints() -> SwiftType {
+    SwiftType::Dictionary(Box::new(SwiftType::String), Box::new(SwiftType::Integer))
+}
+
+fn set_of_ints() -> SwiftType {
+    SwiftType::Set(Box::new(SwiftType::Integer))
+}
+
+fn numbers() -> Statement {
+    var("numbers", array(vec![int(5), int(3), int(8), int(1)]))
+}
+
+#[test]
+fn arrays_dictionaries_sets_and_optionals_have_their_standard_members() {
+    let program = program(vec![
+        numbers(),
+        print(vec![with_closure(ident("numbers"), "map", binary(first(), "*", int(2))), with_closure(ident("numbers"), "filter", binary(first(), ">", int(2)))]),
+        print(vec![method(ident("numbers"), "reduce", vec![arg(int(0)), arg(closure_of(&["a", "b"], vec![expression(binary(ident("a"), "+", ident("b")))]))])]),
+        print(vec![
+            with_closure(ident("numbers"), "sorted", binary(first(), ">", ident("$1"))),
+            method(ident("numbers"), "sorted", vec![]),
+            method(ident("numbers"), "reversed", vec![]),
+        ]),
+        expression(method(ident("numbers"), "append", vec![labeled("contentsOf", array(vec![int(10), int(11)]))])),
+        expression(method(ident("numbers"), "append", vec![arg(int(2))])),
+        expression(with_closure(ident("numbers"), "removeAll", binary(binary(first(), "%", int(2)), "==", int(0)))),
+        print(vec![ident("numbers"), member(ident("numbers"), "count"), member(ident("numbers"), "first"), member(ident("numbers"), "last")]),
+        print(vec![
+            with_closure(ident("numbers"), "first", binary(first(), ">", int(4))),
+            method(ident("numbers"), "firstIndex", vec![labeled("of", int(1))]),
+            method(ident("numbers"), "contains", vec![arg(int(3))]),
+            method(ident("numbers"), "min", vec![]),
+            method(ident("numbers"), "max", vec![]),
+        ]),
+        expression(method(ident("numbers"), "insert", vec![arg(int(7)), labeled("at", int(1))])),
+        let_("removed", method(ident("numbers"), "remove", vec![labeled("at", int(0))])),
+        print(vec![ident("removed"), ident("numbers")]),
+        let_("words", array(vec![string("b"), string("a"), string("b"), string("c"), string("b")])),
+        typed_var("counts", dictionary_of_ints(), dictionary(vec![])),
+        for_in("w", ident("words"), vec![expression(binary(subscript_or(ident("counts"), ident("w"), int(0)), "+=", int(1)))]),
+        print(vec![subscript_or(ident("counts"), string("b"), int(0)), subscript_or(ident("counts"), string("z"), int(0)), member(ident("counts"), "count")]),
+        let_("tenfold", with_closure(ident("counts"), "mapValues", binary(first(), "*", int(10)))),
+        print(vec![subscript_or(ident("tenfold"), string("a"), int(0)), method(member(ident("counts"), "keys"), "sorted", vec![])]),
+        for_in("pair", with_closure(ident("counts"), "sorted", binary(member(first(), "0"), "<", member(ident("$1"), "0"))), vec![print(vec![ident("pair")])]),
+        expression(trailing(member(method(ident("words"), "enumerated", vec![]), "forEach"), vec![], closure_of(&["i", "w"], vec![print(vec![ident("i"), ident("w")])]))),
+        print(vec![method(with_closure(ident("words"), "compactMap", binary(first(), "+", string("!"))), "joined", vec![labeled("separator", string("-"))])]),
+        typed_let("odds", set_of_ints(), array(vec![int(1), int(3), int(5)])),
+        typed_var("small", set_of_ints(), array(vec![int(1), int(2), int(3)])),
+        print(vec![
+            method(method(ident("odds"), "union", vec![arg(ident("small"))]), "sorted", vec![]),
+            method(method(ident("odds"), "intersection", vec![arg(ident("small"))]), "sorted", vec![]),
+            method(method(ident("odds"), "symmetricDifference", vec![arg(ident("small"))]), "sorted", vec![]),
+        ]),
+        print(vec![
+            method(ident("small"), "insert", vec![arg(int(2))]),
+            method(ident("small"), "isSubset", vec![labeled("of", ident("odds"))]),
+            method(ident("odds"), "contains", vec![arg(int(5))]),
+        ]),
+        typed_let("maybe", optional(SwiftType::Integer), int(4)),
+        print(vec![with_closure(ident("maybe"), "map", binary(first(), "+", int(1)))]),
+        for_in("p", call(ident("zip"), vec![arg(array(vec![int(1), int(2), int(3)])), arg(array(vec![string("a"), string("b")]))]), vec![print(vec![ident("p")])]),
+        for_in("x", call(ident("stride"), vec![labeled("from", int(0)), labeled("to", int(10)), labeled("by", int(3))]), vec![expression(call(
+            ident("print"),
+            vec![arg(ident("x")), labeled("terminator", string(" "))],
+        ))]),
+        print(vec![call(ident("stride"), vec![labeled("from", float(1.0)), labeled("through", float(0.0)), labeled("by", float(-0.25))])]),
+        print(vec![
+            call(ident("min"), vec![arg(int(3)), arg(int(9))]),
+            call(ident("max"), vec![arg(string("pear")), arg(string("apple"))]),
+            call(ident("abs"), vec![arg(int(-7))]),
+        ]),
+        expression(call(ident("print"), vec![arg(string("a")), arg(string("b")), labeled("separator", string(", ")), labeled("terminator", string("!\n"))])),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(
+        output,
+        "[10, 6, 16, 2] [5, 3, 8]\n17\n[8, 5, 3, 1] [1, 3, 5, 8] [1, 8, 3, 5]\n[5, 3, 1, 11] 4 Optional(5) Optional(11)\n\
+         Optional(5) Optional(2) true Optional(1) Optional(11)\n5 [7, 3, 1, 11]\n3 0 3\n10 [\"a\", \"b\", \"c\"]\n\
+         (key: \"a\", value: 1)\n(key: \"b\", value: 3)\n(key: \"c\", value: 1)\n0 b\n1 a\n2 b\n3 c\n4 b\nb!-a!-b!-c!-b!\n\
+         [1, 2, 3, 5] [1, 3] [2, 5]\n(inserted: false, memberAfterInsert: 2) false true\nOptional(5)\n(1, \"a\")\n(2, \"b\")\n\
+         0 3 6 9 [1.0, 0.75, 0.5, 0.25, 0.0]\n3 pear 7\na, b!\n"
+    );
+}
+
+#[test]
+fn removing_from_an_empty_array_traps() {
+    let program = program(vec![
+        numbers(),
+        expression(method(ident("numbers"), "removeAll", vec![])),
+        print(vec![member(ident("numbers"), "isEmpty")]),
+        expression(method(ident("numbers"), "removeLast", vec![])),
+    ]);
+    let Some((output, message)) = run_trapping(&compile(&program, &Options::default())) else { return };
+    assert_eq!((output.as_str(), message.as_str()), ("true\n", "Can't remove last element from an empty collection"));
+}
+
+#[test]
+fn elements_are_labeled_tuples_and_global_functions_take_their_labels() {
+    let program = program(vec![
+        typed_let("counts", dictionary_of_ints(), dictionary(vec![(string("a"), int(1)), (string("b"), int(2))])),
+        for_in("pair", ident("counts"), vec![print(vec![member(ident("pair"), "key"), member(ident("pair"), "value"), member(ident("pair"), "1"), ident("pair")])]),
+        print(vec![with_closure(ident("counts"), "map", binary(member(first(), "key"), "+", call(ident("String"), vec![arg(member(first(), "value"))])))]),
+        let_("letters", array(vec![string("x"), string("y")])),
+        for_in("item", method(ident("letters"), "enumerated", vec![]), vec![print(vec![member(ident("item"), "offset"), member(ident("item"), "element")])]),
+        typed_var("seen", set_of_ints(), array(vec![int(1)])),
+        print(vec![member(method(ident("seen"), "insert", vec![arg(int(1))]), "inserted"), member(method(ident("seen"), "insert", vec![arg(int(2))]), "memberAfterInsert")]),
+        expression(call(ident("dump"), vec![arg(ident("letters")), labeled("name", string("letters")), labeled("maxDepth", int(1))])),
+        expression(call(ident("print"), vec![arg(string("a")), arg(string("b")), labeled("terminator", string("!\n"))])),
+        print(vec![call(ident("max"), vec![arg(int(1)), arg(int(7)), arg(int(3))])]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(
+        output,
+        "a 1 1 (key: \"a\", value: 1)\nb 2 2 (key: \"b\", value: 2)\n[\"a1\", \"b2\"]\n0 x\n1 y\nfalse 2\n▿ letters: 2 elements\n  - \"x\"\n  - \"y\"\na b!\n7\n"
+    );
+}
+
+#[test]
+fn calls_must_match_a_declaration_in_the_manifest() {
+    let program = program(vec![
+        let_("fixed", array(vec![int(1)])),
+        expression(method(ident("fixed"), "append", vec![arg(int(2))])),
+        print(vec![member(ident("fixed"), "size")]),
+        expression(method(ident("fixed"), "insert", vec![arg(int(2)), labeled("position", int(0))])),
+        typed_let("counts", dictionary_of_ints(), dictionary(vec![])),
+        for_in("pair", ident("counts"), vec![print(vec![member(ident("pair"), "first"), member(ident("pair"), "2")])]),
+        print(vec![call(ident("stride"), vec![labeled("from", int(0)), labeled("to", int(3))])]),
+        print(vec![call(ident("abs"), vec![arg(int(1)), arg(int(2))])]),
+        print(vec![call(ident("min"), vec![arg(int(1))])]),
+        expression(call(ident("print"), vec![arg(string("a")), labeled("terminator", string("")), labeled("separator", string(""))])),
+        expression(call(ident("dump"), vec![arg(int(1)), labeled("depth", int(1))])),
+    ]);
+    assert_eq!(errors(&program), [
+        "cannot use mutating member on immutable value: 'fixed' is a 'let' constant",
+        "value of type '[Int]' has no member 'size'",
+        "no exact matches in call to instance method 'insert'",
+        "value of tuple type '(key: String, value: Int)' has no member 'first'",
+        "value of tuple type '(key: String, value: Int)' has no member '2'",
+        "no exact matches in call to global function 'stride'",
+        "no exact matches in call to global function 'abs'",
+        "no exact matches in call to global function 'min'",
+        "no exact matches in call to global function 'print'",
+        "no exact matches in call to global function 'dump'",
+    ]);
+}
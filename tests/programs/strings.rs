@@ -0,0 +1,90 @@
+use crate::build::*;
+use crate::{compile, errors, output, run_trapping};
+use swift_oxide::options::Options;
+use swift_oxide::syntax::expression::Argument;
+use swift_oxide::syntax::{Expression, SwiftType};
+
+fn method(target: Expression, name: &str, arguments: Vec<Argument>) -> Expression {
+    call(member(target, name), arguments)
+}
+
+fn count(target: Expression) -> Expression {
+    member(target, "count")
+}
+
+#[test]
+fn strings_count_index_and_compare_by_grapheme_cluster() {
+    let index_after_start = method(ident("family"), "index", vec![labeled("after", member(ident("family"), "startIndex"))]);
+    let program = program(vec![
+        let_("flags", string("🇯🇵🇺🇸")),
+        let_("family", string("👨‍👩‍👧‍👦 family")),
+        print(vec![
+            count(ident("flags")),
+            count(member(ident("flags"), "utf16")),
+            count(member(ident("flags"), "unicodeScalars")),
+            count(member(ident("flags"), "utf8")),
+        ]),
+        print(vec![count(ident("family")), member(ident("family"), "first"), member(ident("family"), "last"), member(ident("family"), "isEmpty")]),
+        let_("second", method(ident("family"), "index", vec![arg(member(ident("family"), "startIndex")), labeled("offsetBy", int(2))])),
+        print(vec![subscript(ident("family"), ident("second")), subscript(ident("family"), index_after_start)]),
+        print(vec![subscript(ident("flags"), method(ident("flags"), "index", vec![labeled("before", member(ident("flags"), "endIndex"))]))]),
+        let_("precomposed", string("caf\u{e9}")),
+        let_("decomposed", string("cafe\u{301}")),
+        print(vec![
+            binary(ident("precomposed"), "==", ident("decomposed")),
+            count(ident("precomposed")),
+            count(ident("decomposed")),
+            count(member(ident("decomposed"), "unicodeScalars")),
+        ]),
+        print(vec![
+            binary(string("apple"), "<", string("banana")),
+            binary(ident("decomposed"), "<", string("cafz")),
+            method(ident("decomposed"), "hasSuffix", vec![arg(string("\u{e9}"))]),
+            method(ident("flags"), "hasPrefix", vec![arg(string("🇯"))]),
+        ]),
+        typed_let("accent", SwiftType::Character, string("e\u{301}")),
+        print(vec![
+            binary(ident("accent"), "==", character("\u{e9}")),
+            count(member(ident("accent"), "unicodeScalars")),
+            member(ident("accent"), "isASCII"),
+            member(character("A"), "asciiValue"),
+        ]),
+        var("n", int(0)),
+        for_in("c", ident("family"), vec![
+            if_(binary(ident("c"), "==", character(" ")), vec![break_()], None),
+            expression(binary(ident("n"), "+=", int(1))),
+            print(vec![ident("c")]),
+        ]),
+        print(vec![ident("n")]),
+        for_in("b", member(string("é!"), "utf8"), vec![print(vec![ident("b")])]),
+        for_in("u", member(string("a🇯"), "unicodeScalars"), vec![print(vec![member(ident("u"), "value")])]),
+        switch_default(ident("decomposed"), vec![(string_pattern("caf\u{e9}"), vec![print(vec![string("matched")])])], Some(vec![print(vec![string("missed")])])),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(
+        output,
+        "2 8 4 16\n8 Optional(\"👨‍👩‍👧‍👦\") Optional(\"y\") false\nf  \n🇺🇸\ntrue 4 4 5\ntrue false true false\ntrue 2 false Optional(65)\n👨‍👩‍👧‍👦\n1\n195\n169\n33\n97\n127471\nmatched\n"
+    );
+}
+
+#[test]
+fn indices_past_the_end_trap() {
+    let past_end = method(string("ab"), "index", vec![arg(int(0)), labeled("offsetBy", int(5))]);
+    let program = program(vec![print(vec![subscript(string("ab"), past_end)])]);
+    let Some((output, message)) = run_trapping(&compile(&program, &Options::default())) else { return };
+    assert_eq!((output.as_str(), message.as_str()), ("", "String index is out of bounds"));
+}
+
+#[test]
+fn characters_are_single_grapheme_clusters() {
+    let program = program(vec![
+        let_("pair", character("ab")),
+        typed_let("word", SwiftType::Character, string("no")),
+        for_in("i", int(3), vec![]),
+    ]);
+    assert_eq!(errors(&program), [
+        "character literal 'ab' must be a single extended grapheme cluster",
+        "cannot convert value of type 'String' to specified type 'Character'",
+        "for-in loop requires 'Int' to conform to 'Sequence'",
+    ]);
+}
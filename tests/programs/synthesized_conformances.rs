@@ -0,0 +1,85 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::{Expression, SwiftType};
+
+fn point(x: i64, y: i64) -> Expression {
+    call(ident("Point"), vec![labeled("x", int(x)), labeled("y", int(y))])
+}
+
+fn suit(name: &str) -> Expression {
+    member(ident("Suit"), name)
+}
+
+fn shape(name: &str, values: Vec<Expression>) -> Expression {
+    call(member(ident("Shape"), name), values.into_iter().map(arg).collect())
+}
+
+fn tagged(name: &str, origin: Expression) -> Expression {
+    call(ident("Tagged"), vec![labeled("name", string(name)), labeled("tags", array(vec![string("x")])), labeled("origin", origin)])
+}
+
+#[test]
+fn equality_hashing_and_ordering_are_synthesized_from_members() {
+    let point_properties = vec![stored("x", Some(SwiftType::Integer), None), stored("y", Some(SwiftType::Integer), None)];
+    let suits = vec![case("clubs", vec![]), case("diamonds", vec![]), case("hearts", vec![]), case("spades", vec![])];
+    let shapes = vec![case("circle", vec![named("Point")]), case("rect", vec![SwiftType::Integer, SwiftType::Integer]), case("empty", vec![])];
+    let tagged_properties = vec![
+        stored("name", Some(SwiftType::String), None),
+        stored("tags", Some(SwiftType::Array(Box::new(SwiftType::String))), None),
+        stored("origin", Some(optional(named("Point"))), None),
+    ];
+    let program = program(vec![
+        conforming(structure("Point", point_properties, vec![], vec![]), &["Hashable"]),
+        conforming(enumeration("Suit", suits, None, vec![], vec![]), &["Comparable"]),
+        conforming(enumeration("Shape", shapes, None, vec![], vec![]), &["Hashable"]),
+        conforming(structure("Tagged", tagged_properties, vec![], vec![]), &["Equatable"]),
+        print(vec![binary(point(1, 2), "==", point(1, 2)), binary(point(1, 2), "==", point(2, 1))]),
+        typed_var("visits", SwiftType::Dictionary(Box::new(named("Point")), Box::new(SwiftType::Integer)), dictionary(vec![])),
+        expression(binary(subscript_or(ident("visits"), point(1, 1), int(0)), "+=", int(1))),
+        expression(binary(subscript_or(ident("visits"), point(1, 1), int(0)), "+=", int(1))),
+        print(vec![subscript(ident("visits"), point(1, 1)), member(ident("visits"), "count")]),
+        print(vec![
+            binary(suit("clubs"), "<", suit("hearts")),
+            binary(suit("spades"), "<", suit("hearts")),
+            binary(suit("spades"), ">=", suit("spades")),
+        ]),
+        let_("hand", array(vec![suit("spades"), suit("clubs"), suit("hearts")])),
+        print(vec![
+            binary(call(member(ident("hand"), "sorted"), vec![]), "==", array(vec![suit("clubs"), suit("hearts"), suit("spades")])),
+            binary(call(member(ident("hand"), "max"), vec![]), "==", suit("spades")),
+        ]),
+        typed_let("shapes", SwiftType::Set(Box::new(named("Shape"))), array(vec![
+            shape("circle", vec![point(0, 0)]),
+            shape("circle", vec![point(0, 0)]),
+            shape("rect", vec![int(1), int(2)]),
+            shape("rect", vec![int(2), int(1)]),
+            member(ident("Shape"), "empty"),
+            member(ident("Shape"), "empty"),
+        ])),
+        print(vec![
+            member(ident("shapes"), "count"),
+            call(member(ident("shapes"), "contains"), vec![arg(shape("rect", vec![int(2), int(1)]))]),
+            binary(shape("circle", vec![point(0, 0)]), "!=", shape("circle", vec![point(0, 1)])),
+        ]),
+        let_("a", tagged("caf\u{e9}", point(1, 1))),
+        let_("b", tagged("cafe\u{301}", point(1, 1))),
+        let_("c", tagged("cafe", nil())),
+        print(vec![binary(ident("a"), "==", ident("b")), binary(ident("a"), "==", ident("c"))]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "true false\nOptional(2) 1\ntrue false true\ntrue true\n4 true true\ntrue false\n");
+}
+
+#[test]
+fn synthesis_needs_a_struct_or_enum_whose_members_qualify() {
+    let program = program(vec![
+        conforming(class("Node", None, vec![stored("id", Some(SwiftType::Integer), Some(int(0)))], vec![], vec![]), &["Equatable"]),
+        conforming(structure("Pair", vec![stored("a", Some(SwiftType::Integer), None)], vec![], vec![]), &["Comparable"]),
+        conforming(structure("Holder", vec![stored("node", Some(named("Node")), None)], vec![], vec![]), &["Hashable"]),
+    ]);
+    assert_eq!(errors(&program), [
+        "type 'Node' does not conform to protocol 'Equatable'",
+        "type 'Pair' does not conform to protocol 'Comparable'",
+        "type 'Holder' does not conform to protocol 'Hashable'",
+    ]);
+}
@@ -0,0 +1,110 @@
+use crate::build::*;
+use crate::{errors, output};
+use swift_oxide::syntax::declaration::{FunDeclaration, InitializerDeclaration};
+use swift_oxide::syntax::{FunctionTypeAttributes, IntegerType, SwiftType};
+
+#[test]
+fn types_parse_and_print_as_swift_spells_them() {
+    let attributes = FunctionTypeAttributes { is_async: true, is_throwing: true, is_escaping: false };
+    let cases = [
+        ("[String: Int]?", optional(SwiftType::Dictionary(Box::new(SwiftType::String), Box::new(SwiftType::Integer)))),
+        ("(Int) async throws -> Bool", SwiftType::Function(vec![SwiftType::Integer], Box::new(SwiftType::Bool), attributes)),
+        ("Set<UInt8>", SwiftType::Set(Box::new(SwiftType::SizedInteger(IntegerType::UInt8)))),
+        ("Result<String, Error>", SwiftType::Generic("Result".into(), vec![SwiftType::String, named("Error")])),
+        ("any Shape & Named", SwiftType::Existential(Box::new(composition(&["Shape", "Named"])))),
+        ("T.Type", SwiftType::Metatype(Box::new(named("T")))),
+        ("String!", SwiftType::ImplicitlyUnwrappedOptional(Box::new(SwiftType::String))),
+        ("Double", SwiftType::Double),
+        ("Float", SwiftType::Float),
+    ];
+    for (source, ty) in cases {
+        assert_eq!(source.parse::<SwiftType>(), Ok(ty.clone()), "parsing {source}");
+        assert_eq!(ty.to_string(), source);
+    }
+    assert_eq!("[Int".parse::<SwiftType>(), Err("expected ']' in type '[Int'".to_string()));
+}
+
+#[test]
+fn fixed_width_integers_sets_any_and_metatypes() {
+    let initializer = InitializerDeclaration {
+        is_required: true,
+        ..initializer(vec![param(Some("seed"), "seed", SwiftType::Integer)], vec![assign(member(this(), "n"), ident("seed"))])
+    };
+    let make = FunDeclaration {
+        generics: Some(generics(vec![("T", vec![named("Makeable")])], vec![])),
+        ..function("make", vec![param(None, "t", SwiftType::Metatype(Box::new(named("T"))))], Some(named("T")), vec![ret(call(ident("T"), vec![labeled("seed", int(3))]))])
+    };
+    let byte = SwiftType::SizedInteger(IntegerType::UInt8);
+    let program = program(vec![
+        protocol("Makeable", &[], vec![], vec![], vec![vec![param(Some("seed"), "seed", SwiftType::Integer)]]),
+        conforming(class("Counter", None, vec![stored("n", Some(SwiftType::Integer), None)], vec![], vec![initializer]), &["Makeable"]),
+        function_declaration(make),
+        typed_let("byte", byte.clone(), int(200)),
+        let_("wide", call(ident("Int64"), vec![arg(ident("byte"))])),
+        let_("half", call(ident("Float"), vec![arg(float(0.1))])),
+        let_("d", call(ident("Double"), vec![arg(ident("byte"))])),
+        print(vec![ident("byte"), ident("wide"), binary(ident("half"), "==", float(0.1)), ident("d")]),
+        typed_let("big", SwiftType::Any, int(300)),
+        print(vec![
+            binary(cast(ident("big"), byte), "==", nil()),
+            binary(cast(ident("big"), SwiftType::Integer), "!=", nil()),
+            binary(cast(ident("big"), SwiftType::AnyObject), "==", nil()),
+        ]),
+        typed_let("object", SwiftType::AnyObject, call(ident("Counter"), vec![labeled("seed", int(1))])),
+        print(vec![binary(cast(ident("object"), named("Counter")), "!=", nil())]),
+        typed_var("seen", SwiftType::Set(Box::new(SwiftType::Integer)), array(vec![int(1), int(2), int(2)])),
+        print(vec![member(call(ident("make"), vec![arg(member(ident("Counter"), "self"))]), "n")]),
+        declare_var("later", SwiftType::ImplicitlyUnwrappedOptional(Box::new(SwiftType::String))),
+        assign(ident("later"), string("ready")),
+        print(vec![ident("later")]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "200 200 true 200.0\ntrue true true\ntrue\n3\nOptional(\"ready\")\n");
+}
+
+#[test]
+fn doubles_and_floats_keep_their_precision_and_box_into_any() {
+    let program = program(vec![
+        typed_let("d", SwiftType::Double, float(2.0)),
+        typed_let("f", SwiftType::Float, call(ident("Float"), vec![arg(int(1))])),
+        typed_let("a", SwiftType::Any, ident("d")),
+        typed_let("i", SwiftType::Any, int(1)),
+        typed_let("g", SwiftType::Any, ident("f")),
+        print(vec![ident("a"), ident("i"), ident("g")]),
+        print(vec![cast(ident("a"), optional(SwiftType::Integer)), cast(ident("i"), optional(SwiftType::Double))]),
+        print(vec![
+            binary(cast(ident("a"), SwiftType::Integer), "==", nil()),
+            binary(cast(ident("a"), SwiftType::Double), "!=", nil()),
+            binary(cast(ident("i"), SwiftType::Double), "==", nil()),
+            binary(cast(ident("g"), SwiftType::Double), "==", nil()),
+            binary(cast(ident("g"), SwiftType::Float), "!=", nil()),
+        ]),
+        typed_let("mixed", SwiftType::Array(Box::new(SwiftType::Any)), array(vec![int(1), ident("d"), string("x")])),
+        print(vec![ident("mixed")]),
+        typed_let("h", SwiftType::Float, float(0.1)),
+        typed_let("e", SwiftType::Double, int(1)),
+        print(vec![binary(ident("h"), "*", float(2.0)), ident("e"), binary(ident("e"), "/", int(4))]),
+        typed_var("sum", SwiftType::Float, int(0)),
+        typed_var("total", SwiftType::Double, int(0)),
+        for_in("_", call(ident("stride"), vec![labeled("from", int(0)), labeled("to", int(10)), labeled("by", int(1))]), vec![
+            expression(binary(ident("sum"), "+=", float(0.1))),
+            expression(binary(ident("total"), "+=", float(0.1))),
+        ]),
+        print(vec![ident("sum"), ident("total"), binary(binary(ident("h"), "+", float(0.2)), "==", float(0.3))]),
+    ]);
+    let Some(output) = output(&program) else { return };
+    assert_eq!(output, "2.0 1 1.0\nnil nil\ntrue true true true true\n[1, 2.0, \"x\"]\n0.2 1.0 0.25\n1.0000001 0.9999999999999999 true\n");
+}
+
+#[test]
+fn generic_types_are_specialized_with_as_many_arguments_as_they_declare() {
+    let program = program(vec![
+        class("Counter", None, vec![], vec![], vec![]),
+        declare_var("x", SwiftType::Generic("Counter".into(), vec![SwiftType::Integer])),
+        declare_var("y", SwiftType::Generic("Array".into(), vec![SwiftType::Integer, SwiftType::Integer])),
+    ]);
+    assert_eq!(errors(&program), [
+        "cannot specialize non-generic type 'Counter'",
+        "generic type 'Array' specialized with too many type parameters (got 2, but expected 1)",
+    ]);
+}